@@ -1032,7 +1032,7 @@ function SessionDetailPanel({
   );
 }
 export default function SessionsPage() {
-  const { sessions, providers, isLoading } = useSessions();
+  const { sessions, providers, providerErrors, isLoading } = useSessions();
   const [selectedProvider, setSelectedProvider] = useState<string>('all');
   const [detailMode, setDetailMode] = useSessionDetailMode();
   const { getDetail, getError, requestDetail, isFetching, queryClient } =
@@ -1364,6 +1364,16 @@ export default function SessionsPage() {
 
   const main = (
     <div className="flex h-full w-full flex-1 min-h-0 flex-col gap-4 px-4 py-6">
+      {providerErrors.length > 0 ? (
+        <div className="flex flex-col gap-1 rounded-md border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-800">
+          {providerErrors.map((error) => (
+            <p key={error.provider}>
+              <span className="font-medium capitalize">{error.provider}</span> sessions
+              unavailable: {error.message}
+            </p>
+          ))}
+        </div>
+      ) : null}
       <div className="flex h-full flex-1 min-h-0 flex-col gap-4 overflow-hidden lg:flex-row">
         <div className="flex h-full flex-1 min-h-0 min-w-0 flex-col overflow-hidden lg:flex-[0.45]">
           <SessionSummaryList
@@ -725,7 +725,13 @@ const DEFAULT_MESSAGE_ACCENT: MessageAccent = {
 
 function getMessageAccent(detail: SessionEvent): MessageAccent {
   const category = detail.category.trim().toLowerCase();
-  if (category === "tool_use" || category === "tool_result") {
+  const display = detail.display;
+  if (
+    display === "tool_use" ||
+    display === "tool_result" ||
+    category === "tool_use" ||
+    category === "tool_result"
+  ) {
     return {
       container: "border-stone-300 bg-stone-100",
       title: "text-stone-800",
@@ -734,7 +740,10 @@ function getMessageAccent(detail: SessionEvent): MessageAccent {
     };
   }
 
-  const actor = detail.actor?.trim().toLowerCase();
+  const actor =
+    display === "user" || display === "assistant"
+      ? display
+      : detail.actor?.trim().toLowerCase();
   if (actor) {
     switch (actor) {
       case "user":
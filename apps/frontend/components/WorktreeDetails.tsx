@@ -16,13 +16,19 @@ import type {
   WorktreeSummary,
 } from '@/types';
 import WorktreeGitSection from './WorktreeGitSection';
+import WorktreeNotes from './WorktreeNotes';
 import WorktreeSessions from './WorktreeSessions';
 import { useLaunchWorktreeCommand } from '@/features/command/hooks/useLaunchWorktreeCommand';
 import { useLaunchWorktreeShell } from '@/features/command/hooks/useLaunchWorktreeShell';
+import { useOpenWorktreeEditor } from '@/features/command/hooks/useOpenWorktreeEditor';
+import { usePasteClipboard } from '@/features/command/hooks/usePasteClipboard';
 import { useMergeWorktree } from '@/hooks/useMergeWorktree';
 import { useDeleteWorktree } from '@/hooks/useDeleteWorktree';
+import { useWorktreeConflicts } from '@/hooks/useWorktreeConflicts';
 import { ApiError } from '@/lib/apiClient';
 import { getSessionKey } from '@/lib/session-utils';
+import { copyToClipboard } from '@/lib/utils';
+import { getBranchLabel } from '@/lib/worktree-utils';
 
 interface WorktreeDetailsProps {
   worktree: WorktreeSummary | null;
@@ -30,7 +36,7 @@ interface WorktreeDetailsProps {
 }
 
 type FeedbackMessage = {
-  type: 'success' | 'error';
+  type: 'success' | 'info' | 'error';
   message: string;
 };
 
@@ -132,6 +138,7 @@ export default function WorktreeDetails({
   const [isMergeDialogOpen, setIsMergeDialogOpen] = useState(false);
   const [mergeStrategy, setMergeStrategy] = useState<MergeStrategyOption>('ff-only');
   const [mergePushEnabled, setMergePushEnabled] = useState(false);
+  const [mergeRemote, setMergeRemote] = useState('origin');
   const [mergeCleanupEnabled, setMergeCleanupEnabled] = useState(false);
   const [mergeError, setMergeError] = useState<string | null>(null);
   const [isDeleteDialogOpen, setIsDeleteDialogOpen] = useState(false);
@@ -144,6 +151,16 @@ export default function WorktreeDetails({
     isPending: isLaunchingShell,
     reset: resetLaunchShell,
   } = useLaunchWorktreeShell();
+  const {
+    mutateAsync: openWorktreeEditor,
+    isPending: isOpeningEditor,
+    reset: resetOpenEditor,
+  } = useOpenWorktreeEditor();
+  const {
+    mutateAsync: pasteClipboard,
+    isPending: isPastingClipboard,
+    reset: resetPasteClipboard,
+  } = usePasteClipboard();
   const {
     mutateAsync: mergeWorktree,
     reset: resetMerge,
@@ -154,10 +171,12 @@ export default function WorktreeDetails({
     reset: resetDelete,
     isPending: isDeleting,
   } = useDeleteWorktree();
+  const { conflicts } = useWorktreeConflicts();
 
   const mergeDialogTitleId = useId();
   const mergeDialogDescriptionId = useId();
   const mergePushCheckboxId = useId();
+  const mergeRemoteInputId = useId();
   const mergeCleanupCheckboxId = useId();
   const deleteDialogTitleId = useId();
   const deleteDialogDescriptionId = useId();
@@ -180,6 +199,22 @@ export default function WorktreeDetails({
     });
   }, [worktree]);
 
+  // Other worktrees with uncommitted edits to the same files as this one.
+  const overlappingWorktrees = useMemo(() => {
+    if (!worktree) {
+      return [];
+    }
+    return conflicts.flatMap((conflict) => {
+      if (conflict.worktree_a === worktree.id) {
+        return [{ id: conflict.worktree_b, files: conflict.files }];
+      }
+      if (conflict.worktree_b === worktree.id) {
+        return [{ id: conflict.worktree_a, files: conflict.files }];
+      }
+      return [];
+    });
+  }, [conflicts, worktree]);
+
   const hasWorktree = Boolean(worktree?.id);
   const selectedMergeStrategy = MERGE_STRATEGIES.find(
     (item) => item.value === mergeStrategy,
@@ -190,6 +225,7 @@ export default function WorktreeDetails({
     setMergeError(null);
     setMergeStrategy('ff-only');
     setMergePushEnabled(false);
+    setMergeRemote('origin');
     setMergeCleanupEnabled(false);
     resetMerge();
   }, [resetMerge]);
@@ -229,6 +265,7 @@ export default function WorktreeDetails({
         worktreeId: worktree.id,
         strategy: mergeStrategy,
         push: mergePushEnabled,
+        ...(mergePushEnabled && mergeRemote.trim() ? { remote: mergeRemote.trim() } : {}),
         cleanup: mergeCleanupEnabled,
       });
       const strategyLabel = getStrategyLabel(mergeStrategy);
@@ -244,6 +281,7 @@ export default function WorktreeDetails({
     closeMergeDialog,
     mergeCleanupEnabled,
     mergePushEnabled,
+    mergeRemote,
     mergeStrategy,
     mergeWorktree,
     worktree?.id,
@@ -278,6 +316,8 @@ export default function WorktreeDetails({
     setActionFeedback(null);
     resetLaunchCommand();
     resetLaunchShell();
+    resetOpenEditor();
+    resetPasteClipboard();
     closeMergeDialog();
     closeDeleteDialog();
   }, [
@@ -285,6 +325,8 @@ export default function WorktreeDetails({
     closeMergeDialog,
     resetLaunchShell,
     resetLaunchCommand,
+    resetOpenEditor,
+    resetPasteClipboard,
     worktree?.id,
   ]);
 
@@ -293,7 +335,7 @@ export default function WorktreeDetails({
   }, [worktree?.id]);
 
   useEffect(() => {
-    if (!actionFeedback || actionFeedback.type !== 'success') {
+    if (!actionFeedback || actionFeedback.type === 'error') {
       return;
     }
     const timer = window.setTimeout(() => {
@@ -367,6 +409,121 @@ export default function WorktreeDetails({
     }
   }, [launchWorktreeShell, worktree?.id]);
 
+  const handleOpenEditor = useCallback(async () => {
+    const worktreeId = worktree?.id;
+    if (!worktreeId) {
+      return;
+    }
+
+    setActionFeedback(null);
+
+    try {
+      const result = await openWorktreeEditor(worktreeId);
+      setActionFeedback({
+        type: 'success',
+        message: `Opening ${worktree?.name ?? worktreeId} in ${result.editor}.`,
+      });
+    } catch (error) {
+      setActionFeedback({
+        type: 'error',
+        message: toActionErrorMessage(error, 'Failed to open editor'),
+      });
+    }
+  }, [openWorktreeEditor, worktree?.id, worktree?.name]);
+
+  const handlePasteClipboard = useCallback(async () => {
+    const worktreeId = worktree?.id;
+    if (!worktreeId) {
+      return;
+    }
+
+    setActionFeedback(null);
+
+    try {
+      await pasteClipboard(worktreeId);
+      setActionFeedback({
+        type: 'success',
+        message: `Pasted clipboard into ${worktree?.name ?? worktreeId}.`,
+      });
+    } catch (error) {
+      setActionFeedback({
+        type: 'error',
+        message: toActionErrorMessage(error, 'Failed to paste clipboard'),
+      });
+    }
+  }, [pasteClipboard, worktree?.id, worktree?.name]);
+
+  const handleCopyPath = useCallback(async () => {
+    const path = worktree?.path;
+    if (!path) {
+      setActionFeedback({
+        type: 'info',
+        message: 'Select a worktree to copy its path.',
+      });
+      return;
+    }
+
+    try {
+      await copyToClipboard(path);
+      setActionFeedback({ type: 'success', message: `Copied ${path}` });
+    } catch (error) {
+      setActionFeedback({
+        type: 'error',
+        message: toActionErrorMessage(error, 'Failed to copy path'),
+      });
+    }
+  }, [worktree?.path]);
+
+  useEffect(() => {
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.metaKey || event.ctrlKey || event.altKey) {
+        return;
+      }
+      if (event.key !== 't' && event.key !== 'y' && event.key !== 'v') {
+        return;
+      }
+      const target = event.target as HTMLElement | null;
+      if (
+        target &&
+        (target.isContentEditable ||
+          ['INPUT', 'TEXTAREA', 'SELECT'].includes(target.tagName))
+      ) {
+        return;
+      }
+      if (isRunCommandDialogOpen || isMergeDialogOpen || isDeleteDialogOpen) {
+        return;
+      }
+      if (event.key === 'y') {
+        event.preventDefault();
+        void handleCopyPath();
+        return;
+      }
+      if (event.key === 'v') {
+        if (!isPastingClipboard) {
+          event.preventDefault();
+          void handlePasteClipboard();
+        }
+        return;
+      }
+      if (isOpeningEditor) {
+        return;
+      }
+      event.preventDefault();
+      void handleOpenEditor();
+    };
+    window.addEventListener('keydown', handleKeyDown);
+    return () => window.removeEventListener('keydown', handleKeyDown);
+  }, [
+    handleCopyPath,
+    handleOpenEditor,
+    handlePasteClipboard,
+    isDeleteDialogOpen,
+    isMergeDialogOpen,
+    isOpeningEditor,
+    isPastingClipboard,
+    isRunCommandDialogOpen,
+  ]);
+
   const openRunCommandDialog = useCallback(() => {
     if (!worktree?.id) {
       return;
@@ -427,6 +584,9 @@ export default function WorktreeDetails({
             <p className="text-xs mt-2">
               Git status, recent commits, and session summaries will appear here.
             </p>
+            {actionFeedback && (
+              <p className="text-xs mt-3 text-gray-600">{actionFeedback.message}</p>
+            )}
           </div>
         )}
       </div>
@@ -456,7 +616,7 @@ export default function WorktreeDetails({
     },
     {
       label: 'Repo / Branch',
-      value: `${worktree.repo_name}/${worktree.branch}`,
+      value: `${worktree.repo_name}/${getBranchLabel(worktree)}`,
       monospace: true,
     },
     {
@@ -512,6 +672,8 @@ export default function WorktreeDetails({
           </pre>
         </section>
       )}
+
+      <WorktreeNotes key={worktree.id} worktreeId={worktree.id} notes={worktree.notes} />
     </div>
   );
 
@@ -527,6 +689,19 @@ export default function WorktreeDetails({
                 <p className="mt-1 text-sm text-gray-500">
                   Managed as <span className="font-mono">{worktree.id}</span>
                 </p>
+                {overlappingWorktrees.length > 0 && (
+                  <div
+                    className="mt-2 rounded-md border border-amber-200 bg-amber-50 px-3 py-2 text-xs text-amber-800"
+                    title="Uncommitted changes to the same files will likely conflict when merged"
+                  >
+                    {overlappingWorktrees.map(({ id, files }) => (
+                      <p key={id}>
+                        ⚠️ Also edited in <span className="font-mono">{id}</span>:{' '}
+                        <span className="font-mono">{files.join(', ')}</span>
+                      </p>
+                    ))}
+                  </div>
+                )}
               </div>
               <div className="flex flex-wrap gap-2">
                 <button
@@ -537,6 +712,33 @@ export default function WorktreeDetails({
                 >
                   {isLaunchingVsCode ? 'Opening…' : 'Open in VSCode'}
                 </button>
+                <button
+                  type="button"
+                  onClick={handleOpenEditor}
+                  disabled={!hasWorktree || isOpeningEditor}
+                  title="Open in the editor configured in state (shortcut: t)"
+                  className="rounded-md border border-gray-200 bg-white px-3 py-2 text-sm text-gray-600 transition hover:border-gray-300 hover:text-gray-800 disabled:opacity-60"
+                >
+                  {isOpeningEditor ? 'Opening editor…' : 'Open in editor'}
+                </button>
+                <button
+                  type="button"
+                  onClick={handleCopyPath}
+                  disabled={!hasWorktree}
+                  title="Copy the worktree path to the clipboard (shortcut: y)"
+                  className="rounded-md border border-gray-200 bg-white px-3 py-2 text-sm text-gray-600 transition hover:border-gray-300 hover:text-gray-800 disabled:opacity-60"
+                >
+                  Copy path
+                </button>
+                <button
+                  type="button"
+                  onClick={handlePasteClipboard}
+                  disabled={!hasWorktree || isPastingClipboard}
+                  title="Paste the clipboard into the agent's tmux session (shortcut: v)"
+                  className="rounded-md border border-gray-200 bg-white px-3 py-2 text-sm text-gray-600 transition hover:border-gray-300 hover:text-gray-800 disabled:opacity-60"
+                >
+                  {isPastingClipboard ? 'Pasting…' : 'Paste clipboard'}
+                </button>
                 <button
                   type="button"
                   onClick={handleOpenShell}
@@ -576,7 +778,9 @@ export default function WorktreeDetails({
                 className={`mt-4 rounded-md border px-3 py-2 text-xs ${
                   actionFeedback.type === 'success'
                     ? 'border-green-200 bg-green-50 text-green-700'
-                    : 'border-rose-200 bg-rose-50 text-rose-700'
+                    : actionFeedback.type === 'info'
+                      ? 'border-gray-200 bg-gray-50 text-gray-600'
+                      : 'border-rose-200 bg-rose-50 text-rose-700'
                 }`}
               >
                 {actionFeedback.message}
@@ -801,6 +1005,25 @@ export default function WorktreeDetails({
                     </span>
                   </label>
 
+                  {mergePushEnabled && (
+                    <div className="ml-7">
+                      <label
+                        htmlFor={mergeRemoteInputId}
+                        className="block text-xs font-medium text-gray-700"
+                      >
+                        Remote
+                      </label>
+                      <input
+                        id={mergeRemoteInputId}
+                        type="text"
+                        value={mergeRemote}
+                        onChange={(event) => setMergeRemote(event.target.value)}
+                        placeholder="origin"
+                        className="mt-1 w-48 rounded-md border border-gray-300 px-2 py-1 font-mono text-sm text-gray-900 focus:border-blue-500 focus:outline-none focus:ring-1 focus:ring-blue-500"
+                      />
+                    </div>
+                  )}
+
                   <label htmlFor={mergeCleanupCheckboxId} className="flex items-start gap-3">
                     <input
                       id={mergeCleanupCheckboxId}
@@ -340,6 +340,9 @@ export default function WorktreeGitSection({
                       {formatCommitId(entry.commit_id)}
                     </span>
                     <span>
+                      {entry.author && (
+                        <span title={entry.author_email ?? undefined}>{entry.author} · </span>
+                      )}
                       {entry.timestamp ? formatTimestamp(entry.timestamp) : 'unknown'}
                     </span>
                   </div>
@@ -362,7 +365,12 @@ export default function WorktreeGitSection({
             <div className="mt-2 space-y-2">
               <p className="break-all font-mono text-sm text-gray-700">{commit.commit_id}</p>
               <p className="text-sm text-gray-900">{commit.summary}</p>
-              <p className="text-xs text-gray-400">{lastCommitTime ?? 'Time unknown'}</p>
+              <p className="text-xs text-gray-400">
+                {commit.author && (
+                  <span title={commit.author_email ?? undefined}>{commit.author} · </span>
+                )}
+                {lastCommitTime ?? 'Time unknown'}
+              </p>
             </div>
           </div>
         ) : (
@@ -2,10 +2,19 @@
 
 import { FormEvent, useEffect, useMemo, useState } from 'react';
 import { cn } from '@/lib/utils';
+import { getBranchLabel } from '@/lib/worktree-utils';
 import { Button } from '@/components/ui/button';
-import type { DiscoveredWorktree, WorktreeSummary } from '@/types';
+import type { AgentActivity, DiscoveredWorktree, WorktreeSummary } from '@/types';
 import type { DiscoveryParams } from '@/hooks/useDiscoveredWorktrees';
 
+// Pinned worktrees first, then most recently active.
+function compareWorktrees(a: WorktreeSummary, b: WorktreeSummary): number {
+  if (Boolean(a.pinned) !== Boolean(b.pinned)) {
+    return a.pinned ? -1 : 1;
+  }
+  return b.last_activity_at.localeCompare(a.last_activity_at);
+}
+
 interface WorktreeListProps {
   worktrees: WorktreeSummary[];
   isLoading: boolean;
@@ -49,6 +58,32 @@ function formatRelativeTime(isoString: string) {
   return timestamp.toLocaleDateString();
 }
 
+function activityLabel(activity: AgentActivity) {
+  if (activity.running_tool) {
+    return 'Running a tool';
+  }
+  if (activity.waiting_for_input) {
+    return 'Waiting for input';
+  }
+  if (activity.status === 'error') {
+    return 'Error';
+  }
+  return activity.idle ? 'Idle' : 'Working';
+}
+
+function activityDotClass(activity: AgentActivity) {
+  if (activity.status === 'error') {
+    return 'bg-red-500';
+  }
+  if (activity.waiting_for_input) {
+    return 'bg-amber-500';
+  }
+  if (activity.idle) {
+    return 'bg-muted-foreground/50';
+  }
+  return 'bg-green-500';
+}
+
 function DiscoveryLoadingNotice({ label }: { label: string }) {
   return (
     <div className="px-3 py-2 text-xs text-muted-foreground">
@@ -74,9 +109,7 @@ export default function WorktreeList({
   onRefreshDiscovery = () => {},
 }: WorktreeListProps) {
   const sortedWorktrees = useMemo(() => {
-    return [...worktrees].sort((a, b) =>
-      b.last_activity_at.localeCompare(a.last_activity_at),
-    );
+    return [...worktrees].sort(compareWorktrees);
   }, [worktrees]);
   const groupedWorktrees = useMemo(() => {
     const byRepo = new Map<
@@ -101,12 +134,14 @@ export default function WorktreeList({
     return Array.from(byRepo.entries())
       .map(([repoName, { items, latestActivity }]) => ({
         repoName,
-        items: items.sort((a, b) =>
-          b.last_activity_at.localeCompare(a.last_activity_at),
-        ),
+        items: items.sort(compareWorktrees),
         latestActivity,
+        hasPinned: items.some((item) => item.pinned),
       }))
       .sort((a, b) => {
+        if (a.hasPinned !== b.hasPinned) {
+          return a.hasPinned ? -1 : 1;
+        }
         if (a.latestActivity !== b.latestActivity) {
           return b.latestActivity - a.latestActivity;
         }
@@ -195,6 +230,33 @@ export default function WorktreeList({
                             <span className="truncate text-sm font-medium text-foreground">
                               {worktree.name}
                             </span>
+                            {worktree.pinned && (
+                              <span
+                                className="text-[0.7rem]"
+                                title="Pinned"
+                                aria-label="Pinned worktree"
+                              >
+                                📌
+                              </span>
+                            )}
+                            {worktree.locked && (
+                              <span
+                                className="text-[0.7rem]"
+                                title={worktree.lock_reason ?? 'Locked'}
+                                aria-label="Locked worktree"
+                              >
+                                🔒
+                              </span>
+                            )}
+                            {worktree.symlink_path && (
+                              <span
+                                className="text-[0.7rem]"
+                                title={`Linked at ${worktree.symlink_path}`}
+                                aria-label="Linked worktree"
+                              >
+                                🔗
+                              </span>
+                            )}
                             {status && (
                               <span
                                 className={`text-[0.65rem] px-1.5 py-0.5 rounded-full ${
@@ -213,7 +275,7 @@ export default function WorktreeList({
                         </div>
                         <div className="mt-1 flex flex-wrap items-center gap-1 text-[0.7rem] text-muted-foreground">
                           <span className="rounded bg-muted px-1.5 py-0.5 font-mono text-[0.7rem]">
-                            {worktree.repo_name}/{worktree.branch}
+                            {worktree.repo_name}/{getBranchLabel(worktree)}
                           </span>
                           {status && (status.ahead > 0 || status.behind > 0) && (
                             <span className="rounded bg-primary/10 px-1.5 py-0.5 text-primary">
@@ -225,6 +287,23 @@ export default function WorktreeList({
                             </span>
                           )}
                         </div>
+                        {worktree.agent_activity?.last_line && (
+                          <div
+                            className="mt-1 flex items-center gap-1.5 text-[0.7rem] text-muted-foreground"
+                            title={worktree.agent_activity.last_line}
+                          >
+                            <span
+                              className={cn(
+                                'h-1.5 w-1.5 shrink-0 rounded-full',
+                                activityDotClass(worktree.agent_activity),
+                              )}
+                              aria-label={activityLabel(worktree.agent_activity)}
+                            />
+                            <span className="truncate font-mono">
+                              {worktree.agent_activity.last_line}
+                            </span>
+                          </div>
+                        )}
                       </button>
                     );
                   })}
@@ -0,0 +1,88 @@
+'use client';
+
+import { FormEvent, useState } from 'react';
+import { useUpdateWorktreeNotes } from '@/hooks/useUpdateWorktreeNotes';
+
+interface WorktreeNotesProps {
+  worktreeId: string;
+  notes?: string | null;
+}
+
+export default function WorktreeNotes({ worktreeId, notes }: WorktreeNotesProps) {
+  const [isEditing, setIsEditing] = useState(false);
+  const [draft, setDraft] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const { mutateAsync: updateNotes, isPending } = useUpdateWorktreeNotes();
+
+  const startEditing = () => {
+    setDraft(notes ?? '');
+    setError(null);
+    setIsEditing(true);
+  };
+
+  const handleSubmit = async (event: FormEvent<HTMLFormElement>) => {
+    event.preventDefault();
+    setError(null);
+    try {
+      await updateNotes({ worktreeId, notes: draft });
+      setIsEditing(false);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to save notes');
+    }
+  };
+
+  return (
+    <section className="rounded-lg border border-gray-200 bg-white px-4 py-4">
+      <div className="flex items-center justify-between gap-4">
+        <h3 className="text-sm font-semibold uppercase tracking-wide text-gray-900">
+          Notes
+        </h3>
+        {!isEditing && (
+          <button
+            type="button"
+            onClick={startEditing}
+            className="text-xs text-gray-500 transition hover:text-gray-800"
+          >
+            {notes ? 'Edit' : 'Add note'}
+          </button>
+        )}
+      </div>
+      {isEditing ? (
+        <form onSubmit={handleSubmit} className="mt-3 space-y-2">
+          <textarea
+            value={draft}
+            onChange={(event) => setDraft(event.target.value)}
+            rows={4}
+            autoFocus
+            placeholder="What else was this agent asked to do?"
+            className="w-full rounded-md border border-gray-200 px-3 py-2 text-sm text-gray-800 focus:border-gray-400 focus:outline-none"
+          />
+          {error && <p className="text-xs text-rose-600">{error}</p>}
+          <div className="flex justify-end gap-2">
+            <button
+              type="button"
+              onClick={() => setIsEditing(false)}
+              disabled={isPending}
+              className="rounded-md border border-gray-200 bg-white px-3 py-1.5 text-xs text-gray-600 transition hover:border-gray-300 hover:text-gray-800 disabled:opacity-60"
+            >
+              Cancel
+            </button>
+            <button
+              type="submit"
+              disabled={isPending}
+              className="rounded-md bg-gray-900 px-3 py-1.5 text-xs text-white transition hover:bg-gray-700 disabled:opacity-60"
+            >
+              {isPending ? 'Saving…' : 'Save'}
+            </button>
+          </div>
+        </form>
+      ) : notes ? (
+        <pre className="mt-3 whitespace-pre-wrap text-sm text-gray-800">{notes}</pre>
+      ) : (
+        <p className="mt-3 text-sm text-gray-400">
+          No notes yet. Add them here or with `agentdev worktree note`.
+        </p>
+      )}
+    </section>
+  );
+}
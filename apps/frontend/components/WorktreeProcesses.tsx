@@ -17,6 +17,7 @@ import {
 } from '@/types';
 import { cn } from '@/lib/utils';
 import { useLaunchWorktreeCommand } from '@/features/command/hooks/useLaunchWorktreeCommand';
+import { useRetryWorktreeProcess } from '@/features/command/hooks/useRetryWorktreeProcess';
 
 interface WorktreeProcessesProps {
   worktreeId: string | null;
@@ -104,7 +105,15 @@ function getStatusLabel(status: WorktreeProcessStatus) {
   }
 }
 
-function ProcessCard({ process }: { process: WorktreeProcessSummary }) {
+function ProcessCard({
+  process,
+  onRetry,
+  isRetrying = false,
+}: {
+  process: WorktreeProcessSummary;
+  onRetry?: (processId: string) => void;
+  isRetrying?: boolean;
+}) {
   const statusClass = STATUS_STYLES[process.status] ?? STATUS_STYLES.unknown;
   const statusLabel = getStatusLabel(process.status);
   const hasStdout = Boolean(process.stdout && process.stdout.length > 0);
@@ -139,6 +148,8 @@ function ProcessCard({ process }: { process: WorktreeProcessSummary }) {
     return `Exit code ${process.exit_code}`;
   }, [process.exit_code]);
 
+  const canRetry = process.status === 'failed' || process.status === 'unknown';
+
   return (
     <div className="flex flex-col gap-3 rounded-lg border border-border bg-card px-4 py-4 shadow-sm">
       <div className="flex flex-wrap items-start justify-between gap-3">
@@ -150,6 +161,19 @@ function ProcessCard({ process }: { process: WorktreeProcessSummary }) {
             {exitCodeLabel && (
               <span className="text-xs text-muted-foreground">{exitCodeLabel}</span>
             )}
+            {process.parent_id && (
+              <span className="text-xs text-muted-foreground">Retry</span>
+            )}
+            {canRetry && onRetry && (
+              <button
+                type="button"
+                onClick={() => onRetry(process.id)}
+                disabled={isRetrying}
+                className="rounded-md border border-border bg-background px-2 py-0.5 text-xs font-medium text-foreground hover:bg-muted disabled:opacity-50"
+              >
+                {isRetrying ? 'Retrying…' : 'Retry'}
+              </button>
+            )}
           </div>
           <code className="rounded bg-muted px-2 py-1 text-sm text-foreground">
             {formatCommand(process.command)}
@@ -245,6 +269,13 @@ export default function WorktreeProcesses({
     isPending: isSubmitting,
     reset: resetLaunchCommand,
   } = useLaunchWorktreeCommand();
+  const {
+    mutateAsync: retryWorktreeProcess,
+    isPending: isRetrying,
+    variables: retryVariables,
+    reset: resetRetryProcess,
+  } = useRetryWorktreeProcess();
+  const [retryError, setRetryError] = useState<string | null>(null);
 
   useEffect(() => {
     setOptimisticProcesses([]);
@@ -252,8 +283,10 @@ export default function WorktreeProcesses({
     setCommandInput('');
     setDescriptionInput('');
     setLaunchError(null);
+    setRetryError(null);
     resetLaunchCommand();
-  }, [resetLaunchCommand, worktreeId]);
+    resetRetryProcess();
+  }, [resetLaunchCommand, resetRetryProcess, worktreeId]);
 
   useEffect(() => {
     if (processes.length === 0) {
@@ -301,6 +334,25 @@ export default function WorktreeProcesses({
     }
   }, [commandInput, descriptionInput, launchWorktreeCommand, refetch, worktreeId]);
 
+  const handleRetry = useCallback(async (processId: string) => {
+    if (!worktreeId) {
+      return;
+    }
+
+    setRetryError(null);
+
+    try {
+      const payload = await retryWorktreeProcess({ worktreeId, processId });
+      setOptimisticProcesses((current) => [
+        payload.process,
+        ...current.filter((entry) => entry.id !== payload.process.id),
+      ]);
+    } catch (err) {
+      const message = err instanceof Error ? err.message : 'Failed to retry command';
+      setRetryError(message);
+    }
+  }, [retryWorktreeProcess, worktreeId]);
+
   const handleCancelLaunch = useCallback(() => {
     setIsFormOpen(false);
     setLaunchError(null);
@@ -443,10 +495,19 @@ export default function WorktreeProcesses({
           </div>
         )}
 
+        {retryError && (
+          <p className="text-xs text-red-600">{retryError}</p>
+        )}
+
         {!showEmptyState && !showLoadingState && displayProcesses.length > 0 && (
           <div className="space-y-3 pb-4">
             {displayProcesses.map((process) => (
-              <ProcessCard key={process.id} process={process} />
+              <ProcessCard
+                key={process.id}
+                process={process}
+                onRetry={handleRetry}
+                isRetrying={isRetrying && retryVariables?.processId === process.id}
+              />
             ))}
           </div>
         )}
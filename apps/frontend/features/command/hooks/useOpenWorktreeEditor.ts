@@ -0,0 +1,15 @@
+'use client';
+
+import { useMutation } from '@tanstack/react-query';
+import { postJson } from '@/lib/apiClient';
+import type { OpenEditorResponse } from '@/types';
+
+export function useOpenWorktreeEditor() {
+  return useMutation({
+    mutationFn: async (worktreeId: string) =>
+      postJson<OpenEditorResponse, Record<string, never>>(
+        `/api/worktrees/${encodeURIComponent(worktreeId)}/editor`,
+        {},
+      ),
+  });
+}
@@ -0,0 +1,15 @@
+'use client';
+
+import { useMutation } from '@tanstack/react-query';
+import { postJson } from '@/lib/apiClient';
+import type { PasteClipboardResponse } from '@/types';
+
+export function usePasteClipboard() {
+  return useMutation({
+    mutationFn: async (worktreeId: string) =>
+      postJson<PasteClipboardResponse, Record<string, never>>(
+        `/api/worktrees/${encodeURIComponent(worktreeId)}/paste`,
+        {},
+      ),
+  });
+}
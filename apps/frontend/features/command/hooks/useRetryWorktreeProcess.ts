@@ -0,0 +1,28 @@
+'use client';
+
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { postJson } from '@/lib/apiClient';
+import { queryKeys } from '@/lib/queryKeys';
+import type { LaunchWorktreeCommandResponse } from '@/types';
+
+export interface RetryWorktreeProcessInput {
+  worktreeId: string;
+  processId: string;
+}
+
+export function useRetryWorktreeProcess() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: async ({ worktreeId, processId }: RetryWorktreeProcessInput) =>
+      postJson<LaunchWorktreeCommandResponse, Record<string, never>>(
+        `/api/worktrees/${encodeURIComponent(worktreeId)}/processes/${encodeURIComponent(processId)}/retry`,
+        {},
+      ),
+    onSuccess: (_response, variables) => {
+      void queryClient.invalidateQueries({
+        queryKey: queryKeys.worktrees.processes(variables.worktreeId),
+      });
+    },
+  });
+}
@@ -0,0 +1,18 @@
+'use client';
+
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { postJson } from '@/lib/apiClient';
+import { queryKeys } from '@/lib/queryKeys';
+import type { CreateWorktreeRequest, WorktreeSummary } from '@/types';
+
+export function useCreateWorktree() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: async (body: CreateWorktreeRequest) =>
+      postJson<WorktreeSummary, CreateWorktreeRequest>('/api/worktrees', body),
+    onSuccess: () => {
+      void queryClient.invalidateQueries({ queryKey: queryKeys.worktrees.list });
+    },
+  });
+}
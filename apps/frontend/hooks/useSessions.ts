@@ -6,6 +6,7 @@ import { getJson } from '@/lib/apiClient';
 import { queryKeys } from '@/lib/queryKeys';
 import type {
   SessionListResponse,
+  SessionProviderError,
   SessionProviderSummary,
   SessionSummary,
 } from '@/types';
@@ -29,6 +30,7 @@ export function useSessions() {
 
   const sessionsData = query.data?.sessions;
   const providersData = query.data?.providers;
+  const providerErrorsData = query.data?.provider_errors;
 
   const sessions = useMemo<SessionSummary[]>(
     () => sessionsData ?? [],
@@ -40,9 +42,15 @@ export function useSessions() {
     [providersData],
   );
 
+  const providerErrors = useMemo<SessionProviderError[]>(
+    () => (Array.isArray(providerErrorsData) ? providerErrorsData : []),
+    [providerErrorsData],
+  );
+
   return {
     sessions,
     providers,
+    providerErrors,
     isLoading: query.isLoading && !query.isFetched,
     isFetching: query.isFetching,
     error: toErrorMessage(query.error),
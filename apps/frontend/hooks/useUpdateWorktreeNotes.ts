@@ -0,0 +1,29 @@
+'use client';
+
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { patchJson } from '@/lib/apiClient';
+import { queryKeys } from '@/lib/queryKeys';
+import type { UpdateWorktreeRequest, WorktreeSummary } from '@/types';
+
+export interface UpdateWorktreeNotesInput {
+  worktreeId: string;
+  notes: string;
+}
+
+export function useUpdateWorktreeNotes() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: async ({ worktreeId, notes }: UpdateWorktreeNotesInput) =>
+      patchJson<WorktreeSummary, UpdateWorktreeRequest>(
+        `/api/worktrees/${encodeURIComponent(worktreeId)}`,
+        { notes },
+      ),
+    onSuccess: (_data, variables) => {
+      void queryClient.invalidateQueries({ queryKey: queryKeys.worktrees.list });
+      void queryClient.invalidateQueries({
+        queryKey: queryKeys.worktrees.detail(variables.worktreeId),
+      });
+    },
+  });
+}
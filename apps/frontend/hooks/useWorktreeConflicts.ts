@@ -0,0 +1,27 @@
+'use client';
+
+import { useMemo } from 'react';
+import { useQuery } from '@tanstack/react-query';
+import { getJson } from '@/lib/apiClient';
+import { queryKeys } from '@/lib/queryKeys';
+import type { CrossWorktreeConflict } from '@/types';
+
+export function useWorktreeConflicts() {
+  const query = useQuery({
+    queryKey: queryKeys.worktrees.conflicts,
+    queryFn: ({ signal }) =>
+      getJson<CrossWorktreeConflict[]>('/api/conflicts', { signal }),
+    refetchInterval: 15000,
+  });
+
+  const conflicts = useMemo<CrossWorktreeConflict[]>(
+    () => query.data ?? [],
+    [query.data],
+  );
+
+  return {
+    conflicts,
+    isLoading: query.isLoading && !query.isFetched,
+    query,
+  };
+}
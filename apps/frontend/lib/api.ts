@@ -5,6 +5,22 @@ const normalizedBase =
     ? rawBase.replace(/\/+$/, '')
     : '';
 
+const API_TOKEN_STORAGE_KEY = 'agentdev.apiToken';
+
+// Token for servers started with AGENTDEV_API_TOKEN. Opening the UI once with
+// `?token=<value>` stores it for later visits.
+export function apiToken(): string | null {
+  if (typeof window === 'undefined') {
+    return null;
+  }
+  const fromUrl = new URLSearchParams(window.location.search).get('token');
+  if (fromUrl) {
+    window.localStorage.setItem(API_TOKEN_STORAGE_KEY, fromUrl);
+    return fromUrl;
+  }
+  return window.localStorage.getItem(API_TOKEN_STORAGE_KEY);
+}
+
 export function apiUrl(path: string) {
   if (!normalizedBase || /^https?:\/\//i.test(path)) {
     return path;
@@ -17,20 +33,31 @@ export function apiUrl(path: string) {
   return `${normalizedBase}${path}`;
 }
 
+function withToken(url: string) {
+  const token = apiToken();
+  if (!token) {
+    return url;
+  }
+  const separator = url.includes('?') ? '&' : '?';
+  return `${url}${separator}token=${encodeURIComponent(token)}`;
+}
+
+// WebSocket handshakes cannot carry an Authorization header, so the token
+// travels as a query parameter instead.
 export function websocketUrl(path: string) {
   if (/^wss?:\/\//i.test(path)) {
-    return path;
+    return withToken(path);
   }
 
   if (!normalizedBase) {
-    return path;
+    return withToken(path);
   }
 
   const wsBase = normalizedBase.replace(/^http/i, 'ws');
 
   if (!path.startsWith('/')) {
-    return `${wsBase}/${path}`;
+    return withToken(`${wsBase}/${path}`);
   }
 
-  return `${wsBase}${path}`;
+  return withToken(`${wsBase}${path}`);
 }
@@ -1,6 +1,6 @@
 'use client';
 
-import { apiUrl } from '@/lib/api';
+import { apiToken, apiUrl } from '@/lib/api';
 
 interface ApiErrorInit {
   status: number;
@@ -57,6 +57,11 @@ async function assertOk(response: Response): Promise<void> {
 
 type JsonHeaders = HeadersInit | undefined;
 
+function authHeaders(): Record<string, string> {
+  const token = apiToken();
+  return token ? { Authorization: `Bearer ${token}` } : {};
+}
+
 function mergeHeaders(defaults: HeadersInit, override?: JsonHeaders): HeadersInit {
   if (!override) {
     return defaults;
@@ -87,6 +92,7 @@ export async function getJson<TResponse>(
     headers: mergeHeaders(
       {
         Accept: 'application/json',
+        ...authHeaders(),
       },
       headers,
     ),
@@ -105,15 +111,33 @@ export async function postJson<TResponse, TBody>(
   path: string,
   body: TBody,
   options: PostJsonOptions = {},
+): Promise<TResponse> {
+  return sendJson<TResponse, TBody>('POST', path, body, options);
+}
+
+export async function patchJson<TResponse, TBody>(
+  path: string,
+  body: TBody,
+  options: PostJsonOptions = {},
+): Promise<TResponse> {
+  return sendJson<TResponse, TBody>('PATCH', path, body, options);
+}
+
+async function sendJson<TResponse, TBody>(
+  method: 'POST' | 'PATCH',
+  path: string,
+  body: TBody,
+  options: PostJsonOptions,
 ): Promise<TResponse> {
   const { headers, ...rest } = options;
   const response = await fetch(apiUrl(path), {
-    method: 'POST',
+    method,
     body: body != null ? JSON.stringify(body) : undefined,
     headers: mergeHeaders(
       {
         Accept: 'application/json',
         'Content-Type': 'application/json',
+        ...authHeaders(),
       },
       headers,
     ),
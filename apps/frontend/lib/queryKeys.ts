@@ -8,6 +8,7 @@ export const queryKeys = {
     git: (id: string) => ['worktrees', 'git', id] as const,
     discovery: (recursive: boolean, root: string | null) =>
       ['worktrees', 'discovery', recursive, root] as const,
+    conflicts: ['worktrees', 'conflicts'] as const,
   },
   sessions: {
     list: ['sessions', 'list'] as const,
@@ -22,5 +23,6 @@ export type QueryKey =
   | ReturnType<(typeof queryKeys.worktrees)['processes']>
   | ReturnType<(typeof queryKeys.worktrees)['git']>
   | ReturnType<(typeof queryKeys.worktrees)['discovery']>
+  | (typeof queryKeys.worktrees.conflicts)
   | (typeof queryKeys.sessions.list)
   | ReturnType<(typeof queryKeys.sessions)['detail']>;
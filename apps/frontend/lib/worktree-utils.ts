@@ -0,0 +1,9 @@
+import type { WorktreeSummary } from '@/types';
+
+export function getBranchLabel(worktree: WorktreeSummary): string {
+  const status = worktree.git_status;
+  if (!status?.detached) {
+    return worktree.branch;
+  }
+  return status.head ? `(detached @${status.head})` : '(detached)';
+}
@@ -1,5 +1,7 @@
 export interface WorktreeGitStatus {
-  branch: string;
+  branch?: string | null;
+  detached?: boolean;
+  head?: string | null;
   upstream?: string | null;
   ahead: number;
   behind: number;
@@ -14,6 +16,8 @@ export interface WorktreeCommitInfo {
   commit_id: string;
   summary: string;
   timestamp?: string | null;
+  author?: string | null;
+  author_email?: string | null;
 }
 
 export interface WorktreeCommitsAhead {
@@ -61,8 +65,16 @@ export interface SessionEvent {
   timestamp?: string;
   raw?: unknown;
   tool?: SessionToolEvent | null;
+  display?: SessionEventDisplay | null;
 }
 
+export type SessionEventDisplay =
+  | 'user'
+  | 'assistant'
+  | 'tool_use'
+  | 'tool_result'
+  | 'system';
+
 export type SessionToolPhase = 'use' | 'result';
 
 export interface SessionToolEvent {
@@ -84,6 +96,14 @@ export interface SessionDetailResponse {
   events: SessionEvent[];
 }
 
+export interface AgentActivity {
+  status: 'waiting_for_input' | 'processing' | 'error' | 'idle';
+  waiting_for_input: boolean;
+  running_tool: boolean;
+  idle: boolean;
+  last_line?: string | null;
+}
+
 export interface WorktreeSummary {
   id: string;
   name: string;
@@ -96,19 +116,39 @@ export interface WorktreeSummary {
   task_name?: string | null;
   initial_prompt?: string | null;
   agent_alias?: string | null;
+  locked?: boolean;
+  lock_reason?: string | null;
+  pinned?: boolean;
+  notes?: string | null;
+  symlink_path?: string | null;
   git_status?: WorktreeGitStatus | null;
   head_commit?: WorktreeCommitInfo | null;
   commits_ahead?: WorktreeCommitsAhead | null;
+  size_bytes?: number | null;
+  agent_activity?: AgentActivity | null;
   sessions: WorktreeSessionSummary[];
 }
 
+export interface WorktreeSize {
+  worktree_id: string;
+  size_bytes: number;
+  respect_gitignore: boolean;
+}
+
 export interface WorktreeListResponse {
   worktrees: WorktreeSummary[];
 }
 
+export interface SessionProviderError {
+  provider: string;
+  message: string;
+}
+
 export interface SessionListResponse {
   sessions: SessionSummary[];
   providers?: SessionProviderSummary[];
+  provider_errors?: SessionProviderError[];
+  details?: SessionDetailResponse[];
 }
 
 export interface WorktreeCommitDiff {
@@ -148,6 +188,7 @@ export interface WorktreeProcessSummary {
   description?: string | null;
   stdout?: string | null;
   stderr?: string | null;
+  parent_id?: string | null;
 }
 
 export interface WorktreeProcessListResponse {
@@ -164,6 +205,13 @@ export interface DiscoveredWorktree {
   bare: boolean;
 }
 
+export interface CrossWorktreeConflict {
+  repo_name: string;
+  worktree_a: string;
+  worktree_b: string;
+  files: string[];
+}
+
 export interface LaunchWorktreeCommandResponse {
   process: WorktreeProcessSummary;
 }
@@ -172,12 +220,28 @@ export interface LaunchWorktreeShellResponse {
   status: 'launched';
 }
 
+export interface UpdateWorktreeRequest {
+  notes?: string;
+}
+
+export interface OpenEditorResponse {
+  status: 'launched';
+  editor: string;
+}
+
+export interface PasteClipboardResponse {
+  status: 'pasted';
+  session: string;
+}
+
 export type MergeStrategyOption = 'ff-only' | 'merge' | 'squash';
 
 export interface MergeWorktreeRequest {
   strategy?: MergeStrategyOption;
   push?: boolean;
   cleanup?: boolean;
+  remote?: string;
+  set_upstream?: boolean;
 }
 
 export interface MergeWorktreeResponse {
@@ -186,6 +250,14 @@ export interface MergeWorktreeResponse {
   stderr?: string;
 }
 
+export interface CreateWorktreeRequest {
+  name?: string;
+  /** Repository path, or the name of a repository with managed worktrees. */
+  repo: string;
+  base?: string;
+  agent?: string;
+}
+
 export interface DeleteWorktreeRequest {
   force?: boolean;
 }
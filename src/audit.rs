@@ -0,0 +1,135 @@
+//! Append-only JSONL log of worktree lifecycle events (create, delete, merge).
+//!
+//! One JSON object per line in `<config dir>/audit.jsonl`, or wherever
+//! `AGENTDEV_AUDIT_LOG` points. Writing is best-effort: a failure is reported
+//! on stderr and never fails the operation being audited.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::state::get_config_dir;
+
+/// Env var overriding where the audit log is written.
+pub const AUDIT_LOG_ENV: &str = "AGENTDEV_AUDIT_LOG";
+/// Env var naming what triggered the operation; the web server sets it to `web`.
+pub const AUDIT_SOURCE_ENV: &str = "AGENTDEV_AUDIT_SOURCE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Delete,
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+    /// The user declined a confirmation prompt
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub user: String,
+    pub action: AuditAction,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+    pub outcome: AuditOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// Start an entry for `action`; fill in the target as it becomes known.
+    pub fn new(action: AuditAction) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            user: current_user(),
+            action,
+            source: std::env::var(AUDIT_SOURCE_ENV)
+                .ok()
+                .filter(|source| !source.trim().is_empty())
+                .unwrap_or_else(|| "cli".to_string()),
+            repo: None,
+            branch: None,
+            worktree: None,
+            outcome: AuditOutcome::Success,
+            error: None,
+        }
+    }
+
+    /// Mark the operation as declined by the user instead of completed.
+    pub fn cancelled(&mut self) {
+        self.outcome = AuditOutcome::Cancelled;
+    }
+
+    /// Set the outcome from the operation's result and append the entry.
+    pub fn finish<T>(mut self, result: &Result<T>) {
+        if let Err(err) = result {
+            self.outcome = AuditOutcome::Failure;
+            self.error = Some(format!("{err:#}"));
+        }
+        if let Err(err) = append(&self) {
+            eprintln!("⚠️  Failed to write audit log: {err:#}");
+        }
+    }
+}
+
+/// Audit log location, from `AGENTDEV_AUDIT_LOG` or the config directory.
+pub fn audit_log_path() -> Result<PathBuf> {
+    if let Some(path) = std::env::var_os(AUDIT_LOG_ENV).filter(|path| !path.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(get_config_dir()?.join("audit.jsonl"))
+}
+
+fn append(entry: &AuditEntry) -> Result<()> {
+    let path = audit_log_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    // A single write of the whole line keeps concurrent appends from interleaving
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .with_context(|| format!("Failed to append to {}", path.display()))
+}
+
+/// Every entry in the audit log, oldest first; unreadable lines are skipped.
+pub fn read_entries() -> Result<Vec<AuditEntry>> {
+    let path = audit_log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn current_user() -> String {
+    ["USER", "USERNAME"]
+        .into_iter()
+        .find_map(|key| std::env::var(key).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
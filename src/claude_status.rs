@@ -13,7 +13,32 @@ pub enum ClaudeStatus {
     Idle,
 }
 
-// Note: Display helpers for ClaudeStatus were removed as they were unused.
+impl ClaudeStatus {
+    /// Stable snake_case identifier used in webhook payloads.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaudeStatus::WaitingForInput => "waiting_for_input",
+            ClaudeStatus::Processing => "processing",
+            ClaudeStatus::Error => "error",
+            ClaudeStatus::Idle => "idle",
+        }
+    }
+}
+
+/// Richer summary of what an agent appears to be doing, derived from its output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentActivity {
+    /// Coarse status, as returned by [`ClaudeStatusDetector::analyze_output`]
+    pub status: ClaudeStatus,
+    /// The agent is showing its input prompt
+    pub waiting_for_input: bool,
+    /// The agent is in the middle of a tool call (shell command, edit, ...)
+    pub running_tool: bool,
+    /// No meaningful output yet
+    pub idle: bool,
+    /// Last non-empty output line with TUI borders stripped
+    pub last_line: Option<String>,
+}
 
 /// High-level panel status for display: simplified and robust.
 /// Combines tmux session existence with Claude output analysis.
@@ -69,7 +94,7 @@ pub fn to_panel_status(has_session: bool, claude: Option<ClaudeStatus>) -> Panel
 
 pub struct ClaudeStatusDetector {
     last_analysis: Option<Instant>,
-    cache: Option<(String, ClaudeStatus)>,
+    cache: Option<(String, AgentActivity)>,
 }
 
 impl ClaudeStatusDetector {
@@ -82,22 +107,35 @@ impl ClaudeStatusDetector {
 
     /// Analyze Claude output to determine current status
     pub fn analyze_output(&mut self, output: &str) -> ClaudeStatus {
+        self.analyze_activity(output).status
+    }
+
+    /// Analyze Claude output into an [`AgentActivity`] summary
+    pub fn analyze_activity(&mut self, output: &str) -> AgentActivity {
         // Check cache (avoid re-analyzing within 500ms)
         if let Some(ref last) = self.last_analysis
             && last.elapsed() < Duration::from_millis(500)
-            && let Some((ref cached_output, ref status)) = self.cache
+            && let Some((ref cached_output, ref activity)) = self.cache
             && cached_output == output
         {
-            return status.clone();
+            return activity.clone();
         }
 
         let status = self.detect_status_from_output(output);
+        let last_lines: Vec<&str> = output.lines().rev().take(20).collect();
+        let activity = AgentActivity {
+            waiting_for_input: status == ClaudeStatus::WaitingForInput,
+            running_tool: status == ClaudeStatus::Processing && self.is_running_tool(&last_lines),
+            idle: status == ClaudeStatus::Idle,
+            last_line: last_output_line(output),
+            status,
+        };
 
         // Update cache
         self.last_analysis = Some(Instant::now());
-        self.cache = Some((output.to_string(), status.clone()));
+        self.cache = Some((output.to_string(), activity.clone()));
 
-        status
+        activity
     }
 
     fn detect_status_from_output(&self, output: &str) -> ClaudeStatus {
@@ -182,6 +220,23 @@ impl ClaudeStatusDetector {
         false
     }
 
+    fn is_running_tool(&self, last_lines: &[&str]) -> bool {
+        last_lines.iter().any(|line| {
+            let trimmed = line.trim_start();
+            // Tool output (`⎿  Running…`) or a tool call header like `⏺ Bash(cargo test)`
+            (trimmed.starts_with("⎿") && (line.contains("Running") || line.contains("…")))
+                || ["⏺", "●"].iter().any(|bullet| {
+                    trimmed.strip_prefix(bullet).is_some_and(|rest| {
+                        let rest = rest.trim_start();
+                        rest.starts_with(|c: char| c.is_ascii_uppercase())
+                            && rest
+                                .split_once('(')
+                                .is_some_and(|(name, _)| name.chars().all(char::is_alphanumeric))
+                    })
+                })
+        })
+    }
+
     fn is_processing(&self, last_lines: &[&&str]) -> bool {
         for line in last_lines {
             // Most reliable: "(esc to interrupt)" indicator
@@ -234,6 +289,21 @@ impl ClaudeStatusDetector {
     }
 }
 
+/// Last line with visible text, ignoring TUI box borders and the empty input prompt.
+fn last_output_line(output: &str) -> Option<String> {
+    const BORDER_CHARS: &[char] = &['│', '╭', '╮', '╰', '╯', '─', '┃', '━'];
+    output.lines().rev().find_map(|line| {
+        let text = line
+            .trim_matches(|c: char| c.is_whitespace() || BORDER_CHARS.contains(&c))
+            .trim();
+        if text.is_empty() || text == ">" || text.chars().all(|c| BORDER_CHARS.contains(&c)) {
+            None
+        } else {
+            Some(text.to_string())
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,4 +342,32 @@ mod tests {
         let output = "Let me analyze this code...\nChecking the files\nProcessing...";
         assert_eq!(detector.analyze_output(output), ClaudeStatus::Processing);
     }
+
+    #[test]
+    fn test_activity_summary() {
+        let mut detector = ClaudeStatusDetector::new();
+
+        let output = "⏺ Bash(cargo test)\n  ⎿  Running…\n\n✽ Working… (esc to interrupt)\n";
+        let activity = detector.analyze_activity(output);
+        assert_eq!(activity.status, ClaudeStatus::Processing);
+        assert!(activity.running_tool);
+        assert!(!activity.waiting_for_input);
+        assert_eq!(
+            activity.last_line.as_deref(),
+            Some("✽ Working… (esc to interrupt)")
+        );
+
+        let output = "⏺ Done, all tests pass.\n\n╭──────────╮\n│ >        │\n╰──────────╯\n";
+        let activity = detector.analyze_activity(output);
+        assert!(activity.waiting_for_input);
+        assert!(!activity.running_tool);
+        assert_eq!(
+            activity.last_line.as_deref(),
+            Some("⏺ Done, all tests pass.")
+        );
+
+        let activity = detector.analyze_activity("");
+        assert!(activity.idle);
+        assert_eq!(activity.last_line, None);
+    }
 }
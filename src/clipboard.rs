@@ -0,0 +1,152 @@
+//! Access to the system clipboard through the platform's command-line tools.
+//!
+//! macOS ships `pbcopy`/`pbpaste`. Elsewhere `wl-clipboard` is tried first
+//! on Wayland, then `xclip` and `xsel`, since XWayland sessions often only
+//! have the X11 tools installed.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+type ClipboardCommand = (&'static str, &'static [&'static str]);
+
+/// Commands that print the clipboard, in the order they are tried.
+fn paste_commands(wayland: bool) -> Vec<ClipboardCommand> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbpaste", &[])];
+    }
+    let mut commands: Vec<ClipboardCommand> = Vec::new();
+    if wayland {
+        commands.push(("wl-paste", &["--no-newline"]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard", "-o"]));
+    commands.push(("xsel", &["--clipboard", "--output"]));
+    commands
+}
+
+/// Commands that replace the clipboard with their stdin, in the order they
+/// are tried.
+fn copy_commands(wayland: bool) -> Vec<ClipboardCommand> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbcopy", &[])];
+    }
+    let mut commands: Vec<ClipboardCommand> = Vec::new();
+    if wayland {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard", "-i"]));
+    commands.push(("xsel", &["--clipboard", "--input"]));
+    commands
+}
+
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some_and(|value| !value.is_empty())
+}
+
+/// Read the system clipboard as text with the first available tool.
+pub fn read_clipboard() -> Result<String> {
+    let stdout =
+        run_first(&paste_commands(is_wayland()), None).context("Failed to read the clipboard")?;
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Replace the system clipboard with `text` using the first available tool.
+pub fn write_clipboard(text: &str) -> Result<()> {
+    run_first(&copy_commands(is_wayland()), Some(text))
+        .context("Failed to copy to the clipboard")?;
+    Ok(())
+}
+
+/// Run the first command that is installed, feeding it `input`, and return
+/// its stdout.
+fn run_first(commands: &[ClipboardCommand], input: Option<&str>) -> Result<Vec<u8>> {
+    let mut last_error = None;
+    for (program, args) in commands {
+        match run(program, args, input) {
+            Ok(stdout) => return Ok(stdout),
+            Err(RunError::NotFound) => {}
+            Err(RunError::Failed(err)) => last_error = Some(format!("{program}: {err}")),
+        }
+    }
+
+    let tried: Vec<&str> = commands.iter().map(|(program, _)| *program).collect();
+    match last_error {
+        Some(err) => anyhow::bail!(err),
+        None => anyhow::bail!(
+            "No clipboard tool found; install one of: {}",
+            tried.join(", ")
+        ),
+    }
+}
+
+enum RunError {
+    NotFound,
+    Failed(String),
+}
+
+fn run(program: &str, args: &[&str], input: Option<&str>) -> Result<Vec<u8>, RunError> {
+    let spawn_error = |err: std::io::Error| match err.kind() {
+        std::io::ErrorKind::NotFound => RunError::NotFound,
+        _ => RunError::Failed(err.to_string()),
+    };
+
+    let Some(text) = input else {
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(spawn_error)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(RunError::Failed(stderr.trim().to_string()));
+        }
+        return Ok(output.stdout);
+    };
+
+    // xclip and wl-copy leave a process behind to serve the selection; it
+    // would hold piped stdout/stderr open, so only the exit status is read.
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(spawn_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|err| RunError::Failed(err.to_string()))?;
+    }
+    let status = child
+        .wait()
+        .map_err(|err| RunError::Failed(err.to_string()))?;
+    if !status.success() {
+        return Err(RunError::Failed(format!("exited with {status}")));
+    }
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn programs(commands: Vec<ClipboardCommand>) -> Vec<&'static str> {
+        commands.into_iter().map(|(program, _)| program).collect()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn wayland_tools_are_tried_first() {
+        assert_eq!(
+            programs(paste_commands(true)),
+            vec!["wl-paste", "xclip", "xsel"]
+        );
+        assert_eq!(programs(paste_commands(false)), vec!["xclip", "xsel"]);
+        assert_eq!(
+            programs(copy_commands(true)),
+            vec!["wl-copy", "xclip", "xsel"]
+        );
+        assert_eq!(programs(copy_commands(false)), vec!["xclip", "xsel"]);
+    }
+}
@@ -2,12 +2,50 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use agentdev::git::{get_current_branch, get_repo_name, is_in_worktree};
+use agentdev::git::{
+    execute_git, get_current_branch, get_repo_name, git_metadata_present, is_in_worktree,
+};
 use agentdev::state::{WorktreeInfo, XlaudeState};
-use agentdev::utils::sanitize_branch_name;
+use agentdev::utils::{execute_in_dir, sanitize_branch_name};
 
 pub fn handle_add(name: Option<String>) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    register_worktree(&current_dir, name, "Current directory")
+}
+
+/// Register the worktree at `path` without having to `cd` into it first.
+pub fn handle_adopt(path: PathBuf, name: Option<String>) -> Result<()> {
+    let path = fs::canonicalize(&path)
+        .with_context(|| format!("Path '{}' does not exist", path.display()))?;
+    if !path.is_dir() {
+        anyhow::bail!("Path '{}' is not a directory", path.display());
+    }
+    if !git_metadata_present(&path) {
+        anyhow::bail!(
+            "Path '{}' is not a git worktree (no .git entry at its root)",
+            path.display()
+        );
+    }
+
+    execute_in_dir(&path, || {
+        let toplevel = execute_git(&["rev-parse", "--show-toplevel"])
+            .with_context(|| format!("Path '{}' is not a git worktree", path.display()))?;
+        if fs::canonicalize(toplevel.trim()).ok().as_deref() != Some(path.as_path()) {
+            anyhow::bail!(
+                "Path '{}' is inside the worktree at '{}'; pass the worktree root",
+                path.display(),
+                toplevel.trim()
+            );
+        }
+        register_worktree(&path, name, "Path")
+    })
+}
+
+/// Add the worktree at `dir` to state. Git queries run in the current
+/// directory, so callers must already be inside `dir`.
+fn register_worktree(dir: &Path, name: Option<String>, label: &str) -> Result<()> {
     // Check if we're in a git repository
     let repo_name = get_repo_name().context("Not in a git repository")?;
 
@@ -25,26 +63,24 @@ pub fn handle_add(name: Option<String>) -> Result<()> {
         None => sanitize_branch_name(&current_branch),
     };
 
-    // Get current directory
-    let current_dir = std::env::current_dir()?;
-
     // Load state
     let mut state = XlaudeState::load()?;
 
     let normalize_path = |path: &std::path::Path| -> std::path::PathBuf {
         fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
     };
-    let current_dir_key = normalize_path(&current_dir);
+    let dir_key = normalize_path(dir);
 
     // Check if this path is already managed under another worktree
     if let Some(existing) = state
         .worktrees
         .values()
-        .find(|info| normalize_path(&info.path) == current_dir_key)
+        .find(|info| normalize_path(&info.path) == dir_key)
     {
         anyhow::bail!(
-            "Current directory '{}' is already managed by xlaude as '{}/{}'",
-            current_dir.display(),
+            "{} '{}' is already managed by xlaude as '{}/{}'",
+            label,
+            dir.display(),
             existing.repo_name,
             existing.name
         );
@@ -72,13 +108,21 @@ pub fn handle_add(name: Option<String>) -> Result<()> {
         WorktreeInfo {
             name: worktree_name.clone(),
             branch: current_branch,
-            path: current_dir.clone(),
+            path: dir.to_path_buf(),
             repo_name,
             created_at: Utc::now(),
             task_id: None,
             task_name: None,
             initial_prompt: None,
             agent_alias: None,
+            locked: false,
+            lock_reason: None,
+            last_agent_activity: None,
+            base_ref: None,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
         },
     );
     state.save()?;
@@ -88,7 +132,7 @@ pub fn handle_add(name: Option<String>) -> Result<()> {
         "✅".green(),
         worktree_name.cyan()
     );
-    println!("  {} {}", "Path:".bright_black(), current_dir.display());
+    println!("  {} {}", "Path:".bright_black(), dir.display());
 
     Ok(())
 }
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+
+use agentdev::audit::{AuditAction, AuditEntry, AuditOutcome, audit_log_path, read_entries};
+
+/// Print the tail of the audit log, optionally filtered by action and repo.
+pub fn handle_audit_list(
+    limit: usize,
+    action: Option<AuditAction>,
+    repo: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let mut entries: Vec<AuditEntry> = read_entries()?
+        .into_iter()
+        .filter(|entry| action.is_none_or(|action| entry.action == action))
+        .filter(|entry| {
+            repo.as_deref()
+                .is_none_or(|repo| entry.repo.as_deref() == Some(repo))
+        })
+        .collect();
+    if limit > 0 && entries.len() > limit {
+        entries.drain(..entries.len() - limit);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "{} No audit entries in {}",
+            "📭".yellow(),
+            audit_log_path()?.display()
+        );
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let outcome = match entry.outcome {
+            AuditOutcome::Success => "ok".green(),
+            AuditOutcome::Failure => "failed".red(),
+            AuditOutcome::Cancelled => "cancelled".yellow(),
+        };
+        let action = match entry.action {
+            AuditAction::Create => "create",
+            AuditAction::Delete => "delete",
+            AuditAction::Merge => "merge",
+        };
+        let target = match (&entry.repo, &entry.worktree) {
+            (Some(repo), Some(worktree)) => format!("{repo}/{worktree}"),
+            (None, Some(worktree)) => worktree.clone(),
+            (Some(repo), None) => repo.clone(),
+            (None, None) => "-".to_string(),
+        };
+        println!(
+            "{}  {:<6}  {:<9}  {}  {}  {}",
+            entry
+                .timestamp
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .dimmed(),
+            action,
+            outcome,
+            target.cyan(),
+            format!("({}@{})", entry.user, entry.source).dimmed(),
+            entry
+                .branch
+                .as_deref()
+                .map(|branch| format!("[{branch}]"))
+                .unwrap_or_default()
+        );
+        if let Some(error) = &entry.error {
+            println!("    {}", error.red());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use crate::input::get_command_arg;
+use agentdev::git::{CHECKPOINT_TAG_PREFIX, execute_git};
+use agentdev::state::XlaudeState;
+
+/// Mark the worktree's HEAD with an annotated `checkpoint-<timestamp>` tag
+/// and remember the tag on the worktree.
+pub fn handle_checkpoint(name: Option<String>, message: String) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let display_name = git_wt.display_name();
+
+    let path_str = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+
+    let tag = format!(
+        "{CHECKPOINT_TAG_PREFIX}{}",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    execute_git(&["-C", path_str, "tag", "-a", &tag, "-m", &message, "HEAD"])
+        .with_context(|| format!("Failed to create tag {tag}"))?;
+    let short_sha = execute_git(&["-C", path_str, "rev-parse", "--short", "HEAD"])?;
+
+    println!(
+        "{} Checkpoint '{}' of '{}' at {}: {}",
+        "🏁".green(),
+        tag.cyan(),
+        display_name.cyan(),
+        short_sha.yellow(),
+        message
+    );
+
+    if let Some(key) = state_key {
+        let mut state = XlaudeState::load()?;
+        if let Some(info) = state.worktrees.get_mut(&key) {
+            info.checkpoints.push(tag);
+            state.save()?;
+        }
+    }
+
+    Ok(())
+}
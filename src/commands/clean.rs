@@ -1,13 +1,20 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use agentdev::git::{list_worktrees, resolve_main_repo_dir};
+use agentdev::git::{
+    default_branch, execute_git, git_metadata_present, list_worktrees, resolve_main_repo_dir,
+};
 use agentdev::state::XlaudeState;
 use agentdev::utils::execute_in_dir;
 
-pub fn handle_clean() -> Result<()> {
+/// Drop worktrees that git no longer knows about from state.
+///
+/// With `repair`, worktrees whose directory still exists but whose `.git`
+/// link is broken (typically because the main repository moved) are first
+/// re-linked with `git worktree repair` instead of being dropped.
+pub fn handle_clean(repair: bool) -> Result<()> {
     let mut state = XlaudeState::load()?;
 
     if state.worktrees.is_empty() {
@@ -15,6 +22,12 @@ pub fn handle_clean() -> Result<()> {
         return Ok(());
     }
 
+    let repaired_count = if repair {
+        repair_broken_worktrees(&state)
+    } else {
+        0
+    };
+
     println!("{} Checking for invalid worktrees...", "🔍".cyan());
 
     // Collect all actual worktrees from all repositories
@@ -54,13 +67,133 @@ pub fn handle_clean() -> Result<()> {
             removed_count,
             if removed_count == 1 { "" } else { "s" }
         );
-    } else {
+    } else if repaired_count == 0 {
         println!("{} All worktrees are valid", "✨".green());
     }
+    if repaired_count > 0 {
+        println!(
+            "{} Repaired {} worktree{}",
+            "🔧".green(),
+            repaired_count,
+            if repaired_count == 1 { "" } else { "s" }
+        );
+    }
 
     Ok(())
 }
 
+/// Re-link managed worktrees whose directory exists but whose gitdir no
+/// longer resolves, returning how many were repaired.
+///
+/// `git worktree repair` has to run from the main repository. Candidates are
+/// the repositories of the still-valid worktrees plus the repository of the
+/// current directory, so a moved repository can be repaired by running clean
+/// from inside it. A candidate is used only when it still holds the
+/// administrative directory the worktree's `.git` file points to.
+fn repair_broken_worktrees(state: &XlaudeState) -> usize {
+    let broken: Vec<_> = state
+        .worktrees
+        .iter()
+        .filter(|(_, info)| info.path.is_dir() && !git_metadata_present(&info.path))
+        .collect();
+    if broken.is_empty() {
+        return 0;
+    }
+
+    println!("{} Repairing broken worktree links...", "🔧".cyan());
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    let valid_repos = state
+        .worktrees
+        .values()
+        .filter(|info| git_metadata_present(&info.path))
+        .filter_map(|info| resolve_main_repo_dir(&info.path).ok());
+    let current_repo = std::env::current_dir()
+        .ok()
+        .and_then(|dir| resolve_main_repo_dir(&dir).ok());
+    for repo in valid_repos.chain(current_repo) {
+        if !candidates.contains(&repo) {
+            candidates.push(repo);
+        }
+    }
+
+    let mut repaired = 0;
+    for (name, info) in broken {
+        let Some(admin_name) = linked_admin_dir_name(&info.path) else {
+            println!(
+                "  {} Cannot repair {}: .git does not name a gitdir",
+                "⚠️ ".yellow(),
+                name.yellow()
+            );
+            continue;
+        };
+        let Some(repo) = candidates
+            .iter()
+            .find(|repo| repo_has_admin_dir(repo, &admin_name))
+        else {
+            println!(
+                "  {} Cannot repair {}: main repository not found (run clean --repair from inside it)",
+                "⚠️ ".yellow(),
+                name.yellow()
+            );
+            continue;
+        };
+
+        let repaired_ok = match (repo.to_str(), info.path.to_str()) {
+            (Some(repo_str), Some(path_str)) => {
+                execute_git(&["-C", repo_str, "worktree", "repair", path_str]).is_ok()
+                    && git_metadata_present(&info.path)
+            }
+            _ => false,
+        };
+        if repaired_ok {
+            println!(
+                "  {} Repaired worktree: {} ({})",
+                "🔧".green(),
+                name.cyan(),
+                info.path.display()
+            );
+            repaired += 1;
+        } else {
+            println!(
+                "  {} Failed to repair {} from {}",
+                "⚠️ ".yellow(),
+                name.yellow(),
+                repo.display()
+            );
+        }
+    }
+    repaired
+}
+
+/// Name of the `.git/worktrees/<name>` directory a linked worktree's `.git`
+/// file points to, even when that path no longer exists.
+fn linked_admin_dir_name(worktree_path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(worktree_path.join(".git")).ok()?;
+    let gitdir = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?
+        .trim();
+    Path::new(gitdir)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn repo_has_admin_dir(repo: &Path, admin_name: &str) -> bool {
+    let Some(repo_str) = repo.to_str() else {
+        return false;
+    };
+    let Ok(common_dir) = execute_git(&["-C", repo_str, "rev-parse", "--git-common-dir"]) else {
+        return false;
+    };
+    let common_dir = PathBuf::from(common_dir);
+    let common_dir = if common_dir.is_absolute() {
+        common_dir
+    } else {
+        repo.join(common_dir)
+    };
+    common_dir.join("worktrees").join(admin_name).is_dir()
+}
+
 fn collect_all_worktrees(state: &XlaudeState) -> Result<HashSet<PathBuf>> {
     let mut all_worktrees = HashSet::new();
 
@@ -82,3 +215,143 @@ fn collect_all_worktrees(state: &XlaudeState) -> Result<HashSet<PathBuf>> {
 
     Ok(all_worktrees)
 }
+
+/// Delete remote branches on `origin` that are already merged into the default
+/// branch, for every repository with a managed worktree.
+///
+/// Without `confirm` only the plan is printed.
+pub fn handle_clean_branches(confirm: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+
+    // Remote branches are shared by every worktree of a repository, so scan
+    // each repository once through its first existing managed worktree.
+    let mut repos: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    for info in state.worktrees.values() {
+        if !info.path.exists() {
+            continue;
+        }
+        if let Ok(repo_root) = resolve_main_repo_dir(&info.path) {
+            repos.entry(repo_root).or_insert_with(|| info.path.clone());
+        }
+    }
+
+    if repos.is_empty() {
+        println!("{} No managed worktrees to scan", "✨".green());
+        return Ok(());
+    }
+
+    // Never delete a branch an existing worktree is still built on
+    let active: HashSet<&str> = state
+        .worktrees
+        .values()
+        .filter(|info| info.path.exists())
+        .map(|info| info.branch.as_str())
+        .collect();
+
+    let mut plan: Vec<(PathBuf, PathBuf, Vec<String>)> = Vec::new();
+    for (repo_root, worktree_path) in repos {
+        println!(
+            "{} Checking remote branches for {}...",
+            "🔍".cyan(),
+            repo_root.display()
+        );
+        match merged_remote_branches(&worktree_path) {
+            Ok(branches) => {
+                let branches: Vec<String> = branches
+                    .into_iter()
+                    .filter(|branch| !active.contains(branch.as_str()))
+                    .collect();
+                if !branches.is_empty() {
+                    plan.push((repo_root, worktree_path, branches));
+                }
+            }
+            Err(err) => println!("  {} Skipped: {err:#}", "⚠️ ".yellow()),
+        }
+    }
+
+    let total: usize = plan.iter().map(|(_, _, branches)| branches.len()).sum();
+    if total == 0 {
+        println!("{} No merged remote branches to delete", "✨".green());
+        return Ok(());
+    }
+
+    println!();
+    println!("{} Merged remote branches:", "🧹".cyan());
+    for (repo_root, _, branches) in &plan {
+        println!(
+            "  {} {}",
+            "📦".blue(),
+            repo_root.display().to_string().bold()
+        );
+        for branch in branches {
+            println!("    {} origin/{}", "•".red(), branch.yellow());
+        }
+    }
+    println!();
+
+    if !confirm {
+        println!(
+            "{} Dry run: {} branch{} would be deleted. Re-run with --confirm to delete them.",
+            "ℹ️ ".blue(),
+            total,
+            if total == 1 { "" } else { "es" }
+        );
+        return Ok(());
+    }
+
+    let mut deleted = 0;
+    for (repo_root, worktree_path, branches) in &plan {
+        let path = worktree_path
+            .to_str()
+            .context("Worktree path contains invalid UTF-8")?;
+        println!(
+            "  {} {}",
+            "📦".blue(),
+            repo_root.display().to_string().bold()
+        );
+        for branch in branches {
+            match execute_git(&["-C", path, "push", "origin", "--delete", branch]) {
+                Ok(_) => {
+                    println!("    {} Deleted origin/{}", "✅".green(), branch);
+                    deleted += 1;
+                }
+                Err(err) => println!(
+                    "    {} Failed to delete origin/{}: {err:#}",
+                    "❌".red(),
+                    branch
+                ),
+            }
+        }
+    }
+
+    println!(
+        "{} Deleted {} of {} remote branch{}",
+        "✅".green(),
+        deleted,
+        total,
+        if total == 1 { "" } else { "es" }
+    );
+    Ok(())
+}
+
+/// Branches on `origin` merged into `origin/<default>`, excluding the default
+/// branch itself and the symbolic `origin/HEAD`.
+fn merged_remote_branches(worktree_path: &Path) -> Result<Vec<String>> {
+    let path = worktree_path
+        .to_str()
+        .context("Worktree path contains invalid UTF-8")?;
+    let default_branch = default_branch(worktree_path);
+    let base = format!("origin/{default_branch}");
+    execute_git(&["-C", path, "rev-parse", "--verify", "--quiet", &base])
+        .with_context(|| format!("{base} does not exist"))?;
+
+    let output = execute_git(&["-C", path, "branch", "-r", "--merged", &base])?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.contains("->"))
+        .filter_map(|line| line.strip_prefix("origin/"))
+        .filter(|branch| *branch != default_branch && *branch != "HEAD")
+        .map(str::to_string)
+        .collect())
+}
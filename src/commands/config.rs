@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::Command;
+
+use agentdev::config::{
+    agent_config_path, default_agent_config, load_agent_config, parse_agent_config, split_cmdline,
+};
+use agentdev::state::XlaudeState;
+
+/// Where a resolved config value comes from.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ValueSource {
+    /// Matches the bundled reference config.
+    Default,
+    /// Added or changed in the user's config file.
+    File,
+}
+
+impl ValueSource {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::File => "file",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ResolvedAgent {
+    command: String,
+    source: ValueSource,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonOutput {
+    path: String,
+    agents: BTreeMap<String, ResolvedAgent>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    templates: BTreeMap<String, String>,
+}
+
+pub fn handle_config_show(json: bool) -> Result<()> {
+    let path = agent_config_path();
+    let config = load_agent_config()?;
+    let defaults = default_agent_config();
+
+    let templates: BTreeMap<String, String> = config.worktree.templates.into_iter().collect();
+    let agents: BTreeMap<String, ResolvedAgent> = config
+        .agents
+        .into_iter()
+        .map(|(alias, command)| {
+            let source = if defaults.agents.get(&alias) == Some(&command) {
+                ValueSource::Default
+            } else {
+                ValueSource::File
+            };
+            (alias, ResolvedAgent { command, source })
+        })
+        .collect();
+
+    if json {
+        let output = JsonOutput {
+            path: path.display().to_string(),
+            agents,
+            templates,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("{}", format!("# {}", path.display()).bright_black());
+    println!(
+        "{}",
+        "# default = bundled reference value, file = set in the config file".bright_black()
+    );
+    println!();
+    println!("[agents]");
+    for (alias, agent) in &agents {
+        let source = format!("# {}", agent.source.label());
+        let source = match agent.source {
+            ValueSource::Default => source.bright_black(),
+            ValueSource::File => source.green(),
+        };
+        println!(
+            "{} = {} {}",
+            toml_key(alias),
+            toml::Value::String(agent.command.clone()),
+            source
+        );
+    }
+
+    if !templates.is_empty() {
+        println!();
+        println!("[worktree.templates]");
+        for (name, dir) in &templates {
+            println!("{} = {}", toml_key(name), toml::Value::String(dir.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the config file in the user's editor and save it only if it still parses.
+///
+/// The file is edited through a sibling draft so an invalid edit never
+/// replaces the working config.
+pub fn handle_config_edit() -> Result<()> {
+    let path = agent_config_path();
+    // Creates the reference config on first run
+    load_agent_config()?;
+    let original = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config: {}", path.display()))?;
+
+    let draft = path.with_extension("toml.edit");
+    fs::write(&draft, &original)
+        .with_context(|| format!("Failed to write draft: {}", draft.display()))?;
+
+    let editor = resolve_editor();
+    let (program, args) = split_cmdline(&editor)?;
+    println!(
+        "{} Opening {} with {}...",
+        "📝".cyan(),
+        path.display(),
+        editor.cyan()
+    );
+    let status = Command::new(&program)
+        .args(&args)
+        .arg(&draft)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{editor}'"))?;
+    if !status.success() {
+        let _ = fs::remove_file(&draft);
+        anyhow::bail!("Editor exited with {status}; config left unchanged");
+    }
+
+    let edited = fs::read_to_string(&draft)
+        .with_context(|| format!("Failed to read draft: {}", draft.display()))?;
+    if let Err(err) = parse_agent_config(&edited) {
+        anyhow::bail!(
+            "{err:#}. Config left unchanged; your edits are kept in {}",
+            draft.display()
+        );
+    }
+    fs::remove_file(&draft).ok();
+
+    if edited == original {
+        println!("{} No changes", "✨".green());
+        return Ok(());
+    }
+
+    fs::write(&path, edited)
+        .with_context(|| format!("Failed to write config: {}", path.display()))?;
+    println!("{} Config saved", "✅".green());
+    Ok(())
+}
+
+/// `$VISUAL`, then `$EDITOR`, then the editor recorded in state, then `vi`.
+fn resolve_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .chain(XlaudeState::load().ok().and_then(|state| state.editor))
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
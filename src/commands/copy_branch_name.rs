@@ -0,0 +1,28 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use agentdev::clipboard::write_clipboard;
+use agentdev::state::XlaudeState;
+
+/// Print a worktree's branch name, for PR and review workflows.
+///
+/// Stdout holds only the branch name; with `clipboard` it is also copied and
+/// the confirmation goes to stderr.
+pub fn handle_copy_branch_name(name: Option<String>, clipboard: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let (_, git_wt) = resolve_worktree_target(&state, name)?;
+    let Some(branch) = git_wt.branch.as_deref() else {
+        anyhow::bail!(
+            "Worktree '{}' is in detached HEAD state and has no branch",
+            git_wt.display_name()
+        );
+    };
+
+    println!("{branch}");
+    if clipboard {
+        write_clipboard(branch)?;
+        eprintln!("{} Copied {} to the clipboard", "📋".green(), branch.cyan());
+    }
+    Ok(())
+}
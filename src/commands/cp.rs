@@ -0,0 +1,236 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use super::lock::resolve_worktree_target;
+use agentdev::git::{DiffLineKind, classify_diff_lines, execute_git};
+use agentdev::state::XlaudeState;
+
+/// Copy uncommitted changes from one managed worktree to another.
+///
+/// The patch is checked against the destination before anything is touched,
+/// so a conflicting copy leaves the destination unchanged.
+pub fn handle_cp(source: String, dest: String, staged_only: bool) -> Result<()> {
+    if source == dest {
+        anyhow::bail!("Source and destination are the same worktree");
+    }
+
+    let state = XlaudeState::load()?;
+    let (_, source_wt) = resolve_worktree_target(&state, Some(source.clone()))?;
+    let (_, dest_wt) = resolve_worktree_target(&state, Some(dest.clone()))?;
+
+    let diff_args: &[&str] = if staged_only {
+        &["diff", "--cached", "--binary"]
+    } else {
+        &["diff", "HEAD", "--binary"]
+    };
+    let patch = run_git(&source_wt.path, diff_args, None)?;
+    if !patch.status.success() {
+        anyhow::bail!(
+            "Failed to read changes from '{}': {}",
+            source,
+            String::from_utf8_lossy(&patch.stderr).trim()
+        );
+    }
+    let patch = patch.stdout;
+
+    if patch.is_empty() {
+        let what = if staged_only {
+            "staged changes"
+        } else {
+            "uncommitted changes"
+        };
+        println!(
+            "{} Worktree '{}' has no {} to copy",
+            "✨".green(),
+            source.cyan(),
+            what
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Copying {} changes from '{}' to '{}'...",
+        "📋".cyan(),
+        if staged_only { "staged" } else { "uncommitted" },
+        source.cyan(),
+        dest.cyan()
+    );
+
+    let check = run_git(
+        &dest_wt.path,
+        &["apply", "--index", "--check", "-"],
+        Some(&patch),
+    )?;
+    if !check.status.success() {
+        let stderr = String::from_utf8_lossy(&check.stderr);
+        println!(
+            "{} Changes do not apply cleanly to '{}'; nothing was copied",
+            "❌".red(),
+            dest.cyan()
+        );
+        for line in stderr.lines() {
+            println!("  {}", line.yellow());
+        }
+        let patch_text = String::from_utf8_lossy(&patch);
+        for hunk in rejected_hunks(&patch_text, &stderr) {
+            println!();
+            for (kind, line) in classify_diff_lines(&hunk) {
+                let line = match kind {
+                    DiffLineKind::Header => line.bold(),
+                    DiffLineKind::Hunk => line.cyan(),
+                    DiffLineKind::Added => line.green(),
+                    DiffLineKind::Removed => line.red(),
+                    DiffLineKind::Context => line.normal(),
+                };
+                println!("  {line}");
+            }
+        }
+        anyhow::bail!("Patch from '{}' does not apply to '{}'", source, dest);
+    }
+
+    let applied = run_git(&dest_wt.path, &["apply", "--index", "-"], Some(&patch))?;
+    if !applied.status.success() {
+        anyhow::bail!(
+            "Failed to apply changes to '{}': {}",
+            dest,
+            String::from_utf8_lossy(&applied.stderr).trim()
+        );
+    }
+
+    let (files, added, removed) = summarize_patch(&dest_wt.path, &patch)?;
+    println!(
+        "{} Copied {} file{} ({}, {}) from '{}' to '{}'",
+        "✅".green(),
+        files,
+        if files == 1 { "" } else { "s" },
+        format!("+{added}").green(),
+        format!("-{removed}").red(),
+        source.cyan(),
+        dest.cyan()
+    );
+
+    if !staged_only {
+        let source_path = source_wt
+            .path
+            .to_str()
+            .context("Path contains invalid UTF-8")?;
+        let untracked = execute_git(&[
+            "-C",
+            source_path,
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+        ])?;
+        let untracked = untracked.lines().filter(|l| !l.is_empty()).count();
+        if untracked > 0 {
+            println!(
+                "  {} {} untracked file{} in '{}' not copied (stage them with git add -N to include)",
+                "ℹ️ ".blue(),
+                untracked,
+                if untracked == 1 { "" } else { "s" },
+                source
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Run git in `dir`, optionally feeding `input` on stdin, keeping the raw output.
+fn run_git(dir: &Path, args: &[&str], input: Option<&[u8]>) -> Result<Output> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to spawn git {}", args.join(" ")))?;
+
+    if let Some(input) = input {
+        child
+            .stdin
+            .take()
+            .context("Failed to open git stdin")?
+            .write_all(input)
+            .context("Failed to write patch to git")?;
+    }
+
+    child
+        .wait_with_output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))
+}
+
+/// Files touched plus added/removed line counts, from `git apply --numstat`.
+fn summarize_patch(dir: &Path, patch: &[u8]) -> Result<(usize, u64, u64)> {
+    let output = run_git(dir, &["apply", "--numstat", "-"], Some(patch))?;
+    let stats = String::from_utf8_lossy(&output.stdout);
+    let mut files = 0;
+    let mut added = 0;
+    let mut removed = 0;
+    for line in stats.lines() {
+        let mut parts = line.split('\t');
+        // Binary files report "-" for both counts
+        added += parts
+            .next()
+            .and_then(|n| n.parse::<u64>().ok())
+            .unwrap_or(0);
+        removed += parts
+            .next()
+            .and_then(|n| n.parse::<u64>().ok())
+            .unwrap_or(0);
+        files += 1;
+    }
+    Ok((files, added, removed))
+}
+
+/// Hunks named by `error: patch failed: <path>:<line>` in git apply's stderr.
+fn rejected_hunks(patch: &str, stderr: &str) -> Vec<String> {
+    let failures: Vec<(&str, &str)> = stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix("error: patch failed: "))
+        .filter_map(|rest| rest.rsplit_once(':'))
+        .collect();
+
+    let mut hunks = Vec::new();
+    let mut current_file: Option<&str> = None;
+    let mut current_hunk: Option<String> = None;
+
+    for line in patch.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            hunks.extend(current_hunk.take());
+            current_file = header
+                .split_once(" b/")
+                .map(|(a, _)| a.trim_start_matches("a/"));
+            continue;
+        }
+        if let Some(range) = line.strip_prefix("@@ -") {
+            hunks.extend(current_hunk.take());
+            let start = range.split([',', ' ']).next().unwrap_or_default();
+            let rejected = current_file.is_some_and(|file| {
+                failures
+                    .iter()
+                    .any(|(path, line)| *path == file && *line == start)
+            });
+            if rejected {
+                current_hunk = Some(format!("{}\n{line}", current_file.unwrap_or_default()));
+            }
+            continue;
+        }
+        if let Some(hunk) = current_hunk.as_mut() {
+            hunk.push('\n');
+            hunk.push_str(line);
+        }
+    }
+    hunks.extend(current_hunk.take());
+
+    hunks
+}
@@ -3,26 +3,70 @@ use chrono::Utc;
 use colored::Colorize;
 use dialoguer::Input;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use crate::input::{get_command_arg, is_piped_input};
+use agentdev::audit::{AuditAction, AuditEntry};
+use agentdev::config::{
+    MAX_WORKTREES_ENV, TemplateSource, load_agent_config, resolve_max_worktrees,
+};
 use agentdev::tmux::TmuxManager;
 use agentdev::git::{
-    execute_git, extract_repo_name_from_url, get_repo_name, list_worktrees, update_submodules,
+    default_branch, execute_git, extract_repo_name_from_url, get_repo_name, list_worktrees,
+    update_submodules,
 };
 use agentdev::state::{WorktreeInfo, XlaudeState};
 use agentdev::utils::{resolve_agent_command_with_override, sanitize_branch_name};
 
-pub fn handle_create(name: Option<String>, agent: Option<String>) -> Result<()> {
-    handle_create_in_dir(name, None, agent)
+/// How `create` should treat git submodules in the new worktree
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SubmoduleMode {
+    /// Update submodules when `.gitmodules` exists; failures are only warnings
+    #[default]
+    Auto,
+    /// Always run the update and fail if it does not succeed
+    Force,
+    /// Never touch submodules
+    Skip,
+}
+
+impl SubmoduleMode {
+    pub fn from_flags(submodules: bool, no_submodules: bool) -> Self {
+        match (submodules, no_submodules) {
+            (true, _) => SubmoduleMode::Force,
+            (_, true) => SubmoduleMode::Skip,
+            _ => SubmoduleMode::Auto,
+        }
+    }
+}
+
+/// Template from `[worktree.templates]` or `[templates]` to copy into the new worktree
+#[derive(Clone, Debug)]
+pub struct TemplateOptions {
+    pub name: String,
+    /// Overwrite files tracked by git
+    pub force: bool,
+}
+
+pub fn handle_create(
+    name: Option<String>,
+    agent: Option<String>,
+    submodules: SubmoduleMode,
+    template: Option<TemplateOptions>,
+    from: Option<String>,
+) -> Result<()> {
+    handle_create_in_dir(name, None, agent, submodules, template, from)
 }
 
 pub fn handle_create_in_dir(
     name: Option<String>,
     repo_path: Option<PathBuf>,
     agent: Option<String>,
+    submodules: SubmoduleMode,
+    template: Option<TemplateOptions>,
+    from: Option<String>,
 ) -> Result<()> {
-    handle_create_in_dir_quiet(name, repo_path, false, agent)?;
+    handle_create_in_dir_quiet(name, repo_path, false, agent, submodules, template, from)?;
     Ok(())
 }
 
@@ -32,7 +76,50 @@ pub fn handle_create_in_dir_quiet(
     repo_path: Option<PathBuf>,
     quiet: bool,
     agent: Option<String>,
+    submodules: SubmoduleMode,
+    template: Option<TemplateOptions>,
+    from: Option<String>,
 ) -> Result<String> {
+    let mut audit = AuditEntry::new(AuditAction::Create);
+    let result = create_worktree(
+        name, repo_path, quiet, agent, submodules, template, from, &mut audit,
+    );
+    audit.finish(&result);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_worktree(
+    name: Option<String>,
+    repo_path: Option<PathBuf>,
+    quiet: bool,
+    agent: Option<String>,
+    submodules: SubmoduleMode,
+    template: Option<TemplateOptions>,
+    from: Option<String>,
+    audit: &mut AuditEntry,
+) -> Result<String> {
+    // Resolve the template up front so a typo does not leave a half-set-up worktree
+    let template = match template {
+        Some(options) => {
+            let files = match load_agent_config()?.template(&options.name)? {
+                TemplateSource::Directory(dir) => template_dir_files(&dir)?,
+                TemplateSource::Files(files) => {
+                    let repo_root = match &repo_path {
+                        Some(path) => path.clone(),
+                        None => PathBuf::from(
+                            execute_git(&["rev-parse", "--show-toplevel"])
+                                .context("Not in a git repository")?,
+                        ),
+                    };
+                    resolve_template_files(&options.name, &repo_root, files)?
+                }
+            };
+            Some((options, files))
+        }
+        None => None,
+    };
+
     // Helper to execute git in the right directory using git -C
     let exec_git = |args: &[&str]| -> Result<String> {
         if let Some(ref path) = repo_path {
@@ -61,16 +148,31 @@ pub fn handle_create_in_dir_quiet(
     } else {
         get_repo_name().context("Not in a git repository")?
     };
+    audit.repo = Some(repo_name.clone());
+
+    // Resolve --from up front so a typo fails before anything is created
+    let from = match from {
+        Some(reference) => {
+            exec_git(&[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{reference}^{{commit}}"),
+            ])
+            .map_err(|_| anyhow::anyhow!("Unknown ref '{reference}'"))?;
+            Some(reference)
+        }
+        None => None,
+    };
 
     // Only check base branch if no repo_path is provided (i.e., running from CLI in current directory)
     // When invoked programmatically (e.g. from the web UI) with a specific repo_path,
     // we don't need this check as we'll create the worktree from the default branch.
-    if repo_path.is_none() {
+    // An explicit --from makes the current branch irrelevant.
+    if repo_path.is_none() && from.is_none() {
         let current_branch = exec_git(&["branch", "--show-current"])?;
-        let default_branch = exec_git(&["symbolic-ref", "refs/remotes/origin/HEAD"])
-            .ok()
-            .and_then(|s| s.strip_prefix("refs/remotes/origin/").map(String::from))
-            .unwrap_or_else(|| "main".to_string());
+        let cwd = std::env::current_dir().context("Failed to read current directory")?;
+        let default_branch = default_branch(&cwd);
 
         let base_branches = ["main", "master", "develop", &default_branch];
         if !base_branches.contains(&current_branch.as_str()) {
@@ -109,9 +211,23 @@ pub fn handle_create_in_dir_quiet(
 
     // Sanitize the branch name for use in directory names
     let worktree_name = sanitize_branch_name(&branch_name);
+    audit.branch = Some(branch_name.clone());
+    audit.worktree = Some(worktree_name.clone());
 
-    // Check if a worktree with this name already exists in xlaude state
     let state = XlaudeState::load()?;
+    if let Some(limit) = resolve_max_worktrees()?
+        && state.worktrees.len() >= limit
+    {
+        anyhow::bail!(
+            "Worktree limit reached: {} worktrees exist and the limit is {} ({} or max_worktrees). \
+             Delete old worktrees with 'agentdev worktree delete <name>' or 'agentdev worktree gc' first.",
+            state.worktrees.len(),
+            limit,
+            MAX_WORKTREES_ENV
+        );
+    }
+
+    // Check if a worktree with this name already exists in xlaude state
     let key = XlaudeState::make_key(&repo_name, &worktree_name);
     if state.worktrees.contains_key(&key) {
         anyhow::bail!(
@@ -180,6 +296,13 @@ pub fn handle_create_in_dir_quiet(
     .is_ok();
 
     if branch_already_exists {
+        if let Some(reference) = &from {
+            anyhow::bail!(
+                "Branch '{}' already exists; --from {} only applies to new branches",
+                branch_name,
+                reference
+            );
+        }
         if !quiet {
             println!(
                 "{} Creating worktree '{}' from existing branch '{}'...",
@@ -198,14 +321,13 @@ pub fn handle_create_in_dir_quiet(
             );
         }
 
-        // When repo_path is provided, create branch from the default branch
-        // Otherwise create from current branch
-        if repo_path.is_some() {
-            // Get the default branch
-            let default_branch = exec_git(&["symbolic-ref", "refs/remotes/origin/HEAD"])
-                .ok()
-                .and_then(|s| s.strip_prefix("refs/remotes/origin/").map(String::from))
-                .unwrap_or_else(|| "main".to_string());
+        // Branch off --from when given; otherwise, when repo_path is provided,
+        // create branch from the default branch, else from the current branch
+        if let Some(reference) = &from {
+            exec_git(&["branch", &branch_name, reference])
+                .with_context(|| format!("Failed to create branch from '{reference}'"))?;
+        } else if let Some(path) = &repo_path {
+            let default_branch = default_branch(path);
 
             // Create branch from the default branch
             exec_git(&[
@@ -236,20 +358,50 @@ pub fn handle_create_in_dir_quiet(
     // Get absolute path
     let worktree_path = worktree_dir_path.clone();
 
-    // Update submodules if they exist
-    if let Err(e) = update_submodules(&worktree_path) {
-        if !quiet {
-            println!(
-                "{} Warning: Failed to update submodules: {}",
-                "⚠️".yellow(),
-                e
-            );
+    // Update submodules according to the requested mode
+    let has_submodules = worktree_path.join(".gitmodules").exists();
+    match submodules {
+        SubmoduleMode::Skip => {
+            if has_submodules && !quiet {
+                println!(
+                    "{} Skipped submodule update (--no-submodules)",
+                    "⏭️".yellow()
+                );
+            }
         }
-    } else {
-        // Check if submodules were actually updated
-        let gitmodules = worktree_path.join(".gitmodules");
-        if gitmodules.exists() && !quiet {
-            println!("{} Updated submodules", "📦".green());
+        SubmoduleMode::Force => {
+            if let Err(e) = update_submodules(&worktree_path) {
+                // Nothing is recorded in state yet, so undo the worktree (and the
+                // branch if we created it) instead of leaving them orphaned
+                let _ = exec_git(&["worktree", "remove", "--force", &worktree_dir]);
+                if !branch_already_exists {
+                    let _ = exec_git(&["branch", "-D", &branch_name]);
+                }
+                return Err(e.context(format!(
+                    "Submodule update failed; removed the new worktree at {}",
+                    worktree_path.display()
+                )));
+            }
+            if !quiet {
+                if has_submodules {
+                    println!("{} Updated submodules", "📦".green());
+                } else {
+                    println!("{} No submodules to update", "📦".green());
+                }
+            }
+        }
+        SubmoduleMode::Auto => {
+            if let Err(e) = update_submodules(&worktree_path) {
+                if !quiet {
+                    println!(
+                        "{} Warning: Failed to update submodules: {}",
+                        "⚠️".yellow(),
+                        e
+                    );
+                }
+            } else if has_submodules && !quiet {
+                println!("{} Updated submodules", "📦".green());
+            }
         }
     }
 
@@ -282,6 +434,14 @@ pub fn handle_create_in_dir_quiet(
             task_name: None,
             initial_prompt: None,
             agent_alias: None,
+            locked: false,
+            lock_reason: None,
+            last_agent_activity: None,
+            base_ref: from,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
         },
     );
     state.save()?;
@@ -294,6 +454,26 @@ pub fn handle_create_in_dir_quiet(
         );
     }
 
+    if let Some((options, files)) = &template {
+        let copied =
+            copy_template_files(files, &worktree_path, options.force).with_context(|| {
+                format!(
+                    "Worktree created at {} but template '{}' was not applied",
+                    worktree_path.display(),
+                    options.name
+                )
+            })?;
+        if !quiet {
+            println!(
+                "{} Copied {} file{} from template '{}'",
+                "📄".green(),
+                copied,
+                if copied == 1 { "" } else { "s" },
+                options.name.cyan()
+            );
+        }
+    }
+
     // Launch agent in tmux (detached) unless in test mode or explicitly disabled
     if !quiet {
         let skip_launch = std::env::var("XLAUDE_TEST_MODE").is_ok()
@@ -341,3 +521,99 @@ pub fn handle_create_in_dir_quiet(
 
     Ok(worktree_name)
 }
+
+/// Pair each template file with its destination: relative paths come from the
+/// main repository and keep their location, other files land in the worktree root.
+fn resolve_template_files(
+    name: &str,
+    repo_root: &Path,
+    files: Vec<PathBuf>,
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    files
+        .into_iter()
+        .map(|file| {
+            let (source, target) = if file.is_relative() {
+                let escapes = file.components().any(|component| {
+                    !matches!(component, Component::Normal(_) | Component::CurDir)
+                });
+                if escapes {
+                    anyhow::bail!(
+                        "Template '{name}' file must stay inside the repository: {}",
+                        file.display()
+                    );
+                }
+                (repo_root.join(&file), file)
+            } else {
+                let file_name = file
+                    .file_name()
+                    .with_context(|| {
+                        format!("Template '{name}' file has no name: {}", file.display())
+                    })?
+                    .into();
+                (file, file_name)
+            };
+            if !source.is_file() {
+                anyhow::bail!("Template '{name}' file not found: {}", source.display());
+            }
+            Ok((source, target))
+        })
+        .collect()
+}
+
+/// Every file under `template_dir`, paired with its path relative to it.
+fn template_dir_files(template_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(template_dir).follow_links(true) {
+        let entry = entry.context("Failed to read template directory")?;
+        if entry.file_type().is_file() {
+            let relative = entry
+                .path()
+                .strip_prefix(template_dir)
+                .context("Template entry outside template directory")?
+                .to_path_buf();
+            files.push((entry.into_path(), relative));
+        }
+    }
+    Ok(files)
+}
+
+/// Copy template files into `worktree`, returning how many were copied.
+///
+/// Nothing is copied when a file would replace one tracked by git, unless `force`.
+fn copy_template_files(
+    files: &[(PathBuf, PathBuf)],
+    worktree: &Path,
+    force: bool,
+) -> Result<usize> {
+    if !force {
+        let existing: Vec<&str> = files
+            .iter()
+            .filter(|(_, relative)| worktree.join(relative).exists())
+            .map(|(_, relative)| relative.to_str().context("Path contains invalid UTF-8"))
+            .collect::<Result<_>>()?;
+        if !existing.is_empty() {
+            let worktree_str = worktree.to_str().context("Path contains invalid UTF-8")?;
+            let mut args = vec!["-C", worktree_str, "ls-files", "--"];
+            args.extend(existing);
+            let tracked = execute_git(&args)?;
+            if !tracked.trim().is_empty() {
+                let tracked: Vec<&str> = tracked.lines().collect();
+                anyhow::bail!(
+                    "Template would overwrite tracked files: {} (use --force-template to overwrite)",
+                    tracked.join(", ")
+                );
+            }
+        }
+    }
+
+    for (source, relative) in files {
+        let target = worktree.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::copy(source, &target)
+            .with_context(|| format!("Failed to copy {}", relative.display()))?;
+    }
+    Ok(files.len())
+}
@@ -3,7 +3,8 @@ use colored::Colorize;
 use std::io;
 use std::path::PathBuf;
 
-use crate::input::{get_command_arg, smart_confirm};
+use crate::input::{get_command_arg, is_piped_input, select_worktree, smart_confirm};
+use agentdev::audit::{AuditAction, AuditEntry};
 use agentdev::discovery::GitWorktree;
 use agentdev::git::{execute_git, has_unpushed_commits, is_working_tree_clean};
 use agentdev::state::XlaudeState;
@@ -47,23 +48,57 @@ impl DeletionConfig {
     }
 }
 
-pub fn handle_delete(name: Option<String>) -> Result<()> {
+pub fn handle_delete(name: Option<String>, force: bool) -> Result<()> {
+    let mut audit = AuditEntry::new(AuditAction::Delete);
+    let result = delete_worktree(name, force, &mut audit);
+    audit.finish(&result);
+    result
+}
+
+fn delete_worktree(name: Option<String>, force: bool, audit: &mut AuditEntry) -> Result<()> {
     let state = XlaudeState::load()?;
 
     // Get name from CLI args or pipe
     let target_name = get_command_arg(name)?;
+    audit.worktree = target_name.clone();
 
     // Resolve worktree - returns GitWorktree (from git) and optional state_key
     let (state_key, git_wt) = resolve_worktree_for_delete(&state, target_name)?;
     let config = DeletionConfig::from_git_worktree(&git_wt)?;
 
     let display_name = git_wt.display_name();
+    audit.repo = Some(git_wt.repo_name());
+    audit.branch = git_wt.branch.clone();
+    audit.worktree = Some(display_name.clone());
     println!(
         "{} Checking worktree '{}'...",
         "🔍".yellow(),
         display_name.cyan()
     );
 
+    // Locked worktrees are protected unless the caller explicitly forces deletion
+    let lock_reason = resolve_lock_reason(&state, state_key.as_deref(), &git_wt);
+    if let Some(ref reason) = lock_reason {
+        if !force {
+            let detail = if reason.is_empty() {
+                String::new()
+            } else {
+                format!(" ({reason})")
+            };
+            anyhow::bail!(
+                "Worktree '{}' is locked{}. Run 'agentdev worktree unlock {}' first or pass --force.",
+                display_name,
+                detail,
+                display_name
+            );
+        }
+        println!(
+            "{} Worktree '{}' is locked; deleting anyway (--force)",
+            "⚠️ ".yellow(),
+            display_name.cyan()
+        );
+    }
+
     // Proactively stop tmux session for this worktree if running
     let tmux = TmuxManager::new();
     let _ = tmux.kill_session(&display_name);
@@ -72,6 +107,7 @@ pub fn handle_delete(name: Option<String>) -> Result<()> {
     if !config.worktree_exists {
         if !handle_missing_worktree(&git_wt)? {
             println!("{} Cancelled", "❌".red());
+            audit.cancelled();
             return Ok(());
         }
     } else {
@@ -88,10 +124,20 @@ pub fn handle_delete(name: Option<String>) -> Result<()> {
 
         if !confirm_deletion(&git_wt, &checks, &config)? {
             println!("{} Cancelled", "❌".red());
+            audit.cancelled();
             return Ok(());
         }
     }
 
+    // git refuses to remove locked worktrees, so drop the lock first
+    if lock_reason.is_some() && config.worktree_exists {
+        let path_str = git_wt.path.to_str().context("Path contains invalid UTF-8")?;
+        execute_in_dir(&git_wt.repo_root, || {
+            execute_git(&["worktree", "unlock", path_str]).map(|_| ())
+        })
+        .context("Failed to unlock worktree before deletion")?;
+    }
+
     // Execute deletion
     perform_deletion(&git_wt, &config)?;
 
@@ -137,12 +183,21 @@ fn resolve_worktree_for_delete(
 
         Ok((Some(key), git_wt))
     } else {
-        // No name: try current directory
-        let git_wt = GitWorktree::from_current_dir()?
-            .ok_or_else(|| anyhow::anyhow!(
+        // No name: try current directory, then let the user pick one
+        let Some(git_wt) = GitWorktree::from_current_dir()? else {
+            if !is_piped_input()
+                && let Some((key, info)) = select_worktree("Select a worktree to delete", state)?
+            {
+                let git_wt = GitWorktree::from_path(&info.path)?.ok_or_else(|| {
+                    anyhow::anyhow!("Path '{}' is not a git worktree", info.path.display())
+                })?;
+                return Ok((Some(key), git_wt));
+            }
+            anyhow::bail!(
                 "Current directory is not a git worktree. \
                  If you're in the main repository, specify the worktree name."
-            ))?;
+            );
+        };
 
         // Try to find matching state entry
         let state_key = find_state_key_by_path(state, &git_wt.path);
@@ -151,6 +206,25 @@ fn resolve_worktree_for_delete(
     }
 }
 
+/// Determine whether the worktree is locked, preferring git's view and falling
+/// back to the flag recorded in agentdev state.
+fn resolve_lock_reason(
+    state: &XlaudeState,
+    state_key: Option<&str>,
+    git_wt: &GitWorktree,
+) -> Option<String> {
+    if git_wt.path.exists()
+        && let Ok(status) = git_wt.lock_status()
+    {
+        return status;
+    }
+
+    state_key
+        .and_then(|key| state.worktrees.get(key))
+        .filter(|info| info.locked)
+        .map(|info| info.lock_reason.clone().unwrap_or_default())
+}
+
 /// Find the state key for a worktree by its path
 fn find_state_key_by_path(state: &XlaudeState, path: &PathBuf) -> Option<String> {
     let path_canon = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
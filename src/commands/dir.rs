@@ -1,9 +1,38 @@
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 
-use crate::input::{get_command_arg, smart_select};
+use crate::input::{get_command_arg, select_worktree};
 use agentdev::state::XlaudeState;
+use agentdev::utils::shell_quote;
 
-pub fn handle_dir(name: Option<String>) -> Result<()> {
+/// Env var named by `dir --format export`.
+const WORKTREE_PATH_VAR: &str = "AGENTDEV_WORKTREE";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum DirFormat {
+    /// The bare path
+    Plain,
+    /// `cd '<path>'`, ready for `eval`
+    Cd,
+    /// `AGENTDEV_WORKTREE='<path>'`, ready for `eval`
+    Export,
+}
+
+impl DirFormat {
+    fn render(self, path: &str) -> String {
+        match self {
+            Self::Plain => path.to_string(),
+            Self::Cd => format!("cd {}", shell_quote(path)),
+            Self::Export => format!("{WORKTREE_PATH_VAR}={}", shell_quote(path)),
+        }
+    }
+}
+
+/// Print a worktree's path. Scripts rely on stdout holding the path and
+/// nothing else; prompts and warnings go to stderr.
+///
+/// `format` wraps the path in a shell snippet for `eval "$(agentdev dir ...)"`.
+pub fn handle_dir(name: Option<String>, format: DirFormat) -> Result<()> {
     let state = XlaudeState::load()?;
 
     if state.worktrees.is_empty() {
@@ -23,15 +52,9 @@ pub fn handle_dir(name: Option<String>) -> Result<()> {
             .map(|(k, w)| (k.clone(), w.clone()))
             .context(format!("Worktree '{n}' not found"))?
     } else {
-        // Interactive selection - show repo/name format
-        let worktree_list = state.prioritized_worktree_list();
-
-        let selection = smart_select("Select a worktree", &worktree_list, |(_, info)| {
-            format!("{}/{}", info.repo_name, info.name)
-        })?;
-
-        match selection {
-            Some(idx) => worktree_list[idx].clone(),
+        // Interactive selection
+        match select_worktree("Select a worktree", &state)? {
+            Some(entry) => entry,
             None => anyhow::bail!(
                 "Interactive selection not available in non-interactive mode. Please specify a worktree name."
             ),
@@ -40,7 +63,7 @@ pub fn handle_dir(name: Option<String>) -> Result<()> {
 
     // Output only the path - no decorations, no colors
     // This makes it easy to use in shell commands: cd $(xlaude dir name)
-    println!("{}", worktree_info.path.display());
+    println!("{}", format.render(&worktree_info.path.to_string_lossy()));
 
     Ok(())
 }
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use colored::{ColoredString, Colorize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::process::Command;
+
+use agentdev::config::{agent_config_path, parse_agent_config, split_cmdline};
+use agentdev::state::{XlaudeState, get_default_agent};
+use agentdev::tmux::TmuxManager;
+
+const MIN_GIT_VERSION: (u32, u32) = (2, 30);
+const MIN_TMUX_VERSION: (u32, u32) = (3, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> ColoredString {
+        match self {
+            Self::Pass => "PASS".green(),
+            Self::Warn => "WARN".yellow(),
+            Self::Fail => "FAIL".red().bold(),
+        }
+    }
+}
+
+struct Check {
+    name: &'static str,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Check the environment agentdev depends on and report each result.
+///
+/// Fails (exit code 1) when any check fails; warnings alone do not.
+pub fn handle_doctor() -> Result<()> {
+    let state = XlaudeState::load();
+
+    let mut checks = vec![check_git(), check_tmux()];
+    match &state {
+        Ok(state) => {
+            checks.push(Check::new(
+                "state file",
+                CheckStatus::Pass,
+                format!("{} managed worktree(s)", state.worktrees.len()),
+            ));
+            checks.push(check_agent(state));
+            checks.push(check_duplicate_paths(state));
+            checks.push(check_orphaned_sessions(state));
+        }
+        Err(err) => checks.push(Check::new(
+            "state file",
+            CheckStatus::Fail,
+            format!("{err:#}"),
+        )),
+    }
+    checks.push(check_config());
+
+    let name_width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in &checks {
+        println!(
+            "{}  {:<name_width$}  {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let (passed, warned, failed) = (
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+    );
+    println!();
+    println!(
+        "{} passed, {} warning(s), {} failed",
+        passed.to_string().green(),
+        warned.to_string().yellow(),
+        failed.to_string().red()
+    );
+
+    if failed > 0 {
+        anyhow::bail!("{failed} doctor check(s) failed");
+    }
+    Ok(())
+}
+
+fn check_git() -> Check {
+    let Some(output) = command_stdout("git", &["--version"]) else {
+        return Check::new("git", CheckStatus::Fail, "git is not installed");
+    };
+    version_check("git", &output, MIN_GIT_VERSION, CheckStatus::Fail)
+}
+
+fn check_tmux() -> Check {
+    let Some(output) = command_stdout("tmux", &["-V"]) else {
+        return Check::new(
+            "tmux",
+            CheckStatus::Warn,
+            "tmux is not installed; agent sessions and `worktree send` are unavailable",
+        );
+    };
+    version_check("tmux", &output, MIN_TMUX_VERSION, CheckStatus::Fail)
+}
+
+fn version_check(
+    name: &'static str,
+    output: &str,
+    minimum: (u32, u32),
+    too_old: CheckStatus,
+) -> Check {
+    let required = format!("{}.{}", minimum.0, minimum.1);
+    match parse_version(output) {
+        Some(version) if version >= minimum => Check::new(
+            name,
+            CheckStatus::Pass,
+            format!("{}.{} (>= {required})", version.0, version.1),
+        ),
+        Some(version) => Check::new(
+            name,
+            too_old,
+            format!("{}.{} is older than {required}", version.0, version.1),
+        ),
+        None => Check::new(
+            name,
+            CheckStatus::Warn,
+            format!("could not parse version from '{}'", output.trim()),
+        ),
+    }
+}
+
+/// First `major.minor` in output such as `git version 2.43.0` or `tmux 3.3a`.
+fn parse_version(output: &str) -> Option<(u32, u32)> {
+    output.split_whitespace().find_map(|word| {
+        let mut parts = word.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor: String = parts
+            .next()?
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        Some((major, minor.parse().ok()?))
+    })
+}
+
+fn check_agent(state: &XlaudeState) -> Check {
+    let cmdline = state.agent.clone().unwrap_or_else(get_default_agent);
+    let program = match split_cmdline(&cmdline) {
+        Ok((program, _)) => program,
+        Err(err) => return Check::new("agent", CheckStatus::Fail, format!("{err:#}")),
+    };
+    match which::which(&program) {
+        Ok(path) => Check::new(
+            "agent",
+            CheckStatus::Pass,
+            format!("{program} ({})", path.display()),
+        ),
+        Err(_) => Check::new(
+            "agent",
+            CheckStatus::Fail,
+            format!("'{program}' from agent command '{cmdline}' is not on PATH"),
+        ),
+    }
+}
+
+fn check_duplicate_paths(state: &XlaudeState) -> Check {
+    let mut by_path: BTreeMap<_, Vec<&str>> = BTreeMap::new();
+    for (key, info) in &state.worktrees {
+        let path = info
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| info.path.clone());
+        by_path.entry(path).or_default().push(key);
+    }
+
+    let duplicates: Vec<String> = by_path
+        .into_iter()
+        .filter(|(_, keys)| keys.len() > 1)
+        .map(|(path, mut keys)| {
+            keys.sort();
+            format!("{} ({})", path.display(), keys.join(", "))
+        })
+        .collect();
+
+    if duplicates.is_empty() {
+        Check::new("worktree paths", CheckStatus::Pass, "no duplicates")
+    } else {
+        Check::new(
+            "worktree paths",
+            CheckStatus::Fail,
+            format!("shared by several entries: {}", duplicates.join("; ")),
+        )
+    }
+}
+
+fn check_orphaned_sessions(state: &XlaudeState) -> Check {
+    if !TmuxManager::is_available() {
+        return Check::new(
+            "tmux sessions",
+            CheckStatus::Warn,
+            "skipped; tmux not found",
+        );
+    }
+    let tmux = TmuxManager::new();
+    let sessions = match tmux.list_sessions() {
+        Ok(sessions) => sessions,
+        Err(err) => return Check::new("tmux sessions", CheckStatus::Warn, format!("{err:#}")),
+    };
+
+    let known: HashSet<String> = state
+        .worktrees
+        .values()
+        .map(|info| tmux.session_name(&info.name))
+        .collect();
+    let orphaned: Vec<String> = sessions
+        .iter()
+        .map(|session| tmux.session_name(&session.project))
+        .filter(|name| !known.contains(name))
+        .collect();
+
+    if orphaned.is_empty() {
+        Check::new(
+            "tmux sessions",
+            CheckStatus::Pass,
+            format!("{} session(s), none orphaned", sessions.len()),
+        )
+    } else {
+        Check::new(
+            "tmux sessions",
+            CheckStatus::Warn,
+            format!(
+                "no worktree for {}; stop with `tmux kill-session -t <name>`",
+                orphaned.join(", ")
+            ),
+        )
+    }
+}
+
+fn check_config() -> Check {
+    let path = agent_config_path();
+    if !path.exists() {
+        return Check::new(
+            "config",
+            CheckStatus::Pass,
+            format!("{} not created yet; using defaults", path.display()),
+        );
+    }
+    let parsed = fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| parse_agent_config(&content));
+    match parsed {
+        Ok(config) => Check::new(
+            "config",
+            CheckStatus::Pass,
+            format!("{} ({} agent(s))", path.display(), config.agents.len()),
+        ),
+        Err(err) => Check::new(
+            "config",
+            CheckStatus::Fail,
+            format!("{}: {err:#}", path.display()),
+        ),
+    }
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -11,7 +11,7 @@ use agentdev::process_registry::{
     MAX_PROCESSES_PER_WORKTREE, ProcessRecord, ProcessRegistry, ProcessStatus,
 };
 
-use crate::input::smart_select;
+use crate::input::select_worktree;
 use agentdev::state::{WorktreeInfo, XlaudeState};
 
 /// Execute an arbitrary command inside a managed worktree.
@@ -59,6 +59,7 @@ pub fn handle_exec(worktree_flag: Option<String>, mut raw_args: Vec<String>) ->
         .context("Command tokens unexpectedly empty")?;
 
     let worktree_key = XlaudeState::make_key(&worktree.repo_name, &worktree.name);
+    XlaudeState::record_activity(&worktree_key)?;
     let mut record = ProcessRecord::new(
         worktree_key,
         worktree.name.clone(),
@@ -238,14 +239,8 @@ fn resolve_target_worktree(state: &XlaudeState, explicit: Option<String>) -> Res
         return Ok(info);
     }
 
-    let worktree_list = state.prioritized_worktree_list();
-
-    let selection = smart_select("Select a worktree", &worktree_list, |(_, info)| {
-        format!("{}/{}", info.repo_name, info.name)
-    })?;
-
-    match selection {
-        Some(idx) => Ok(worktree_list[idx].1.clone()),
+    match select_worktree("Select a worktree", state)? {
+        Some((_, info)) => Ok(info),
         None => bail!(
             "Interactive selection not available. Please specify a worktree using '--worktree <name>'."
         ),
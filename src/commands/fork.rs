@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use std::fs;
+
+use super::lock::resolve_worktree_target;
+use agentdev::git::{execute_git, update_submodules};
+use agentdev::state::{WorktreeInfo, XlaudeState};
+use agentdev::utils::sanitize_branch_name;
+
+/// Create a worktree on a new branch at another worktree's current HEAD.
+///
+/// The fork inherits the source's task and initial prompt so another agent can
+/// pick up where the first one left off. Uncommitted changes stay behind.
+pub fn handle_fork(source: String, new_name: String) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let (source_key, source_wt) = resolve_worktree_target(&state, Some(source.clone()))?;
+    let source_info = source_key
+        .and_then(|key| state.worktrees.get(&key))
+        .cloned()
+        .context(format!("Worktree '{source}' not found in agentdev state"))?;
+
+    let source_path = source_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let head = execute_git(&["-C", source_path, "rev-parse", "HEAD"])
+        .with_context(|| format!("Failed to read HEAD of '{source}'"))?;
+    let short_head = execute_git(&["-C", source_path, "rev-parse", "--short", "HEAD"])?;
+
+    let branch_name = new_name.trim().to_string();
+    if branch_name.is_empty() {
+        anyhow::bail!("Name for the forked worktree cannot be empty");
+    }
+    let worktree_name = sanitize_branch_name(&branch_name);
+    let repo_name = source_info.repo_name.clone();
+    let key = XlaudeState::make_key(&repo_name, &worktree_name);
+    if state.worktrees.contains_key(&key) {
+        anyhow::bail!(
+            "A worktree named '{}' already exists for repository '{}'",
+            worktree_name,
+            repo_name
+        );
+    }
+
+    let repo_root = source_wt
+        .repo_root
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let worktree_base_dir = source_wt
+        .repo_root
+        .parent()
+        .context("Repository directory has no parent directory")?
+        .join(format!("{repo_name}.worktrees"));
+    let worktree_path = worktree_base_dir.join(&worktree_name);
+    if worktree_path.exists() {
+        anyhow::bail!(
+            "Directory '{}' already exists. Please choose a different name.",
+            worktree_path.display()
+        );
+    }
+    if execute_git(&[
+        "-C",
+        repo_root,
+        "show-ref",
+        "--verify",
+        "--quiet",
+        &format!("refs/heads/{branch_name}"),
+    ])
+    .is_ok()
+    {
+        anyhow::bail!("Branch '{}' already exists", branch_name);
+    }
+
+    let dirty = execute_git(&["-C", source_path, "status", "--porcelain"])?;
+    if !dirty.trim().is_empty() {
+        println!(
+            "{} '{}' has uncommitted changes that will not be forked (run {} first to keep them)",
+            "⚠️".yellow(),
+            source.cyan(),
+            "agentdev worktree snapshot".cyan()
+        );
+    }
+
+    println!(
+        "{} Forking '{}' at {} into '{}'...",
+        "🍴".green(),
+        source.cyan(),
+        short_head.yellow(),
+        worktree_name.cyan()
+    );
+
+    execute_git(&["-C", repo_root, "branch", &branch_name, &head])
+        .context("Failed to create branch")?;
+    fs::create_dir_all(&worktree_base_dir).with_context(|| {
+        format!(
+            "Failed to create worktree root at {}",
+            worktree_base_dir.display()
+        )
+    })?;
+    let worktree_dir = worktree_path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    execute_git(&[
+        "-C",
+        repo_root,
+        "worktree",
+        "add",
+        worktree_dir,
+        &branch_name,
+    ])
+    .context("Failed to create worktree")?;
+
+    if let Err(e) = update_submodules(&worktree_path) {
+        println!(
+            "{} Warning: Failed to update submodules: {}",
+            "⚠️".yellow(),
+            e
+        );
+    }
+
+    let mut state = XlaudeState::load()?;
+    state.worktrees.insert(
+        key,
+        WorktreeInfo {
+            name: worktree_name.clone(),
+            branch: branch_name,
+            path: worktree_path.clone(),
+            repo_name,
+            created_at: Utc::now(),
+            task_id: source_info.task_id,
+            task_name: source_info.task_name,
+            initial_prompt: source_info.initial_prompt,
+            agent_alias: None,
+            locked: false,
+            lock_reason: None,
+            last_agent_activity: None,
+            base_ref: None,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
+        },
+    );
+    state.save()?;
+
+    println!(
+        "{} Worktree created at: {}",
+        "✅".green(),
+        worktree_path.display()
+    );
+    println!(
+        "  {} To start an agent, run: {} {}",
+        "💡".cyan(),
+        "agentdev worktree open".cyan(),
+        worktree_name.cyan()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::input::smart_confirm;
+use agentdev::discovery::GitWorktree;
+use agentdev::git::{default_branch, execute_git, is_working_tree_clean};
+use agentdev::state::XlaudeState;
+use agentdev::tmux::TmuxManager;
+use agentdev::utils::execute_in_dir;
+
+/// How a branch made it into the default branch.
+enum MergeKind {
+    /// Every commit on the branch is reachable from the base
+    Merged,
+    /// The branch's combined change landed as a single squash commit
+    Squashed,
+}
+
+struct Candidate {
+    key: String,
+    name: String,
+    branch: String,
+    path: PathBuf,
+    repo_root: PathBuf,
+    base: String,
+    kind: MergeKind,
+}
+
+impl Candidate {
+    fn describe(&self) -> String {
+        match self.kind {
+            MergeKind::Merged => format!("merged into {}", self.base),
+            MergeKind::Squashed => format!("squash-merged into {}", self.base),
+        }
+    }
+}
+
+/// Remove managed worktrees whose branches already landed in the default branch.
+///
+/// Locked worktrees and worktrees with uncommitted changes are never touched.
+pub fn handle_gc(auto: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+
+    let mut entries: Vec<_> = state.worktrees.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+    for (key, info) in entries {
+        if info.locked {
+            skipped.push(format!("{} (locked)", info.name));
+            continue;
+        }
+        if !info.path.exists() {
+            // `worktree clean` handles entries whose directory is gone
+            continue;
+        }
+        let Some(git_wt) = GitWorktree::from_path(&info.path)? else {
+            continue;
+        };
+        let Some(branch) = git_wt.branch.clone() else {
+            continue;
+        };
+
+        let checked = execute_in_dir(&git_wt.path, || {
+            if !is_working_tree_clean()? {
+                return Ok(Err("uncommitted changes"));
+            }
+            let base = merge_base_ref()?;
+            Ok(Ok(merge_kind(&branch, &base)?.map(|kind| (base, kind))))
+        });
+        match checked {
+            Ok(Ok(Some((base, kind)))) => candidates.push(Candidate {
+                key: key.clone(),
+                name: info.name.clone(),
+                branch,
+                path: git_wt.path,
+                repo_root: git_wt.repo_root,
+                base,
+                kind,
+            }),
+            Ok(Ok(None)) => {}
+            Ok(Err(reason)) => skipped.push(format!("{} ({reason})", info.name)),
+            Err(err) => skipped.push(format!("{} ({err:#})", info.name)),
+        }
+    }
+
+    if !skipped.is_empty() {
+        println!(
+            "{} Skipped: {}",
+            "ℹ️ ".blue(),
+            skipped.join(", ").bright_black()
+        );
+    }
+
+    if candidates.is_empty() {
+        println!("{} No merged worktrees to remove", "✨".green());
+        return Ok(());
+    }
+
+    print_candidates(&candidates);
+
+    let confirmed = auto
+        || smart_confirm(
+            &format!(
+                "Remove {} worktree(s) and their branches?",
+                candidates.len()
+            ),
+            false,
+        )?;
+    if !confirmed {
+        println!("{} Cancelled", "❌".red());
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for candidate in &candidates {
+        match remove_candidate(candidate) {
+            Ok(()) => println!(
+                "{} Removed '{}' ({})",
+                "✅".green(),
+                candidate.name.cyan(),
+                candidate.branch
+            ),
+            Err(err) => {
+                eprintln!(
+                    "{} Failed to remove '{}': {err:#}",
+                    "❌".red(),
+                    candidate.name
+                );
+                failed.push(candidate.name.clone());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to remove: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+fn print_candidates(candidates: &[Candidate]) {
+    let name_width = candidates
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+    let branch_width = candidates
+        .iter()
+        .map(|c| c.branch.len())
+        .max()
+        .unwrap_or(0)
+        .max("BRANCH".len());
+
+    println!("{} Candidates to remove:", "🧹".yellow());
+    println!(
+        "  {:<name_width$}  {:<branch_width$}  {}",
+        "NAME".bold(),
+        "BRANCH".bold(),
+        "STATUS".bold()
+    );
+    for candidate in candidates {
+        println!(
+            "  {:<name_width$}  {:<branch_width$}  {}",
+            candidate.name.cyan(),
+            candidate.branch,
+            candidate.describe().green()
+        );
+    }
+    println!();
+}
+
+/// `origin/<default>` when the remote-tracking branch exists, else the local default.
+fn merge_base_ref() -> Result<String> {
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let default_branch = default_branch(&cwd);
+    let remote_ref = format!("origin/{default_branch}");
+    if execute_git(&["rev-parse", "--verify", "--quiet", &remote_ref]).is_ok() {
+        Ok(remote_ref)
+    } else {
+        Ok(default_branch)
+    }
+}
+
+fn merge_kind(branch: &str, base: &str) -> Result<Option<MergeKind>> {
+    let exclusive = execute_git(&["log", branch, "--not", base, "--oneline"])?;
+    if exclusive.trim().is_empty() {
+        return Ok(Some(MergeKind::Merged));
+    }
+
+    // A squash merge leaves the branch commits unreachable from the base. Fold
+    // the branch into one commit on top of the merge base and ask git cherry
+    // whether an equivalent patch is already in the base.
+    let merge_base = execute_git(&["merge-base", base, branch])?;
+    let tree = execute_git(&["rev-parse", &format!("{branch}^{{tree}}")])?;
+    let folded = execute_git(&[
+        "commit-tree",
+        tree.trim(),
+        "-p",
+        merge_base.trim(),
+        "-m",
+        "agentdev gc probe",
+    ])?;
+    let cherry = execute_git(&["cherry", base, folded.trim()])?;
+    if cherry.trim_start().starts_with('-') {
+        Ok(Some(MergeKind::Squashed))
+    } else {
+        Ok(None)
+    }
+}
+
+fn remove_candidate(candidate: &Candidate) -> Result<()> {
+    let _ = TmuxManager::new().kill_session(&candidate.name);
+
+    let path = candidate
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    execute_in_dir(&candidate.repo_root, || {
+        execute_git(&["worktree", "remove", path])?;
+        // Squash-merged branches look unmerged to `git branch -d`
+        execute_git(&["branch", "-D", &candidate.branch])?;
+        Ok(())
+    })?;
+
+    let mut state = XlaudeState::load()?;
+    state.worktrees.remove(&candidate.key);
+    state.save()
+}
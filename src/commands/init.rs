@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::input::smart_confirm;
+use agentdev::config::{agent_config_path, load_agent_config};
+use agentdev::git::{default_branch, execute_git, get_current_branch};
+
+/// Prepare the repository in the current directory for agentdev: write a
+/// `CLAUDE.md` and the reference config when they are missing, then print
+/// how to get started.
+///
+/// Must run from the main checkout on the default branch, since that is
+/// where `worktree create` branches from.
+pub fn handle_init(non_interactive: bool) -> Result<()> {
+    let root = execute_git(&["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .context("Not in a git repository; run 'git init' first")?;
+    let repo_name = root
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("Failed to get repository name")?
+        .to_string();
+
+    let current = get_current_branch().context("HEAD is detached; check out a branch first")?;
+    let default = default_branch(&root);
+    if current != default {
+        anyhow::bail!(
+            "agentdev init must run on the default branch '{default}' (currently on '{current}'); check it out first"
+        );
+    }
+
+    let confirm = |prompt: &str| -> Result<bool> {
+        if non_interactive {
+            Ok(true)
+        } else {
+            smart_confirm(prompt, true)
+        }
+    };
+
+    println!(
+        "{} Setting up '{}' for agentdev",
+        "🛠".cyan(),
+        repo_name.cyan()
+    );
+
+    let claude_md = root.join("CLAUDE.md");
+    if claude_md.exists() {
+        println!("  {} CLAUDE.md already exists", "✓".green());
+    } else if confirm("Create CLAUDE.md with project instructions for agents?")? {
+        fs::write(&claude_md, claude_md_template(&repo_name, &root))
+            .with_context(|| format!("Failed to write {}", claude_md.display()))?;
+        println!("  {} Created {}", "✅".green(), claude_md.display());
+    } else {
+        println!("  {} Skipped CLAUDE.md", "-".bright_black());
+    }
+
+    let config_path = agent_config_path();
+    if config_path.exists() {
+        println!(
+            "  {} Config already exists at {}",
+            "✓".green(),
+            config_path.display()
+        );
+    } else if confirm(&format!(
+        "Create the reference config at {}?",
+        config_path.display()
+    ))? {
+        // Writes the bundled reference config on first use
+        load_agent_config()?;
+        println!("  {} Created {}", "✅".green(), config_path.display());
+    } else {
+        println!("  {} Skipped config file", "-".bright_black());
+    }
+
+    print_getting_started();
+    Ok(())
+}
+
+/// Build and test commands for the project types we can recognize.
+fn detect_commands(root: &Path) -> Vec<(&'static str, &'static str)> {
+    let mut commands = Vec::new();
+    if root.join("Cargo.toml").exists() {
+        commands.push(("Build", "cargo build"));
+        commands.push(("Test", "cargo test"));
+        commands.push(("Lint", "cargo clippy --all-targets -- -D warnings"));
+    }
+    if root.join("package.json").exists() {
+        let runner = if root.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if root.join("yarn.lock").exists() {
+            "yarn"
+        } else {
+            "npm"
+        };
+        let (build, test) = match runner {
+            "pnpm" => ("pnpm build", "pnpm test"),
+            "yarn" => ("yarn build", "yarn test"),
+            _ => ("npm run build", "npm test"),
+        };
+        commands.push(("Build", build));
+        commands.push(("Test", test));
+    }
+    if root.join("go.mod").exists() {
+        commands.push(("Build", "go build ./..."));
+        commands.push(("Test", "go test ./..."));
+    }
+    if root.join("pyproject.toml").exists() {
+        commands.push(("Test", "pytest"));
+    }
+    commands
+}
+
+fn claude_md_template(repo_name: &str, root: &Path) -> String {
+    let mut content = format!(
+        "# {repo_name}\n\n\
+         Instructions for coding agents working in this repository.\n\n\
+         ## Overview\n\n\
+         <!-- What the project does and how the code is laid out. -->\n\n\
+         ## Commands\n\n"
+    );
+    let commands = detect_commands(root);
+    if commands.is_empty() {
+        content.push_str("<!-- How to build, test and lint the project. -->\n");
+    } else {
+        for (label, command) in commands {
+            content.push_str(&format!("- {label}: `{command}`\n"));
+        }
+    }
+    content.push_str(
+        "\n## Conventions\n\n\
+         - Keep changes focused on the task; avoid unrelated refactors.\n\
+         - Run the tests before reporting a task as done.\n",
+    );
+    content
+}
+
+fn print_getting_started() {
+    println!();
+    println!("{}", "Getting started".bold());
+    let steps = [
+        (
+            "agentdev worktree create <name>",
+            "create a worktree and branch for a task",
+        ),
+        (
+            "agentdev worktree open <name>",
+            "start or attach to the agent in a tmux session",
+        ),
+        (
+            "agentdev worktree list",
+            "see every worktree and its sessions",
+        ),
+        (
+            "agentdev worktree merge <name>",
+            "merge the finished work back",
+        ),
+        ("agentdev ui", "manage worktrees from the web dashboard"),
+        ("agentdev config edit", "choose agents and other settings"),
+    ];
+    for (command, description) in steps {
+        println!(
+            "  {} {}",
+            command.cyan(),
+            format!("# {description}").bright_black()
+        );
+    }
+    println!();
+    println!("Run {} if anything looks off.", "agentdev doctor".cyan());
+}
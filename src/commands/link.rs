@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::lock::resolve_worktree_target;
+use agentdev::state::XlaudeState;
+
+/// Point a symlink at `symlink_path` to the worktree, giving editor
+/// integrations a stable path to the active worktree.
+///
+/// An existing symlink at that path is re-pointed, and any other worktree
+/// that recorded it is unlinked. Anything else at the path is left alone.
+pub fn handle_link(name: String, symlink_path: PathBuf) -> Result<()> {
+    let mut state = XlaudeState::load()?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, Some(name))?;
+    let key = state_key.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Worktree '{}' is not managed by agentdev; adopt it first with 'agentdev worktree adopt'",
+            git_wt.display_name()
+        )
+    })?;
+
+    let target = fs::canonicalize(&git_wt.path)
+        .with_context(|| format!("Failed to resolve {}", git_wt.path.display()))?;
+    let link = std::path::absolute(&symlink_path)
+        .with_context(|| format!("Invalid symlink path {}", symlink_path.display()))?;
+
+    match fs::symlink_metadata(&link) {
+        Ok(meta) if meta.file_type().is_symlink() => remove_symlink(&link)
+            .with_context(|| format!("Failed to replace symlink {}", link.display()))?,
+        Ok(_) => anyhow::bail!(
+            "{} already exists and is not a symlink; choose another path",
+            link.display()
+        ),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            if let Some(parent) = link.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+        }
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to inspect {}", link.display()));
+        }
+    }
+    create_symlink(&target, &link)
+        .with_context(|| format!("Failed to create symlink {}", link.display()))?;
+
+    for (other_key, info) in state.worktrees.iter_mut() {
+        if *other_key != key && info.symlink_path.as_deref() == Some(link.as_path()) {
+            info.symlink_path = None;
+        }
+    }
+    let info = state
+        .worktrees
+        .get_mut(&key)
+        .context("Worktree disappeared from state")?;
+    info.symlink_path = Some(link.clone());
+    let display_name = info.name.clone();
+    state.save()?;
+
+    println!(
+        "{} Linked {} -> '{}'",
+        "🔗".green(),
+        link.display(),
+        display_name.cyan()
+    );
+    Ok(())
+}
+
+/// Remove the symlink created by [`handle_link`] and forget it.
+pub fn handle_unlink(name: String) -> Result<()> {
+    let mut state = XlaudeState::load()?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, Some(name))?;
+    let info = state_key
+        .and_then(|key| state.worktrees.get_mut(&key))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Worktree '{}' is not managed by agentdev; adopt it first with 'agentdev worktree adopt'",
+                git_wt.display_name()
+            )
+        })?;
+    let display_name = info.name.clone();
+    let Some(link) = info.symlink_path.take() else {
+        anyhow::bail!("Worktree '{display_name}' has no symlink");
+    };
+
+    match fs::symlink_metadata(&link) {
+        Ok(meta) if meta.file_type().is_symlink() => {
+            remove_symlink(&link)
+                .with_context(|| format!("Failed to remove symlink {}", link.display()))?;
+        }
+        Ok(_) => eprintln!(
+            "{} {} is no longer a symlink; leaving it in place",
+            "⚠️ ".yellow(),
+            link.display()
+        ),
+        Err(_) => {}
+    }
+    state.save()?;
+
+    println!(
+        "{} Removed {} for '{}'",
+        "🔗".green(),
+        link.display(),
+        display_name.cyan()
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+#[cfg(unix)]
+fn remove_symlink(link: &Path) -> io::Result<()> {
+    fs::remove_file(link)
+}
+
+#[cfg(windows)]
+fn remove_symlink(link: &Path) -> io::Result<()> {
+    // Directory symlinks are removed like directories on Windows
+    fs::remove_dir(link)
+}
@@ -1,14 +1,22 @@
-use anyhow::Result;
-use chrono::{DateTime, Utc};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
 use colored::Colorize;
+use crossterm::{cursor, execute, terminal};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
 use agentdev::claude::get_claude_sessions;
+use agentdev::discovery::GitWorktree;
+use agentdev::disk_usage::{DiskUsageCache, format_size};
 use agentdev::git::{
     HeadCommitInfo, WorktreeGitStatus, head_commit_info, summarize_worktree_status,
 };
-use agentdev::state::XlaudeState;
+use agentdev::state::{WorktreeInfo, XlaudeState};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct JsonSessionInfo {
@@ -19,7 +27,11 @@ struct JsonSessionInfo {
 
 #[derive(Debug, Serialize, Deserialize)]
 struct JsonGitStatus {
-    branch: String,
+    branch: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    detached: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    head: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     upstream: Option<String>,
     ahead: u32,
@@ -37,6 +49,10 @@ struct JsonCommitInfo {
     summary: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author_email: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,11 +70,21 @@ struct JsonWorktreeInfo {
     initial_prompt: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     agent_alias: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_reason: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pinned: bool,
     last_activity_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    symlink_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     git_status: Option<JsonGitStatus>,
     #[serde(skip_serializing_if = "Option::is_none")]
     head_commit: Option<JsonCommitInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
     sessions: Vec<JsonSessionInfo>,
 }
 
@@ -67,115 +93,168 @@ struct JsonOutput {
     worktrees: Vec<JsonWorktreeInfo>,
 }
 
-pub fn handle_list(json: bool) -> Result<()> {
-    let state = XlaudeState::load()?;
+/// Paths longer than this are shortened from the left in `--output table`.
+const TABLE_PATH_WIDTH: usize = 48;
 
-    if state.worktrees.is_empty() {
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ListOutput {
+    /// Worktrees grouped by repository, with recent sessions
+    Text,
+    /// One aligned row per worktree
+    Table,
+    Json,
+    /// One JSON object per worktree per line, printed as each is inspected
+    Ndjson,
+    /// RFC 4180 CSV with a header row
+    Csv,
+    /// `key<TAB>name<TAB>branch<TAB>repo<TAB>path` per worktree, no header;
+    /// the field order is stable for scripts
+    Porcelain,
+}
+
+impl ListOutput {
+    /// `--json`, `--ndjson` and `--porcelain` are kept as shorthands for the
+    /// matching `--output` values.
+    pub fn from_flags(
+        json: bool,
+        ndjson: bool,
+        porcelain: bool,
+        output: Option<ListOutput>,
+    ) -> Self {
         if json {
-            let output = JsonOutput { worktrees: vec![] };
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            Self::Json
+        } else if ndjson {
+            Self::Ndjson
+        } else if porcelain {
+            Self::Porcelain
         } else {
-            println!("{} No active worktrees", "📭".yellow());
+            output.unwrap_or(Self::Text)
         }
-        return Ok(());
     }
+}
 
-    if json {
-        // JSON output
-        let mut worktrees = Vec::new();
+/// Which files `--size` counts, if sizes were requested at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeMode {
+    Skip,
+    All,
+    ExcludeIgnored,
+}
 
-        for info in state.worktrees.values() {
-            let sessions = get_claude_sessions(&info.path);
-            let json_sessions: Vec<JsonSessionInfo> = sessions
-                .into_iter()
-                .map(|session| {
-                    let time_ago = session.last_timestamp.as_ref().map_or_else(
-                        || "unknown".to_string(),
-                        |ts| {
-                            let now = Utc::now();
-                            let diff = now.signed_duration_since(*ts);
-
-                            if diff.num_minutes() < 60 {
-                                format!("{}m ago", diff.num_minutes())
-                            } else if diff.num_hours() < 24 {
-                                format!("{}h ago", diff.num_hours())
-                            } else {
-                                format!("{}d ago", diff.num_days())
-                            }
-                        },
-                    );
+impl SizeMode {
+    pub fn from_flags(size: bool, exclude_ignored: bool) -> Self {
+        match (size, exclude_ignored) {
+            (false, _) => Self::Skip,
+            (true, false) => Self::All,
+            (true, true) => Self::ExcludeIgnored,
+        }
+    }
 
-                    JsonSessionInfo {
-                        last_user_message: session.last_user_message,
-                        last_timestamp: session.last_timestamp,
-                        time_ago,
-                    }
-                })
-                .collect();
-
-            let git_status = summarize_worktree_status(&info.path, &info.branch)
-                .map(JsonGitStatus::from)
-                .map_err(|err| {
-                    eprintln!(
-                        "⚠️  Failed to inspect git status for {}: {err}",
-                        info.path.display()
-                    );
-                    err
-                })
-                .ok();
-
-            let head_commit = head_commit_info(&info.path)
-                .map(|info_opt| info_opt.map(JsonCommitInfo::from))
-                .map_err(|err| {
-                    eprintln!(
-                        "⚠️  Failed to read last commit for {}: {err}",
-                        info.path.display()
-                    );
-                    err
-                })
-                .ok()
-                .flatten();
-
-            let mut last_activity = info.created_at;
-            if let Some(ref commit) = head_commit {
-                if let Some(ts) = commit.timestamp {
-                    if ts > last_activity {
-                        last_activity = ts;
-                    }
-                }
-            }
-            for session in &json_sessions {
-                if let Some(ts) = session.last_timestamp {
-                    if ts > last_activity {
-                        last_activity = ts;
-                    }
-                }
-            }
+    fn measure(self, cache: &mut DiskUsageCache, path: &Path) -> Option<u64> {
+        let respect_gitignore = match self {
+            Self::Skip => return None,
+            Self::All => false,
+            Self::ExcludeIgnored => true,
+        };
+        cache
+            .size_of(path, respect_gitignore)
+            .map_err(|err| eprintln!("⚠️  Failed to measure {}: {err}", path.display()))
+            .ok()
+    }
+}
+
+pub fn handle_list(output: ListOutput, size: SizeMode, watch: Option<u64>) -> Result<()> {
+    match watch {
+        Some(interval) => watch_list(output, size, interval),
+        None => print_list(output, size),
+    }
+}
+
+/// Clear the screen and reprint the list every `interval` seconds until interrupted.
+fn watch_list(output: ListOutput, size: SizeMode, interval: u64) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        anyhow::bail!("--watch needs an interactive terminal; run without it when piping output");
+    }
+
+    execute!(io::stdout(), cursor::Hide)?;
+    ctrlc::set_handler(|| {
+        let _ = execute!(io::stdout(), cursor::Show);
+        println!();
+        std::process::exit(0);
+    })
+    .context("Failed to install Ctrl-C handler")?;
 
-            worktrees.push(JsonWorktreeInfo {
-                name: info.name.clone(),
-                branch: info.branch.clone(),
-                path: info.path.display().to_string(),
-                repo_name: info.repo_name.clone(),
-                created_at: info.created_at,
-                task_id: info.task_id.clone(),
-                task_name: info.task_name.clone(),
-                initial_prompt: info.initial_prompt.clone(),
-                agent_alias: info.agent_alias.clone(),
-                last_activity_at: last_activity,
-                git_status,
-                head_commit,
-                sessions: json_sessions,
-            });
+    loop {
+        execute!(
+            io::stdout(),
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        println!(
+            "{}",
+            format!(
+                "Every {interval}s: agentdev worktree list    {}",
+                Local::now().format("%Y-%m-%d %H:%M:%S")
+            )
+            .bright_black()
+        );
+        println!();
+        if let Err(err) = print_list(output, size) {
+            println!("{} {err:#}", "⚠️ ".yellow());
+        }
+        io::stdout().flush()?;
+
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+fn print_list(output: ListOutput, size: SizeMode) -> Result<()> {
+    match output {
+        ListOutput::Table => return handle_list_table(size),
+        ListOutput::Csv => return handle_list_csv(size),
+        ListOutput::Porcelain => return handle_list_porcelain(),
+        ListOutput::Text | ListOutput::Json | ListOutput::Ndjson => {}
+    }
+    let json = output == ListOutput::Json;
+    let ndjson = output == ListOutput::Ndjson;
+
+    let state = XlaudeState::load()?;
+    let mut size_cache = DiskUsageCache::load();
+
+    if state.worktrees.is_empty() {
+        if json {
+            let output = JsonOutput { worktrees: vec![] };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else if !ndjson {
+            println!("{} No active worktrees", "📭".yellow());
         }
+        return Ok(());
+    }
 
-        // Sort worktrees by repo name and then by name
-        worktrees.sort_by(|a, b| {
+    if json || ndjson {
+        let mut infos: Vec<&WorktreeInfo> = state.worktrees.values().collect();
+        infos.sort_by(|a, b| {
             a.repo_name
                 .cmp(&b.repo_name)
                 .then_with(|| a.name.cmp(&b.name))
         });
 
+        if ndjson {
+            // One line per worktree as soon as it is inspected, so consumers
+            // can start before slow git and session scans finish
+            let mut stdout = io::stdout();
+            for info in infos {
+                let worktree = json_worktree_info(info, size, &mut size_cache);
+                writeln!(stdout, "{}", serde_json::to_string(&worktree)?)?;
+                stdout.flush()?;
+            }
+            return Ok(());
+        }
+
+        let worktrees = infos
+            .into_iter()
+            .map(|info| json_worktree_info(info, size, &mut size_cache))
+            .collect();
         let output = JsonOutput { worktrees };
         println!("{}", serde_json::to_string_pretty(&output)?);
     } else {
@@ -192,21 +271,43 @@ pub fn handle_list(json: bool) -> Result<()> {
                 .push(info);
         }
 
-        // Display grouped by repository
+        // Display grouped by repository, repos holding pinned worktrees first
+        let mut grouped: Vec<_> = grouped.into_iter().collect();
+        grouped.sort_by_key(|(_, worktrees)| !worktrees.iter().any(|w| w.pinned));
         for (repo_name, mut worktrees) in grouped {
             println!("  {} {}", "📦".blue(), repo_name.bold());
 
-            // Sort worktrees within each repo by name
-            worktrees.sort_by_key(|w| &w.name);
+            // Pinned worktrees first, then by name
+            worktrees.sort_by_key(|w| (!w.pinned, &w.name));
 
             for info in worktrees {
-                println!("    {} {}", "•".green(), info.name.cyan());
+                let mut markers = String::new();
+                if info.pinned {
+                    markers.push_str(&format!(" {}", "📌".yellow()));
+                }
+                let lock_reason = resolve_lock_reason(info);
+                if lock_reason.is_some() {
+                    markers.push_str(&format!(" {}", "🔒".yellow()));
+                }
+                if info.symlink_path.is_some() {
+                    markers.push_str(&format!(" {}", "🔗".blue()));
+                }
+                println!("    {} {}{}", "•".green(), info.name.cyan(), markers);
                 println!("      {} {}", "Path:".bright_black(), info.path.display());
+                if let Some(reason) = lock_reason.as_deref().filter(|r| !r.is_empty()) {
+                    println!("      {} {}", "Locked:".bright_black(), reason);
+                }
+                if let Some(link) = &info.symlink_path {
+                    println!("      {} {}", "Linked:".bright_black(), link.display());
+                }
                 println!(
                     "      {} {}",
                     "Created:".bright_black(),
                     info.created_at.format("%Y-%m-%d %H:%M:%S")
                 );
+                if let Some(bytes) = size.measure(&mut size_cache, &info.path) {
+                    println!("      {} {}", "Size:".bright_black(), format_size(bytes));
+                }
 
                 // Get Claude sessions for this worktree
                 let sessions = get_claude_sessions(&info.path);
@@ -268,13 +369,326 @@ pub fn handle_list(json: bool) -> Result<()> {
         }
     }
 
+    let _ = size_cache.save();
+    Ok(())
+}
+
+/// Managed worktrees with their state keys, pinned first, then by repository and name.
+fn sorted_worktrees(state: &XlaudeState) -> Vec<(&String, &WorktreeInfo)> {
+    let mut worktrees: Vec<_> = state.worktrees.iter().collect();
+    worktrees.sort_by(|(_, a), (_, b)| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(|| a.repo_name.cmp(&b.repo_name))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    worktrees
+}
+
+fn handle_list_table(size: SizeMode) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let mut size_cache = DiskUsageCache::load();
+    let with_size = size != SizeMode::Skip;
+
+    let mut headers = vec!["NAME", "REPO", "BRANCH", "STATUS", "SESSIONS"];
+    if with_size {
+        headers.push("SIZE");
+    }
+    headers.extend(["CREATED", "PATH"]);
+    let numeric: Vec<bool> = headers
+        .iter()
+        .map(|header| matches!(*header, "SESSIONS" | "SIZE"))
+        .collect();
+
+    let rows: Vec<Vec<String>> = sorted_worktrees(&state)
+        .into_iter()
+        .map(|(_, info)| {
+            let status = summarize_worktree_status(&info.path, &info.branch).ok();
+            let mut row = vec![
+                info.name.clone(),
+                info.repo_name.clone(),
+                status
+                    .as_ref()
+                    .filter(|status| status.detached)
+                    .map_or_else(|| info.branch.clone(), WorktreeGitStatus::branch_label),
+                status
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), git_status_cell),
+                get_claude_sessions(&info.path).len().to_string(),
+            ];
+            if with_size {
+                row.push(
+                    size.measure(&mut size_cache, &info.path)
+                        .map_or_else(|| "-".to_string(), format_size),
+                );
+            }
+            row.push(info.created_at.format("%Y-%m-%d %H:%M").to_string());
+            row.push(truncate_left(
+                &info.path.display().to_string(),
+                TABLE_PATH_WIDTH,
+            ));
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render = |cells: Vec<String>| {
+        let last = cells.len() - 1;
+        cells
+            .into_iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let width = widths[index];
+                if numeric[index] {
+                    format!("{cell:>width$}")
+                } else if index == last {
+                    cell
+                } else {
+                    format!("{cell:<width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    let header_line = render(headers.iter().map(|header| header.to_string()).collect());
+    println!("{}", header_line.bold());
+    for row in rows {
+        println!("{}", render(row));
+    }
+
+    let _ = size_cache.save();
+    Ok(())
+}
+
+fn handle_list_csv(size: SizeMode) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let mut size_cache = DiskUsageCache::load();
+    let with_size = size != SizeMode::Skip;
+
+    let mut out = String::from("id,name,branch,repo_name,path,created_at");
+    if with_size {
+        out.push_str(",size_bytes");
+    }
+    out.push_str("\r\n");
+
+    for (key, info) in sorted_worktrees(&state) {
+        let mut fields = vec![
+            key.clone(),
+            info.name.clone(),
+            info.branch.clone(),
+            info.repo_name.clone(),
+            info.path.display().to_string(),
+            info.created_at.to_rfc3339(),
+        ];
+        if with_size {
+            fields.push(
+                size.measure(&mut size_cache, &info.path)
+                    .map(|bytes| bytes.to_string())
+                    .unwrap_or_default(),
+            );
+        }
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+
+    print!("{out}");
+    let _ = size_cache.save();
+    Ok(())
+}
+
+/// Whether the worktree is locked, preferring git's view and falling back to
+/// the flag recorded in agentdev state. `Some("")` means locked without a reason.
+fn resolve_lock_reason(info: &WorktreeInfo) -> Option<String> {
+    if info.path.exists() {
+        match GitWorktree::from_path(&info.path) {
+            // The main checkout cannot be locked
+            Ok(None) => return None,
+            Ok(Some(git_wt)) => {
+                if let Ok(status) = git_wt.lock_status() {
+                    return status;
+                }
+            }
+            Err(_) => {}
+        }
+    }
+
+    info.locked.then(|| info.lock_reason.clone().unwrap_or_default())
+}
+
+/// Everything `--json` and `--ndjson` report for one worktree.
+fn json_worktree_info(
+    info: &WorktreeInfo,
+    size: SizeMode,
+    size_cache: &mut DiskUsageCache,
+) -> JsonWorktreeInfo {
+    let sessions = get_claude_sessions(&info.path);
+    let json_sessions: Vec<JsonSessionInfo> = sessions
+        .into_iter()
+        .map(|session| {
+            let time_ago = session.last_timestamp.as_ref().map_or_else(
+                || "unknown".to_string(),
+                |ts| {
+                    let now = Utc::now();
+                    let diff = now.signed_duration_since(*ts);
+
+                    if diff.num_minutes() < 60 {
+                        format!("{}m ago", diff.num_minutes())
+                    } else if diff.num_hours() < 24 {
+                        format!("{}h ago", diff.num_hours())
+                    } else {
+                        format!("{}d ago", diff.num_days())
+                    }
+                },
+            );
+
+            JsonSessionInfo {
+                last_user_message: session.last_user_message,
+                last_timestamp: session.last_timestamp,
+                time_ago,
+            }
+        })
+        .collect();
+
+    let git_status = summarize_worktree_status(&info.path, &info.branch)
+        .map(JsonGitStatus::from)
+        .map_err(|err| {
+            eprintln!(
+                "⚠️  Failed to inspect git status for {}: {err}",
+                info.path.display()
+            );
+            err
+        })
+        .ok();
+
+    let head_commit = head_commit_info(&info.path)
+        .map(|info_opt| info_opt.map(JsonCommitInfo::from))
+        .map_err(|err| {
+            eprintln!(
+                "⚠️  Failed to read last commit for {}: {err}",
+                info.path.display()
+            );
+            err
+        })
+        .ok()
+        .flatten();
+
+    let lock_reason = resolve_lock_reason(info);
+
+    let mut last_activity = info.created_at;
+    if let Some(ref commit) = head_commit {
+        if let Some(ts) = commit.timestamp {
+            if ts > last_activity {
+                last_activity = ts;
+            }
+        }
+    }
+    for session in &json_sessions {
+        if let Some(ts) = session.last_timestamp {
+            if ts > last_activity {
+                last_activity = ts;
+            }
+        }
+    }
+    if let Some(ts) = info.last_agent_activity {
+        last_activity = last_activity.max(ts);
+    }
+
+    JsonWorktreeInfo {
+        name: info.name.clone(),
+        branch: info.branch.clone(),
+        path: info.path.display().to_string(),
+        repo_name: info.repo_name.clone(),
+        created_at: info.created_at,
+        task_id: info.task_id.clone(),
+        task_name: info.task_name.clone(),
+        initial_prompt: info.initial_prompt.clone(),
+        agent_alias: info.agent_alias.clone(),
+        locked: lock_reason.is_some(),
+        lock_reason: lock_reason.filter(|reason| !reason.is_empty()),
+        pinned: info.pinned,
+        last_activity_at: last_activity,
+        symlink_path: info
+            .symlink_path
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        git_status,
+        head_commit,
+        size_bytes: size.measure(size_cache, &info.path),
+        sessions: json_sessions,
+    }
+}
+
+/// One tab-separated line per worktree and nothing else, not even when empty.
+fn handle_list_porcelain() -> Result<()> {
+    let state = XlaudeState::load()?;
+    let mut out = String::new();
+    for (key, info) in sorted_worktrees(&state) {
+        out.push_str(&format!(
+            "{key}\t{}\t{}\t{}\t{}\n",
+            info.name,
+            info.branch,
+            info.repo_name,
+            info.path.display()
+        ));
+    }
+    print!("{out}");
     Ok(())
 }
 
+/// Compact git status such as `+1 ~2 ?3 ↑1`, or `clean`.
+fn git_status_cell(status: &WorktreeGitStatus) -> String {
+    let parts: Vec<String> = [
+        ("+", status.staged),
+        ("~", status.unstaged),
+        ("?", status.untracked),
+        ("!", status.conflicts),
+        ("↑", status.ahead as usize),
+        ("↓", status.behind as usize),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(marker, count)| format!("{marker}{count}"))
+    .collect();
+    if parts.is_empty() {
+        "clean".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Keep the last `max` characters of `value`, marking the cut with `…`.
+fn truncate_left(value: &str, max: usize) -> String {
+    let count = value.chars().count();
+    if count <= max {
+        return value.to_string();
+    }
+    let tail: String = value.chars().skip(count - (max - 1)).collect();
+    format!("…{tail}")
+}
+
 impl From<WorktreeGitStatus> for JsonGitStatus {
     fn from(value: WorktreeGitStatus) -> Self {
         Self {
             branch: value.branch,
+            detached: value.detached,
+            head: value.head,
             upstream: value.upstream,
             ahead: value.ahead,
             behind: value.behind,
@@ -293,6 +707,8 @@ impl From<HeadCommitInfo> for JsonCommitInfo {
             commit_id: value.commit_id,
             summary: value.summary,
             timestamp: value.timestamp,
+            author: value.author,
+            author_email: value.author_email,
         }
     }
 }
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::Path;
+
+use crate::input::get_command_arg;
+use agentdev::discovery::GitWorktree;
+use agentdev::git::execute_git;
+use agentdev::state::XlaudeState;
+
+pub fn handle_lock(name: Option<String>, reason: Option<String>) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let display_name = git_wt.display_name();
+
+    let reason = reason
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let path_str = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let repo_str = git_wt
+        .repo_root
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let mut args = vec!["-C", repo_str, "worktree", "lock"];
+    if let Some(ref reason) = reason {
+        args.push("--reason");
+        args.push(reason);
+    }
+    args.push(path_str);
+    execute_git(&args).context("Failed to lock worktree")?;
+
+    if let Some(key) = state_key {
+        let mut state = XlaudeState::load()?;
+        if let Some(info) = state.worktrees.get_mut(&key) {
+            info.locked = true;
+            info.lock_reason = reason.clone();
+            state.save()?;
+        }
+    }
+
+    println!("{} Worktree '{}' locked", "🔒".green(), display_name.cyan());
+    if let Some(reason) = reason {
+        println!("  {} {}", "Reason:".bright_black(), reason);
+    }
+
+    Ok(())
+}
+
+pub fn handle_unlock(name: Option<String>) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let display_name = git_wt.display_name();
+
+    let path_str = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let repo_str = git_wt
+        .repo_root
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    execute_git(&["-C", repo_str, "worktree", "unlock", path_str])
+        .context("Failed to unlock worktree")?;
+
+    if let Some(key) = state_key {
+        let mut state = XlaudeState::load()?;
+        if let Some(info) = state.worktrees.get_mut(&key) {
+            info.locked = false;
+            info.lock_reason = None;
+            state.save()?;
+        }
+    }
+
+    println!(
+        "{} Worktree '{}' unlocked",
+        "🔓".green(),
+        display_name.cyan()
+    );
+
+    Ok(())
+}
+
+/// Resolve the worktree targeted by a command, by managed name or current directory.
+///
+/// Returns `(Option<state_key>, GitWorktree)`; the state key is present when the
+/// worktree is managed by agentdev so its metadata can be kept in sync.
+pub(super) fn resolve_worktree_target(
+    state: &XlaudeState,
+    name: Option<String>,
+) -> Result<(Option<String>, GitWorktree)> {
+    if let Some(n) = name {
+        let (key, info) = state
+            .worktrees
+            .iter()
+            .find(|(_, w)| w.name == n)
+            .map(|(k, w)| (k.clone(), w.clone()))
+            .context(format!("Worktree '{}' not found in agentdev state", n))?;
+
+        let git_wt = GitWorktree::from_path(&info.path)?.ok_or_else(|| {
+            anyhow::anyhow!("Path '{}' is not a git worktree", info.path.display())
+        })?;
+
+        Ok((Some(key), git_wt))
+    } else {
+        let git_wt = GitWorktree::from_current_dir()?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Current directory is not a git worktree. \
+                 If you're in the main repository, specify the worktree name."
+            )
+        })?;
+
+        let state_key = find_state_key_by_path(state, &git_wt.path);
+        Ok((state_key, git_wt))
+    }
+}
+
+fn find_state_key_by_path(state: &XlaudeState, path: &Path) -> Option<String> {
+    let path_canon = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    state
+        .worktrees
+        .iter()
+        .find(|(_, w)| {
+            let w_canon = std::fs::canonicalize(&w.path).unwrap_or_else(|_| w.path.clone());
+            w_canon == path_canon
+        })
+        .map(|(k, _)| k.clone())
+}
@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+
+use super::lock::resolve_worktree_target;
+use crate::input::get_command_arg;
+use agentdev::git::execute_git;
+use agentdev::state::XlaudeState;
+
+/// Commits shown by `worktree log`.
+const LOG_LIMIT: &str = "50";
+
+/// Show the commit graph of a worktree without changing into it.
+pub fn handle_log(
+    name: Option<String>,
+    all: bool,
+    since: Option<String>,
+    author: Option<String>,
+) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (_, git_wt) = resolve_worktree_target(&state, target_name)?;
+
+    let path = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+    let since = since.map(|date| format!("--since={date}"));
+    let author = author.map(|pattern| format!("--author={pattern}"));
+
+    let mut args = vec![
+        "-C",
+        path,
+        "log",
+        "--oneline",
+        "--graph",
+        "--decorate",
+        "-n",
+        LOG_LIMIT,
+    ];
+    if all {
+        args.push("--all");
+    }
+    args.extend(since.as_deref());
+    args.extend(author.as_deref());
+
+    let output = execute_git(&args)?;
+    if !output.is_empty() {
+        println!("{output}");
+    }
+    Ok(())
+}
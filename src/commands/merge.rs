@@ -1,18 +1,32 @@
 use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
 use clap::ValueEnum;
 use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
 
 use super::delete::handle_delete;
-use crate::input::{get_command_arg, smart_confirm};
+use crate::input::{get_command_arg, is_piped_input, select_worktree, smart_confirm};
+use agentdev::audit::{AuditAction, AuditEntry};
 use agentdev::discovery::GitWorktree;
+use agentdev::claude_status::{ClaudeStatus, ClaudeStatusDetector};
+use agentdev::config::resolve_typing_pace;
 use agentdev::git::{
-    ahead_behind, execute_git, get_current_branch, get_default_branch, is_working_tree_clean,
+    ahead_behind, default_branch_for_remote, execute_git, get_current_branch, is_working_tree_clean,
 };
 use agentdev::state::XlaudeState;
+use agentdev::tmux::TmuxManager;
 use agentdev::utils::execute_in_dir;
 
+const PRE_MERGE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const PRE_MERGE_CAPTURE_LINES: usize = 50;
+/// Unchanged polls after which a quiet pane counts as finished
+const PRE_MERGE_SETTLE_POLLS: u32 = 3;
+const SPINNER_TICK: Duration = Duration::from_millis(100);
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 #[clap(rename_all = "kebab-case")]
 pub enum MergeStrategy {
@@ -21,18 +35,100 @@ pub enum MergeStrategy {
     Squash,
 }
 
+/// Whether to delete the worktree once its branch is merged.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Cleanup {
+    /// Ask, with `default` as the answer when there is nobody to ask
+    Prompt {
+        default: bool,
+    },
+    Always,
+    Never,
+}
+
+/// Prompt typed into the agent session before merging.
+pub struct PreMergePrompt {
+    pub text: String,
+    /// How long to wait for the agent to finish responding
+    pub timeout: Duration,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_merge(
     name: Option<String>,
     push: bool,
     cleanup: bool,
-    strategy: Option<MergeStrategy>,
-    squash_flag: bool,
+    strategy: MergeStrategy,
+    remote: String,
+    set_upstream: bool,
+    pre_merge_prompt: Option<PreMergePrompt>,
+    quiet: bool,
+) -> Result<()> {
+    let mut audit = AuditEntry::new(AuditAction::Merge);
+    let result = merge_worktree(
+        name,
+        push,
+        Cleanup::Prompt { default: cleanup },
+        strategy,
+        remote,
+        set_upstream,
+        pre_merge_prompt,
+        quiet,
+        &mut audit,
+    );
+    audit.finish(&result);
+    result
+}
+
+/// Merge a managed worktree without any prompts, deleting it afterwards only
+/// when `cleanup` is set. Used to merge a task's worktrees one by one.
+pub fn merge_managed_worktree(
+    name: String,
+    strategy: MergeStrategy,
+    remote: String,
+    cleanup: bool,
+) -> Result<()> {
+    let mut audit = AuditEntry::new(AuditAction::Merge);
+    let result = merge_worktree(
+        Some(name),
+        false,
+        if cleanup {
+            Cleanup::Always
+        } else {
+            Cleanup::Never
+        },
+        strategy,
+        remote,
+        false,
+        None,
+        false,
+        &mut audit,
+    );
+    audit.finish(&result);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn merge_worktree(
+    name: Option<String>,
+    push: bool,
+    cleanup: Cleanup,
+    strategy: MergeStrategy,
+    remote: String,
+    set_upstream: bool,
+    pre_merge_prompt: Option<PreMergePrompt>,
+    quiet: bool,
+    audit: &mut AuditEntry,
 ) -> Result<()> {
     let state = XlaudeState::load()?;
     let target_name = get_command_arg(name)?;
+    audit.worktree = target_name.clone();
 
     // Resolve worktree - returns GitWorktree (from git) and optional managed name
     let (git_wt, managed_name) = resolve_worktree_for_merge(&state, target_name)?;
+    audit.repo = Some(git_wt.repo_name());
+    audit.branch = git_wt.branch.clone();
+    audit.worktree = Some(git_wt.display_name());
 
     if !git_wt.path.exists() {
         bail!(
@@ -52,13 +148,23 @@ pub fn handle_merge(
         anyhow::anyhow!("Cannot merge: worktree is in detached HEAD state")
     })?;
 
+    if let Some(prompt) = &pre_merge_prompt {
+        let session = managed_name.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("--pre-merge-prompt needs a worktree managed by agentdev")
+        })?;
+        let alias = state
+            .worktrees
+            .values()
+            .find(|info| info.name == session)
+            .and_then(|info| info.agent_alias.as_deref());
+        run_pre_merge_prompt(session, alias, prompt)?;
+    }
+
     ensure_clean(&git_wt.path, "worktree")
         .with_context(|| format!("Worktree '{}' has pending changes", git_wt.display_name()))?;
     ensure_clean(&git_wt.repo_root, "main repository")
         .context("Main repository has pending changes")?;
 
-    let resolved_strategy = resolve_strategy(strategy, squash_flag)?;
-
     println!(
         "{} Merging '{}' into default branch for '{}'.",
         "🔀".green(),
@@ -66,22 +172,34 @@ pub fn handle_merge(
         git_wt.repo_name().cyan()
     );
 
-    fetch_origin(&git_wt.repo_root)?;
-    let default_branch = determine_default_branch(&git_wt.repo_root)?;
-
-    checkout_base_branch(&git_wt.repo_root, &default_branch)?;
-    update_base_branch(&git_wt.repo_root, &default_branch)?;
+    let progress = MergeProgress::new(quiet);
+    let default_branch = determine_default_branch(&progress, &git_wt.repo_root, &remote)?;
+    progress.step(format!("Fetching latest {default_branch}…"), || {
+        fetch_remote(&progress, &git_wt.repo_root, &remote)?;
+        checkout_base_branch(&progress, &git_wt.repo_root, &default_branch)?;
+        update_base_branch(&progress, &git_wt.repo_root, &remote, &default_branch)
+    })?;
 
     let outcome = merge_branch(
+        &progress,
         &git_wt.repo_root,
         branch,
         &default_branch,
-        resolved_strategy,
+        strategy,
     )?;
 
     if push {
-        push_default_branch(&git_wt.repo_root, &default_branch)?;
+        progress.step(format!("Pushing {default_branch} to {remote}…"), || {
+            push_default_branch(
+                &progress,
+                &git_wt.repo_root,
+                &remote,
+                &default_branch,
+                set_upstream,
+            )
+        })?;
     }
+    progress.finish();
 
     println!(
         "{} '{}' merged into '{}' successfully",
@@ -101,23 +219,27 @@ pub fn handle_merge(
 
     if !push {
         println!(
-            "  {} Run `git push origin {}` to publish the merge",
+            "  {} Run `git push {} {}` to publish the merge",
             "ℹ️".blue(),
+            remote,
             default_branch
         );
     }
 
     // For cleanup, use managed name if available, otherwise use None (delete from current dir)
     let display_name = managed_name.clone().unwrap_or_else(|| git_wt.display_name());
-    let delete_now = smart_confirm(
-        &format!("Delete worktree '{}' now?", display_name),
-        cleanup,
-    )?;
+    let delete_now = match cleanup {
+        Cleanup::Prompt { default } => {
+            smart_confirm(&format!("Delete worktree '{}' now?", display_name), default)?
+        }
+        Cleanup::Always => true,
+        Cleanup::Never => false,
+    };
 
     if delete_now {
         // Pass managed name if available, None otherwise (delete will use current dir)
-        handle_delete(managed_name)?;
-    } else {
+        handle_delete(managed_name, false)?;
+    } else if cleanup != Cleanup::Never {
         println!(
             "  {} Run `agentdev worktree delete` to clean up the worktree",
             "ℹ️".blue()
@@ -155,12 +277,21 @@ fn resolve_worktree_for_merge(
         return Ok((git_wt, Some(info.name)));
     }
 
-    // No name: try current directory
-    let git_wt = GitWorktree::from_current_dir()?
-        .ok_or_else(|| anyhow::anyhow!(
+    // No name: try current directory, then let the user pick one
+    let Some(git_wt) = GitWorktree::from_current_dir()? else {
+        if !is_piped_input()
+            && let Some((_, info)) = select_worktree("Select a worktree to merge", state)?
+        {
+            let git_wt = GitWorktree::from_path(&info.path)?.ok_or_else(|| {
+                anyhow::anyhow!("Path '{}' is not a git worktree", info.path.display())
+            })?;
+            return Ok((git_wt, Some(info.name)));
+        }
+        bail!(
             "Current directory is not a git worktree. \
              If you're in the main repository, specify the worktree name."
-        ))?;
+        );
+    };
 
     // Try to find matching state entry for managed name
     let managed_name = find_managed_name_by_path(state, &git_wt.path);
@@ -182,7 +313,10 @@ fn find_managed_name_by_path(state: &XlaudeState, path: &std::path::Path) -> Opt
         .map(|w| w.name.clone())
 }
 
-fn resolve_strategy(strategy: Option<MergeStrategy>, squash_flag: bool) -> Result<MergeStrategy> {
+pub fn resolve_strategy(
+    strategy: Option<MergeStrategy>,
+    squash_flag: bool,
+) -> Result<MergeStrategy> {
     if squash_flag {
         if let Some(s) = strategy {
             if s != MergeStrategy::Squash {
@@ -195,6 +329,79 @@ fn resolve_strategy(strategy: Option<MergeStrategy>, squash_flag: bool) -> Resul
     Ok(strategy.unwrap_or(MergeStrategy::FfOnly))
 }
 
+/// Send the prompt to the worktree's agent and wait until it is done with it.
+///
+/// The agent counts as done once it is waiting for input again after either
+/// reporting that it was processing or leaving the pane unchanged for a few
+/// polls. An error status or an exited session aborts the merge.
+fn run_pre_merge_prompt(name: &str, alias: Option<&str>, prompt: &PreMergePrompt) -> Result<()> {
+    if !TmuxManager::is_available() {
+        bail!("tmux is not installed; cannot send the pre-merge prompt to '{name}'");
+    }
+    let tmux = TmuxManager::new();
+    if !tmux.session_exists(name) {
+        bail!(
+            "No tmux session for worktree '{}'. Start one with 'agentdev worktree open {}'.",
+            name,
+            name
+        );
+    }
+
+    let pace = resolve_typing_pace(alias);
+    tmux.send_text_paced(name, &prompt.text, pace)?;
+    tmux.send_enter_paced(name, pace)?;
+    println!(
+        "{} Sent pre-merge prompt to '{}'; waiting up to {}s for the agent",
+        "📨".green(),
+        name.cyan(),
+        prompt.timeout.as_secs()
+    );
+
+    let deadline = Instant::now() + prompt.timeout;
+    let mut detector = ClaudeStatusDetector::new();
+    let mut last_output: Option<String> = None;
+    let mut stable_polls = 0;
+    let mut started = false;
+    loop {
+        thread::sleep(PRE_MERGE_POLL_INTERVAL);
+        if !tmux.session_exists(name) {
+            bail!(
+                "Agent session for '{name}' exited before finishing the pre-merge prompt; merge aborted"
+            );
+        }
+        let output = tmux.capture_pane(name, PRE_MERGE_CAPTURE_LINES)?;
+        if last_output.as_ref() == Some(&output) {
+            stable_polls += 1;
+        } else {
+            stable_polls = 0;
+        }
+
+        match detector.analyze_output(&output) {
+            ClaudeStatus::Error => {
+                bail!(
+                    "Agent in '{name}' reported an error after the pre-merge prompt; merge aborted"
+                )
+            }
+            ClaudeStatus::Processing => started = true,
+            ClaudeStatus::WaitingForInput | ClaudeStatus::Idle => {
+                if started || stable_polls >= PRE_MERGE_SETTLE_POLLS {
+                    println!("  {} Agent finished the pre-merge prompt", "✓".green());
+                    return Ok(());
+                }
+            }
+        }
+        last_output = Some(output);
+
+        if Instant::now() >= deadline {
+            bail!(
+                "Timed out after {}s waiting for the agent in '{}' to finish the pre-merge prompt; merge aborted",
+                prompt.timeout.as_secs(),
+                name
+            );
+        }
+    }
+}
+
 fn ensure_clean(path: &Path, label: &str) -> Result<()> {
     execute_in_dir(path, || {
         if is_working_tree_clean()? {
@@ -205,62 +412,159 @@ fn ensure_clean(path: &Path, label: &str) -> Result<()> {
     })
 }
 
-fn fetch_origin(main_repo_path: &Path) -> Result<()> {
+/// Spinner naming the merge step in progress.
+///
+/// Hidden with `--quiet` and whenever stderr is not a terminal; detail lines
+/// still go to stdout either way.
+struct MergeProgress {
+    bar: Option<ProgressBar>,
+}
+
+impl MergeProgress {
+    fn new(quiet: bool) -> Self {
+        if quiet {
+            return Self { bar: None };
+        }
+        let bar = ProgressBar::new_spinner();
+        bar.set_style(
+            ProgressStyle::with_template("  {spinner:.cyan} {msg}")
+                .expect("spinner template is valid"),
+        );
+        bar.enable_steady_tick(SPINNER_TICK);
+        Self { bar: Some(bar) }
+    }
+
+    /// Print a line to stdout without tearing the spinner.
+    fn println(&self, line: impl AsRef<str>) {
+        match &self.bar {
+            Some(bar) => bar.suspend(|| println!("{}", line.as_ref())),
+            None => println!("{}", line.as_ref()),
+        }
+    }
+
+    /// Run `f` as the named step. A failure leaves the spinner on a red
+    /// cross next to the step; the error (git's stderr included) is returned.
+    fn step<T>(&self, message: String, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let Some(bar) = &self.bar else {
+            return f();
+        };
+        bar.set_message(message.clone());
+        let label = message.trim_end_matches('…');
+        match f() {
+            Ok(value) => {
+                if !bar.is_hidden() {
+                    bar.suspend(|| println!("  {} {}", "✓".green(), label));
+                }
+                Ok(value)
+            }
+            Err(err) => {
+                settle(bar, format!("{} {} failed", "✗".red(), label));
+                Err(err)
+            }
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            settle(bar, format!("{} Done", "✓".green()));
+        }
+    }
+}
+
+/// Replace the spinner with a final `message` and leave it on screen.
+fn settle(bar: &ProgressBar, message: String) {
+    bar.set_style(ProgressStyle::with_template("  {msg}").expect("final template is valid"));
+    bar.abandon_with_message(message);
+    if !bar.is_hidden() {
+        // The last drawn line keeps the cursor; end it before anything else prints.
+        eprintln!();
+    }
+}
+
+fn fetch_remote(progress: &MergeProgress, main_repo_path: &Path, remote: &str) -> Result<()> {
     execute_in_dir(main_repo_path, || {
-        println!("  {} Fetching origin", "→".blue());
-        execute_git(&["fetch", "origin"])?;
+        if execute_git(&["remote", "get-url", remote]).is_err() {
+            bail!(
+                "Remote '{}' is not configured in {}",
+                remote,
+                main_repo_path.display()
+            );
+        }
+        progress.println(format!("  {} Fetching {}", "→".blue(), remote));
+        execute_git(&["fetch", remote])?;
         Ok(())
     })
 }
 
-fn determine_default_branch(main_repo_path: &Path) -> Result<String> {
-    execute_in_dir(main_repo_path, || {
-        let branch = get_default_branch()?;
-        println!(
-            "  {} Default branch detected: {}",
-            "→".blue(),
-            branch.cyan()
-        );
-        Ok(branch)
-    })
+fn determine_default_branch(
+    progress: &MergeProgress,
+    main_repo_path: &Path,
+    remote: &str,
+) -> Result<String> {
+    let branch = default_branch_for_remote(main_repo_path, remote);
+    progress.println(format!(
+        "  {} Default branch detected: {}",
+        "→".blue(),
+        branch.cyan()
+    ));
+    Ok(branch)
 }
 
-fn checkout_base_branch(main_repo_path: &Path, default_branch: &str) -> Result<()> {
+fn checkout_base_branch(
+    progress: &MergeProgress,
+    main_repo_path: &Path,
+    default_branch: &str,
+) -> Result<()> {
     execute_in_dir(main_repo_path, || {
         let current = get_current_branch()?;
         if current != default_branch {
-            println!("  {} Checking out {}", "→".blue(), default_branch.cyan());
+            progress.println(format!(
+                "  {} Checking out {}",
+                "→".blue(),
+                default_branch.cyan()
+            ));
             execute_git(&["checkout", default_branch])?;
         }
         Ok(())
     })
 }
 
-fn update_base_branch(main_repo_path: &Path, default_branch: &str) -> Result<()> {
+fn update_base_branch(
+    progress: &MergeProgress,
+    main_repo_path: &Path,
+    remote: &str,
+    default_branch: &str,
+) -> Result<()> {
     execute_in_dir(main_repo_path, || {
-        let upstream_ref = format!("origin/{}", default_branch);
+        let upstream_ref = format!("{}/{}", remote, default_branch);
         let counts = ahead_behind(default_branch, &upstream_ref)?;
 
         match (counts.behind, counts.ahead) {
             (0, a) if a > 0 => {
-                println!(
-                    "  {} Local {} ahead of origin; skipping pull",
+                progress.println(format!(
+                    "  {} Local {} ahead of {}; skipping pull",
                     "ℹ️".blue(),
-                    default_branch.cyan()
-                );
+                    default_branch.cyan(),
+                    remote
+                ));
                 Ok(())
             }
             (0, _) => {
-                println!(
-                    "  {} {} already up to date with origin",
+                progress.println(format!(
+                    "  {} {} already up to date with {}",
                     "ℹ️".blue(),
-                    default_branch.cyan()
-                );
+                    default_branch.cyan(),
+                    remote
+                ));
                 Ok(())
             }
             (b, 0) if b > 0 => {
-                println!("  {} Pulling latest {}", "→".blue(), default_branch.cyan());
-                execute_git(&["pull", "--ff-only", "origin", default_branch])?;
+                progress.println(format!(
+                    "  {} Pulling latest {}",
+                    "→".blue(),
+                    default_branch.cyan()
+                ));
+                execute_git(&["pull", "--ff-only", remote, default_branch])?;
                 Ok(())
             }
             _ => bail!(
@@ -282,6 +586,7 @@ struct CommitMessageParts {
 }
 
 fn merge_branch(
+    progress: &MergeProgress,
     main_repo_path: &Path,
     branch: &str,
     default_branch: &str,
@@ -290,41 +595,46 @@ fn merge_branch(
     let branch = branch.to_string();
     let merge_result = execute_in_dir(main_repo_path, || match strategy {
         MergeStrategy::FfOnly => {
-            println!("  {} Fast-forward merging {}", "→".blue(), branch.cyan());
-            execute_git(&["merge", "--ff-only", &branch])?;
+            progress.step(format!("Fast-forward merging {branch}…"), || {
+                execute_git(&["merge", "--ff-only", &branch])
+            })?;
             Ok(MergeOutcome {
                 squash_commit_subject: None,
                 squash_detail: None,
             })
         }
         MergeStrategy::Merge => {
-            println!("  {} Merging {}", "→".blue(), branch.cyan());
-            execute_git(&["merge", "--no-ff", &branch])?;
+            progress.step(format!("Merging {branch}…"), || {
+                execute_git(&["merge", "--no-ff", &branch])
+            })?;
             Ok(MergeOutcome {
                 squash_commit_subject: None,
                 squash_detail: None,
             })
         }
         MergeStrategy::Squash => {
-            println!("  {} Squash merging {}", "→".blue(), branch.cyan());
-            execute_git(&["merge", "--squash", &branch])?;
-
-            let staged = execute_git(&["diff", "--cached", "--name-only"])?;
-            if staged.trim().is_empty() {
-                bail!(
-                    "Squash merge produced no staged changes. Branch '{}' may already be merged into '{}'",
-                    branch,
-                    default_branch
-                );
-            }
+            let message = progress.step("Computing squash…".to_string(), || {
+                execute_git(&["merge", "--squash", &branch])?;
+
+                let staged = execute_git(&["diff", "--cached", "--name-only"])?;
+                if staged.trim().is_empty() {
+                    bail!(
+                        "Squash merge produced no staged changes. Branch '{}' may already be merged into '{}'",
+                        branch,
+                        default_branch
+                    );
+                }
 
-            let message = build_squash_commit_message(&branch, default_branch)?;
+                build_squash_commit_message(&branch, default_branch)
+            })?;
 
-            if let Some(body) = &message.body {
-                execute_git(&["commit", "-m", &message.subject, "-m", body])?;
-            } else {
-                execute_git(&["commit", "-m", &message.subject])?;
-            }
+            progress.step("Committing…".to_string(), || {
+                if let Some(body) = &message.body {
+                    execute_git(&["commit", "-m", &message.subject, "-m", body])
+                } else {
+                    execute_git(&["commit", "-m", &message.subject])
+                }
+            })?;
 
             Ok(MergeOutcome {
                 squash_commit_subject: Some(message.subject),
@@ -403,14 +713,54 @@ fn build_squash_commit_message(branch: &str, default_branch: &str) -> Result<Com
     })
 }
 
-fn push_default_branch(main_repo_path: &Path, default_branch: &str) -> Result<()> {
-    execute_in_dir(main_repo_path, || {
-        println!(
-            "  {} Pushing {} to origin",
-            "→".blue(),
-            default_branch.cyan()
-        );
-        execute_git(&["push", "origin", default_branch])?;
-        Ok(())
-    })
+fn push_default_branch(
+    progress: &MergeProgress,
+    main_repo_path: &Path,
+    remote: &str,
+    default_branch: &str,
+    set_upstream: bool,
+) -> Result<()> {
+    progress.println(format!(
+        "  {} Pushing {} to {}",
+        "→".blue(),
+        default_branch.cyan(),
+        remote
+    ));
+
+    let mut args = vec!["push"];
+    if set_upstream {
+        args.push("--set-upstream");
+    }
+    args.extend([remote, default_branch]);
+
+    // Run git directly so the remote's full rejection message reaches the user
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(main_repo_path)
+        .output()
+        .context("Failed to run git push")?;
+    if output.status.success() {
+        if set_upstream {
+            progress.println(format!(
+                "  {} {} now tracks {}/{}",
+                "ℹ️".blue(),
+                default_branch.cyan(),
+                remote,
+                default_branch
+            ));
+        }
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = stderr.trim();
+    let hint = if stderr.contains("non-fast-forward") || stderr.contains("fetch first") {
+        format!(
+            "\nThe merge is committed locally; pull {}/{} and push again.",
+            remote, default_branch
+        )
+    } else {
+        String::new()
+    };
+    bail!("Push to '{}' was rejected:\n{}{}", remote, stderr, hint)
 }
@@ -1,29 +1,76 @@
 pub mod add;
+pub mod audit;
+pub mod checkpoint;
 pub mod clean;
 pub mod complete;
+pub mod config;
+pub mod copy_branch_name;
+pub mod cp;
 pub mod create;
 pub mod delete;
 pub mod dir;
 pub mod discovery;
+pub mod doctor;
 pub mod exec;
+pub mod fork;
+pub mod gc;
+pub mod init;
+pub mod link;
 pub mod list;
+pub mod lock;
+pub mod log;
 pub mod merge;
+pub mod note;
 pub mod open;
+pub mod pin;
+pub mod reap;
 pub mod rename;
+pub mod reset;
+pub mod send;
 pub mod sessions;
+pub mod shell;
+pub mod snapshot;
+pub mod status;
+pub mod tasks;
 pub mod ui;
+pub mod watch;
 
-pub use add::handle_add;
-pub use clean::handle_clean;
+pub use add::{handle_add, handle_adopt};
+pub use audit::handle_audit_list;
+pub use checkpoint::handle_checkpoint;
+pub use clean::{handle_clean, handle_clean_branches};
 pub use complete::handle_complete_worktrees;
-pub use create::handle_create;
+pub use config::{handle_config_edit, handle_config_show};
+pub use copy_branch_name::handle_copy_branch_name;
+pub use cp::handle_cp;
+pub use create::{SubmoduleMode, TemplateOptions, handle_create};
 pub use delete::handle_delete;
-pub use dir::handle_dir;
+pub use dir::{DirFormat, handle_dir};
 pub use discovery::handle_discovery;
+pub use doctor::handle_doctor;
 pub use exec::handle_exec;
-pub use list::handle_list;
-pub use merge::{MergeStrategy, handle_merge};
+pub use fork::handle_fork;
+pub use gc::handle_gc;
+pub use init::handle_init;
+pub use link::{handle_link, handle_unlink};
+pub use list::{ListOutput, SizeMode, handle_list};
+pub use lock::{handle_lock, handle_unlock};
+pub use log::handle_log;
+pub use merge::{MergeStrategy, PreMergePrompt, handle_merge, resolve_strategy};
+pub use note::handle_note;
 pub use open::handle_open;
+pub use pin::{handle_pin, handle_unpin};
+pub use reap::handle_reap;
 pub use rename::handle_rename;
-pub use sessions::handle_sessions_list;
+pub use reset::handle_reset;
+pub use send::handle_send;
+pub use sessions::{
+    SessionsFormat, handle_sessions_delete, handle_sessions_export, handle_sessions_list,
+    handle_sessions_prune,
+};
+pub use shell::handle_shell;
+pub use snapshot::handle_snapshot;
+pub use status::handle_worktree_status;
+pub use tasks::{handle_tasks_delete, handle_tasks_list, handle_tasks_merge};
 pub use ui::handle_ui;
+pub use watch::handle_watch;
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use agentdev::state::XlaudeState;
+
+/// Append `text` to a worktree's notes on a new line, or replace them with
+/// `set`. Replacing with empty text clears the notes.
+pub fn handle_note(name: String, text: String, set: bool) -> Result<()> {
+    let mut state = XlaudeState::load()?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, Some(name))?;
+    let info = state_key
+        .and_then(|key| state.worktrees.get_mut(&key))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Worktree '{}' is not managed by agentdev; adopt it first with 'agentdev worktree adopt'",
+                git_wt.display_name()
+            )
+        })?;
+
+    let text = text.trim();
+    if text.is_empty() && !set {
+        anyhow::bail!("Note text is empty; use --set with empty text to clear the notes");
+    }
+    let notes = match info.notes.take() {
+        Some(existing) if !set => format!("{existing}\n{text}"),
+        _ => text.to_string(),
+    };
+    info.notes = (!notes.is_empty()).then_some(notes);
+
+    let display_name = info.name.clone();
+    let cleared = info.notes.is_none();
+    state.save()?;
+
+    if cleared {
+        println!(
+            "{} Cleared notes on '{}'",
+            "📝".green(),
+            display_name.cyan()
+        );
+    } else if set {
+        println!("{} Set notes on '{}'", "📝".green(), display_name.cyan());
+    } else {
+        println!("{} Added note to '{}'", "📝".green(), display_name.cyan());
+    }
+    Ok(())
+}
@@ -1,14 +1,30 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
 
-use crate::input::{drain_stdin, get_command_arg, is_piped_input, smart_confirm, smart_select};
-use agentdev::tmux::TmuxManager;
+use crate::input::{drain_stdin, get_command_arg, is_piped_input, select_worktree, smart_confirm};
+use agentdev::config::resolve_typing_pace;
+use agentdev::tmux::{TmuxManager, TypingPace};
 use agentdev::git::{get_current_branch, get_repo_name, is_base_branch, is_in_worktree};
 use agentdev::state::{WorktreeInfo, XlaudeState};
 use agentdev::utils::{resolve_agent_command_with_override, sanitize_branch_name};
 
-pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
+/// Delay before typing a `--send` prompt into a new session so the agent has time to boot.
+const SEND_PROMPT_DELAY: Duration = Duration::from_millis(1500);
+
+pub fn handle_open(
+    name: Option<String>,
+    agent: Option<String>,
+    yes: bool,
+    recreate: bool,
+    send: Option<String>,
+    send_file: Option<PathBuf>,
+) -> Result<()> {
+    let send = read_send_text(send, send_file)?;
     let mut state = XlaudeState::load()?;
 
     // Check if current path is a worktree when no name is provided
@@ -82,6 +98,14 @@ pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
                         task_name: None,
                         initial_prompt: None,
                         agent_alias: None,
+                        locked: false,
+                        lock_reason: None,
+                        last_agent_activity: None,
+                        base_ref: None,
+                        pinned: false,
+                        checkpoints: Vec::new(),
+                        notes: None,
+                        symlink_path: None,
                     },
                 );
                 state.save()?;
@@ -100,7 +124,10 @@ pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
                 drain_stdin()?;
             }
 
-            launch_agent_via_tmux(&worktree_name, &current_dir, agent)?;
+            XlaudeState::record_activity(&key)?;
+            let session = SessionChoice::from_flags(yes, recreate);
+            let send = send.map(|text| SendPrompt::new(text, &state.worktrees[&key]));
+            launch_agent_via_tmux(&worktree_name, &current_dir, agent, session, send)?;
             return Ok(());
         }
     }
@@ -113,7 +140,7 @@ pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
     let target_name = get_command_arg(name)?;
 
     // Determine which worktree to open
-    let (_key, worktree_info) = if let Some(n) = target_name {
+    let (key, worktree_info) = if let Some(n) = target_name {
         // Find worktree by name across all projects
         state
             .worktrees
@@ -122,15 +149,9 @@ pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
             .map(|(k, w)| (k.clone(), w.clone()))
             .context(format!("Worktree '{n}' not found"))?
     } else {
-        // Interactive selection - show repo/name format
-        let worktree_list = state.prioritized_worktree_list();
-
-        let selection = smart_select("Select a worktree to open", &worktree_list, |(_, info)| {
-            format!("{}/{}", info.repo_name, info.name)
-        })?;
-
-        match selection {
-            Some(idx) => worktree_list[idx].clone(),
+        // Interactive selection
+        match select_worktree("Select a worktree to open", &state)? {
+            Some(entry) => entry,
             None => anyhow::bail!(
                 "Interactive selection not available in non-interactive mode. Please specify a worktree name."
             ),
@@ -151,16 +172,94 @@ pub fn handle_open(name: Option<String>, agent: Option<String>) -> Result<()> {
         drain_stdin()?;
     }
 
-    launch_agent_via_tmux(worktree_name, &worktree_info.path, agent)?;
+    XlaudeState::record_activity(&key)?;
+    launch_agent_via_tmux(
+        worktree_name,
+        &worktree_info.path,
+        agent,
+        SessionChoice::from_flags(yes, recreate),
+        send.map(|text| SendPrompt::new(text, &worktree_info)),
+    )?;
 
     Ok(())
 }
 
-/// Launch agent in a tmux session. If session exists, attach to it; otherwise create and attach.
+/// What to do when a worktree has no live tmux session to attach to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SessionChoice {
+    /// Ask before starting a new agent session
+    Prompt,
+    /// Start a new session without asking (`--yes`)
+    Start,
+    /// Replace any existing session, live or not (`--recreate`)
+    Recreate,
+}
+
+impl SessionChoice {
+    fn from_flags(yes: bool, recreate: bool) -> Self {
+        if recreate {
+            Self::Recreate
+        } else if yes {
+            Self::Start
+        } else {
+            Self::Prompt
+        }
+    }
+}
+
+/// Prompt typed into the agent session by `--send` or `--send-file`.
+struct SendPrompt {
+    text: String,
+    pace: TypingPace,
+}
+
+impl SendPrompt {
+    fn new(text: String, info: &WorktreeInfo) -> Self {
+        Self {
+            text,
+            pace: resolve_typing_pace(info.agent_alias.as_deref()),
+        }
+    }
+
+    fn type_into(&self, tmux: &TmuxManager, session_id: &str) -> Result<()> {
+        tmux.send_text_paced(session_id, &self.text, self.pace)?;
+        tmux.send_enter_paced(session_id, self.pace)?;
+        println!(
+            "{} Sent prompt to '{}'; attach with 'agentdev worktree open {}'",
+            "📨".green(),
+            session_id.cyan(),
+            session_id
+        );
+        Ok(())
+    }
+}
+
+/// The `--send` text, read from `--send-file` when given.
+fn read_send_text(send: Option<String>, send_file: Option<PathBuf>) -> Result<Option<String>> {
+    let text = match (send, send_file) {
+        (Some(text), _) => text,
+        (None, Some(path)) => fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read prompt from {}", path.display()))?,
+        (None, None) => return Ok(None),
+    };
+    let text = text.trim_end().to_string();
+    if text.trim().is_empty() {
+        anyhow::bail!("Prompt to send is empty");
+    }
+    Ok(Some(text))
+}
+
+/// Launch agent in a tmux session. If a live session exists, attach to it; otherwise
+/// (after confirming) create a new one and attach.
+///
+/// With `send`, the prompt is typed into the session instead and the terminal is
+/// never attached.
 fn launch_agent_via_tmux(
     worktree_name: &str,
     work_dir: &std::path::Path,
     agent: Option<String>,
+    choice: SessionChoice,
+    send: Option<SendPrompt>,
 ) -> Result<()> {
     let tmux = TmuxManager::new();
 
@@ -177,23 +276,54 @@ fn launch_agent_via_tmux(
     let session_id = worktree_name;
 
     if tmux.session_exists(session_id) {
-        // Session exists, just attach
-        println!(
-            "{} Attaching to existing tmux session...",
-            "📎".cyan()
-        );
-        tmux.attach_session(session_id)?;
-    } else {
-        // Create new session with agent
-        println!(
-            "{} Creating tmux session and starting agent...",
-            "🚀".cyan()
-        );
+        if choice == SessionChoice::Recreate {
+            println!("{} Stopping existing tmux session...", "🔄".cyan());
+            tmux.kill_session(session_id)?;
+        } else if tmux.session_is_dead(session_id) {
+            // A session whose agent exited is useless to attach to
+            println!(
+                "{} The agent in tmux session '{}' has exited",
+                "⚠️".yellow(),
+                tmux.session_name(session_id)
+            );
+            tmux.kill_session(session_id)?;
+        } else {
+            if let Some(send) = &send {
+                return send.type_into(&tmux, session_id);
+            }
+            // Session is alive, just attach
+            println!(
+                "{} Attaching to existing tmux session...",
+                "📎".cyan()
+            );
+            tmux.attach_session(session_id)?;
+            return Ok(());
+        }
+    }
+
+    if choice == SessionChoice::Prompt
+        && !smart_confirm(
+            &format!("No live agent session for '{worktree_name}'. Start a new one?"),
+            true,
+        )?
+    {
+        println!("{} Cancelled", "❌".red());
+        return Ok(());
+    }
+
+    // Create new session with agent
+    println!(
+        "{} Creating tmux session and starting agent...",
+        "🚀".cyan()
+    );
 
-        let (program, args) = resolve_agent_command_with_override(agent)?;
-        tmux.create_session_with_command(session_id, work_dir, &program, &args)?;
-        tmux.attach_session(session_id)?;
+    let (program, args) = resolve_agent_command_with_override(agent)?;
+    tmux.create_session_with_command(session_id, work_dir, &program, &args)?;
+    if let Some(send) = &send {
+        thread::sleep(SEND_PROMPT_DELAY);
+        return send.type_into(&tmux, session_id);
     }
+    tmux.attach_session(session_id)?;
 
     Ok(())
 }
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use crate::input::get_command_arg;
+use agentdev::state::XlaudeState;
+
+pub fn handle_pin(name: Option<String>) -> Result<()> {
+    let display_name = set_pinned(name, true)?;
+    println!("{} Worktree '{}' pinned", "📌".green(), display_name.cyan());
+    Ok(())
+}
+
+pub fn handle_unpin(name: Option<String>) -> Result<()> {
+    let display_name = set_pinned(name, false)?;
+    println!(
+        "{} Worktree '{}' unpinned",
+        "📍".green(),
+        display_name.cyan()
+    );
+    Ok(())
+}
+
+/// Record the pin flag for a managed worktree, returning its name.
+fn set_pinned(name: Option<String>, pinned: bool) -> Result<String> {
+    let mut state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let info = state_key
+        .and_then(|key| state.worktrees.get_mut(&key))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Worktree '{}' is not managed by agentdev; adopt it first with 'agentdev worktree adopt'",
+                git_wt.display_name()
+            )
+        })?;
+
+    info.pinned = pinned;
+    let display_name = info.name.clone();
+    state.save()?;
+    Ok(display_name)
+}
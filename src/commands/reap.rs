@@ -0,0 +1,91 @@
+use anyhow::{Result, bail};
+use chrono::Utc;
+use colored::Colorize;
+use std::collections::HashMap;
+
+use agentdev::state::XlaudeState;
+use agentdev::tmux::{SessionInfo, TmuxManager};
+
+/// Kill agentdev tmux sessions with no activity in the last `idle_hours`.
+///
+/// Idleness comes from tmux's own `session_activity` timestamp, so no pane
+/// is captured. Attached sessions are always kept; `dry_run` only lists what
+/// would be killed.
+pub fn handle_reap(idle_hours: u64, dry_run: bool) -> Result<()> {
+    if !TmuxManager::is_available() {
+        bail!("tmux is not installed");
+    }
+
+    let state = XlaudeState::load()?;
+    let tmux = TmuxManager::new();
+    let worktree_names: HashMap<String, &str> = state
+        .worktrees
+        .values()
+        .map(|info| (tmux.session_name(&info.name), info.name.as_str()))
+        .collect();
+
+    let threshold = i64::try_from(idle_hours.saturating_mul(3600)).unwrap_or(i64::MAX);
+    let now = Utc::now().timestamp();
+    let mut sessions = tmux.list_sessions()?;
+    sessions.sort_by_key(|session| session.last_activity);
+
+    let mut idle = Vec::new();
+    let mut attached = 0;
+    for session in sessions {
+        if now.saturating_sub(session.last_activity) < threshold {
+            continue;
+        }
+        if session.is_attached {
+            attached += 1;
+            continue;
+        }
+        idle.push(session);
+    }
+
+    if attached > 0 {
+        println!(
+            "{} Keeping {} idle session(s) that are attached",
+            "ℹ️ ".blue(),
+            attached
+        );
+    }
+    if idle.is_empty() {
+        println!(
+            "{} No tmux sessions idle for {}h or more",
+            "✨".green(),
+            idle_hours
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} session(s) idle for {}h or more:",
+        "💤".yellow(),
+        idle.len(),
+        idle_hours
+    );
+    for session in &idle {
+        let session_name = tmux.session_name(&session.project);
+        let label = match worktree_names.get(&session_name) {
+            Some(name) => name.cyan().to_string(),
+            None => format!("{} {}", session_name.cyan(), "(no worktree)".bright_black()),
+        };
+        println!(
+            "  {} {}  idle {}",
+            "•".bright_black(),
+            label,
+            SessionInfo::format_duration_since(session.last_activity)
+        );
+    }
+
+    if dry_run {
+        println!("{} Dry run: nothing was killed", "ℹ️ ".blue());
+        return Ok(());
+    }
+
+    for session in &idle {
+        tmux.kill_session(&session.project)?;
+    }
+    println!("{} Killed {} session(s)", "✅".green(), idle.len());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use crate::input::{get_command_arg, smart_confirm};
+use agentdev::git::execute_git;
+use agentdev::state::XlaudeState;
+
+/// Untracked files listed before asking for confirmation.
+const MAX_UNTRACKED_SHOWN: usize = 20;
+
+/// Discard every uncommitted change in a worktree: `git reset --hard HEAD`
+/// followed by `git clean -fd`. Ignored files are left alone.
+pub fn handle_reset(name: Option<String>, yes: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (_, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let display_name = git_wt.display_name();
+
+    let path_str = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+
+    let status = execute_git(&[
+        "-C",
+        path_str,
+        "status",
+        "--porcelain",
+        "--untracked-files=all",
+    ])?;
+    let (untracked, tracked): (Vec<&str>, Vec<&str>) = status
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .partition(|line| line.starts_with("??"));
+    if tracked.is_empty() && untracked.is_empty() {
+        println!(
+            "{} Worktree '{}' is clean; nothing to reset",
+            "✨".green(),
+            display_name.cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Uncommitted changes in '{}':",
+        "⚠️ ".yellow(),
+        display_name.cyan()
+    );
+    if !tracked.is_empty() {
+        let stat = execute_git(&["-C", path_str, "diff", "HEAD", "--stat"])?;
+        for line in stat.lines() {
+            println!("  {line}");
+        }
+    }
+    for line in untracked.iter().take(MAX_UNTRACKED_SHOWN) {
+        println!("  {} {}", "+".green(), line[3..].trim());
+    }
+    if untracked.len() > MAX_UNTRACKED_SHOWN {
+        println!(
+            "  {} ... and {} more untracked file(s)",
+            "+".green(),
+            untracked.len() - MAX_UNTRACKED_SHOWN
+        );
+    }
+    println!();
+
+    let non_interactive = yes || std::env::var("XLAUDE_NON_INTERACTIVE").is_ok();
+    if !non_interactive
+        && !smart_confirm("Discard all of these changes? This cannot be undone", false)?
+    {
+        println!("{} Cancelled", "❌".red());
+        return Ok(());
+    }
+
+    execute_git(&["-C", path_str, "reset", "--hard", "HEAD"])
+        .context("Failed to reset tracked files")?;
+    execute_git(&["-C", path_str, "clean", "-fd"]).context("Failed to remove untracked files")?;
+
+    println!(
+        "{} Reset '{}': {} file(s) restored, {} untracked file(s) removed",
+        "✅".green(),
+        display_name.cyan(),
+        tracked.len(),
+        untracked.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use agentdev::config::resolve_typing_pace;
+use agentdev::state::XlaudeState;
+use agentdev::tmux::TmuxManager;
+
+/// Type `message` into the agent session of a managed worktree.
+pub fn handle_send(name: String, message: String, no_enter: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let (key, info) = state
+        .worktrees
+        .iter()
+        .find(|(_, info)| info.name == name)
+        .with_context(|| format!("Worktree '{name}' not found in agentdev state"))?;
+
+    if !TmuxManager::is_available() {
+        anyhow::bail!("tmux is not installed; cannot send to worktree '{name}'");
+    }
+    let tmux = TmuxManager::new();
+    if !tmux.session_exists(&info.name) {
+        anyhow::bail!(
+            "No tmux session for worktree '{}'. Start one with 'agentdev worktree open {}'.",
+            info.name,
+            info.name
+        );
+    }
+
+    let pace = resolve_typing_pace(info.agent_alias.as_deref());
+    tmux.send_text_paced(&info.name, &message, pace)?;
+    if !no_enter {
+        tmux.send_enter_paced(&info.name, pace)?;
+    }
+    XlaudeState::record_activity(key)?;
+
+    println!(
+        "{} Sent message to '{}'{}",
+        "📨".green(),
+        info.name.cyan(),
+        if no_enter { " (without Enter)" } else { "" }
+    );
+    Ok(())
+}
@@ -1,14 +1,26 @@
 use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
-use anyhow::Result;
-use chrono::{DateTime, Utc};
-use colored::Colorize;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta, Utc};
+use clap::ValueEnum;
+use colored::{Color, ColoredString, Colorize};
+use crossterm::{cursor, execute, terminal};
 use serde::Serialize;
 
-use agentdev::sessions::{SessionRecord, canonicalize, default_providers};
+use agentdev::disk_usage::format_size;
+use agentdev::sessions::{
+    ProviderDiagnostics, SessionProvider, SessionRecency, SessionRecord, canonicalize,
+    dedup_sessions_by, default_providers, find_session, render_markdown,
+};
 use agentdev::state::{WorktreeInfo, XlaudeState};
 
+use crate::input::smart_confirm;
+
 #[derive(Debug, Serialize)]
 struct JsonSession {
     provider: String,
@@ -42,74 +54,500 @@ struct SessionWithWorktree {
     worktree_path: Option<PathBuf>,
 }
 
-pub fn handle_sessions_list(worktree: Option<String>, all: bool, json: bool) -> Result<()> {
-    let state = XlaudeState::load()?;
+/// Session IDs are cut to this many characters in `--format table`.
+const TABLE_ID_WIDTH: usize = 12;
+/// Last messages are truncated to this length in `--format table`.
+const TABLE_MESSAGE_WIDTH: usize = 40;
 
-    let worktree_entries = build_worktree_index(&state);
-    let worktree_filter = worktree.as_deref();
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum SessionsFormat {
+    /// Sessions grouped by provider and worktree
+    Text,
+    /// One aligned row per session
+    Table,
+    Json,
+    /// `<provider>/<id> <timestamp> <last message>` per session
+    Compact,
+}
 
-    let mut sessions: Vec<SessionWithWorktree> = Vec::new();
-    for provider in default_providers() {
+impl SessionsFormat {
+    /// `--json` is kept as a deprecated shorthand for `--format json`.
+    pub fn from_flags(json: bool, format: Option<SessionsFormat>) -> Self {
+        if json {
+            Self::Json
+        } else {
+            format.unwrap_or(Self::Text)
+        }
+    }
+}
+
+pub fn handle_sessions_list(
+    worktree: Option<String>,
+    all: bool,
+    format: SessionsFormat,
+    ndjson: bool,
+    watch: Option<u64>,
+    diagnose: bool,
+    dedup: bool,
+) -> Result<()> {
+    if diagnose {
+        return print_diagnostics(format == SessionsFormat::Json);
+    }
+    if let Some(interval) = watch {
+        return watch_sessions(worktree.as_deref(), all, dedup, format, interval);
+    }
+    if ndjson {
+        return stream_sessions(worktree.as_deref(), all, dedup);
+    }
+
+    let sessions = collect_sessions(worktree.as_deref(), all, dedup)?;
+
+    if format == SessionsFormat::Json {
+        let payload = serde_json::to_string_pretty(&build_json_output(&sessions))?;
+        println!("{payload}");
+    } else {
+        print_sessions(&sessions, format);
+    }
+
+    Ok(())
+}
+
+/// Delete session files whose last activity is older than `older_than_days`.
+///
+/// Only prints what would be removed unless `yes` is set. Sessions without a
+/// timestamp are never pruned since their age is unknown.
+pub fn handle_sessions_prune(
+    older_than_days: u64,
+    provider: Option<String>,
+    yes: bool,
+) -> Result<()> {
+    let providers: Vec<_> = default_providers()
+        .into_iter()
+        .filter(|candidate| {
+            provider
+                .as_deref()
+                .is_none_or(|name| candidate.name().eq_ignore_ascii_case(name))
+        })
+        .collect();
+    if providers.is_empty() {
+        let known: Vec<_> = default_providers().iter().map(|p| p.name()).collect();
+        anyhow::bail!(
+            "Unknown provider '{}' (expected one of: {})",
+            provider.unwrap_or_default(),
+            known.join(", ")
+        );
+    }
+
+    let days = i64::try_from(older_than_days).context("--older-than is too large")?;
+    let cutoff = TimeDelta::try_days(days)
+        .and_then(|age| Utc::now().checked_sub_signed(age))
+        .context("--older-than is too large")?;
+
+    let mut candidates: Vec<(SessionRecord, u64)> = Vec::new();
+    for provider in providers {
         match provider.list_sessions() {
-            Ok(records) => {
-                for record in records {
-                    let matched = record
-                        .working_dir
-                        .as_ref()
-                        .and_then(|path| match_worktree(path, &worktree_entries));
-
-                    let (worktree_key, worktree_name, worktree_path) = match matched {
-                        Some((key, info)) => (
-                            Some(key.clone()),
-                            Some(info.name.clone()),
-                            Some(info.path.clone()),
-                        ),
-                        None => (None, None, None),
-                    };
-
-                    sessions.push(SessionWithWorktree {
-                        record,
-                        worktree_key,
-                        worktree_name,
-                        worktree_path,
-                    });
-                }
-            }
+            Ok(records) => candidates.extend(
+                records
+                    .into_iter()
+                    .filter(|record| record.last_timestamp.is_some_and(|ts| ts < cutoff))
+                    .filter_map(|record| {
+                        let size = fs::metadata(&record.file_path).ok()?.len();
+                        Some((record, size))
+                    }),
+            ),
             Err(err) => {
                 eprintln!("{} {}: {}", "[warn]".yellow(), provider.name(), err);
             }
         }
     }
 
-    if !all {
-        sessions.retain(|session| session.worktree_key.is_some());
+    if candidates.is_empty() {
+        println!(
+            "{} No sessions older than {older_than_days} day(s)",
+            "✨".green()
+        );
+        return Ok(());
+    }
+
+    candidates.sort_by_key(|(record, _)| record.last_timestamp);
+    print_prune_table(&candidates);
+
+    let total: u64 = candidates.iter().map(|(_, size)| size).sum();
+    if !yes {
+        println!(
+            "{} Dry run: would delete {} session file(s), freeing {}. Re-run with {} to delete.",
+            "ℹ️ ".blue(),
+            candidates.len(),
+            format_size(total),
+            "--yes".cyan()
+        );
+        return Ok(());
     }
 
-    if let Some(filter) = worktree_filter {
-        sessions.retain(|session| {
-            session
-                .worktree_key
+    let mut freed = 0;
+    let mut deleted = 0;
+    let mut failed = 0;
+    for (record, size) in &candidates {
+        match fs::remove_file(&record.file_path) {
+            Ok(()) => {
+                freed += size;
+                deleted += 1;
+            }
+            Err(err) => {
+                eprintln!(
+                    "{} Failed to delete {}: {err}",
+                    "❌".red(),
+                    record.file_path.display()
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} Deleted {deleted} session file(s), freed {}",
+        "✅".green(),
+        format_size(freed)
+    );
+    if failed > 0 {
+        anyhow::bail!("Failed to delete {failed} session file(s)");
+    }
+    Ok(())
+}
+
+/// Archive one session's log files so it no longer shows up in listings.
+pub fn handle_sessions_delete(provider: String, session_id: String, yes: bool) -> Result<()> {
+    let Some((provider, record)) = find_session(&provider, &session_id)? else {
+        anyhow::bail!("Session '{session_id}' not found for provider '{provider}'");
+    };
+
+    println!(
+        "{} {} session {}",
+        "🗂".cyan(),
+        provider.name().color(provider_color(provider.name())),
+        record.id.bold()
+    );
+    if let Some(message) = record.first_user_message.as_deref() {
+        println!("  {} {}", "Summary:".bright_black(), truncate(message, 100));
+    }
+    if let Some(ts) = record.last_timestamp {
+        println!(
+            "  {} {}",
+            "Last activity:".bright_black(),
+            format_relative(ts)
+        );
+    }
+    println!(
+        "  {} {}",
+        "File:".bright_black(),
+        record.file_path.display()
+    );
+
+    if !yes && !smart_confirm("Move this session to the archive?", false)? {
+        println!("{} Cancelled", "❌".red());
+        return Ok(());
+    }
+
+    let archived = provider.delete_session(&record)?;
+    println!(
+        "{} Archived session {} ({} file(s))",
+        "✅".green(),
+        record.id.cyan(),
+        archived.len()
+    );
+    for path in archived {
+        println!("  {} {}", "→".bright_black(), path.display());
+    }
+    Ok(())
+}
+
+/// Write every session transcript to `<output_dir>/<provider>/<session_id>.md`.
+///
+/// `since` accepts a date (`2025-01-31`) or an RFC 3339 timestamp and keeps
+/// only sessions active at or after it; sessions without a timestamp are
+/// skipped in that case.
+pub fn handle_sessions_export(
+    output_dir: PathBuf,
+    provider: Option<String>,
+    since: Option<String>,
+) -> Result<()> {
+    let providers: Vec<_> = default_providers()
+        .into_iter()
+        .filter(|candidate| {
+            provider
                 .as_deref()
-                .is_some_and(|key| key == filter)
-                || session
-                    .worktree_name
-                    .as_deref()
-                    .is_some_and(|name| name == filter)
-        });
+                .is_none_or(|name| candidate.name().eq_ignore_ascii_case(name))
+        })
+        .collect();
+    if providers.is_empty() {
+        let known: Vec<_> = default_providers().iter().map(|p| p.name()).collect();
+        anyhow::bail!(
+            "Unknown provider '{}' (expected one of: {})",
+            provider.unwrap_or_default(),
+            known.join(", ")
+        );
     }
+    let since = since.as_deref().map(parse_since).transpose()?;
 
-    sessions.sort_by(|a, b| b.record.last_timestamp.cmp(&a.record.last_timestamp));
+    let mut files = 0usize;
+    let mut bytes = 0u64;
+    for provider in providers {
+        let records = match provider.list_sessions() {
+            Ok(records) => records,
+            Err(err) => {
+                eprintln!("{} {}: {}", "[warn]".yellow(), provider.name(), err);
+                continue;
+            }
+        };
+        let provider_dir = output_dir.join(provider.name());
+        for record in records {
+            if let Some(cutoff) = since
+                && record.last_timestamp.is_none_or(|ts| ts < cutoff)
+            {
+                continue;
+            }
+            let events = match provider.load_session_events(&record) {
+                Ok(events) => events,
+                Err(err) => {
+                    eprintln!(
+                        "{} {} {}: {}",
+                        "[warn]".yellow(),
+                        provider.name(),
+                        record.id,
+                        err
+                    );
+                    continue;
+                }
+            };
+            let markdown = render_markdown(&record, &events);
+            fs::create_dir_all(&provider_dir)
+                .with_context(|| format!("Failed to create {}", provider_dir.display()))?;
+            let path = provider_dir.join(format!("{}.md", record.id.replace(['/', '\\'], "_")));
+            fs::write(&path, &markdown)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            files += 1;
+            bytes += markdown.len() as u64;
+        }
+    }
 
-    if json {
-        let payload = serde_json::to_string_pretty(&build_json_output(&sessions))?;
-        println!("{payload}");
-    } else {
-        print_human_readable(&sessions);
+    println!(
+        "{} Exported {} session(s) ({}) to {}",
+        "✅".green(),
+        files,
+        format_size(bytes),
+        output_dir.display()
+    );
+    Ok(())
+}
+
+fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|ts| ts.with_timezone(&Utc))
+        .with_context(|| format!("Invalid --since '{value}' (expected YYYY-MM-DD or RFC 3339)"))
+}
+
+fn print_prune_table(candidates: &[(SessionRecord, u64)]) {
+    let provider_width = candidates
+        .iter()
+        .map(|(record, _)| record.provider.len())
+        .max()
+        .unwrap_or(0)
+        .max("PROVIDER".len());
+    let id_width = candidates
+        .iter()
+        .map(|(record, _)| record.id.len())
+        .max()
+        .unwrap_or(0)
+        .max("SESSION".len());
+
+    println!(
+        "{:<provider_width$}  {:<id_width$}  {:<12}  {:>10}  {}",
+        "PROVIDER".bold(),
+        "SESSION".bold(),
+        "LAST ACTIVE".bold(),
+        "SIZE".bold(),
+        "FILE".bold()
+    );
+    for (record, size) in candidates {
+        let last_active = record
+            .last_timestamp
+            .map(format_relative)
+            .unwrap_or_default();
+        println!(
+            "{:<provider_width$}  {:<id_width$}  {:<12}  {:>10}  {}",
+            record.provider.color(provider_color(&record.provider)),
+            record.id,
+            last_active,
+            format_size(*size),
+            record.file_path.display().to_string().bright_black()
+        );
+    }
+    println!();
+}
+
+/// Re-run the scan every `interval` seconds until interrupted.
+///
+/// Human output redraws the screen like `watch`; JSON output emits one compact
+/// document per line so it can be piped into `jq -c .`.
+fn watch_sessions(
+    worktree: Option<&str>,
+    all: bool,
+    dedup: bool,
+    format: SessionsFormat,
+    interval: u64,
+) -> Result<()> {
+    let json = format == SessionsFormat::Json;
+    let redraw = !json;
+    if redraw {
+        execute!(io::stdout(), cursor::Hide)?;
     }
+    ctrlc::set_handler(move || {
+        if redraw {
+            let _ = execute!(io::stdout(), cursor::Show);
+            println!();
+        }
+        std::process::exit(0);
+    })
+    .context("Failed to install Ctrl-C handler")?;
+
+    loop {
+        let sessions = collect_sessions(worktree, all, dedup)?;
+
+        if json {
+            let payload = serde_json::to_string(&build_json_output(&sessions))?;
+            println!("{payload}");
+        } else {
+            execute!(
+                io::stdout(),
+                terminal::Clear(terminal::ClearType::All),
+                cursor::MoveTo(0, 0)
+            )?;
+            println!(
+                "{}",
+                format!(
+                    "Every {interval}s: agentdev sessions list    {}",
+                    Local::now().format("%Y-%m-%d %H:%M:%S")
+                )
+                .bright_black()
+            );
+            println!();
+            print_sessions(&sessions, format);
+        }
+        io::stdout().flush()?;
+
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+fn collect_sessions(
+    worktree_filter: Option<&str>,
+    all: bool,
+    dedup: bool,
+) -> Result<Vec<SessionWithWorktree>> {
+    let state = XlaudeState::load()?;
+
+    let worktree_entries = build_worktree_index(&state);
+
+    let mut sessions: Vec<SessionWithWorktree> = Vec::new();
+    for provider in default_providers() {
+        sessions.extend(provider_sessions(
+            provider.as_ref(),
+            &worktree_entries,
+            worktree_filter,
+            all,
+            dedup,
+        ));
+    }
+
+    sessions.sort_by(|a, b| b.record.last_timestamp.cmp(&a.record.last_timestamp));
 
+    Ok(sessions)
+}
+
+/// Print one JSON object per session, flushing after each provider's scan.
+///
+/// Sessions are newest first within a provider; there is no global order
+/// since earlier providers are written before later ones are scanned.
+fn stream_sessions(worktree_filter: Option<&str>, all: bool, dedup: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let worktree_entries = build_worktree_index(&state);
+
+    let mut stdout = io::stdout();
+    for provider in default_providers() {
+        let mut sessions = provider_sessions(
+            provider.as_ref(),
+            &worktree_entries,
+            worktree_filter,
+            all,
+            dedup,
+        );
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.record.last_timestamp));
+        for session in &sessions {
+            writeln!(stdout, "{}", serde_json::to_string(&json_session(session))?)?;
+        }
+        stdout.flush()?;
+    }
     Ok(())
 }
 
+/// Sessions from one provider, matched to worktrees and filtered.
+///
+/// Duplicates never span providers, so `dedup` can run per provider.
+fn provider_sessions(
+    provider: &(dyn SessionProvider + Send + Sync),
+    worktree_entries: &[(String, WorktreeInfo, Option<PathBuf>)],
+    worktree_filter: Option<&str>,
+    all: bool,
+    dedup: bool,
+) -> Vec<SessionWithWorktree> {
+    let mut records = match provider.list_sessions() {
+        Ok(records) => records,
+        Err(err) => {
+            eprintln!("{} {}: {}", "[warn]".yellow(), provider.name(), err);
+            return Vec::new();
+        }
+    };
+    if dedup {
+        records = dedup_sessions_by(records, |record| record);
+    }
+
+    records
+        .into_iter()
+        .map(|record| {
+            let matched = record
+                .working_dir
+                .as_ref()
+                .and_then(|path| match_worktree(path, worktree_entries));
+
+            let (worktree_key, worktree_name, worktree_path) = match matched {
+                Some((key, info)) => (
+                    Some(key.clone()),
+                    Some(info.name.clone()),
+                    Some(info.path.clone()),
+                ),
+                None => (None, None, None),
+            };
+
+            SessionWithWorktree {
+                record,
+                worktree_key,
+                worktree_name,
+                worktree_path,
+            }
+        })
+        .filter(|session| all || session.worktree_key.is_some())
+        .filter(|session| {
+            worktree_filter.is_none_or(|filter| {
+                session.worktree_key.as_deref() == Some(filter)
+                    || session.worktree_name.as_deref() == Some(filter)
+            })
+        })
+        .collect()
+}
+
 fn build_worktree_index(state: &XlaudeState) -> Vec<(String, WorktreeInfo, Option<PathBuf>)> {
     state
         .worktrees
@@ -143,33 +581,172 @@ fn build_json_output(sessions: &[SessionWithWorktree]) -> HashMap<&'static str,
     let mut map: HashMap<&'static str, Vec<JsonSession>> = HashMap::new();
 
     for session in sessions {
-        let entry = map.entry("sessions").or_default();
-        entry.push(JsonSession {
-            provider: session.record.provider.clone(),
-            session_id: session.record.id.clone(),
-            worktree_key: session.worktree_key.clone(),
-            worktree_name: session.worktree_name.clone(),
-            worktree_path: session
-                .worktree_path
-                .as_ref()
-                .map(|p| p.display().to_string()),
-            working_dir: session
-                .record
-                .working_dir
-                .as_ref()
-                .map(|p| p.display().to_string()),
-            originator: session.record.originator.clone(),
-            instructions: session.record.instructions.clone(),
-            first_user_message: session.record.first_user_message.clone(),
-            last_user_message: session.record.last_user_message.clone(),
-            last_timestamp: session.record.last_timestamp.map(|ts| ts.to_rfc3339()),
-            file_path: session.record.file_path.display().to_string(),
-        });
+        map.entry("sessions")
+            .or_default()
+            .push(json_session(session));
     }
 
     map
 }
 
+fn json_session(session: &SessionWithWorktree) -> JsonSession {
+    JsonSession {
+        provider: session.record.provider.clone(),
+        session_id: session.record.id.clone(),
+        worktree_key: session.worktree_key.clone(),
+        worktree_name: session.worktree_name.clone(),
+        worktree_path: session
+            .worktree_path
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        working_dir: session
+            .record
+            .working_dir
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        originator: session.record.originator.clone(),
+        instructions: session.record.instructions.clone(),
+        first_user_message: session.record.first_user_message.clone(),
+        last_user_message: session.record.last_user_message.clone(),
+        last_timestamp: session.record.last_timestamp.map(|ts| ts.to_rfc3339()),
+        file_path: session.record.file_path.display().to_string(),
+    }
+}
+
+/// Report what each provider scanned, so an empty listing can be explained.
+fn print_diagnostics(json: bool) -> Result<()> {
+    let reports: Vec<ProviderDiagnostics> = default_providers()
+        .iter()
+        .map(|provider| provider.diagnostics())
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+        return Ok(());
+    }
+
+    println!("{} Session provider diagnostics:", "🔍".cyan());
+    for report in &reports {
+        println!();
+        println!(
+            "{}",
+            report
+                .provider
+                .bold()
+                .color(provider_color(&report.provider))
+        );
+        let Some(dir) = &report.sessions_dir else {
+            println!(
+                "  {} {}",
+                "Directory:".bright_black(),
+                "unknown (HOME is not set)".red()
+            );
+            continue;
+        };
+        let exists = if report.dir_exists {
+            "exists".green()
+        } else {
+            "missing".red()
+        };
+        println!(
+            "  {} {} ({})",
+            "Directory:".bright_black(),
+            dir.display(),
+            exists
+        );
+        if !report.dir_exists {
+            continue;
+        }
+        println!(
+            "  {} {}  {} {}",
+            "Candidate files:".bright_black(),
+            report.candidate_files,
+            "Parsed sessions:".bright_black(),
+            report.parsed_sessions
+        );
+        if let Some(error) = &report.first_error {
+            println!("  {} {}", "First parse error:".bright_black(), error.red());
+        }
+    }
+    Ok(())
+}
+
+fn print_sessions(sessions: &[SessionWithWorktree], format: SessionsFormat) {
+    match format {
+        SessionsFormat::Table => print_table(sessions),
+        SessionsFormat::Compact => print_compact(sessions),
+        SessionsFormat::Text | SessionsFormat::Json => print_human_readable(sessions),
+    }
+}
+
+/// The most recent user message on a single line, or `-`.
+fn last_message_line(record: &SessionRecord) -> String {
+    record
+        .last_user_message
+        .as_deref()
+        .map(|message| message.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|message| !message.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_table(sessions: &[SessionWithWorktree]) {
+    let headers = ["PROVIDER", "SESSION", "LAST MESSAGE", "TIMESTAMP"];
+    let rows: Vec<[String; 4]> = sessions
+        .iter()
+        .map(|session| {
+            let record = &session.record;
+            [
+                record.provider.clone(),
+                record.id.chars().take(TABLE_ID_WIDTH).collect(),
+                truncate(&last_message_line(record), TABLE_MESSAGE_WIDTH),
+                record.last_timestamp.map_or_else(
+                    || "-".to_string(),
+                    |ts| ts.format("%Y-%m-%d %H:%M").to_string(),
+                ),
+            ]
+        })
+        .collect();
+
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let render = |cells: [&str; 4]| {
+        format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        )
+    };
+
+    println!("{}", render(headers).bold());
+    for row in &rows {
+        println!("{}", render(row.each_ref().map(String::as_str)));
+    }
+}
+
+fn print_compact(sessions: &[SessionWithWorktree]) {
+    for session in sessions {
+        let record = &session.record;
+        println!(
+            "{}/{} {} {}",
+            record.provider,
+            record.id,
+            record
+                .last_timestamp
+                .map_or_else(|| "-".to_string(), |ts| ts.to_rfc3339()),
+            last_message_line(record)
+        );
+    }
+}
+
 fn print_human_readable(sessions: &[SessionWithWorktree]) {
     if sessions.is_empty() {
         println!("{} No sessions found", "📭".yellow());
@@ -179,43 +756,123 @@ fn print_human_readable(sessions: &[SessionWithWorktree]) {
     println!("{} Sessions:", "🗂".cyan());
     println!();
 
+    let now = Utc::now();
+    for (provider, worktrees) in group_sessions(sessions) {
+        let count: usize = worktrees.iter().map(|(_, entries)| entries.len()).sum();
+        println!(
+            "{} {}",
+            provider.bold().color(provider_color(provider)),
+            format!("({count})").bright_black()
+        );
+
+        for (worktree_label, entries) in worktrees {
+            println!("  {} {}", "•".green(), worktree_label.cyan());
+
+            for session in entries {
+                let recency = session.record.recency(now);
+                let title = session
+                    .record
+                    .first_user_message
+                    .as_deref()
+                    .unwrap_or("(no user messages)");
+                let last_ts = session
+                    .record
+                    .last_timestamp
+                    .map(|ts| format!("{} ({})", ts.to_rfc3339(), format_relative(ts)))
+                    .unwrap_or_else(|| "unknown".to_string());
+                let message_count = session.record.user_messages.len();
+
+                println!(
+                    "    {} {} {} {}",
+                    recency_icon(recency),
+                    session.record.id,
+                    recency_label(recency),
+                    format!(
+                        "· {message_count} user message{}",
+                        if message_count == 1 { "" } else { "s" }
+                    )
+                    .bright_black()
+                );
+                println!("      {} {}", "Last activity:".bright_black(), last_ts);
+                if let Some(path) = session
+                    .record
+                    .working_dir
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                {
+                    println!("      {} {}", "Dir:".bright_black(), path);
+                }
+                println!(
+                    "      {} {}",
+                    "Summary:".bright_black(),
+                    truncate(title, 100)
+                );
+            }
+        }
+        println!();
+    }
+}
+
+type WorktreeGroup<'a> = (&'a str, Vec<&'a SessionWithWorktree>);
+type ProviderGroup<'a> = (&'a str, Vec<WorktreeGroup<'a>>);
+
+/// Group sessions by provider, then by worktree label, preserving the
+/// incoming (most recent first) order within each level.
+fn group_sessions(sessions: &[SessionWithWorktree]) -> Vec<ProviderGroup<'_>> {
+    let mut groups: Vec<ProviderGroup<'_>> = Vec::new();
+
     for session in sessions {
         let provider = session.record.provider.as_str();
-        let title = session
-            .record
-            .first_user_message
-            .as_deref()
-            .unwrap_or("(no user messages)");
-        let last_ts = session
-            .record
-            .last_timestamp
-            .map(|ts| format!("{} ({})", ts.to_rfc3339(), format_relative(ts)))
-            .unwrap_or_else(|| "unknown".to_string());
-
         let worktree_label = session
             .worktree_name
             .as_deref()
             .or(session.worktree_key.as_deref())
             .unwrap_or("unmapped");
 
-        println!(
-            "  {} {} {}",
-            "•".green(),
-            provider.bold(),
-            worktree_label.cyan()
-        );
-        println!("    {} {}", "Session:".bright_black(), session.record.id);
-        println!("    {} {}", "Last activity:".bright_black(), last_ts);
-        if let Some(path) = session
-            .record
-            .working_dir
-            .as_ref()
-            .map(|p| p.display().to_string())
+        let provider_index = match groups.iter().position(|(name, _)| *name == provider) {
+            Some(index) => index,
+            None => {
+                groups.push((provider, Vec::new()));
+                groups.len() - 1
+            }
+        };
+        let worktrees = &mut groups[provider_index].1;
+        match worktrees
+            .iter_mut()
+            .find(|(label, _)| *label == worktree_label)
         {
-            println!("    {} {}", "Dir:".bright_black(), path);
+            Some((_, entries)) => entries.push(session),
+            None => worktrees.push((worktree_label, vec![session])),
         }
-        println!("    {} {}", "Summary:".bright_black(), truncate(title, 100));
-        println!();
+    }
+
+    groups
+}
+
+fn provider_color(provider: &str) -> Color {
+    match provider.to_ascii_lowercase().as_str() {
+        "claude" => Color::Magenta,
+        "codex" => Color::Blue,
+        "kimi" => Color::Yellow,
+        _ => Color::White,
+    }
+}
+
+fn recency_icon(recency: SessionRecency) -> ColoredString {
+    match recency {
+        SessionRecency::Active => "●".green(),
+        SessionRecency::Idle => "◐".yellow(),
+        SessionRecency::Stale => "○".bright_black(),
+        SessionRecency::Unknown => "?".bright_black(),
+    }
+}
+
+fn recency_label(recency: SessionRecency) -> ColoredString {
+    let label = format!("[{}]", recency.label());
+    match recency {
+        SessionRecency::Active => label.green(),
+        SessionRecency::Idle => label.yellow(),
+        SessionRecency::Stale | SessionRecency::Unknown => label.bright_black(),
     }
 }
 
@@ -0,0 +1,80 @@
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use std::fs;
+use std::process::Command;
+
+use crate::input::select_worktree;
+use agentdev::state::{WorktreeInfo, XlaudeState};
+
+/// Open an interactive login shell (`$SHELL -l`) inside a worktree.
+///
+/// The shell shares this terminal and agentdev waits for it, so exiting the
+/// shell returns here. Its exit status is not treated as an error, since it
+/// only reflects the last command run in it.
+pub fn handle_shell(worktree: Option<String>) -> Result<()> {
+    let state = XlaudeState::load()?;
+    if state.worktrees.is_empty() {
+        bail!("No worktrees found. Create one first with 'agentdev worktree create'");
+    }
+
+    let info = resolve_worktree(&state, worktree)?;
+    if !info.path.exists() {
+        bail!(
+            "Worktree path {} no longer exists; run 'agentdev worktree clean' to remove stale entries",
+            info.path.display()
+        );
+    }
+
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| "/bin/sh".to_string());
+
+    let key = XlaudeState::make_key(&info.repo_name, &info.name);
+    XlaudeState::record_activity(&key)?;
+
+    eprintln!(
+        "{} Starting {} in {}/{} ({}); exit to return",
+        "🐚".green(),
+        shell.cyan(),
+        info.repo_name,
+        info.name.cyan(),
+        info.path.display()
+    );
+
+    Command::new(&shell)
+        .arg("-l")
+        .current_dir(&info.path)
+        .status()
+        .with_context(|| format!("Failed to start '{shell}'"))?;
+
+    eprintln!("{} Left {}", "👋".green(), info.name.cyan());
+    Ok(())
+}
+
+fn resolve_worktree(state: &XlaudeState, name: Option<String>) -> Result<WorktreeInfo> {
+    if let Some(name) = name {
+        return state
+            .worktrees
+            .values()
+            .find(|info| info.name == name)
+            .cloned()
+            .with_context(|| format!("Worktree '{name}' not found"));
+    }
+
+    let cwd = fs::canonicalize(std::env::current_dir()?)?;
+    if let Some(info) = state
+        .worktrees
+        .values()
+        .find(|info| fs::canonicalize(&info.path).is_ok_and(|path| cwd.starts_with(path)))
+    {
+        return Ok(info.clone());
+    }
+
+    match select_worktree("Select a worktree", state)? {
+        Some((_, info)) => Ok(info),
+        None => bail!(
+            "Interactive selection not available in non-interactive mode. Please specify a worktree name."
+        ),
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use chrono::{Local, SecondsFormat};
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use crate::input::get_command_arg;
+use agentdev::git::execute_git;
+use agentdev::state::XlaudeState;
+
+pub fn handle_snapshot(name: Option<String>, push: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let display_name = git_wt.display_name();
+
+    let path_str = git_wt
+        .path
+        .to_str()
+        .context("Path contains invalid UTF-8")?;
+
+    let status = execute_git(&["-C", path_str, "status", "--porcelain"])?;
+    if status.trim().is_empty() {
+        println!(
+            "{} Worktree '{}' is clean; nothing to snapshot",
+            "✨".green(),
+            display_name.cyan()
+        );
+        return Ok(());
+    }
+
+    let message = format!(
+        "WIP: snapshot {}",
+        Local::now().to_rfc3339_opts(SecondsFormat::Secs, false)
+    );
+    execute_git(&["-C", path_str, "add", "-A"]).context("Failed to stage changes")?;
+    execute_git(&["-C", path_str, "commit", "--no-gpg-sign", "-m", &message])
+        .context("Failed to create snapshot commit")?;
+    let short_sha = execute_git(&["-C", path_str, "rev-parse", "--short", "HEAD"])?;
+
+    println!(
+        "{} Snapshot of '{}' committed: {} {}",
+        "📸".green(),
+        display_name.cyan(),
+        short_sha.yellow(),
+        message
+    );
+
+    if push {
+        let branch = git_wt.branch.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("Cannot push snapshot: worktree is in detached HEAD state")
+        })?;
+        println!("  {} Pushing {} to origin", "→".blue(), branch.cyan());
+        execute_git(&["-C", path_str, "push", "--set-upstream", "origin", branch])
+            .context("Failed to push snapshot")?;
+    }
+
+    if let Some(key) = state_key {
+        XlaudeState::record_activity(&key)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use agentdev::git::{WorktreeGitStatus, summarize_worktree_status};
+use agentdev::state::{WorktreeInfo, XlaudeState};
+
+const HEADERS: [&str; 8] = [
+    "NAME",
+    "BRANCH",
+    "AHEAD",
+    "BEHIND",
+    "STAGED",
+    "UNSTAGED",
+    "UNTRACKED",
+    "CLEAN",
+];
+
+#[derive(Debug, Serialize)]
+struct JsonWorktreeStatus {
+    name: String,
+    repo_name: String,
+    branch: String,
+    path: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    path_missing: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ahead: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    behind: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    staged: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unstaged: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    untracked: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clean: Option<bool>,
+}
+
+enum Inspection {
+    Missing,
+    Failed(String),
+    Status(WorktreeGitStatus),
+}
+
+/// Print a one-line git status summary for every managed worktree.
+pub fn handle_worktree_status(json: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let mut worktrees: Vec<&WorktreeInfo> = state.worktrees.values().collect();
+    worktrees.sort_by(|a, b| {
+        a.repo_name
+            .cmp(&b.repo_name)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let inspected: Vec<(&WorktreeInfo, Inspection)> = worktrees
+        .into_iter()
+        .map(|info| {
+            let inspection = if !info.path.exists() {
+                Inspection::Missing
+            } else {
+                match summarize_worktree_status(&info.path, &info.branch) {
+                    Ok(status) => Inspection::Status(status),
+                    Err(err) => Inspection::Failed(format!("{err:#}")),
+                }
+            };
+            (info, inspection)
+        })
+        .collect();
+
+    if json {
+        let entries: Vec<JsonWorktreeStatus> = inspected
+            .into_iter()
+            .map(|(info, inspection)| json_entry(info, inspection))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if inspected.is_empty() {
+        println!("{} No active worktrees", "📭".yellow());
+        return Ok(());
+    }
+
+    print_table(&inspected);
+    Ok(())
+}
+
+fn json_entry(info: &WorktreeInfo, inspection: Inspection) -> JsonWorktreeStatus {
+    let mut entry = JsonWorktreeStatus {
+        name: info.name.clone(),
+        repo_name: info.repo_name.clone(),
+        branch: info.branch.clone(),
+        path: info.path.display().to_string(),
+        path_missing: false,
+        error: None,
+        ahead: None,
+        behind: None,
+        staged: None,
+        unstaged: None,
+        untracked: None,
+        clean: None,
+    };
+    match inspection {
+        Inspection::Missing => entry.path_missing = true,
+        Inspection::Failed(err) => entry.error = Some(err),
+        Inspection::Status(status) => {
+            if status.detached {
+                entry.branch = status.branch_label();
+            }
+            entry.ahead = Some(status.ahead);
+            entry.behind = Some(status.behind);
+            entry.staged = Some(status.staged);
+            entry.unstaged = Some(status.unstaged);
+            entry.untracked = Some(status.untracked);
+            entry.clean = Some(status.is_clean);
+        }
+    }
+    entry
+}
+
+fn print_table(inspected: &[(&WorktreeInfo, Inspection)]) {
+    let branch_of = |info: &WorktreeInfo, inspection: &Inspection| match inspection {
+        Inspection::Status(status) if status.detached => status.branch_label(),
+        _ => info.branch.clone(),
+    };
+
+    let mut widths = HEADERS.map(str::len);
+    for (info, inspection) in inspected {
+        widths[0] = widths[0].max(info.name.chars().count());
+        widths[1] = widths[1].max(branch_of(info, inspection).chars().count());
+        if let Inspection::Status(status) = inspection {
+            for (index, count) in counts(status).iter().enumerate() {
+                widths[index + 2] = widths[index + 2].max(count.to_string().len());
+            }
+        }
+    }
+
+    let header = HEADERS
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(index, (header, width))| {
+            if (2..7).contains(&index) {
+                format!("{header:>width$}")
+            } else {
+                format!("{header:<width$}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("{}", header.trim_end().bold());
+
+    for (info, inspection) in inspected {
+        let mut line = format!(
+            "{:<name_width$}  {:<branch_width$}",
+            info.name,
+            branch_of(info, inspection),
+            name_width = widths[0],
+            branch_width = widths[1]
+        );
+        match inspection {
+            Inspection::Missing => line.push_str(&format!("  {}", "PATH MISSING".red())),
+            Inspection::Failed(err) => line.push_str(&format!("  {}", err.red())),
+            Inspection::Status(status) => {
+                for (index, count) in counts(status).iter().enumerate() {
+                    let width = widths[index + 2];
+                    line.push_str(&format!("  {count:>width$}"));
+                }
+                let clean = if status.is_clean {
+                    "yes".green()
+                } else {
+                    "no".red()
+                };
+                line.push_str(&format!("  {clean}"));
+            }
+        }
+        println!("{line}");
+    }
+}
+
+/// The numeric AHEAD..UNTRACKED columns, in header order.
+fn counts(status: &WorktreeGitStatus) -> [usize; 5] {
+    [
+        status.ahead as usize,
+        status.behind as usize,
+        status.staged,
+        status.unstaged,
+        status.untracked,
+    ]
+}
@@ -0,0 +1,325 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+use agentdev::git::commits_since_merge_base;
+use agentdev::state::XlaudeState;
+use agentdev::tasks::{TaskMember, TaskMemberStatus, TaskSummary, collect_tasks};
+
+use super::delete::handle_delete;
+use super::merge::{MergeStrategy, merge_managed_worktree};
+
+#[derive(Debug, Serialize)]
+struct JsonOutput {
+    tasks: Vec<JsonTask>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonTask {
+    #[serde(flatten)]
+    summary: TaskSummary,
+    worktree_count: usize,
+    running_count: usize,
+}
+
+pub fn handle_tasks_list(all: bool, json: bool) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let tasks = collect_tasks(&state, all);
+
+    if json {
+        let tasks = tasks
+            .into_iter()
+            .map(|summary| JsonTask {
+                worktree_count: summary.members.len(),
+                running_count: summary.running_count(),
+                summary,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&JsonOutput { tasks })?);
+        return Ok(());
+    }
+
+    if tasks.is_empty() {
+        println!("{} No tasks found", "📋".yellow());
+        if !all {
+            println!(
+                "  {} Launch a multi-agent task from the web UI ({}), or pass {} to list single worktrees",
+                "💡".cyan(),
+                "agentdev ui".cyan(),
+                "--all".cyan()
+            );
+        }
+        return Ok(());
+    }
+
+    println!("{} Tasks:", "📋".cyan());
+    println!();
+
+    for task in tasks {
+        let title = task.task_name.as_deref().unwrap_or(&task.task_id);
+        println!(
+            "  {} {} {}",
+            "🧩".blue(),
+            title.bold(),
+            format!("({})", task.task_id).bright_black()
+        );
+        println!(
+            "      {} {}  {} {}  {} {}",
+            "Worktrees:".bright_black(),
+            task.members.len(),
+            "Running:".bright_black(),
+            task.running_count(),
+            "Created:".bright_black(),
+            task.created_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        if let Some(prompt) = task.initial_prompt.as_deref() {
+            println!(
+                "      {} {}",
+                "Prompt:".bright_black(),
+                truncate(prompt, 72)
+            );
+        }
+
+        for member in &task.members {
+            let status = match member.status {
+                TaskMemberStatus::Running => member.status.label().green(),
+                TaskMemberStatus::Stopped => member.status.label().yellow(),
+                TaskMemberStatus::Missing => member.status.label().red(),
+            };
+            let agent = member.agent_alias.as_deref().unwrap_or("agent");
+            println!(
+                "    {} {} {} {} [{}]",
+                "•".green(),
+                agent.magenta(),
+                "→".bright_black(),
+                member.worktree_name.cyan(),
+                status
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// How far a task member's branch has diverged from its base branch.
+enum MergeState {
+    Merged { base: String },
+    Unmerged { base: String, commits: usize },
+    Unknown,
+}
+
+impl MergeState {
+    fn of(member: &TaskMember) -> Self {
+        if member.status == TaskMemberStatus::Missing {
+            return Self::Unknown;
+        }
+        match commits_since_merge_base(Path::new(&member.path)) {
+            Ok(Some(ahead)) if ahead.commits.is_empty() => Self::Merged {
+                base: ahead.base_branch,
+            },
+            Ok(Some(ahead)) => Self::Unmerged {
+                base: ahead.base_branch,
+                commits: ahead.commits.len(),
+            },
+            _ => Self::Unknown,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Merged { base } => format!("merged into {base}").green().to_string(),
+            Self::Unmerged { base, commits } => format!("{commits} unmerged commit(s) vs {base}")
+                .yellow()
+                .to_string(),
+            Self::Unknown => "merge status unknown".bright_black().to_string(),
+        }
+    }
+}
+
+/// Find a task by id or name, including single-worktree tasks.
+fn find_task(state: &XlaudeState, task: &str) -> Result<TaskSummary> {
+    collect_tasks(state, true)
+        .into_iter()
+        .find(|summary| summary.task_id == task || summary.task_name.as_deref() == Some(task))
+        .with_context(|| format!("Task '{task}' not found"))
+}
+
+pub fn handle_tasks_delete(
+    task: String,
+    merged_only: bool,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let summary = find_task(&state, &task)?;
+
+    let title = summary.task_name.as_deref().unwrap_or(&summary.task_id);
+    println!(
+        "{} Task {} {}",
+        "🧩".blue(),
+        title.bold(),
+        format!("({})", summary.task_id).bright_black()
+    );
+
+    let mut planned = Vec::new();
+    let mut kept = 0;
+    for member in &summary.members {
+        let merge_state = MergeState::of(member);
+        // Only members known to be fully merged are safe to drop with --merged-only
+        let delete = !merged_only || matches!(merge_state, MergeState::Merged { .. });
+        let action = if delete {
+            "delete".red()
+        } else {
+            "keep".green()
+        };
+        let agent = member.agent_alias.as_deref().unwrap_or("agent");
+        println!(
+            "    {} {} {} {} [{}] {}",
+            "•".green(),
+            agent.magenta(),
+            "→".bright_black(),
+            member.worktree_name.cyan(),
+            merge_state.describe(),
+            action
+        );
+        if delete {
+            planned.push(member.worktree_name.clone());
+        } else {
+            kept += 1;
+        }
+    }
+    println!();
+
+    if dry_run {
+        println!(
+            "{} Dry run: would delete {} worktree(s) and keep {}",
+            "ℹ️ ".blue(),
+            planned.len(),
+            kept
+        );
+        return Ok(());
+    }
+
+    if planned.is_empty() {
+        println!("{} Nothing to delete", "✨".green());
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for name in planned {
+        if let Err(err) = handle_delete(Some(name.clone()), force) {
+            eprintln!("{} Failed to delete '{}': {err:#}", "❌".red(), name);
+            failed.push(name);
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to delete: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+/// Merge a task's worktrees into their base branch one after another, in the
+/// order `tasks list` shows them.
+///
+/// Members already merged are skipped. The first failed merge (usually a
+/// conflict) stops the run; earlier merges stay in place and the rest are
+/// left untouched, so rerunning after resolving it picks up where it stopped.
+pub fn handle_tasks_merge(
+    task: String,
+    strategy: MergeStrategy,
+    remote: String,
+    cleanup: bool,
+) -> Result<()> {
+    let state = XlaudeState::load()?;
+    let summary = find_task(&state, &task)?;
+    let title = summary.task_name.as_deref().unwrap_or(&summary.task_id);
+    println!(
+        "{} Merging {} worktree(s) of task {} {}",
+        "🧩".blue(),
+        summary.members.len(),
+        title.bold(),
+        format!("({})", summary.task_id).bright_black()
+    );
+    println!();
+
+    let mut merged = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, member) in summary.members.iter().enumerate() {
+        let name = &member.worktree_name;
+        match MergeState::of(member) {
+            MergeState::Merged { base } => {
+                println!(
+                    "{} Skipping '{}': already merged into {}",
+                    "⏭️ ".bright_black(),
+                    name.cyan(),
+                    base
+                );
+                skipped.push(name.clone());
+                continue;
+            }
+            _ if member.status == TaskMemberStatus::Missing => {
+                println!(
+                    "{} Skipping '{}': worktree directory is missing",
+                    "⚠️ ".yellow(),
+                    name.cyan()
+                );
+                skipped.push(name.clone());
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Err(err) = merge_managed_worktree(name.clone(), strategy, remote.clone(), cleanup) {
+            let remaining: Vec<&str> = summary.members[index + 1..]
+                .iter()
+                .map(|member| member.worktree_name.as_str())
+                .collect();
+            eprintln!();
+            eprintln!("{} Stopped at '{}': {err:#}", "❌".red(), name.cyan());
+            print_merge_report(&merged, &skipped);
+            if !remaining.is_empty() {
+                eprintln!(
+                    "  {} Not attempted: {}",
+                    "•".bright_black(),
+                    remaining.join(", ")
+                );
+            }
+            eprintln!(
+                "  {} Resolve it in the main repository (or run `git merge --abort`), then rerun `agentdev tasks merge {}`",
+                "💡".cyan(),
+                task
+            );
+            anyhow::bail!("Task merge stopped at '{name}'");
+        }
+        merged.push(name.clone());
+        println!();
+    }
+
+    println!("{} Task {} merged", "✅".green(), title.bold());
+    print_merge_report(&merged, &skipped);
+    Ok(())
+}
+
+fn print_merge_report(merged: &[String], skipped: &[String]) {
+    if !merged.is_empty() {
+        eprintln!("  {} Merged: {}", "•".green(), merged.join(", "));
+    }
+    if !skipped.is_empty() {
+        eprintln!("  {} Skipped: {}", "•".bright_black(), skipped.join(", "));
+    }
+}
+
+/// Collapse a prompt onto one line and cut it to `max` characters.
+fn truncate(text: &str, max: usize) -> String {
+    let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.chars().count() <= max {
+        return single_line;
+    }
+    let mut truncated: String = single_line.chars().take(max.saturating_sub(3)).collect();
+    truncated.push_str("...");
+    truncated
+}
@@ -0,0 +1,190 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use super::lock::resolve_worktree_target;
+use crate::input::get_command_arg;
+use agentdev::config::load_agent_config;
+use agentdev::sessions::{
+    SessionEvent, SessionProvider, SessionRecord, canonicalize, default_providers,
+};
+use agentdev::state::XlaudeState;
+use agentdev::tmux::{TmuxManager, new_capture_lines};
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const WATCH_CAPTURE_LINES: usize = 200;
+
+/// Follow a worktree's agent output like `tail -f`.
+///
+/// Polls the tmux pane and prints only lines that were not in the previous
+/// capture. Without tmux (or a running session) it follows the newest agent
+/// session log recorded for the worktree instead. The poll interval and the
+/// default line count come from `[dashboard]` in config.toml. Pane lines have
+/// escape sequences stripped and are clamped to the terminal width unless
+/// `AGENTDEV_PREVIEW_RAW=1` is set.
+pub fn handle_watch(name: Option<String>, lines: Option<usize>) -> Result<()> {
+    let dashboard = load_agent_config()
+        .map(|config| config.dashboard)
+        .unwrap_or_default();
+    let interval = dashboard
+        .auto_refresh_interval_ms
+        .filter(|&ms| ms > 0)
+        .map_or(WATCH_POLL_INTERVAL, Duration::from_millis);
+    let lines = lines
+        .or(dashboard.preview_capture_lines)
+        .unwrap_or(WATCH_CAPTURE_LINES);
+
+    let state = XlaudeState::load()?;
+    let target_name = get_command_arg(name)?;
+    let (state_key, git_wt) = resolve_worktree_target(&state, target_name)?;
+    let session = state_key
+        .and_then(|key| state.worktrees.get(&key))
+        .map(|info| info.name.clone())
+        .unwrap_or_else(|| git_wt.display_name());
+
+    ctrlc::set_handler(|| {
+        println!();
+        std::process::exit(0);
+    })
+    .context("Failed to install Ctrl-C handler")?;
+
+    let tmux = TmuxManager::new();
+    if TmuxManager::is_available() && tmux.session_exists(&session) {
+        return watch_pane(&tmux, &session, lines.max(1), interval);
+    }
+
+    let (provider, record) = latest_session_for(&git_wt.path)?.with_context(|| {
+        format!("No tmux session or agent session log found for worktree '{session}'")
+    })?;
+    eprintln!(
+        "{} No tmux session for '{}'; following {} session log {}",
+        "ℹ️".blue(),
+        session.cyan(),
+        record.provider,
+        record.file_path.display()
+    );
+    watch_session_log(provider.as_ref(), &record, lines, interval)
+}
+
+/// Columns available to the preview; unlimited when stdout is not a terminal.
+fn terminal_width() -> usize {
+    if !io::stdout().is_terminal() {
+        return usize::MAX;
+    }
+    crossterm::terminal::size().map_or(usize::MAX, |(columns, _)| usize::from(columns))
+}
+
+fn watch_pane(tmux: &TmuxManager, session: &str, lines: usize, interval: Duration) -> Result<()> {
+    let mut previous: Vec<String> = Vec::new();
+    loop {
+        let mut current: Vec<String> = tmux
+            .capture_pane_preview(session, lines, terminal_width())?
+            .into_iter()
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        // tmux pads the capture with the empty rows below the cursor
+        while current.last().is_some_and(String::is_empty) {
+            current.pop();
+        }
+
+        if current.is_empty() && !tmux.session_exists(session) {
+            eprintln!("{} tmux session for '{}' ended", "⏹️".yellow(), session);
+            return Ok(());
+        }
+        // The cursor row is still being edited (prompts, spinners); print it
+        // once later output has pushed it up
+        current.pop();
+
+        let mut stdout = io::stdout().lock();
+        for line in new_capture_lines(&previous, &current) {
+            writeln!(stdout, "{line}")?;
+        }
+        stdout.flush()?;
+        drop(stdout);
+
+        previous = current;
+        thread::sleep(interval);
+    }
+}
+
+/// Most recently active session whose working directory is inside `worktree`.
+fn latest_session_for(
+    worktree: &Path,
+) -> Result<Option<(Box<dyn SessionProvider + Send + Sync>, SessionRecord)>> {
+    let base = canonicalize(worktree).unwrap_or_else(|| worktree.to_path_buf());
+    let mut latest: Option<(Box<dyn SessionProvider + Send + Sync>, SessionRecord)> = None;
+    for provider in default_providers() {
+        let Ok(records) = provider.list_sessions() else {
+            continue;
+        };
+        let newest = records
+            .into_iter()
+            .filter(|record| {
+                record.working_dir.as_deref().is_some_and(|dir| {
+                    canonicalize(dir)
+                        .unwrap_or_else(|| dir.to_path_buf())
+                        .starts_with(&base)
+                })
+            })
+            .max_by_key(|record| record.last_timestamp);
+        if let Some(record) = newest
+            && latest
+                .as_ref()
+                .is_none_or(|(_, best)| record.last_timestamp > best.last_timestamp)
+        {
+            latest = Some((provider, record));
+        }
+    }
+    Ok(latest)
+}
+
+/// Print the last `lines` events, then every event appended to the log.
+fn watch_session_log(
+    provider: &(dyn SessionProvider + Send + Sync),
+    record: &SessionRecord,
+    lines: usize,
+    interval: Duration,
+) -> Result<()> {
+    let mut last_modified: Option<SystemTime> = None;
+    let mut shown: Option<usize> = None;
+    loop {
+        let modified = std::fs::metadata(&record.file_path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+            let events = provider.load_session_events(record)?;
+            let start = shown.unwrap_or_else(|| events.len().saturating_sub(lines));
+            let mut stdout = io::stdout().lock();
+            for event in events.iter().skip(start) {
+                if let Some(line) = render_event(event) {
+                    writeln!(stdout, "{line}")?;
+                }
+            }
+            stdout.flush()?;
+            shown = Some(events.len());
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn render_event(event: &SessionEvent) -> Option<String> {
+    let text = event
+        .text
+        .as_deref()
+        .or(event.summary_text.as_deref())?
+        .trim();
+    if text.is_empty() {
+        return None;
+    }
+    let label = event
+        .actor
+        .as_deref()
+        .or(event.label.as_deref())
+        .unwrap_or(&event.category);
+    Some(format!("{} {text}", format!("[{label}]").bright_black()))
+}
@@ -1,39 +1,112 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap_complete::Shell;
+use colored::Colorize;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
-pub fn handle_completions(shell: Shell) -> Result<()> {
-    match shell {
-        Shell::Bash => print_bash_completions(),
-        Shell::Zsh => print_zsh_completions(),
-        Shell::Fish => print_fish_completions(),
+const BIN_NAME: &str = "agentdev";
+
+/// Print the completion script for `shell`, or write it to `output`.
+///
+/// `bin_name` replaces `agentdev` in the script for users who install the
+/// binary under another name.
+pub fn handle_completions(
+    shell: Shell,
+    output: Option<PathBuf>,
+    bin_name: Option<String>,
+) -> Result<()> {
+    let script = match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
         _ => {
             eprintln!("Unsupported shell: {:?}", shell);
             eprintln!("Supported shells: bash, zsh, fish");
+            return Ok(());
         }
+    };
+
+    let bin_name = bin_name.unwrap_or_else(|| BIN_NAME.to_string());
+    if bin_name.is_empty()
+        || !bin_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        anyhow::bail!("Invalid binary name '{bin_name}': use letters, digits, '-', '_' or '.'");
+    }
+    let script = if bin_name == BIN_NAME {
+        script
+    } else {
+        script.replace(BIN_NAME, &bin_name)
+    };
+
+    let Some(path) = output else {
+        println!("{script}");
+        return Ok(());
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, &script).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    if io::stdout().is_terminal() {
+        println!(
+            "{} Wrote {} completions to {}",
+            "✅".green(),
+            shell,
+            path.display()
+        );
+        println!(
+            "  {} {}",
+            "💡".cyan(),
+            install_hint(shell, &path, &bin_name)
+        );
     }
     Ok(())
 }
 
-fn print_bash_completions() {
-    println!(
-        r#"#!/bin/bash
+fn install_hint(shell: Shell, path: &Path, bin_name: &str) -> String {
+    match shell {
+        Shell::Zsh => {
+            let dir = path
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            format!(
+                "Name the file _{bin_name} and add `fpath=({} $fpath)` before `compinit` in ~/.zshrc",
+                dir.display()
+            )
+        }
+        Shell::Fish => format!(
+            "Fish loads ~/.config/fish/completions/{bin_name}.fish automatically; otherwise run `source {}`",
+            path.display()
+        ),
+        _ => format!("Add `source {}` to ~/.bashrc", path.display()),
+    }
+}
+
+fn bash_completions() -> String {
+    r#"#!/bin/bash
 
-_agentdev() {{
+_agentdev() {
     local cur prev words cword
     if type _init_completion &>/dev/null; then
         _init_completion || return
     else
         # Fallback for older bash-completion
         COMPREPLY=()
-        cur="${{COMP_WORDS[COMP_CWORD]}}"
-        prev="${{COMP_WORDS[COMP_CWORD-1]}}"
-        words=("${{COMP_WORDS[@]}}")
+        cur="${COMP_WORDS[COMP_CWORD]}"
+        prev="${COMP_WORDS[COMP_CWORD-1]}"
+        words=("${COMP_WORDS[@]}")
         cword=$COMP_CWORD
     fi
 
     # Main commands
-    local commands="worktree sessions ui completions"
-    local wt_subs="create open delete add rename list clean dir"
+    local commands="worktree sessions tasks config audit doctor init reap ui completions"
+    local wt_subs="create open delete add adopt fork rename list clean clean-branches gc dir copy-branch-name lock unlock pin unpin link unlink note status log reset snapshot checkpoint cp send watch shell"
 
     # Complete main commands
     if [[ $cword -eq 1 ]]; then
@@ -42,15 +115,15 @@ _agentdev() {{
     fi
 
     # Complete subcommand arguments
-    case "${{words[1]}}" in
+    case "${words[1]}" in
         worktree)
             # Complete worktree subcommands
             if [[ $cword -eq 2 ]]; then
                 COMPREPLY=($(compgen -W "$wt_subs" -- "$cur"))
                 return
             fi
-            case "${{words[2]}}" in
-                open|dir|delete)
+            case "${words[2]}" in
+                open|dir|copy-branch-name|delete|lock|unlock|pin|unpin|link|unlink|note|log|reset|snapshot|checkpoint|cp|send|watch|shell|fork)
                     if [[ $cword -eq 3 ]]; then
                         local worktrees=$(agentdev complete-worktrees 2>/dev/null)
                         COMPREPLY=($(compgen -W "$worktrees" -- "$cur"))
@@ -62,6 +135,11 @@ _agentdev() {{
                         COMPREPLY=($(compgen -W "$worktrees" -- "$cur"))
                     fi
                     ;;
+                adopt)
+                    if [[ $cword -eq 3 ]]; then
+                        COMPREPLY=($(compgen -d -- "$cur"))
+                    fi
+                    ;;
             esac
             ;;
         open|dir|delete)
@@ -84,27 +162,47 @@ _agentdev() {{
             fi
             ;;
         sessions)
+            if [[ $cword -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "list prune delete export" -- "$cur"))
+            fi
+            ;;
+        tasks)
+            if [[ $cword -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "list merge delete" -- "$cur"))
+            fi
+            ;;
+        config)
+            if [[ $cword -eq 2 ]]; then
+                COMPREPLY=($(compgen -W "show edit" -- "$cur"))
+            fi
+            ;;
+        audit)
             if [[ $cword -eq 2 ]]; then
                 COMPREPLY=($(compgen -W "list" -- "$cur"))
             fi
             ;;
     esac
-}}
+}
 
 complete -F _agentdev agentdev
 "#
-    );
+    .to_string()
 }
 
-fn print_zsh_completions() {
-    println!(
-        r#"#compdef agentdev
+fn zsh_completions() -> String {
+    r#"#compdef agentdev
 
-_agentdev() {{
+_agentdev() {
     local -a commands
     commands=(
         'worktree:Worktree management commands'
         'sessions:Session inspection commands'
+        'tasks:Multi-agent task commands'
+        'config:Inspect or edit the config file'
+        'audit:Inspect the worktree audit log'
+        'doctor:Check the environment for common problems'
+        'init:Set up the current repository for agentdev'
+        'reap:Kill tmux sessions that have been idle for too long'
         'completions:Generate shell completions'
         'ui:Launch web UI for agent management'
     )
@@ -116,7 +214,7 @@ _agentdev() {{
     fi
 
     # Subcommand argument completion
-    case "${{words[2]}}" in
+    case "${words[2]}" in
         worktree)
             local -a wt_subs
             wt_subs=(
@@ -124,22 +222,43 @@ _agentdev() {{
                 'open:Open an existing worktree and launch Claude'
                 'delete:Delete a worktree and clean up'
                 'add:Add current worktree to management'
+                'adopt:Add the worktree at a path to management'
+                'fork:Create a worktree from another worktree HEAD'
                 'rename:Rename a worktree'
                 'list:List all active instances'
                 'clean:Clean up invalid worktrees from state'
+                'clean-branches:Delete merged remote branches'
+                'gc:Remove worktrees whose branches are merged'
                 'dir:Get the directory path of a worktree'
+                'copy-branch-name:Print the branch name of a worktree'
+                'lock:Lock a worktree to protect it from pruning'
+                'unlock:Unlock a previously locked worktree'
+                'pin:Pin a worktree to the top of listings'
+                'unpin:Unpin a previously pinned worktree'
+                'link:Symlink a worktree to a fixed path'
+                'unlink:Remove the symlink created by link'
+                'note:Add a free-text note to a worktree'
+                'status:Summarize the git status of every worktree'
+                'log:Show the commit graph of a worktree'
+                'reset:Discard all uncommitted changes'
+                'snapshot:Commit pending changes as a WIP snapshot'
+                'checkpoint:Tag HEAD with an annotated checkpoint tag'
+                'cp:Copy uncommitted changes to another worktree'
+                'send:Type a message into a worktree agent session'
+                'watch:Follow the agent output of a worktree'
+                'shell:Open a login shell inside a worktree'
             )
             if (( CURRENT == 3 )); then
                 _describe 'worktree command' wt_subs
                 return
             fi
-            case "${{words[3]}}" in
-                open|dir|delete)
+            case "${words[3]}" in
+                open|dir|copy-branch-name|delete|lock|unlock|pin|unpin|link|unlink|note|log|reset|snapshot|checkpoint|cp|send|watch|shell)
                     if (( CURRENT == 4 )); then
                         __agentdev_worktrees
                     fi
                     ;;
-                rename)
+                rename|fork)
                     if (( CURRENT == 4 )); then
                         __agentdev_worktrees
                     elif (( CURRENT == 5 )); then
@@ -151,6 +270,13 @@ _agentdev() {{
                         _message "worktree name"
                     fi
                     ;;
+                adopt)
+                    if (( CURRENT == 4 )); then
+                        _files -/
+                    elif (( CURRENT == 5 )); then
+                        _message "worktree name"
+                    fi
+                    ;;
             esac
             ;;
         sessions)
@@ -158,10 +284,43 @@ _agentdev() {{
                 local -a session_subs
                 session_subs=(
                     'list:List recorded sessions'
+                    'prune:Delete old session files'
+                    'delete:Archive a session'
+                    'export:Write transcripts as Markdown'
                 )
                 _describe 'sessions command' session_subs
             fi
             ;;
+        tasks)
+            if (( CURRENT == 3 )); then
+                local -a task_subs
+                task_subs=(
+                    'list:List tasks grouped from their worktrees'
+                    'merge:Merge the worktrees of a task one after another'
+                    'delete:Delete the worktrees of a task'
+                )
+                _describe 'tasks command' task_subs
+            fi
+            ;;
+        config)
+            if (( CURRENT == 3 )); then
+                local -a config_subs
+                config_subs=(
+                    'show:Print the resolved config'
+                    'edit:Edit the config file in $EDITOR'
+                )
+                _describe 'config command' config_subs
+            fi
+            ;;
+        audit)
+            if (( CURRENT == 3 )); then
+                local -a audit_subs
+                audit_subs=(
+                    'list:Show recent audit log entries'
+                )
+                _describe 'audit command' audit_subs
+            fi
+            ;;
         completions)
             if (( CURRENT == 3 )); then
                 local -a shells
@@ -170,9 +329,9 @@ _agentdev() {{
             fi
             ;;
     esac
-}}
+}
 
-__agentdev_worktrees() {{
+__agentdev_worktrees() {
     local -a worktrees
     local IFS=$'\n'
     
@@ -193,7 +352,7 @@ __agentdev_worktrees() {{
         
         # Use _describe for better presentation
         # -V flag preserves the order (no sorting)
-        if (( ${{#worktrees[@]}} > 0 )); then
+        if (( ${#worktrees[@]} > 0 )); then
             _describe -V -t worktrees 'worktree' worktrees
         fi
     else
@@ -204,16 +363,15 @@ __agentdev_worktrees() {{
             compadd -a simple_worktrees
         fi
     fi
-}}
+}
 
 _agentdev "$@"
 "#
-    );
+    .to_string()
 }
 
-fn print_fish_completions() {
-    println!(
-        r#"# Fish completion for agentdev
+fn fish_completions() -> String {
+    r#"# Fish completion for agentdev
 
 # Disable file completions by default
 complete -c agentdev -f
@@ -221,6 +379,15 @@ complete -c agentdev -f
 # Main commands
 complete -c agentdev -n "__fish_use_subcommand" -a worktree -d "Worktree management commands"
 complete -c agentdev -n "__fish_use_subcommand" -a sessions -d "Session inspection commands"
+complete -c agentdev -n "__fish_use_subcommand" -a tasks -d "Multi-agent task commands"
+complete -c agentdev -n "__fish_use_subcommand" -a config -d "Inspect or edit the config file"
+complete -c agentdev -n "__fish_use_subcommand" -a audit -d "Inspect the worktree audit log"
+complete -c agentdev -n "__fish_use_subcommand" -a doctor -d "Check the environment for common problems"
+complete -c agentdev -n "__fish_use_subcommand" -a init -d "Set up the current repository for agentdev"
+complete -c agentdev -n "__fish_seen_subcommand_from init" -l non-interactive -d "Accept every default without prompting"
+complete -c agentdev -n "__fish_use_subcommand" -a reap -d "Kill tmux sessions that have been idle for too long"
+complete -c agentdev -n "__fish_seen_subcommand_from reap" -l idle-hours -d "Hours without activity before a session is reaped"
+complete -c agentdev -n "__fish_seen_subcommand_from reap" -l dry-run -d "List the idle sessions without killing them"
 complete -c agentdev -n "__fish_use_subcommand" -a ui -d "Launch web UI"
 complete -c agentdev -n "__fish_use_subcommand" -a completions -d "Generate shell completions"
 
@@ -244,12 +411,21 @@ function __agentdev_worktrees_simple
 end
 
 # Worktree completions for commands
-complete -c agentdev -n "__fish_seen_subcommand_from worktree; and __fish_seen_subcommand_from open dir delete" -a "(__agentdev_worktrees)"
+complete -c agentdev -n "__fish_seen_subcommand_from worktree; and __fish_seen_subcommand_from open dir copy-branch-name delete lock unlock pin unpin link unlink note log reset snapshot checkpoint cp send watch shell" -a "(__agentdev_worktrees)"
 complete -c agentdev -n "__fish_seen_subcommand_from worktree; and __fish_seen_subcommand_from rename" -n "not __fish_seen_argument_from (__agentdev_worktrees_simple)" -a "(__agentdev_worktrees)"
 complete -c agentdev -n "__fish_seen_subcommand_from sessions" -a list -d "List recorded sessions"
+complete -c agentdev -n "__fish_seen_subcommand_from sessions" -a prune -d "Delete old session files"
+complete -c agentdev -n "__fish_seen_subcommand_from sessions" -a delete -d "Archive a session"
+complete -c agentdev -n "__fish_seen_subcommand_from sessions" -a export -d "Write transcripts as Markdown"
+complete -c agentdev -n "__fish_seen_subcommand_from tasks" -a list -d "List multi-agent tasks"
+complete -c agentdev -n "__fish_seen_subcommand_from tasks" -a merge -d "Merge the worktrees of a task in sequence"
+complete -c agentdev -n "__fish_seen_subcommand_from tasks" -a delete -d "Delete the worktrees of a task"
+complete -c agentdev -n "__fish_seen_subcommand_from config" -a show -d "Print the resolved config"
+complete -c agentdev -n "__fish_seen_subcommand_from config" -a edit -d "Edit the config file"
+complete -c agentdev -n "__fish_seen_subcommand_from audit" -a list -d "Show recent audit log entries"
 
 # Shell completions for completions command
 complete -c agentdev -n "__fish_seen_subcommand_from completions" -a "bash zsh fish"
 "#
-    );
+    .to_string()
 }
@@ -1,13 +1,269 @@
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-#[derive(Debug, Deserialize, Default)]
+use crate::tmux::TypingPace;
+
+/// Bundled reference config, written on first run.
+pub const DEFAULT_CONFIG_TOML: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml"));
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AgentConfig {
     /// Map of agent alias -> full command line string
     pub agents: HashMap<String, String>,
+    #[serde(default)]
+    pub worktree: WorktreeConfig,
+    /// Map of agent alias -> how fast prompts are typed into its session
+    #[serde(default)]
+    pub typing: HashMap<String, AgentTyping>,
+    /// Map of template name -> files copied into new worktrees by `create --template`
+    #[serde(default)]
+    pub templates: HashMap<String, FileTemplate>,
+    /// Notifications sent by `agentdev ui` when an agent goes idle
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// How text is sent to agent sessions
+    #[serde(default)]
+    pub tmux: TmuxConfig,
+    /// Live views of agent output, such as `worktree watch`
+    #[serde(default)]
+    pub dashboard: DashboardConfig,
+}
+
+/// `[templates.<name>]` section.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct FileTemplate {
+    /// Files to copy; relative paths are taken from the main repository
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// Where the files of a named worktree template come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Every file under a `[worktree.templates]` directory
+    Directory(PathBuf),
+    /// The `files` of a `[templates.<name>]` section, with `~` expanded
+    Files(Vec<PathBuf>),
+}
+
+/// `[typing.<alias>]` section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct AgentTyping {
+    /// Characters per keystroke batch
+    #[serde(default)]
+    pub type_chunk: Option<usize>,
+    /// Pause between batches in milliseconds
+    #[serde(default)]
+    pub type_delay_ms: Option<u64>,
+}
+
+/// `[tmux]` section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct TmuxConfig {
+    /// Characters per keystroke batch for agents without a `[typing.<alias>]` entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_type_chunk: Option<usize>,
+    /// Pause between batches in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_type_delay_ms: Option<u64>,
+    /// Pause between typing a prompt and pressing Enter, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_keys_delay_ms: Option<u64>,
+}
+
+/// `[dashboard]` section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct DashboardConfig {
+    /// How often live views poll the agent, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_refresh_interval_ms: Option<u64>,
+    /// Pane lines captured per poll when no `--lines` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_capture_lines: Option<usize>,
+}
+
+/// `[notify]` section.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NotifyConfig {
+    /// Watch agents for idle transitions while the UI server runs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where notifications go; empty means `log`
+    #[serde(default)]
+    pub channels: Vec<NotifyChannel>,
+    /// Receives a JSON POST per notification when `webhook` is a channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Worktrees to watch, by name or `repo/name`; with `tasks`, empty watches all
+    #[serde(default)]
+    pub worktrees: Vec<String>,
+    /// Task ids whose worktrees are watched
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    /// Seconds an agent must stay idle before a notification is sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debounce_secs: Option<u64>,
+}
+
+/// A destination for idle notifications.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyChannel {
+    /// A line in the server log
+    Log,
+    /// An OS notification; needs the `desktop-notify` feature
+    Desktop,
+    /// A POST to `webhook_url`
+    Webhook,
+}
+
+/// `[worktree]` section.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct WorktreeConfig {
+    /// Map of template name -> directory copied into new worktrees by `create --template`
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+    /// Most worktrees `create` may track at once; 0 or unset means no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_worktrees: Option<usize>,
+    /// Branch treated as every repository's default, skipping detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+}
+
+impl AgentConfig {
+    /// Files of the named worktree template, from `[worktree.templates]` or `[templates]`.
+    pub fn template(&self, name: &str) -> Result<TemplateSource> {
+        if let Some(raw) = self.worktree.templates.get(name) {
+            let dir = expand_home(raw)?;
+            if !dir.is_dir() {
+                anyhow::bail!(
+                    "Template '{name}' directory does not exist: {}",
+                    dir.display()
+                );
+            }
+            return Ok(TemplateSource::Directory(dir));
+        }
+        if let Some(template) = self.templates.get(name) {
+            if template.files.is_empty() {
+                anyhow::bail!("Template '{name}' lists no files");
+            }
+            let files = template
+                .files
+                .iter()
+                .map(|raw| expand_home(raw))
+                .collect::<Result<_>>()?;
+            return Ok(TemplateSource::Files(files));
+        }
+
+        let mut known: Vec<&str> = self
+            .worktree
+            .templates
+            .keys()
+            .chain(self.templates.keys())
+            .map(String::as_str)
+            .collect();
+        known.sort();
+        known.dedup();
+        if known.is_empty() {
+            anyhow::bail!(
+                "Unknown template '{name}'; add it under [templates.{name}] or [worktree.templates] in {}",
+                agent_config_path().display()
+            );
+        }
+        anyhow::bail!(
+            "Unknown template '{name}' (available: {})",
+            known.join(", ")
+        );
+    }
+
+    /// Typing pace for `alias`, taking unset fields from `fallback`.
+    pub fn typing_pace(&self, alias: Option<&str>, fallback: TypingPace) -> TypingPace {
+        let Some(typing) = alias.and_then(|alias| self.typing.get(alias)) else {
+            return fallback;
+        };
+        TypingPace {
+            chunk: typing
+                .type_chunk
+                .filter(|&chunk| chunk > 0)
+                .or(fallback.chunk),
+            delay: typing
+                .type_delay_ms
+                .map_or(fallback.delay, Duration::from_millis),
+            ..fallback
+        }
+    }
+
+    /// Typing pace for agents without their own settings: `[tmux]`, then the
+    /// `AGENTDEV_SLOW_TYPE_*` overrides.
+    pub fn default_typing_pace(&self) -> TypingPace {
+        TypingPace {
+            chunk: self.tmux.slow_type_chunk.filter(|&chunk| chunk > 0),
+            delay: Duration::from_millis(self.tmux.slow_type_delay_ms.unwrap_or(0)),
+            enter_delay: Duration::from_millis(self.tmux.send_keys_delay_ms.unwrap_or(0)),
+        }
+        .with_env_overrides()
+    }
+}
+
+/// Typing pace for the agent `alias` from the config file, falling back to
+/// `[tmux]` and the `AGENTDEV_SLOW_TYPE_*` defaults.
+pub fn resolve_typing_pace(alias: Option<&str>) -> TypingPace {
+    let pace = match load_agent_config() {
+        Ok(config) => config.typing_pace(alias, config.default_typing_pace()),
+        Err(_) => TypingPace::from_env(),
+    };
+    tracing::debug!(
+        agent = alias.unwrap_or("-"),
+        chunk = ?pace.chunk,
+        delay_ms = pace.delay.as_millis() as u64,
+        enter_delay_ms = pace.enter_delay.as_millis() as u64,
+        "resolved typing pace"
+    );
+    pace
+}
+
+/// Env var capping how many worktrees `create` allows; overrides `max_worktrees`.
+pub const MAX_WORKTREES_ENV: &str = "AGENTDEV_MAX_WORKTREES";
+
+/// Worktree limit from `AGENTDEV_MAX_WORKTREES` or `[worktree] max_worktrees`,
+/// or `None` when unlimited (unset or 0).
+pub fn resolve_max_worktrees() -> Result<Option<usize>> {
+    let limit = match std::env::var(MAX_WORKTREES_ENV) {
+        Ok(raw) if !raw.trim().is_empty() => raw.trim().parse::<usize>().with_context(|| {
+            format!("{MAX_WORKTREES_ENV} must be a non-negative integer, got '{raw}'")
+        })?,
+        _ => load_agent_config()?.worktree.max_worktrees.unwrap_or(0),
+    };
+    Ok((limit > 0).then_some(limit))
+}
+
+/// `[worktree] default_branch`, when set. Unlike [`load_agent_config`] this
+/// never writes a default config, and an unreadable file counts as unset.
+pub fn default_branch_override() -> Option<String> {
+    let content = fs::read_to_string(agent_config_path()).ok()?;
+    parse_agent_config(&content)
+        .ok()?
+        .worktree
+        .default_branch
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+}
+
+/// `raw` as a path, with a leading `~` expanded to the home directory.
+fn expand_home(raw: &str) -> Result<PathBuf> {
+    match raw.strip_prefix("~/") {
+        Some(rest) => Ok(directories::BaseDirs::new()
+            .context("Failed to resolve home directory for ~")?
+            .home_dir()
+            .join(rest)),
+        None => Ok(PathBuf::from(raw)),
+    }
 }
 
 /// Return the path to the agentdev config file.
@@ -47,16 +303,23 @@ pub fn load_agent_config() -> Result<AgentConfig> {
                 .with_context(|| format!("Failed to create config directory: {}", dir.display()))?;
         }
 
-        let default_toml =
-            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml"));
-        fs::write(&path, default_toml)
+        fs::write(&path, DEFAULT_CONFIG_TOML)
             .with_context(|| format!("Failed to write default config: {}", path.display()))?;
     }
 
     let content = fs::read_to_string(&path)
         .with_context(|| format!("Failed to read config: {}", path.display()))?;
-    let cfg: AgentConfig = toml::from_str(&content).context("Failed to parse config.toml")?;
-    Ok(cfg)
+    parse_agent_config(&content)
+}
+
+/// Parse the contents of a config file.
+pub fn parse_agent_config(content: &str) -> Result<AgentConfig> {
+    toml::from_str(content).context("Failed to parse config.toml")
+}
+
+/// Agent pool from the bundled reference config.
+pub fn default_agent_config() -> AgentConfig {
+    parse_agent_config(DEFAULT_CONFIG_TOML).expect("bundled config.example.toml is valid")
 }
 
 /// Parse a full command string into program + args using shell-style splitting.
@@ -68,3 +331,43 @@ pub fn split_cmdline(cmdline: &str) -> Result<(String, Vec<String>)> {
     }
     Ok((parts[0].clone(), parts[1..].to_vec()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tmux_section_sets_the_default_typing_pace() {
+        let config = parse_agent_config(
+            r#"
+[agents]
+claude = "claude"
+
+[tmux]
+slow_type_chunk = 80
+slow_type_delay_ms = 40
+send_keys_delay_ms = 20
+
+[typing.claude]
+type_chunk = 16
+
+[dashboard]
+preview_capture_lines = 100
+"#,
+        )
+        .unwrap();
+
+        let fallback = config.default_typing_pace();
+        assert_eq!(config.typing_pace(Some("codex"), fallback), fallback);
+        assert_eq!(
+            config.typing_pace(Some("claude"), fallback),
+            TypingPace {
+                chunk: Some(16),
+                delay: Duration::from_millis(40),
+                enter_delay: Duration::from_millis(20),
+            }
+        );
+        assert_eq!(config.dashboard.preview_capture_lines, Some(100));
+        assert_eq!(config.dashboard.auto_refresh_interval_ms, None);
+    }
+}
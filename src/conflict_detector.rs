@@ -0,0 +1,145 @@
+//! Detection of files edited in more than one worktree of the same repo.
+//!
+//! Agents running in parallel on one repository sometimes touch the same
+//! file, which is a merge conflict waiting to happen. Each worktree's
+//! uncommitted changes (`git diff --name-only HEAD`) are compared pairwise
+//! so the overlap can be flagged while both sides are still in progress.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::git::execute_git;
+use crate::state::XlaudeState;
+
+/// Two worktrees of the same repo with uncommitted changes to the same files.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CrossWorktreeConflict {
+    pub repo_name: String,
+    /// State key (`repo/name`) of the first worktree
+    pub worktree_a: String,
+    /// State key (`repo/name`) of the second worktree
+    pub worktree_b: String,
+    /// Paths relative to the repository root, sorted
+    pub files: Vec<String>,
+}
+
+/// Files with uncommitted changes in one worktree.
+struct WorktreeChanges {
+    id: String,
+    repo_name: String,
+    files: BTreeSet<String>,
+}
+
+/// Files changed relative to HEAD (staged or not) in the worktree at `path`.
+pub fn changed_files(path: &Path) -> Result<BTreeSet<String>> {
+    let repo = path
+        .to_str()
+        .context("worktree path contains non-UTF8 characters")?;
+    let output = execute_git(&["-C", repo, "diff", "--name-only", "HEAD"])?;
+    Ok(output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Every pair of managed worktrees in the same repo whose uncommitted
+/// changes touch a common file.
+///
+/// Worktrees whose directory is gone or whose diff fails are skipped.
+pub fn detect_cross_worktree_conflicts(state: &XlaudeState) -> Vec<CrossWorktreeConflict> {
+    let mut changes: Vec<WorktreeChanges> = state
+        .worktrees
+        .par_iter()
+        .filter(|(_, info)| info.path.exists())
+        .filter_map(|(id, info)| {
+            let files = changed_files(&info.path).ok()?;
+            (!files.is_empty()).then(|| WorktreeChanges {
+                id: id.clone(),
+                repo_name: info.repo_name.clone(),
+                files,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.id.cmp(&b.id));
+    find_conflicts(&changes)
+}
+
+fn find_conflicts(changes: &[WorktreeChanges]) -> Vec<CrossWorktreeConflict> {
+    let mut conflicts = Vec::new();
+    for (index, a) in changes.iter().enumerate() {
+        for b in &changes[index + 1..] {
+            if a.repo_name != b.repo_name {
+                continue;
+            }
+            let files: Vec<String> = a.files.intersection(&b.files).cloned().collect();
+            if !files.is_empty() {
+                conflicts.push(CrossWorktreeConflict {
+                    repo_name: a.repo_name.clone(),
+                    worktree_a: a.id.clone(),
+                    worktree_b: b.id.clone(),
+                    files,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(id: &str, files: &[&str]) -> WorktreeChanges {
+        WorktreeChanges {
+            id: id.to_string(),
+            repo_name: id.split('/').next().unwrap().to_string(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn overlapping_files_are_reported_per_pair() {
+        let conflicts = find_conflicts(&[
+            changes("app/a", &["src/lib.rs", "src/main.rs"]),
+            changes("app/b", &["README.md", "src/main.rs"]),
+            changes("app/c", &["src/lib.rs", "src/main.rs"]),
+        ]);
+        let pairs: Vec<_> = conflicts
+            .iter()
+            .map(|c| {
+                (
+                    c.worktree_a.as_str(),
+                    c.worktree_b.as_str(),
+                    c.files.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("app/a", "app/b", vec!["src/main.rs".to_string()]),
+                (
+                    "app/a",
+                    "app/c",
+                    vec!["src/lib.rs".to_string(), "src/main.rs".to_string()]
+                ),
+                ("app/b", "app/c", vec!["src/main.rs".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn different_repos_never_conflict() {
+        let conflicts = find_conflicts(&[
+            changes("app/a", &["src/main.rs"]),
+            changes("web/a", &["src/main.rs"]),
+        ]);
+        assert!(conflicts.is_empty());
+    }
+}
@@ -39,7 +39,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::git::{execute_git, get_current_branch};
+use crate::git::execute_git;
 use crate::state::{WorktreeInfo, XlaudeState};
 use crate::utils::sanitize_branch_name;
 
@@ -123,7 +123,9 @@ impl GitWorktree {
             .map(|p| p.to_path_buf())
             .context("Failed to determine main repo path")?;
 
-        let branch = get_current_branch().ok();
+        let branch = execute_git(&["-C", path_str, "symbolic-ref", "--short", "HEAD"])
+            .ok()
+            .map(|s| s.trim().to_string());
 
         let head = execute_git(&["-C", path_str, "rev-parse", "HEAD"])
             .ok()
@@ -137,6 +139,20 @@ impl GitWorktree {
         }))
     }
 
+    /// Return the lock reason if git reports this worktree as locked.
+    ///
+    /// `Ok(Some(""))` means the worktree is locked without a reason.
+    pub fn lock_status(&self) -> Result<Option<String>> {
+        let path_str = self.path.to_str().context("Path contains invalid UTF-8")?;
+        let git_dir = execute_git(&["-C", path_str, "rev-parse", "--absolute-git-dir"])?;
+        let lock_file = Path::new(git_dir.trim()).join("locked");
+        if !lock_file.exists() {
+            return Ok(None);
+        }
+        let reason = fs::read_to_string(&lock_file).unwrap_or_default();
+        Ok(Some(reason.trim().to_string()))
+    }
+
     /// Get the repository name (directory name of main repo).
     pub fn repo_name(&self) -> String {
         self.repo_root
@@ -163,6 +179,7 @@ impl GitWorktree {
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct DiscoveredWorktree {
     pub repo: String,
@@ -194,6 +211,16 @@ impl Default for DiscoveryOptions {
 }
 
 pub fn discover_worktrees(options: DiscoveryOptions) -> Result<Vec<DiscoveredWorktree>> {
+    let state = XlaudeState::load()?;
+    let managed_paths = build_managed_path_index(&state)?;
+    discover_unmanaged_worktrees(options, &managed_paths)
+}
+
+/// Discovery core: lists worktrees whose canonical path is not in `managed_paths`.
+fn discover_unmanaged_worktrees(
+    options: DiscoveryOptions,
+    managed_paths: &HashSet<String>,
+) -> Result<Vec<DiscoveredWorktree>> {
     let root = options
         .root
         .unwrap_or(std::env::current_dir().context("Failed to determine current directory")?);
@@ -213,13 +240,11 @@ pub fn discover_worktrees(options: DiscoveryOptions) -> Result<Vec<DiscoveredWor
         return Ok(Vec::new());
     }
 
-    let state = XlaudeState::load()?;
-    let managed_paths = build_managed_path_index(&state)?;
     let mut seen_paths: HashSet<String> = HashSet::new();
     let mut discovered = Vec::new();
 
     for repo in repos {
-        let mut entries = discover_repo_worktrees(&repo, &managed_paths, &mut seen_paths)?;
+        let mut entries = discover_repo_worktrees(&repo, managed_paths, &mut seen_paths)?;
         discovered.append(&mut entries);
     }
 
@@ -294,6 +319,14 @@ pub fn add_discovered_to_state(entries: &[DiscoveredWorktree]) -> Result<Vec<Wor
             task_name: None,
             initial_prompt: None,
             agent_alias: None,
+            locked: entry.locked.is_some(),
+            lock_reason: entry.locked.clone(),
+            last_agent_activity: None,
+            base_ref: None,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
         };
 
         state.worktrees.insert(key.clone(), info.clone());
@@ -613,3 +646,324 @@ fn ensure_unique_name(state: &XlaudeState, repo_name: &str, desired: String) ->
         counter += 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "commit.gpgsign=false",
+                "-c",
+                "init.defaultBranch=main",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Initialize a repository with a single commit at `dir`.
+    fn init_repo(dir: &Path) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        git(dir, &["init", "-q"]);
+        fs::write(dir.join("README.md"), "test\n").unwrap();
+        git(dir, &["add", "README.md"]);
+        git(dir, &["commit", "-q", "-m", "init"]);
+        fs::canonicalize(dir).unwrap()
+    }
+
+    fn add_worktree(repo: &Path, path: &Path, branch: &str) -> PathBuf {
+        git(
+            repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                branch,
+                path.to_str().unwrap(),
+            ],
+        );
+        fs::canonicalize(path).unwrap()
+    }
+
+    fn discover(root: &Path, recursive: bool) -> Vec<DiscoveredWorktree> {
+        discover_unmanaged_worktrees(
+            DiscoveryOptions {
+                recursive,
+                root: Some(root.to_path_buf()),
+            },
+            &HashSet::new(),
+        )
+        .expect("discover worktrees")
+    }
+
+    fn paths(entries: &[DiscoveredWorktree]) -> Vec<PathBuf> {
+        entries
+            .iter()
+            .map(|entry| fs::canonicalize(&entry.path).unwrap())
+            .collect()
+    }
+
+    fn assert_unique(entries: &[DiscoveredWorktree]) {
+        let all = paths(entries);
+        let unique: HashSet<_> = all.iter().collect();
+        assert_eq!(unique.len(), all.len(), "duplicate worktrees: {all:?}");
+    }
+
+    #[test]
+    fn from_path_reads_branch_of_the_given_worktree() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let worktree = add_worktree(&repo, &temp.path().join("feature"), "feature");
+
+        // The test process runs elsewhere; the branch must come from `worktree`
+        let info = GitWorktree::from_path(&worktree)
+            .expect("inspect worktree")
+            .expect("linked worktree");
+        assert_eq!(info.branch.as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn recursive_scan_of_empty_directory_finds_nothing() {
+        let temp = TempDir::new().unwrap();
+        assert!(discover(temp.path(), true).is_empty());
+    }
+
+    #[test]
+    fn recursive_scan_ignores_plain_directories() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/b/c")).unwrap();
+        fs::write(temp.path().join("a/b/file.txt"), "x").unwrap();
+        assert!(discover(temp.path(), true).is_empty());
+    }
+
+    #[test]
+    fn non_recursive_scan_outside_repository_fails() {
+        let temp = TempDir::new().unwrap();
+        let result = discover_unmanaged_worktrees(
+            DiscoveryOptions {
+                recursive: false,
+                root: Some(temp.path().to_path_buf()),
+            },
+            &HashSet::new(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn main_checkout_is_not_reported() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        assert!(discover(&repo, false).is_empty());
+        assert!(discover(temp.path(), true).is_empty());
+    }
+
+    #[test]
+    fn linked_worktree_is_reported_with_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let wt = add_worktree(&repo, &temp.path().join("repo-feature"), "feature/x");
+
+        let entries = discover(&repo, false);
+        assert_eq!(paths(&entries), vec![wt]);
+        assert_eq!(entries[0].branch.as_deref(), Some("feature/x"));
+        assert!(entries[0].head.is_some());
+        assert!(!entries[0].bare);
+    }
+
+    #[test]
+    fn detached_head_worktree_has_no_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let head = git(&repo, &["rev-parse", "HEAD"]);
+        let wt_path = temp.path().join("detached");
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "--detach",
+                wt_path.to_str().unwrap(),
+            ],
+        );
+
+        let entries = discover(&repo, false);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].branch, None);
+        assert_eq!(entries[0].head.as_deref(), Some(head.as_str()));
+    }
+
+    #[test]
+    fn locked_worktree_reports_reason() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let wt = add_worktree(&repo, &temp.path().join("locked"), "locked");
+        git(
+            &repo,
+            &[
+                "worktree",
+                "lock",
+                "--reason",
+                "on usb",
+                wt.to_str().unwrap(),
+            ],
+        );
+
+        let entries = discover(&repo, false);
+        assert_eq!(entries[0].locked.as_deref(), Some("on usb"));
+    }
+
+    #[test]
+    fn nested_repositories_within_depth_limit_are_found() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("a/b/c");
+        let repo = init_repo(&nested.join("repo"));
+        let wt = add_worktree(&repo, &nested.join("repo-wt"), "nested");
+
+        assert_eq!(paths(&discover(temp.path(), true)), vec![wt]);
+    }
+
+    #[test]
+    fn repositories_beyond_depth_limit_are_ignored() {
+        let temp = TempDir::new().unwrap();
+        let mut deep = temp.path().to_path_buf();
+        for level in 0..=MAX_RECURSIVE_DEPTH {
+            deep = deep.join(format!("d{level}"));
+        }
+        let repo = init_repo(&deep.join("repo"));
+        add_worktree(&repo, &deep.join("repo-wt"), "deep");
+
+        assert!(discover(temp.path(), true).is_empty());
+    }
+
+    #[test]
+    fn repositories_inside_git_and_build_directories_are_excluded() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        // A repository stored inside `.git` (e.g. submodule git dirs) must not be scanned.
+        let hidden = init_repo(&repo.join(".git/modules/inner"));
+        add_worktree(&hidden, &repo.join(".git/modules/inner-wt"), "hidden");
+        let vendored = init_repo(&temp.path().join("node_modules/pkg"));
+        add_worktree(
+            &vendored,
+            &temp.path().join("node_modules/pkg-wt"),
+            "vendored",
+        );
+
+        let repos = discover_repositories_recursive(temp.path()).unwrap();
+        assert_eq!(repos, vec![repo]);
+        assert!(discover(temp.path(), true).is_empty());
+    }
+
+    #[test]
+    fn worktrees_inside_scan_root_are_not_duplicated() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let first = add_worktree(&repo, &temp.path().join("repo.worktrees/one"), "one");
+        let second = add_worktree(&repo, &temp.path().join("repo.worktrees/two"), "two");
+
+        // The linked worktrees are also repository roots, but share a common dir.
+        let entries = discover(temp.path(), true);
+        assert_unique(&entries);
+        assert_eq!(paths(&entries), vec![first, second]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_paths_do_not_produce_duplicates() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let wt = add_worktree(&repo, &temp.path().join("repo-wt"), "linked");
+        std::os::unix::fs::symlink(&repo, temp.path().join("repo-alias")).unwrap();
+        std::os::unix::fs::symlink(&wt, temp.path().join("wt-alias")).unwrap();
+
+        let entries = discover(temp.path(), true);
+        assert_unique(&entries);
+        assert_eq!(paths(&entries), vec![wt.clone()]);
+
+        // Scanning through a symlinked root yields the same worktree.
+        let alias_root = TempDir::new().unwrap();
+        let link = alias_root.path().join("root");
+        std::os::unix::fs::symlink(temp.path(), &link).unwrap();
+        assert_eq!(paths(&discover(&link, true)), vec![wt.clone()]);
+        assert_eq!(
+            paths(&discover(&temp.path().join("repo-alias"), false)),
+            vec![wt]
+        );
+    }
+
+    #[test]
+    fn managed_worktrees_are_excluded() {
+        let temp = TempDir::new().unwrap();
+        let repo = init_repo(&temp.path().join("repo"));
+        let managed = add_worktree(&repo, &temp.path().join("managed"), "managed");
+        let unmanaged = add_worktree(&repo, &temp.path().join("unmanaged"), "unmanaged");
+
+        let managed_paths = HashSet::from([canonical_string(&managed).unwrap()]);
+        let entries = discover_unmanaged_worktrees(
+            DiscoveryOptions {
+                recursive: false,
+                root: Some(repo.clone()),
+            },
+            &managed_paths,
+        )
+        .unwrap();
+        assert_eq!(paths(&entries), vec![unmanaged]);
+    }
+
+    #[test]
+    fn results_are_sorted_and_stable_across_runs() {
+        let temp = TempDir::new().unwrap();
+        for repo_name in ["zeta", "alpha"] {
+            let repo = init_repo(&temp.path().join(repo_name));
+            for branch in ["b", "a", "c"] {
+                let wt_path = temp.path().join(format!("{repo_name}-{branch}"));
+                add_worktree(&repo, &wt_path, branch);
+            }
+        }
+
+        let first = discover(temp.path(), true);
+        assert_eq!(first.len(), 6);
+        assert_unique(&first);
+        let mut sorted = first.clone();
+        sorted.sort_by(|a, b| a.repo.cmp(&b.repo).then_with(|| a.path.cmp(&b.path)));
+        assert_eq!(first, sorted);
+
+        for _ in 0..3 {
+            assert_eq!(discover(temp.path(), true), first);
+        }
+    }
+
+    #[test]
+    fn porcelain_parser_handles_all_attributes() {
+        let output = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\n\
+                      worktree /bare\nbare\n\n\
+                      worktree /wt\nHEAD def\ndetached\nlocked\nprunable gitdir file points to non-existent location\n";
+        let entries = parse_git_worktree_porcelain(output).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert!(entries[1].bare);
+        assert_eq!(entries[2].branch, None);
+        assert_eq!(entries[2].head.as_deref(), Some("def"));
+        assert_eq!(entries[2].locked.as_deref(), Some("(no reason provided)"));
+        assert!(entries[2].prunable.is_some());
+    }
+}
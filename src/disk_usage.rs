@@ -0,0 +1,205 @@
+//! On-disk size of worktrees, cached across runs.
+//!
+//! Walking a worktree full of build artifacts can take seconds, so sizes are
+//! cached in `disk-usage.json` next to the state file. A cached size is reused
+//! while the tree's directory count and newest directory mtime are unchanged.
+//! Creating, deleting or renaming a file bumps its parent directory's mtime,
+//! so only in-place growth of existing files goes unnoticed.
+
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::state::get_config_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TreeSignature {
+    dirs: u64,
+    newest_dir_mtime_ns: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedSize {
+    signature: TreeSignature,
+    bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DiskUsageCache {
+    entries: HashMap<String, CachedSize>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl DiskUsageCache {
+    /// Load the persisted cache, starting empty if it is missing or unreadable.
+    pub fn load() -> Self {
+        cache_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the cache if any entry changed since it was loaded.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let path = cache_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+        fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Total size in bytes of the files under `path`.
+    ///
+    /// With `respect_gitignore`, files matched by `.gitignore` (typically
+    /// build output) are left out.
+    pub fn size_of(&mut self, path: &Path, respect_gitignore: bool) -> Result<u64> {
+        let key = format!(
+            "{}{}",
+            path.display(),
+            if respect_gitignore { "#tracked" } else { "" }
+        );
+        let signature = tree_signature(path, respect_gitignore)?;
+        if let Some(cached) = self.entries.get(&key)
+            && cached.signature == signature
+        {
+            return Ok(cached.bytes);
+        }
+
+        let bytes = walk(path, respect_gitignore)
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        self.entries.insert(key, CachedSize { signature, bytes });
+        self.dirty = true;
+        Ok(bytes)
+    }
+}
+
+/// Size of a single worktree through the persisted cache.
+pub fn worktree_size(path: &Path, respect_gitignore: bool) -> Result<u64> {
+    let mut cache = DiskUsageCache::load();
+    let bytes = cache.size_of(path, respect_gitignore)?;
+    // A failed cache write only costs a rescan next time
+    let _ = cache.save();
+    Ok(bytes)
+}
+
+/// Human-readable size using binary units, e.g. `1.5 GiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("disk-usage.json"))
+}
+
+fn walk(path: &Path, respect_gitignore: bool) -> ignore::Walk {
+    WalkBuilder::new(path)
+        .standard_filters(false)
+        .git_ignore(respect_gitignore)
+        .git_exclude(respect_gitignore)
+        .build()
+}
+
+fn tree_signature(path: &Path, respect_gitignore: bool) -> Result<TreeSignature> {
+    if !path.is_dir() {
+        anyhow::bail!("Worktree path missing: {}", path.display());
+    }
+    let mut signature = TreeSignature {
+        dirs: 0,
+        newest_dir_mtime_ns: 0,
+    };
+    for entry in walk(path, respect_gitignore).filter_map(Result::ok) {
+        if !entry.file_type().is_some_and(|kind| kind.is_dir()) {
+            continue;
+        }
+        signature.dirs += 1;
+        let mtime = entry
+            .metadata()
+            .ok()
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        signature.newest_dir_mtime_ns = signature.newest_dir_mtime_ns.max(mtime);
+    }
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn sizes_files_and_reuses_cache_until_tree_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), vec![b'x'; 100]).unwrap();
+        fs::write(root.join("README.md"), vec![b'x'; 20]).unwrap();
+
+        let mut cache = DiskUsageCache::default();
+        assert_eq!(cache.size_of(root, false).unwrap(), 120);
+        assert!(cache.dirty);
+
+        // Same tree: the cached size is served even if file contents grew
+        fs::write(root.join("README.md"), vec![b'x'; 30]).unwrap();
+        assert_eq!(cache.size_of(root, false).unwrap(), 120);
+
+        // A new directory changes the signature and forces a rescan
+        fs::create_dir(root.join("target")).unwrap();
+        fs::write(root.join("target/app"), vec![b'x'; 1000]).unwrap();
+        assert_eq!(cache.size_of(root, false).unwrap(), 1130);
+    }
+
+    #[test]
+    fn respecting_gitignore_skips_ignored_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(root)
+            .status()
+            .unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir(root.join("target")).unwrap();
+        fs::write(root.join("target/app"), vec![b'x'; 1000]).unwrap();
+
+        let mut cache = DiskUsageCache::default();
+        let all = cache.size_of(root, false).unwrap();
+        let tracked = cache.size_of(root, true).unwrap();
+        assert!(all >= tracked + 1000, "all={all} tracked={tracked}");
+    }
+
+    #[test]
+    fn formats_binary_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
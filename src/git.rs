@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 
+use crate::utils::{command_timeout, output_with_timeout};
+
 // Simple in-memory ring buffer for recent git command logs (for the web dashboard debug view)
 // Keep this lightweight and dependency-free.
 #[derive(Clone, Debug)]
@@ -219,13 +221,18 @@ fn truncate_output(text: &str) -> String {
     }
 }
 
+/// `git` with credential prompts disabled so it fails instead of waiting on a terminal.
+fn git_command(args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command.args(args).env("GIT_TERMINAL_PROMPT", "0");
+    command
+}
+
 pub fn execute_git(args: &[&str]) -> Result<String> {
     let display_cmd = format_git_command(args);
 
-    let output = Command::new("git")
-        .args(args)
-        .output()
-        .map_err(|err| anyhow::anyhow!("Failed to spawn git command: {display_cmd} ({err})"))?;
+    let output = output_with_timeout(&mut git_command(args), command_timeout())
+        .map_err(|err| anyhow::anyhow!("Failed to run git command: {display_cmd} ({err:#})"))?;
 
     // Record in debug log buffer
     push_git_log(args, output.status.code(), &output.stdout, &output.stderr);
@@ -285,9 +292,7 @@ pub fn ahead_behind(local_ref: &str, upstream_ref: &str) -> Result<AheadBehind>
 /// which is not an error for our use cases. Other commands should still use
 /// `execute_git` to get strict error handling.
 fn execute_git_allow_code_1(args: &[&str]) -> Result<String> {
-    let output = Command::new("git")
-        .args(args)
-        .output()
+    let output = output_with_timeout(&mut git_command(args), command_timeout())
         .context("Failed to execute git command")?;
 
     // Record in debug log buffer
@@ -382,32 +387,12 @@ pub fn get_current_branch() -> Result<String> {
     execute_git(&["symbolic-ref", "--short", "HEAD"])
 }
 
-pub fn get_default_branch() -> Result<String> {
-    // Try to get the default branch from remote HEAD
-    if let Ok(output) = execute_git(&["remote", "show", "origin"]) {
-        for line in output.lines() {
-            if let Some(branch) = line.strip_prefix("  HEAD branch: ") {
-                return Ok(branch.trim().to_string());
-            }
-        }
-    }
-
-    // Fallback: try to get HEAD from symbolic-ref
-    if let Ok(output) = execute_git(&["symbolic-ref", "refs/remotes/origin/HEAD"])
-        && let Some(branch) = output.strip_prefix("refs/remotes/origin/")
-    {
-        return Ok(branch.to_string());
-    }
-
-    // Final fallback: return "main" as the most common default
-    Ok("main".to_string())
-}
-
 pub fn is_base_branch() -> Result<bool> {
     let current = get_current_branch()?;
 
     // Get the actual default branch from remote
-    let default_branch = get_default_branch().unwrap_or_else(|_| "main".to_string());
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let default_branch = default_branch(&cwd);
 
     // Check if current branch is the default branch
     if current == default_branch {
@@ -457,6 +442,37 @@ pub fn has_unpushed_commits() -> bool {
     execute_git(&["log", "@{u}.."]).is_ok_and(|output| !output.is_empty())
 }
 
+/// Whether `path` has a `.git` directory, or a `.git` file pointing at an
+/// existing git dir as linked worktrees do.
+pub fn git_metadata_present(path: &Path) -> bool {
+    let git_entry = path.join(".git");
+    if git_entry.is_dir() {
+        return true;
+    }
+    if git_entry.is_file() {
+        if let Ok(contents) = std::fs::read_to_string(&git_entry) {
+            for line in contents.lines() {
+                if let Some(rest) = line.strip_prefix("gitdir:") {
+                    let trimmed = rest.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    let candidate = Path::new(trimmed);
+                    let resolved = if candidate.is_absolute() {
+                        candidate.to_path_buf()
+                    } else {
+                        path.join(candidate)
+                    };
+                    if resolved.exists() {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
 pub fn is_in_worktree() -> Result<bool> {
     // Check if we're in a worktree by looking for .git file (not directory)
     let git_path = Path::new(".git");
@@ -550,65 +566,91 @@ pub fn update_submodules(worktree_path: &Path) -> Result<()> {
     Ok(())
 }
 
-static DEFAULT_BRANCH_CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+type DefaultBranchCache = Mutex<HashMap<(PathBuf, String), String>>;
 
-fn detect_default_branch_for_repo(repo: &str) -> Option<String> {
-    if let Some(cache) = DEFAULT_BRANCH_CACHE.get() {
-        if let Ok(cache_guard) = cache.lock() {
-            if let Some(cached) = cache_guard.get(repo) {
-                return cached.clone();
-            }
-        }
-    }
+static DEFAULT_BRANCH_CACHE: OnceLock<DefaultBranchCache> = OnceLock::new();
 
-    // Try local-only methods first to avoid network requests
+/// Default branch of the repository at `repo_path`, as seen from `origin`.
+///
+/// Resolution order, all local (no network):
+/// 1. `[worktree] default_branch` in config.toml
+/// 2. `refs/remotes/origin/HEAD`
+/// 3. `init.defaultBranch`, when that branch exists locally or on `origin`
+/// 4. `main`, then `master`, whichever exists
+/// 5. `main`
+///
+/// Steps 2-5 are cached per repository path for the life of the process.
+pub fn default_branch(repo_path: &Path) -> String {
+    default_branch_for_remote(repo_path, "origin")
+}
 
-    // Method 1: Check symbolic-ref (fastest, no network)
-    let symbolic_branch = execute_git(&["-C", repo, "symbolic-ref", "refs/remotes/origin/HEAD"])
-        .ok()
-        .and_then(|output| {
-            output
-                .trim()
-                .strip_prefix("refs/remotes/origin/")
-                .map(|branch| branch.to_string())
-        });
-
-    if let Some(branch) = symbolic_branch {
-        return store_default_branch(repo, Some(branch));
+/// [`default_branch`] against an arbitrary remote.
+pub fn default_branch_for_remote(repo_path: &Path, remote: &str) -> String {
+    if let Some(branch) = crate::config::default_branch_override() {
+        return branch;
     }
 
-    // Method 2: Check common branch names locally
-    for candidate in ["main", "master", "develop"] {
-        let ref_name = format!("refs/remotes/origin/{candidate}");
-        if execute_git(&["-C", repo, "rev-parse", "--verify", "--quiet", &ref_name]).is_ok() {
-            return store_default_branch(repo, Some(candidate.to_string()));
-        }
+    let key = (repo_path.to_path_buf(), remote.to_string());
+    let cache = DEFAULT_BRANCH_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock()
+        && let Some(cached) = guard.get(&key)
+    {
+        return cached.clone();
     }
 
-    // Method 3: Fallback to remote show (may trigger network request)
-    let remote_show_branch = execute_git(&["-C", repo, "remote", "show", "origin"])
-        .ok()
-        .and_then(|output| {
-            output.lines().find_map(|line| {
-                line.strip_prefix("  HEAD branch: ")
-                    .map(|branch| branch.trim().to_string())
-            })
-        });
-
-    store_default_branch(repo, remote_show_branch)
-}
-
-fn store_default_branch(repo: &str, branch: Option<String>) -> Option<String> {
-    let cache = DEFAULT_BRANCH_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let branch = resolve_default_branch(repo_path, remote);
     if let Ok(mut guard) = cache.lock() {
-        guard.insert(repo.to_string(), branch.clone());
+        guard.insert(key, branch.clone());
     }
     branch
 }
 
+fn resolve_default_branch(repo_path: &Path, remote: &str) -> String {
+    const FALLBACK: &str = "main";
+    let Some(repo) = repo_path.to_str() else {
+        return FALLBACK.to_string();
+    };
+
+    let remote_prefix = format!("refs/remotes/{remote}/");
+    let head_ref = format!("{remote_prefix}HEAD");
+    if let Ok(output) = execute_git(&["-C", repo, "symbolic-ref", "--quiet", &head_ref])
+        && let Some(branch) = output.strip_prefix(&remote_prefix)
+    {
+        return branch.to_string();
+    }
+
+    let exists = |branch: &str| {
+        [
+            format!("refs/heads/{branch}"),
+            format!("{remote_prefix}{branch}"),
+        ]
+        .iter()
+        .any(|reference| {
+            execute_git(&["-C", repo, "rev-parse", "--verify", "--quiet", reference]).is_ok()
+        })
+    };
+
+    if let Ok(configured) = execute_git(&["-C", repo, "config", "--get", "init.defaultBranch"])
+        && !configured.is_empty()
+        && exists(&configured)
+    {
+        return configured;
+    }
+
+    ["main", "master"]
+        .into_iter()
+        .find(|candidate| exists(candidate))
+        .unwrap_or(FALLBACK)
+        .to_string()
+}
+
 #[derive(Debug, Clone)]
 pub struct WorktreeGitStatus {
-    pub branch: String,
+    /// Checked-out branch; `None` when HEAD is detached
+    pub branch: Option<String>,
+    pub detached: bool,
+    /// Abbreviated HEAD commit, absent before the first commit
+    pub head: Option<String>,
     pub upstream: Option<String>,
     pub ahead: u32,
     pub behind: u32,
@@ -624,6 +666,55 @@ pub struct HeadCommitInfo {
     pub commit_id: String,
     pub summary: String,
     pub timestamp: Option<DateTime<Utc>>,
+    pub author: Option<String>,
+    pub author_email: Option<String>,
+}
+
+/// `git log --pretty` format read by [`parse_commit_record`]. Fields are
+/// NUL-separated with the free-form summary last, so no character in it can
+/// shift the other fields.
+const COMMIT_RECORD_FORMAT: &str = "--pretty=format:%H%x00%ct%x00%an%x00%ae%x00%s";
+
+/// Parse one line of [`COMMIT_RECORD_FORMAT`] output.
+fn parse_commit_record(record: &str) -> Option<HeadCommitInfo> {
+    let mut parts = record.splitn(5, '\0');
+    let commit_id = parts.next().unwrap_or_default().trim().to_string();
+    if commit_id.is_empty() {
+        return None;
+    }
+    let timestamp = parts
+        .next()
+        .and_then(|ts| ts.parse::<i64>().ok())
+        .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+    let mut text = || {
+        parts
+            .next()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let author = text();
+    let author_email = text();
+    let summary = text().unwrap_or_default();
+
+    Some(HeadCommitInfo {
+        commit_id,
+        summary,
+        timestamp,
+        author,
+        author_email,
+    })
+}
+
+/// Prefix of the annotated tags created by `worktree checkpoint`.
+pub const CHECKPOINT_TAG_PREFIX: &str = "checkpoint-";
+
+#[derive(Debug, Clone)]
+pub struct CheckpointTag {
+    pub name: String,
+    pub commit_id: String,
+    pub message: String,
+    pub tagged_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -666,6 +757,8 @@ pub struct WorktreeDiffBreakdown {
     pub untracked: Vec<GitFileDiff>,
 }
 
+const SHORT_OID_LEN: usize = 7;
+
 /// Summarize the git status for a worktree.
 pub fn summarize_worktree_status(path: &Path, fallback_branch: &str) -> Result<WorktreeGitStatus> {
     let repo = path
@@ -675,7 +768,9 @@ pub fn summarize_worktree_status(path: &Path, fallback_branch: &str) -> Result<W
     let raw = execute_git(&["-C", repo, "status", "--porcelain=2", "--branch"])?;
 
     let mut status = WorktreeGitStatus {
-        branch: String::new(),
+        branch: None,
+        detached: false,
+        head: None,
         upstream: None,
         ahead: 0,
         behind: 0,
@@ -687,8 +782,18 @@ pub fn summarize_worktree_status(path: &Path, fallback_branch: &str) -> Result<W
     };
 
     for line in raw.lines() {
+        if let Some(oid) = line.strip_prefix("# branch.oid ") {
+            let oid = oid.trim();
+            if oid != "(initial)" {
+                status.head = Some(oid.chars().take(SHORT_OID_LEN).collect());
+            }
+            continue;
+        }
         if let Some(head) = line.strip_prefix("# branch.head ") {
-            status.branch = head.trim().to_string();
+            match head.trim() {
+                "(detached)" => status.detached = true,
+                name => status.branch = Some(name.to_string()),
+            }
             continue;
         }
         if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
@@ -730,8 +835,8 @@ pub fn summarize_worktree_status(path: &Path, fallback_branch: &str) -> Result<W
         }
     }
 
-    if status.branch.is_empty() {
-        status.branch = fallback_branch.to_string();
+    if status.branch.is_none() && !status.detached {
+        status.branch = Some(fallback_branch.to_string());
     }
     status.is_clean = status.staged == 0
         && status.unstaged == 0
@@ -741,6 +846,17 @@ pub fn summarize_worktree_status(path: &Path, fallback_branch: &str) -> Result<W
     Ok(status)
 }
 
+impl WorktreeGitStatus {
+    /// Branch name, or `(detached @abc1234)` when HEAD is not on a branch.
+    pub fn branch_label(&self) -> String {
+        match (&self.branch, &self.head) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(head)) => format!("(detached @{head})"),
+            (None, None) => "(detached)".to_string(),
+        }
+    }
+}
+
 fn note_status_tokens(status: &mut WorktreeGitStatus, rest: &str) {
     if let Some(token) = rest.split_whitespace().next() {
         let mut chars = token.chars();
@@ -767,7 +883,7 @@ pub fn head_commit_info(path: &Path) -> Result<Option<HeadCommitInfo>> {
         .to_str()
         .context("worktree path contains invalid UTF-8")?;
 
-    let args = ["-C", repo, "log", "-1", "--pretty=format:%H%x00%ct%x00%s"];
+    let args = ["-C", repo, "log", "-1", COMMIT_RECORD_FORMAT];
 
     let raw = match execute_git(&args) {
         Ok(output) => output,
@@ -787,23 +903,49 @@ pub fn head_commit_info(path: &Path) -> Result<Option<HeadCommitInfo>> {
         return Ok(None);
     }
 
-    let mut parts = raw.split('\0');
-    let commit_id = parts.next().unwrap_or_default().trim().to_string();
-    let timestamp = parts
-        .next()
-        .and_then(|ts| ts.parse::<i64>().ok())
-        .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
-    let summary = parts.next().unwrap_or_default().trim().to_string();
+    Ok(parse_commit_record(&raw))
+}
 
-    if commit_id.is_empty() && summary.is_empty() {
-        return Ok(None);
-    }
+/// Annotated `checkpoint-*` tags reachable from the worktree's HEAD, oldest first.
+pub fn list_checkpoint_tags(path: &Path) -> Result<Vec<CheckpointTag>> {
+    let repo = path
+        .to_str()
+        .context("worktree path contains invalid UTF-8")?;
+    let pattern = format!("refs/tags/{CHECKPOINT_TAG_PREFIX}*");
+    let raw = execute_git(&[
+        "-C",
+        repo,
+        "for-each-ref",
+        "--merged",
+        "HEAD",
+        "--sort=taggerdate",
+        "--format=%(objecttype)%00%(refname:short)%00%(*objectname)%00%(taggerdate:unix)%00%(contents:subject)",
+        &pattern,
+    ])?;
 
-    Ok(Some(HeadCommitInfo {
-        commit_id,
-        summary,
-        timestamp,
-    }))
+    Ok(raw
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\0');
+            // Lightweight tags point straight at a commit; only annotated ones count.
+            if parts.next()? != "tag" {
+                return None;
+            }
+            let name = parts.next()?.to_string();
+            let commit_id = parts.next()?.to_string();
+            let tagged_at = parts
+                .next()
+                .and_then(|ts| ts.parse::<i64>().ok())
+                .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+            let message = parts.next().unwrap_or_default().to_string();
+            Some(CheckpointTag {
+                name,
+                commit_id,
+                message,
+                tagged_at,
+            })
+        })
+        .collect())
 }
 
 pub fn commits_since_merge_base(path: &Path) -> Result<Option<CommitsAhead>> {
@@ -811,7 +953,7 @@ pub fn commits_since_merge_base(path: &Path) -> Result<Option<CommitsAhead>> {
         .to_str()
         .context("worktree path contains invalid UTF-8")?;
 
-    let default_branch = detect_default_branch_for_repo(repo).unwrap_or_else(|| "main".to_string());
+    let default_branch = default_branch(path);
 
     let candidate_refs = [format!("origin/{default_branch}"), default_branch.clone()];
 
@@ -858,56 +1000,7 @@ pub fn commits_since_merge_base(path: &Path) -> Result<Option<CommitsAhead>> {
         }
     }
 
-    let merge_base = execute_git(&["-C", repo, "merge-base", "HEAD", &base_ref])
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-
-    let range = if let Some(ref base) = merge_base {
-        format!("{base}..HEAD")
-    } else {
-        format!("{base_ref}..HEAD")
-    };
-
-    let log_output = execute_git(&[
-        "-C",
-        repo,
-        "log",
-        "--pretty=format:%H%x09%ct%x09%s",
-        "--reverse",
-        &range,
-    ])?;
-
-    let mut commits = Vec::new();
-    for line in log_output.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let mut parts = line.splitn(3, '\t');
-        let commit_id = parts.next().unwrap_or_default().trim().to_string();
-        if commit_id.is_empty() {
-            continue;
-        }
-
-        let timestamp = parts
-            .next()
-            .and_then(|part| part.parse::<i64>().ok())
-            .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
-        let summary = parts.next().unwrap_or_default().trim().to_string();
-
-        commits.push(HeadCommitInfo {
-            commit_id,
-            summary,
-            timestamp,
-        });
-    }
-
-    let result = Some(CommitsAhead {
-        base_branch: default_branch,
-        merge_base,
-        commits,
-    });
+    let result = Some(commits_ahead_of(repo, &base_ref, default_branch)?);
 
     let cache = COMMITS_AHEAD_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
     if let Ok(mut guard) = cache.lock() {
@@ -925,6 +1018,47 @@ pub fn commits_since_merge_base(path: &Path) -> Result<Option<CommitsAhead>> {
     Ok(result)
 }
 
+/// Commits on HEAD since it diverged from an arbitrary `base_ref`, bypassing
+/// the default-branch detection and cache of [`commits_since_merge_base`].
+///
+/// Returns `Ok(None)` when `base_ref` does not name a commit.
+pub fn commits_since_ref(path: &Path, base_ref: &str) -> Result<Option<CommitsAhead>> {
+    let repo = path
+        .to_str()
+        .context("worktree path contains invalid UTF-8")?;
+    if base_ref.is_empty() || base_ref.starts_with('-') {
+        return Ok(None);
+    }
+    let commit = format!("{base_ref}^{{commit}}");
+    if execute_git(&["-C", repo, "rev-parse", "--verify", "--quiet", &commit]).is_err() {
+        return Ok(None);
+    }
+    commits_ahead_of(repo, base_ref, base_ref.to_string()).map(Some)
+}
+
+fn commits_ahead_of(repo: &str, base_ref: &str, base_branch: String) -> Result<CommitsAhead> {
+    let merge_base = execute_git(&["-C", repo, "merge-base", "HEAD", base_ref])
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let range = if let Some(ref base) = merge_base {
+        format!("{base}..HEAD")
+    } else {
+        format!("{base_ref}..HEAD")
+    };
+
+    let log_output = execute_git(&["-C", repo, "log", COMMIT_RECORD_FORMAT, "--reverse", &range])?;
+
+    let commits = log_output.lines().filter_map(parse_commit_record).collect();
+
+    Ok(CommitsAhead {
+        base_branch,
+        merge_base,
+        commits,
+    })
+}
+
 struct GitNameStatusRecord {
     status: String,
     diff_path: String,
@@ -932,10 +1066,7 @@ struct GitNameStatusRecord {
 }
 
 fn compute_commit_diff_for_repo(repo: &str) -> Option<CommitDiffInfo> {
-    let mut branch_candidates: Vec<String> = Vec::new();
-    if let Some(detected) = detect_default_branch_for_repo(repo) {
-        branch_candidates.push(detected);
-    }
+    let mut branch_candidates = vec![default_branch(Path::new(repo))];
     for fallback in ["main", "master"] {
         if !branch_candidates.iter().any(|b| b == fallback) {
             branch_candidates.push(fallback.to_string());
@@ -1137,6 +1268,53 @@ pub fn collect_worktree_diff_breakdown(path: &Path) -> Result<WorktreeDiffBreakd
     Ok(breakdown)
 }
 
+/// What a single line of a unified diff represents.
+///
+/// Renderers map these to their own styles (terminal colors, CSS classes), so
+/// the parsing lives here once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    /// File-level metadata: `diff --git`, `index`, `---`/`+++`, mode and rename lines.
+    Header,
+    /// A `@@ ... @@` hunk header.
+    Hunk,
+    Added,
+    Removed,
+    /// Unchanged context, including `\ No newline at end of file`.
+    Context,
+}
+
+/// Classify every line of a unified diff.
+///
+/// Lines are read in file-header mode until the first `@@`, so `--- a/x` is a
+/// header there but a removed `-- a/x` line inside a hunk. Text before the
+/// first `diff --git` (such as section titles) is treated as header.
+pub fn classify_diff_lines(diff: &str) -> Vec<(DiffLineKind, &str)> {
+    let mut in_hunk = false;
+    diff.lines()
+        .map(|line| {
+            let kind = if line.starts_with("diff ") {
+                in_hunk = false;
+                DiffLineKind::Header
+            } else if line.starts_with("@@") {
+                in_hunk = true;
+                DiffLineKind::Hunk
+            } else if !in_hunk {
+                DiffLineKind::Header
+            } else if line.starts_with('+') {
+                DiffLineKind::Added
+            } else if line.starts_with('-') {
+                DiffLineKind::Removed
+            } else {
+                DiffLineKind::Context
+            };
+            (kind, line)
+        })
+        .collect()
+}
+
 /// Get a comprehensive git diff for the given worktree path.
 ///
 /// Behavior:
@@ -1199,6 +1377,24 @@ pub fn get_diff_for_path(path: &Path) -> Result<String> {
     Ok(sections.join("\n\n"))
 }
 
+/// Staged changes of the worktree at `path` as one unified diff, suitable for `git apply`.
+pub fn get_staged_diff_for_path(path: &Path) -> Result<String> {
+    let repo = path
+        .to_str()
+        .context("worktree path contains non-UTF8 characters")?;
+    execute_git_allow_code_1(&[
+        "-C",
+        repo,
+        "-c",
+        "core.quotepath=false",
+        "--no-pager",
+        "diff",
+        "--no-ext-diff",
+        "--binary",
+        "--cached",
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1243,25 +1439,11 @@ mod tests {
     }
 
     #[test]
-    fn test_get_default_branch() {
+    fn test_default_branch_of_current_dir() {
         // This test will work based on the actual git repository it's run in
         // We can't make strong assertions about the result since it depends on the repo
-        let result = get_default_branch();
-
-        // Should either succeed with a non-empty string or fail gracefully
-        match result {
-            Ok(branch) => {
-                assert!(!branch.is_empty());
-                // Common default branches
-                assert!(
-                    ["main", "master", "develop"].contains(&branch.as_str()) || !branch.is_empty()
-                );
-            }
-            Err(_) => {
-                // It's okay to fail if we're not in a git repo or no remote
-                // The function should handle this gracefully
-            }
-        }
+        let cwd = std::env::current_dir().unwrap();
+        assert!(!default_branch(&cwd).is_empty());
     }
 
     #[test]
@@ -1300,6 +1482,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_default_branch_fallback_order() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let repo_path = temp.path();
+
+        let run_git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(repo_path)
+                .status()
+                .expect("execute git command");
+            assert!(status.success(), "git {:?} failed", args);
+        };
+
+        run_git(&["init", "-q", "--initial-branch=master"]);
+        run_git(&["config", "user.email", "test@example.com"]);
+        run_git(&["config", "user.name", "Tester"]);
+        run_git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+        assert_eq!(resolve_default_branch(repo_path, "origin"), "master");
+
+        // init.defaultBranch only counts once the branch exists
+        run_git(&["config", "init.defaultBranch", "trunk"]);
+        assert_eq!(resolve_default_branch(repo_path, "origin"), "master");
+        run_git(&["branch", "trunk"]);
+        assert_eq!(resolve_default_branch(repo_path, "origin"), "trunk");
+
+        run_git(&["update-ref", "refs/remotes/origin/develop", "HEAD"]);
+        run_git(&[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/develop",
+        ]);
+        assert_eq!(resolve_default_branch(repo_path, "origin"), "develop");
+        assert_eq!(resolve_default_branch(repo_path, "upstream"), "trunk");
+    }
+
+    #[test]
+    fn test_classify_diff_lines_tracks_hunk_boundaries() {
+        let diff = "diff --git a/a.txt b/a.txt\n\
+                    index 1111111..2222222 100644\n\
+                    --- a/a.txt\n\
+                    +++ b/a.txt\n\
+                    @@ -1,3 +1,3 @@\n\
+                    \x20keep\n\
+                    --- removed dashes\n\
+                    +added\n\
+                    \\ No newline at end of file\n\
+                    diff --git a/b.txt b/b.txt\n\
+                    --- a/b.txt\n";
+        let kinds: Vec<DiffLineKind> = classify_diff_lines(diff)
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect();
+        use DiffLineKind::*;
+        assert_eq!(
+            kinds,
+            [
+                Header, Header, Header, Header, Hunk, Context, Removed, Added, Context, Header,
+                Header
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_staged_diff_for_path_skips_unstaged_changes() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let repo_path = temp.path();
+
+        let run_git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(repo_path)
+                .status()
+                .expect("execute git command");
+            assert!(status.success(), "git {:?} failed", args);
+        };
+
+        run_git(&["init", "--initial-branch=main"]);
+        run_git(&["config", "user.email", "test@example.com"]);
+        run_git(&["config", "user.name", "Tester"]);
+        fs::write(repo_path.join("note.txt"), "line1\n").expect("write file");
+        run_git(&["add", "note.txt"]);
+        run_git(&["commit", "-m", "initial"]);
+
+        fs::write(repo_path.join("note.txt"), "line1\nstaged\n").expect("stage change");
+        run_git(&["add", "note.txt"]);
+        fs::write(repo_path.join("note.txt"), "line1\nstaged\nunstaged\n")
+            .expect("unstaged change");
+
+        let diff = get_staged_diff_for_path(repo_path).expect("collect staged diff");
+        assert!(diff.starts_with("diff --git a/note.txt b/note.txt"));
+        assert!(diff.contains("+staged"));
+        assert!(!diff.contains("+unstaged"));
+    }
+
     #[test]
     fn test_commits_since_merge_base_lists_feature_commits() {
         use std::fs;
@@ -1345,6 +1624,75 @@ mod tests {
         assert_eq!(info.commits[0].summary, "feature change 1");
         assert_eq!(info.commits[1].summary, "feature change 2");
         assert!(info.commits[0].commit_id.len() >= 7);
+        assert_eq!(info.commits[0].author.as_deref(), Some("Tester"));
+        assert_eq!(
+            info.commits[0].author_email.as_deref(),
+            Some("test@example.com")
+        );
+    }
+
+    #[test]
+    fn test_commits_since_ref_uses_requested_base() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let repo_path = temp.path();
+
+        let run_git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(repo_path)
+                .status()
+                .expect("execute git command");
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        let commit = |message: &str| {
+            fs::write(repo_path.join("note.txt"), message).expect("write file");
+            run_git(&["add", "note.txt"]);
+            run_git(&["commit", "-m", message]);
+        };
+
+        run_git(&["init", "--initial-branch=main"]);
+        run_git(&["config", "user.email", "test@example.com"]);
+        run_git(&["config", "user.name", "Tester"]);
+        commit("initial");
+        run_git(&["checkout", "-b", "integration"]);
+        commit("integration change");
+        run_git(&["checkout", "-b", "feature"]);
+        commit("feature change");
+
+        let info = commits_since_ref(repo_path, "integration")
+            .expect("compute commits ahead")
+            .expect("integration exists");
+        assert_eq!(info.base_branch, "integration");
+        let summaries: Vec<_> = info.commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["feature change"]);
+
+        let info = commits_since_ref(repo_path, "main")
+            .expect("compute commits ahead")
+            .expect("main exists");
+        assert_eq!(info.commits.len(), 2);
+
+        assert!(commits_since_ref(repo_path, "missing").unwrap().is_none());
+        assert!(commits_since_ref(repo_path, "--all").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_commit_record_keeps_delimiters_in_summary() {
+        let info = parse_commit_record(
+            "abc123\u{0}1700000000\u{0}Ada\u{0}ada@example.com\u{0}fix:\ta\u{0}b",
+        )
+        .expect("parsed record");
+        assert_eq!(info.commit_id, "abc123");
+        assert_eq!(info.author.as_deref(), Some("Ada"));
+        assert_eq!(info.author_email.as_deref(), Some("ada@example.com"));
+        assert_eq!(info.summary, "fix:\ta\u{0}b");
+        assert!(info.timestamp.is_some());
+
+        // Missing trailing fields are tolerated
+        let info = parse_commit_record("abc123\u{0}1700000000").expect("parsed record");
+        assert_eq!(info.author, None);
+        assert_eq!(info.summary, "");
     }
 
     #[test]
@@ -1383,4 +1731,62 @@ mod tests {
             info.commits
         );
     }
+
+    #[test]
+    fn test_detached_worktree_status() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let repo_path = temp.path().join("repo");
+        let detached_path = temp.path().join("detached");
+        fs::create_dir(&repo_path).expect("create repo dir");
+
+        let run_git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .status()
+                .expect("execute git command");
+            assert!(status.success(), "git {:?} failed", args);
+        };
+
+        run_git(&["init", "--initial-branch=main"]);
+        run_git(&["config", "user.email", "test@example.com"]);
+        run_git(&["config", "user.name", "Tester"]);
+
+        fs::write(repo_path.join("note.txt"), "base\n").expect("write base file");
+        run_git(&["add", "note.txt"]);
+        run_git(&["commit", "-m", "initial"]);
+        run_git(&[
+            "worktree",
+            "add",
+            "--detach",
+            detached_path.to_str().unwrap(),
+            "HEAD",
+        ]);
+
+        let head = execute_git(&["-C", repo_path.to_str().unwrap(), "rev-parse", "HEAD"])
+            .expect("read HEAD");
+        let status =
+            summarize_worktree_status(&detached_path, "stale-branch").expect("summarize status");
+
+        assert!(status.detached);
+        assert_eq!(status.branch, None);
+        assert_eq!(status.head.as_deref(), Some(&head[..SHORT_OID_LEN]));
+        assert_eq!(
+            status.branch_label(),
+            format!("(detached @{})", &head[..SHORT_OID_LEN])
+        );
+        assert!(status.is_clean);
+
+        let info = commits_since_merge_base(&detached_path)
+            .expect("compute commits ahead")
+            .expect("expected comparison data");
+        assert_eq!(info.base_branch, "main");
+        assert!(info.commits.is_empty());
+
+        let attached = summarize_worktree_status(&repo_path, "unused").expect("summarize status");
+        assert!(!attached.detached);
+        assert_eq!(attached.branch.as_deref(), Some("main"));
+    }
 }
@@ -1,3 +1,6 @@
+use agentdev::picker::{self, PickerRow};
+use agentdev::state::{WorktreeInfo, XlaudeState};
+use agentdev::tmux::TmuxManager;
 use anyhow::Result;
 use atty::Stream;
 use dialoguer::{Confirm, Select};
@@ -83,29 +86,28 @@ pub fn smart_confirm(prompt: &str, default: bool) -> Result<bool> {
         .map_err(Into::into)
 }
 
-/// Smart selection that supports piped input
-pub fn smart_select<T>(
+/// Pick a managed worktree, listing the current repository's first.
+///
+/// Piped input selects by index or `repo/name`. Interactive terminals get the
+/// fuzzy picker, falling back to a plain list when it can't start. Returns
+/// `None` in non-interactive mode so callers can ask for an explicit name.
+pub fn select_worktree(
     prompt: &str,
-    items: &[T],
-    display_fn: impl Fn(&T) -> String,
-) -> Result<Option<usize>>
-where
-    T: Clone,
-{
+    state: &XlaudeState,
+) -> Result<Option<(String, WorktreeInfo)>> {
+    let worktrees = state.prioritized_worktree_list();
+    let label = |info: &WorktreeInfo| format!("{}/{}", info.repo_name, info.name);
+
     // 1. Check for piped input
     if let Some(input) = read_piped_line()? {
-        // Try to parse as index
         if let Ok(index) = input.parse::<usize>()
-            && index < items.len()
+            && index < worktrees.len()
         {
-            return Ok(Some(index));
+            return Ok(Some(worktrees[index].clone()));
         }
 
-        // Try to match display text
-        for (i, item) in items.iter().enumerate() {
-            if display_fn(item) == input {
-                return Ok(Some(i));
-            }
+        if let Some(entry) = worktrees.iter().find(|(_, info)| label(info) == input) {
+            return Ok(Some(entry.clone()));
         }
 
         anyhow::bail!("Invalid selection: {}", input);
@@ -116,14 +118,49 @@ where
         return Ok(None);
     }
 
-    // 3. Interactive selection
-    let display_items: Vec<String> = items.iter().map(display_fn).collect();
-    let selection = Select::new()
-        .with_prompt(prompt)
-        .items(&display_items)
-        .interact()?;
+    // 3. Fuzzy picker, or the plain list when the terminal can't host it
+    let tmux = TmuxManager::new();
+    let rows: Vec<PickerRow> = worktrees
+        .iter()
+        .map(|(_, info)| {
+            PickerRow::new(vec![
+                label(info),
+                info.branch.clone(),
+                worktree_status(info, &tmux),
+            ])
+        })
+        .collect();
+    let selection = match picker::fuzzy_select(prompt, &rows) {
+        Ok(Some(index)) => index,
+        Ok(None) => anyhow::bail!("Selection cancelled"),
+        Err(_) => {
+            let labels: Vec<String> = worktrees.iter().map(|(_, info)| label(info)).collect();
+            Select::new()
+                .with_prompt(prompt)
+                .items(&labels)
+                .interact()?
+        }
+    };
+
+    Ok(Some(worktrees[selection].clone()))
+}
 
-    Ok(Some(selection))
+fn worktree_status(info: &WorktreeInfo, tmux: &TmuxManager) -> String {
+    if !info.path.exists() {
+        return "missing".to_string();
+    }
+    let mut status = vec![if tmux.session_exists(&info.name) {
+        "running"
+    } else {
+        "idle"
+    }];
+    if info.locked {
+        status.push("locked");
+    }
+    if info.pinned {
+        status.push("pinned");
+    }
+    status.join(", ")
 }
 
 /// Get command argument with pipe input support
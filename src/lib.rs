@@ -1,12 +1,18 @@
 // Public modules for agentdev library
+pub mod audit;
 pub mod claude;
 pub mod claude_status;
+pub mod clipboard;
 pub mod config;
+pub mod conflict_detector;
 pub mod discovery;
+pub mod disk_usage;
 pub mod git;
+pub mod picker;
 pub mod process_registry;
 pub mod sessions;
 pub mod state;
+pub mod tasks;
 pub mod tmux;
 pub mod utils;
 pub mod web;
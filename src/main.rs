@@ -1,16 +1,26 @@
+use agentdev::audit::AuditAction;
 use agentdev::load_agent_config;
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
+use std::path::PathBuf;
+use std::time::Duration;
 
 mod commands;
 mod completions;
 mod input;
 
 use commands::{
-    MergeStrategy, handle_add, handle_clean, handle_create, handle_delete, handle_dir,
-    handle_discovery, handle_exec, handle_list, handle_merge, handle_open, handle_rename,
-    handle_sessions_list, handle_ui,
+    DirFormat, ListOutput, MergeStrategy, PreMergePrompt, SessionsFormat, SizeMode, SubmoduleMode,
+    TemplateOptions, handle_add, handle_adopt, handle_audit_list, handle_checkpoint, handle_clean,
+    handle_clean_branches, handle_config_edit, handle_config_show, handle_copy_branch_name,
+    handle_cp, handle_create, handle_delete, handle_dir, handle_discovery, handle_doctor,
+    handle_exec, handle_fork, handle_gc, handle_init, handle_link, handle_list, handle_lock,
+    handle_log, handle_merge, handle_note, handle_open, handle_pin, handle_reap, handle_rename,
+    handle_reset, handle_send, handle_sessions_delete, handle_sessions_export,
+    handle_sessions_list, handle_sessions_prune, handle_shell, handle_snapshot,
+    handle_tasks_delete, handle_tasks_list, handle_tasks_merge, handle_ui, handle_unlink,
+    handle_unlock, handle_unpin, handle_watch, handle_worktree_status, resolve_strategy,
 };
 
 #[derive(Parser)]
@@ -43,6 +53,38 @@ enum Commands {
         #[command(subcommand)]
         cmd: SessionCommands,
     },
+    /// Multi-agent task commands
+    Tasks {
+        #[command(subcommand)]
+        cmd: TaskCommands,
+    },
+    /// Inspect or edit the agentdev config file
+    Config {
+        #[command(subcommand)]
+        cmd: ConfigCommands,
+    },
+    /// Inspect the audit log of worktree create/delete/merge events
+    Audit {
+        #[command(subcommand)]
+        cmd: AuditCommands,
+    },
+    /// Check git, tmux, the agent command, state and config for common problems
+    Doctor,
+    /// Set up the current repository for agentdev and show how to get started
+    Init {
+        /// Accept every default without prompting
+        #[arg(long)]
+        non_interactive: bool,
+    },
+    /// Kill agentdev tmux sessions that have been idle for too long
+    Reap {
+        /// Hours without activity before a session is reaped
+        #[arg(long, default_value_t = 72)]
+        idle_hours: u64,
+        /// List the idle sessions without killing them
+        #[arg(long)]
+        dry_run: bool,
+    },
     // Backward-compatible top-level commands (temporarily retained)
     #[command(hide = true)]
     Create {
@@ -51,6 +93,12 @@ enum Commands {
         /// Agent command to use (overrides global config)
         #[arg(long)]
         agent: Option<String>,
+        /// Always initialize and update submodules, failing if the update fails
+        #[arg(long, conflicts_with = "no_submodules")]
+        submodules: bool,
+        /// Skip submodule initialization in the new worktree
+        #[arg(long)]
+        no_submodules: bool,
     },
     #[command(hide = true)]
     Open {
@@ -59,11 +107,26 @@ enum Commands {
         /// Agent command to use (overrides global config)
         #[arg(long)]
         agent: Option<String>,
+        /// Start a new agent session without asking when none is running
+        #[arg(long)]
+        yes: bool,
+        /// Replace the tmux session with a fresh agent even if one is running
+        #[arg(long)]
+        recreate: bool,
+        /// Type this prompt into the agent instead of attaching the terminal
+        #[arg(long, conflicts_with = "send_file")]
+        send: Option<String>,
+        /// Like --send, reading the prompt from a file
+        #[arg(long, value_name = "PATH")]
+        send_file: Option<PathBuf>,
     },
     #[command(hide = true, alias = "rm")]
     Delete {
         /// Name of the worktree to delete (current if not provided)
         name: Option<String>,
+        /// Delete even if the worktree is locked
+        #[arg(long)]
+        force: bool,
     },
     #[command(hide = true)]
     Add {
@@ -79,9 +142,34 @@ enum Commands {
     },
     #[command(hide = true, alias = "ls")]
     List {
-        /// Output as JSON
-        #[arg(long)]
+        /// Output as JSON (same as --output json)
+        #[arg(long, conflicts_with = "output")]
         json: bool,
+        /// One JSON object per line, streamed as each worktree is inspected
+        /// (same as --output ndjson)
+        #[arg(long, conflicts_with_all = ["output", "json"])]
+        ndjson: bool,
+        /// Stable tab-separated output for scripts (same as --output porcelain)
+        #[arg(long, conflicts_with_all = ["output", "json", "ndjson"])]
+        porcelain: bool,
+        /// Output format
+        #[arg(long, value_enum)]
+        output: Option<ListOutput>,
+        /// Report the disk usage of each worktree
+        #[arg(long)]
+        size: bool,
+        /// Leave gitignored files (e.g. build output) out of --size
+        #[arg(long, requires = "size")]
+        exclude_ignored: bool,
+        /// Reprint the list every N seconds (default 2) until interrupted
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        watch: Option<u64>,
     },
     #[command(hide = true)]
     Clean,
@@ -89,12 +177,21 @@ enum Commands {
     Dir {
         /// Name of the worktree (interactive selection if not provided)
         name: Option<String>,
+        /// Print the path bare or as a shell snippet to `eval`
+        #[arg(long, value_enum, default_value_t = DirFormat::Plain)]
+        format: DirFormat,
     },
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for
         #[arg(value_enum)]
         shell: Shell,
+        /// Write the script to this file instead of stdout, creating parent directories
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Command name to complete, if the binary was installed under another name
+        #[arg(long, value_name = "NAME")]
+        bin_name: Option<String>,
     },
     /// Output worktree info for shell completions (hidden)
     #[command(hide = true)]
@@ -125,15 +222,87 @@ fn main() -> Result<()> {
 
     match cli.command {
         Commands::Worktree { cmd } => match cmd {
-            WorktreeCommands::Create { name, agent } => handle_create(name, agent),
-            WorktreeCommands::Open { name, agent } => handle_open(name, agent),
-            WorktreeCommands::Delete { name } => handle_delete(name),
+            WorktreeCommands::Create {
+                name,
+                agent,
+                submodules,
+                no_submodules,
+                template,
+                force_template,
+                from,
+            } => handle_create(
+                name,
+                agent,
+                SubmoduleMode::from_flags(submodules, no_submodules),
+                template.map(|name| TemplateOptions {
+                    name,
+                    force: force_template,
+                }),
+                from,
+            ),
+            WorktreeCommands::Open {
+                name,
+                agent,
+                yes,
+                recreate,
+                send,
+                send_file,
+            } => handle_open(name, agent, yes, recreate, send, send_file),
+            WorktreeCommands::Delete { name, force } => handle_delete(name, force),
             WorktreeCommands::Add { name } => handle_add(name),
+            WorktreeCommands::Adopt { path, name } => handle_adopt(path, name),
+            WorktreeCommands::Fork { source, new_name } => handle_fork(source, new_name),
             WorktreeCommands::Rename { old_name, new_name } => handle_rename(old_name, new_name),
-            WorktreeCommands::List { json } => handle_list(json),
-            WorktreeCommands::Clean => handle_clean(),
-            WorktreeCommands::Dir { name } => handle_dir(name),
+            WorktreeCommands::List {
+                json,
+                ndjson,
+                porcelain,
+                output,
+                size,
+                exclude_ignored,
+                watch,
+            } => handle_list(
+                ListOutput::from_flags(json, ndjson, porcelain, output),
+                SizeMode::from_flags(size, exclude_ignored),
+                watch,
+            ),
+            WorktreeCommands::Clean { repair } => handle_clean(repair),
+            WorktreeCommands::CleanBranches { confirm } => handle_clean_branches(confirm),
+            WorktreeCommands::Gc { auto } => handle_gc(auto),
+            WorktreeCommands::Dir { name, format } => handle_dir(name, format),
+            WorktreeCommands::CopyBranchName { name, clipboard } => {
+                handle_copy_branch_name(name, clipboard)
+            }
+            WorktreeCommands::Lock { name, reason } => handle_lock(name, reason),
+            WorktreeCommands::Unlock { name } => handle_unlock(name),
+            WorktreeCommands::Note { name, text, set } => handle_note(name, text, set),
+            WorktreeCommands::Pin { name } => handle_pin(name),
+            WorktreeCommands::Link { name, symlink_path } => handle_link(name, symlink_path),
+            WorktreeCommands::Unlink { name } => handle_unlink(name),
+            WorktreeCommands::Unpin { name } => handle_unpin(name),
+            WorktreeCommands::Status { json } => handle_worktree_status(json),
+            WorktreeCommands::Log {
+                name,
+                all,
+                since,
+                author,
+            } => handle_log(name, all, since, author),
+            WorktreeCommands::Reset { name, yes } => handle_reset(name, yes),
+            WorktreeCommands::Snapshot { name, push } => handle_snapshot(name, push),
+            WorktreeCommands::Checkpoint { name, message } => handle_checkpoint(name, message),
+            WorktreeCommands::Send {
+                name,
+                message,
+                no_enter,
+            } => handle_send(name, message, no_enter),
+            WorktreeCommands::Watch { name, lines } => handle_watch(name, lines),
+            WorktreeCommands::Cp {
+                source,
+                dest,
+                staged_only,
+            } => handle_cp(source, dest, staged_only),
             WorktreeCommands::Exec { worktree, command } => handle_exec(worktree, command),
+            WorktreeCommands::Shell { worktree } => handle_shell(worktree),
             WorktreeCommands::Discovery { recursive, json } => handle_discovery(recursive, json),
             WorktreeCommands::Merge {
                 name,
@@ -141,27 +310,144 @@ fn main() -> Result<()> {
                 cleanup,
                 strategy,
                 squash,
-            } => handle_merge(name, push, cleanup, strategy, squash),
+                remote,
+                set_upstream,
+                pre_merge_prompt,
+                timeout,
+                quiet,
+            } => handle_merge(
+                name,
+                push,
+                cleanup,
+                resolve_strategy(strategy, squash)?,
+                remote,
+                set_upstream,
+                pre_merge_prompt.map(|text| PreMergePrompt {
+                    text,
+                    timeout: Duration::from_secs(timeout),
+                }),
+                quiet,
+            ),
         },
         Commands::Sessions { cmd } => match cmd {
             SessionCommands::List {
                 worktree,
                 all,
                 json,
-            } => handle_sessions_list(worktree, all, json),
+                format,
+                ndjson,
+                watch,
+                diagnose,
+                dedup,
+            } => {
+                if json {
+                    eprintln!("⚠️  --json is deprecated; use --format json");
+                }
+                handle_sessions_list(
+                    worktree,
+                    all,
+                    SessionsFormat::from_flags(json, format),
+                    ndjson,
+                    watch,
+                    diagnose,
+                    dedup,
+                )
+            }
+            SessionCommands::Prune {
+                older_than,
+                provider,
+                yes,
+            } => handle_sessions_prune(older_than, provider, yes),
+            SessionCommands::Delete {
+                provider,
+                session_id,
+                yes,
+            } => handle_sessions_delete(provider, session_id, yes),
+            SessionCommands::Export {
+                output_dir,
+                provider,
+                since,
+            } => handle_sessions_export(output_dir, provider, since),
+        },
+        Commands::Tasks { cmd } => match cmd {
+            TaskCommands::List { all, json } => handle_tasks_list(all, json),
+            TaskCommands::Delete {
+                task,
+                merged_only,
+                dry_run,
+                force,
+            } => handle_tasks_delete(task, merged_only, dry_run, force),
+            TaskCommands::Merge {
+                task,
+                strategy,
+                remote,
+                cleanup,
+            } => handle_tasks_merge(task, strategy, remote, cleanup),
+        },
+        Commands::Config { cmd } => match cmd {
+            ConfigCommands::Show { json } => handle_config_show(json),
+            ConfigCommands::Edit => handle_config_edit(),
         },
-        Commands::Completions { shell } => completions::handle_completions(shell),
+        Commands::Audit { cmd } => match cmd {
+            AuditCommands::List {
+                limit,
+                action,
+                repo,
+                json,
+            } => handle_audit_list(limit, action, repo, json),
+        },
+        Commands::Doctor => handle_doctor(),
+        Commands::Init { non_interactive } => handle_init(non_interactive),
+        Commands::Reap {
+            idle_hours,
+            dry_run,
+        } => handle_reap(idle_hours, dry_run),
+        Commands::Completions {
+            shell,
+            output,
+            bin_name,
+        } => completions::handle_completions(shell, output, bin_name),
         Commands::CompleteWorktrees { format } => commands::handle_complete_worktrees(&format),
         Commands::Ui { port, host, headless } => handle_ui(port, host, headless),
         // Backward-compatible routing
-        Commands::Create { name, agent } => handle_create(name, agent),
-        Commands::Open { name, agent } => handle_open(name, agent),
-        Commands::Delete { name } => handle_delete(name),
+        Commands::Create {
+            name,
+            agent,
+            submodules,
+            no_submodules,
+        } => handle_create(
+            name,
+            agent,
+            SubmoduleMode::from_flags(submodules, no_submodules),
+            None,
+            None,
+        ),
+        Commands::Open {
+            name,
+            agent,
+            yes,
+            recreate,
+            send,
+            send_file,
+        } => handle_open(name, agent, yes, recreate, send, send_file),
+        Commands::Delete { name, force } => handle_delete(name, force),
         Commands::Add { name } => handle_add(name),
         Commands::Rename { old_name, new_name } => handle_rename(old_name, new_name),
-        Commands::List { json } => handle_list(json),
-        Commands::Clean => handle_clean(),
-        Commands::Dir { name } => handle_dir(name),
+        Commands::List {
+            json,
+            ndjson,
+            porcelain,
+            output,
+            size,
+            exclude_ignored,
+            watch,
+        } => handle_list(
+            ListOutput::from_flags(json, ndjson, porcelain, output),
+            SizeMode::from_flags(size, exclude_ignored),
+            watch,
+        ),
+        Commands::Clean => handle_clean(false),
+        Commands::Dir { name, format } => handle_dir(name, format),
     }
 }
 
@@ -174,6 +460,21 @@ enum WorktreeCommands {
         /// Agent command to use (overrides global config)
         #[arg(long)]
         agent: Option<String>,
+        /// Always initialize and update submodules, failing if the update fails
+        #[arg(long, conflicts_with = "no_submodules")]
+        submodules: bool,
+        /// Skip submodule initialization in the new worktree
+        #[arg(long)]
+        no_submodules: bool,
+        /// Copy the files of a template from [templates] or [worktree.templates] into the new worktree
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+        /// Let --template overwrite files tracked by git
+        #[arg(long, requires = "template")]
+        force_template: bool,
+        /// Branch off this ref (tag, branch or commit) instead of the current HEAD
+        #[arg(long, value_name = "REF")]
+        from: Option<String>,
     },
     /// Open an existing worktree and launch Claude
     Open {
@@ -182,18 +483,47 @@ enum WorktreeCommands {
         /// Agent command to use (overrides global config)
         #[arg(long)]
         agent: Option<String>,
+        /// Start a new agent session without asking when none is running
+        #[arg(long)]
+        yes: bool,
+        /// Replace the tmux session with a fresh agent even if one is running
+        #[arg(long)]
+        recreate: bool,
+        /// Type this prompt into the agent instead of attaching the terminal
+        #[arg(long, conflicts_with = "send_file")]
+        send: Option<String>,
+        /// Like --send, reading the prompt from a file
+        #[arg(long, value_name = "PATH")]
+        send_file: Option<PathBuf>,
     },
     /// Delete a worktree and clean up
     #[command(alias = "rm")]
     Delete {
         /// Name of the worktree to delete (current if not provided)
         name: Option<String>,
+        /// Delete even if the worktree is locked
+        #[arg(long)]
+        force: bool,
     },
     /// Add current worktree to xlaude management
     Add {
         /// Name for the worktree (defaults to current branch name)
         name: Option<String>,
     },
+    /// Add the worktree at PATH to management without changing into it
+    Adopt {
+        /// Root directory of the worktree
+        path: PathBuf,
+        /// Name for the worktree (defaults to its branch name)
+        name: Option<String>,
+    },
+    /// Create a worktree on a new branch at another worktree's current HEAD
+    Fork {
+        /// Worktree to fork from
+        source: String,
+        /// Name for the new worktree and branch
+        new_name: String,
+    },
     /// Rename a worktree
     Rename {
         /// Current name of the worktree
@@ -204,16 +534,185 @@ enum WorktreeCommands {
     /// List all active instances
     #[command(alias = "ls")]
     List {
-        /// Output as JSON
-        #[arg(long)]
+        /// Output as JSON (same as --output json)
+        #[arg(long, conflicts_with = "output")]
         json: bool,
+        /// One JSON object per line, streamed as each worktree is inspected
+        /// (same as --output ndjson)
+        #[arg(long, conflicts_with_all = ["output", "json"])]
+        ndjson: bool,
+        /// Stable tab-separated output for scripts (same as --output porcelain)
+        #[arg(long, conflicts_with_all = ["output", "json", "ndjson"])]
+        porcelain: bool,
+        /// Output format
+        #[arg(long, value_enum)]
+        output: Option<ListOutput>,
+        /// Report the disk usage of each worktree
+        #[arg(long)]
+        size: bool,
+        /// Leave gitignored files (e.g. build output) out of --size
+        #[arg(long, requires = "size")]
+        exclude_ignored: bool,
+        /// Reprint the list every N seconds (default 2) until interrupted
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        watch: Option<u64>,
     },
     /// Clean up invalid worktrees from state
-    Clean,
+    Clean {
+        /// Re-link worktrees with a broken gitdir (e.g. after moving the main repo) instead of dropping them
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Delete remote branches already merged into the default branch
+    CleanBranches {
+        /// Actually delete the branches instead of printing a dry run
+        #[arg(long)]
+        confirm: bool,
+    },
+    /// Remove worktrees whose branches are merged (or squash-merged) into the default branch
+    Gc {
+        /// Remove the candidates without asking
+        #[arg(long)]
+        auto: bool,
+    },
     /// Get the directory path of a worktree
     Dir {
         /// Name of the worktree (interactive selection if not provided)
         name: Option<String>,
+        /// Print the path bare or as a shell snippet to `eval`
+        #[arg(long, value_enum, default_value_t = DirFormat::Plain)]
+        format: DirFormat,
+    },
+    /// Print the branch name of a worktree
+    CopyBranchName {
+        /// Name of the worktree (current if not provided)
+        name: Option<String>,
+        /// Also copy the branch name to the system clipboard
+        #[arg(long)]
+        clipboard: bool,
+    },
+    /// Lock a worktree to protect it from pruning
+    Lock {
+        /// Name of the worktree to lock (current if not provided)
+        name: Option<String>,
+        /// Explanation recorded with the lock
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Unlock a previously locked worktree
+    Unlock {
+        /// Name of the worktree to unlock (current if not provided)
+        name: Option<String>,
+    },
+    /// Add a free-text note to a worktree
+    Note {
+        /// Name of the worktree to annotate
+        name: String,
+        /// Text to append as a new line
+        text: String,
+        /// Replace the existing notes instead of appending (empty text clears them)
+        #[arg(long)]
+        set: bool,
+    },
+    /// Pin a worktree so it is listed first
+    Pin {
+        /// Name of the worktree to pin (current if not provided)
+        name: Option<String>,
+    },
+    /// Unpin a previously pinned worktree
+    Unpin {
+        /// Name of the worktree to unpin (current if not provided)
+        name: Option<String>,
+    },
+    /// Symlink a worktree to a fixed path, e.g. for editor integrations
+    Link {
+        /// Name of the worktree
+        name: String,
+        /// Where to create the symlink; an existing symlink there is replaced
+        symlink_path: PathBuf,
+    },
+    /// Remove the symlink created by `worktree link`
+    Unlink {
+        /// Name of the worktree
+        name: String,
+    },
+    /// Summarize the git status of every managed worktree
+    Status {
+        /// Output as a JSON array
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the commit graph of a worktree
+    Log {
+        /// Name of the worktree (current if not provided)
+        name: Option<String>,
+        /// Include commits from all refs, not just the worktree's HEAD
+        #[arg(long)]
+        all: bool,
+        /// Only show commits newer than this date (anything `git log --since` accepts)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Only show commits whose author matches this pattern
+        #[arg(long, value_name = "PATTERN")]
+        author: Option<String>,
+    },
+    /// Discard all uncommitted changes (git reset --hard + git clean -fd)
+    Reset {
+        /// Name of the worktree to reset (current if not provided)
+        name: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Commit all pending changes as a "WIP: snapshot" commit
+    Snapshot {
+        /// Name of the worktree to snapshot (current if not provided)
+        name: Option<String>,
+        /// Push the snapshot commit to origin afterwards
+        #[arg(long)]
+        push: bool,
+    },
+    /// Tag HEAD with an annotated checkpoint-<timestamp> tag marking a milestone
+    Checkpoint {
+        /// Name of the worktree to checkpoint (current if not provided)
+        name: Option<String>,
+        /// Tag message describing the milestone
+        #[arg(long, short = 'm')]
+        message: String,
+    },
+    /// Type a message into a worktree's agent session
+    Send {
+        /// Name of the worktree whose tmux session receives the message
+        name: String,
+        /// Text to type into the session
+        message: String,
+        /// Type the message without pressing Enter
+        #[arg(long)]
+        no_enter: bool,
+    },
+    /// Follow a worktree's agent output like `tail -f`
+    Watch {
+        /// Name of the worktree (current if not provided)
+        name: Option<String>,
+        /// Pane lines captured per poll (default: `[dashboard] preview_capture_lines`, else 200)
+        #[arg(long)]
+        lines: Option<usize>,
+    },
+    /// Copy uncommitted changes from one worktree to another
+    Cp {
+        /// Worktree to copy changes from
+        source: String,
+        /// Worktree to apply the changes to
+        dest: String,
+        /// Copy only changes staged in the source worktree
+        #[arg(long)]
+        staged_only: bool,
     },
     /// Execute a command inside a worktree
     Exec {
@@ -224,6 +723,11 @@ enum WorktreeCommands {
         #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
         command: Vec<String>,
     },
+    /// Open an interactive login shell inside a worktree
+    Shell {
+        /// Name of the worktree (current or interactive selection if omitted)
+        worktree: Option<String>,
+    },
     /// Discover unmanaged git worktrees
     Discovery {
         /// Recursively search subdirectories for git repositories
@@ -249,6 +753,26 @@ enum WorktreeCommands {
         /// Shortcut for --strategy squash
         #[arg(long)]
         squash: bool,
+        /// Remote to fetch the default branch from and push it to
+        #[arg(long, default_value = "origin")]
+        remote: String,
+        /// Make the default branch track the remote after pushing
+        #[arg(long, requires = "push")]
+        set_upstream: bool,
+        /// Send this prompt to the agent session and wait for it to finish before merging
+        #[arg(long, value_name = "TEXT")]
+        pre_merge_prompt: Option<String>,
+        /// Seconds to wait for the agent to finish the pre-merge prompt
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = 600,
+            requires = "pre_merge_prompt"
+        )]
+        timeout: u64,
+        /// Hide the progress spinner (for CI logs)
+        #[arg(long, short = 'q')]
+        quiet: bool,
     },
 }
 
@@ -262,6 +786,131 @@ enum SessionCommands {
         /// Include sessions without a tracked worktree association
         #[arg(long)]
         all: bool,
+        /// Output as JSON (deprecated: use --format json)
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+        /// Output format
+        #[arg(long, value_enum)]
+        format: Option<SessionsFormat>,
+        /// One JSON object per line, streamed as each provider finishes scanning
+        #[arg(long, conflicts_with_all = ["json", "format", "watch"])]
+        ndjson: bool,
+        /// Re-run the scan every N seconds until interrupted
+        #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+        watch: Option<u64>,
+        /// Show where each provider looks for sessions and what it found
+        #[arg(long, conflicts_with_all = ["watch", "worktree", "all", "ndjson"])]
+        diagnose: bool,
+        /// Collapse copies of the same session (e.g. a log and its rolled-up twin)
+        #[arg(long, conflicts_with = "diagnose")]
+        dedup: bool,
+    },
+    /// Delete session files with no activity for a while
+    Prune {
+        /// Only prune sessions whose last activity is at least this many days old
+        #[arg(long, value_name = "DAYS", default_value_t = 30)]
+        older_than: u64,
+        /// Only prune sessions from this provider (claude, codex or kimi)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Delete the files instead of listing what would be removed
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Move a session's log files into the provider's archive folder
+    #[command(alias = "rm")]
+    Delete {
+        /// Session provider (claude, codex or kimi)
+        provider: String,
+        /// Session id as shown by `sessions list`
+        session_id: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Write session transcripts as Markdown, one file per session
+    Export {
+        /// Directory to write `<provider>/<session_id>.md` files into
+        #[arg(long)]
+        output_dir: PathBuf,
+        /// Only export sessions from this provider (claude, codex or kimi)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Only export sessions active since this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TaskCommands {
+    /// List tasks grouped from their member worktrees
+    #[command(alias = "ls")]
+    List {
+        /// Include single worktrees that are not part of a multi-agent task
+        #[arg(long)]
+        all: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Merge a task's worktrees into their base branch one after another,
+    /// stopping at the first conflict
+    Merge {
+        /// Task id or task name
+        task: String,
+        /// Merge strategy; later branches rarely fast-forward once an earlier one is in
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Merge)]
+        strategy: MergeStrategy,
+        /// Remote to fetch the default branch from
+        #[arg(long, default_value = "origin")]
+        remote: String,
+        /// Delete each worktree after its successful merge
+        #[arg(short = 'c', long)]
+        cleanup: bool,
+    },
+    /// Delete the worktrees belonging to a task
+    #[command(alias = "rm")]
+    Delete {
+        /// Task id or task name
+        task: String,
+        /// Only delete members whose branch is fully merged into its base
+        #[arg(long)]
+        merged_only: bool,
+        /// Print the deletion plan without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete members even if their worktree is locked
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the resolved config, marking values that differ from the defaults
+    Show {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Open the config file in $EDITOR and save it only if it parses
+    Edit,
+}
+
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// Show the most recent audit log entries, oldest first
+    List {
+        /// Number of entries to show (0 for all)
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+        /// Only show entries for this action
+        #[arg(long, value_enum)]
+        action: Option<AuditAction>,
+        /// Only show entries for this repository
+        #[arg(long)]
+        repo: Option<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -0,0 +1,291 @@
+//! Full-screen fuzzy picker for interactive selection.
+//!
+//! Rows are filtered as the user types: every query character has to appear
+//! in the row in order, and rows where matches are adjacent or start a word
+//! rank first. Drawing goes to stderr so commands whose stdout is captured
+//! (`cd $(agentdev worktree dir)`) can still prompt.
+
+use std::io::{self, Write};
+
+use anyhow::{Result, bail};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use unicode_width::UnicodeWidthChar;
+
+/// One selectable row, shown as aligned columns.
+#[derive(Clone, Debug)]
+pub struct PickerRow {
+    pub columns: Vec<String>,
+}
+
+impl PickerRow {
+    pub fn new(columns: Vec<String>) -> Self {
+        Self { columns }
+    }
+
+    fn haystack(&self) -> String {
+        self.columns.join(" ")
+    }
+}
+
+/// Score `text` against `query`, or `None` when it doesn't match.
+///
+/// Matching is case-insensitive and ignores whitespace in the query.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for needle in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let index = (next..text.len()).find(|&i| text[i] == needle)?;
+        score += 1;
+        if index > 0 && previous == Some(index - 1) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (index - next).min(3) as i64;
+        previous = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
+
+/// Indices of the rows matching `query`, best match first. Ties keep the
+/// original order.
+pub fn filter_rows(query: &str, rows: &[PickerRow]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i64)> = rows
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row)| fuzzy_score(query, &row.haystack()).map(|s| (index, s)))
+        .collect();
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Let the user pick one of `rows`.
+///
+/// Returns `Ok(None)` when the user cancels with Esc or Ctrl-C, and an error
+/// when the terminal can't host the picker, so callers can fall back to a
+/// plain prompt.
+pub fn fuzzy_select(prompt: &str, rows: &[PickerRow]) -> Result<Option<usize>> {
+    if !atty::is(atty::Stream::Stderr) || !atty::is(atty::Stream::Stdin) {
+        bail!("Fuzzy picker needs an interactive terminal");
+    }
+
+    let widths = column_widths(rows);
+    let _guard = TerminalGuard::enter()?;
+    let mut out = io::stderr();
+    let mut query = String::new();
+    let mut selected = 0;
+
+    loop {
+        let matches = filter_rows(&query, rows);
+        selected = selected.min(matches.len().saturating_sub(1));
+        draw(&mut out, prompt, &query, rows, &matches, selected, &widths)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => {
+                if let Some(&index) = matches.get(selected) {
+                    return Ok(Some(index));
+                }
+            }
+            KeyCode::Up => selected = selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => selected = selected.saturating_sub(1),
+            KeyCode::Down => selected += 1,
+            KeyCode::Char('n') if ctrl => selected += 1,
+            KeyCode::Char('u') if ctrl => {
+                query.clear();
+                selected = 0;
+            }
+            KeyCode::Backspace => {
+                query.pop();
+                selected = 0;
+            }
+            KeyCode::Char(c) if !ctrl => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Raw mode and the alternate screen, restored on drop.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        if let Err(err) = execute!(io::stderr(), terminal::EnterAlternateScreen) {
+            let _ = terminal::disable_raw_mode();
+            return Err(err.into());
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stderr(), terminal::LeaveAlternateScreen, cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn column_widths(rows: &[PickerRow]) -> Vec<usize> {
+    let mut widths = Vec::new();
+    for row in rows {
+        for (index, column) in row.columns.iter().enumerate() {
+            let width = display_width(column);
+            match widths.get_mut(index) {
+                Some(current) if *current < width => *current = width,
+                Some(_) => {}
+                None => widths.push(width),
+            }
+        }
+    }
+    widths
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+fn format_row(row: &PickerRow, widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (index, column) in row.columns.iter().enumerate() {
+        if index > 0 {
+            line.push_str("  ");
+        }
+        line.push_str(column);
+        if index + 1 < row.columns.len() {
+            let pad = widths[index].saturating_sub(display_width(column));
+            line.push_str(&" ".repeat(pad));
+        }
+    }
+    line
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    let mut width = 0;
+    text.chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width <= max_width
+        })
+        .collect()
+}
+
+fn draw(
+    out: &mut impl Write,
+    prompt: &str,
+    query: &str,
+    rows: &[PickerRow],
+    matches: &[usize],
+    selected: usize,
+    widths: &[usize],
+) -> io::Result<()> {
+    let (cols, height) = terminal::size()?;
+    let cols = cols as usize;
+    let visible = (height as usize).saturating_sub(2).max(1);
+    let offset = (selected + 1).saturating_sub(visible);
+
+    queue!(
+        out,
+        cursor::MoveTo(0, 1),
+        terminal::Clear(ClearType::FromCursorDown)
+    )?;
+    queue!(
+        out,
+        SetAttribute(Attribute::Dim),
+        Print(truncate(
+            &format!(
+                "  {}/{}  ↑/↓ to move, Enter to select, Esc to cancel",
+                matches.len(),
+                rows.len()
+            ),
+            cols
+        )),
+        SetAttribute(Attribute::Reset)
+    )?;
+    for (line, &index) in matches.iter().skip(offset).take(visible).enumerate() {
+        let text = truncate(&format_row(&rows[index], widths), cols.saturating_sub(2));
+        queue!(out, cursor::MoveTo(0, line as u16 + 2))?;
+        if offset + line == selected {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reverse),
+                Print(format!("> {text}")),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(out, Print(format!("  {text}")))?;
+        }
+    }
+
+    let header = truncate(&format!("{prompt}: {query}"), cols.saturating_sub(1));
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(ClearType::CurrentLine),
+        Print(&header),
+        cursor::Show
+    )?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(labels: &[&str]) -> Vec<PickerRow> {
+        labels
+            .iter()
+            .map(|label| PickerRow::new(vec![label.to_string()]))
+            .collect()
+    }
+
+    #[test]
+    fn score_requires_query_characters_in_order() {
+        assert!(fuzzy_score("fx", "repo/feature-x").is_some());
+        assert!(fuzzy_score("FX", "repo/feature-x").is_some());
+        assert!(fuzzy_score("xf", "repo/feature-x").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn filter_ranks_contiguous_and_word_start_matches_first() {
+        let rows = rows(&["app/refactor-auth", "app/fix-login", "web/auth"]);
+        assert_eq!(filter_rows("auth", &rows), vec![2, 0]);
+        assert_eq!(filter_rows("fl", &rows), vec![1]);
+        assert_eq!(filter_rows("", &rows), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn rows_are_padded_to_column_widths() {
+        let rows = vec![
+            PickerRow::new(vec!["a/x".into(), "main".into(), "idle".into()]),
+            PickerRow::new(vec!["repo/long".into(), "b".into(), "running".into()]),
+        ];
+        let widths = column_widths(&rows);
+        assert_eq!(format_row(&rows[0], &widths), "a/x        main  idle");
+        assert_eq!(truncate("héllo", 3), "hél");
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -12,6 +13,8 @@ use crate::state::{XlaudeState, get_config_dir};
 
 const REGISTRY_FILENAME: &str = "processes.json";
 pub const MAX_PROCESSES_PER_WORKTREE: usize = 25;
+/// Finished processes older than this are dropped after each web-launched command.
+pub const FINISHED_PROCESS_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -47,6 +50,9 @@ pub struct ProcessRecord {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stderr: Option<String>,
     pub updated_at: DateTime<Utc>,
+    /// Process this one retries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
 }
 
 impl ProcessRecord {
@@ -76,9 +82,31 @@ impl ProcessRecord {
             stdout: None,
             stderr: None,
             updated_at: started_at,
+            parent_id: None,
         }
     }
 
+    /// A pending copy of this process (same command, cwd and description)
+    /// recorded as its retry.
+    pub fn retry(&self) -> Self {
+        let mut record = Self::new(
+            self.worktree_key.clone(),
+            self.worktree_name.clone(),
+            self.repo_name.clone(),
+            self.command.clone(),
+            self.cwd.clone(),
+            ProcessStatus::Pending,
+        );
+        record.description = self.description.clone();
+        record.parent_id = Some(self.id.clone());
+        record
+    }
+
+    /// Whether the process has stopped, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.status, ProcessStatus::Pending | ProcessStatus::Running)
+    }
+
     pub fn mark_running(&mut self) {
         self.status = ProcessStatus::Running;
         self.started_at = Utc::now();
@@ -180,6 +208,26 @@ impl ProcessRegistry {
         }
     }
 
+    /// Drop succeeded and failed processes that finished more than `max_age` ago.
+    ///
+    /// Pending, running and unknown records are kept regardless of age.
+    /// Returns the number of records removed.
+    pub fn cleanup_finished(&mut self, max_age: Duration) -> usize {
+        let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .checked_sub_signed(max_age)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let before = self.processes.len();
+        self.processes.retain(|_, record| {
+            let finished = matches!(
+                record.status,
+                ProcessStatus::Succeeded | ProcessStatus::Failed
+            );
+            !(finished && record.finished_at.is_some_and(|at| at < cutoff))
+        });
+        before - self.processes.len()
+    }
+
     pub fn mutate<F>(mutator: F) -> Result<()>
     where
         F: FnOnce(&mut ProcessRegistry) -> Result<()>,
@@ -234,3 +282,73 @@ impl ProcessRegistry {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: ProcessStatus, finished_hours_ago: Option<i64>) -> ProcessRecord {
+        let mut record = ProcessRecord::new(
+            "repo/wt".to_string(),
+            "wt".to_string(),
+            "repo".to_string(),
+            vec!["true".to_string()],
+            None,
+            status,
+        );
+        record.finished_at =
+            finished_hours_ago.map(|hours| Utc::now() - chrono::Duration::hours(hours));
+        record
+    }
+
+    #[test]
+    fn retry_copies_the_command_and_links_the_original() {
+        let mut failed = record(ProcessStatus::Failed, Some(1));
+        failed.cwd = Some(PathBuf::from("/work/repo"));
+        failed.description = Some("Run tests".to_string());
+        failed.exit_code = Some(1);
+        failed.stderr = Some("timeout".to_string());
+
+        let retry = failed.retry();
+        assert_ne!(retry.id, failed.id);
+        assert_eq!(retry.parent_id.as_deref(), Some(failed.id.as_str()));
+        assert_eq!(retry.command, failed.command);
+        assert_eq!(retry.cwd, failed.cwd);
+        assert_eq!(retry.description, failed.description);
+        assert_eq!(retry.status, ProcessStatus::Pending);
+        assert_eq!(retry.exit_code, None);
+        assert_eq!(retry.stderr, None);
+        assert!(failed.is_finished());
+        assert!(!retry.is_finished());
+    }
+
+    #[test]
+    fn cleanup_finished_drops_only_old_completed_processes() {
+        let mut registry = ProcessRegistry::default();
+        let old_success = record(ProcessStatus::Succeeded, Some(48));
+        let old_failure = record(ProcessStatus::Failed, Some(30));
+        let recent_success = record(ProcessStatus::Succeeded, Some(1));
+        let running = record(ProcessStatus::Running, None);
+        let stale_unknown = record(ProcessStatus::Unknown, Some(48));
+        let kept = [
+            recent_success.id.clone(),
+            running.id.clone(),
+            stale_unknown.id.clone(),
+        ];
+        for record in [
+            old_success,
+            old_failure,
+            recent_success,
+            running,
+            stale_unknown,
+        ] {
+            registry.insert(record);
+        }
+
+        let removed = registry.cleanup_finished(Duration::from_secs(24 * 60 * 60));
+
+        assert_eq!(removed, 2);
+        assert_eq!(registry.processes.len(), 3);
+        assert!(kept.iter().all(|id| registry.processes.contains_key(id)));
+    }
+}
@@ -5,14 +5,15 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 use super::{
-    SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase, canonicalize,
+    ARCHIVE_DIR, SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase,
+    archive_session_files, canonicalize, merge_records_by_id,
 };
 
 pub struct ClaudeCliSessionProvider {
@@ -37,6 +38,15 @@ impl SessionCache {
             entries: HashMap::new(),
         }
     }
+
+    /// Every cached file that belongs to the session `id`.
+    fn files_for(&self, id: &str) -> Vec<PathBuf> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.record.id == id)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
 }
 
 static CLAUDE_SESSION_CACHE: OnceLock<Mutex<SessionCache>> = OnceLock::new();
@@ -225,6 +235,7 @@ impl ClaudeParsedEntry {
             timestamp: self.timestamp(),
             raw,
             tool,
+            display: None,
         })
     }
 }
@@ -308,6 +319,10 @@ impl SessionProvider for ClaudeCliSessionProvider {
         "claude"
     }
 
+    fn sessions_dir(&self) -> Option<&Path> {
+        self.sessions_dir.as_deref()
+    }
+
     fn list_sessions(&self) -> Result<Vec<SessionRecord>> {
         let Some(root) = self.sessions_root() else {
             return Ok(Vec::new());
@@ -328,7 +343,7 @@ impl SessionProvider for ClaudeCliSessionProvider {
         if let Ok(projects) = fs::read_dir(root) {
             for project_entry in projects.flatten() {
                 let project_path = project_entry.path();
-                if !project_path.is_dir() {
+                if !project_path.is_dir() || project_entry.file_name() == ARCHIVE_DIR {
                     continue;
                 }
 
@@ -410,14 +425,25 @@ impl SessionProvider for ClaudeCliSessionProvider {
             }
         }
 
-        let mut sessions: Vec<SessionRecord> = cache
-            .entries
-            .values()
-            .map(|entry| entry.record.clone())
-            .collect();
+        Ok(merge_records_by_id(
+            cache.entries.values().map(|entry| entry.record.clone()),
+        ))
+    }
 
-        sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
-        Ok(sessions)
+    fn delete_session(&self, record: &SessionRecord) -> Result<Vec<PathBuf>> {
+        let root = self
+            .sessions_root()
+            .context("Claude sessions directory is unknown (HOME is not set)")?;
+        let mut files = vec![record.file_path.clone()];
+        if let Some(cache) = CLAUDE_SESSION_CACHE.get() {
+            files.extend(
+                cache
+                    .lock()
+                    .expect("claude cli session cache mutex poisoned")
+                    .files_for(&record.id),
+            );
+        }
+        archive_session_files(root, files)
     }
 
     fn load_session_events(&self, record: &SessionRecord) -> Result<Vec<SessionEvent>> {
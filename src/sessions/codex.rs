@@ -1,18 +1,20 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use super::{SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase};
+use super::{
+    ARCHIVE_DIR, SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase,
+    archive_session_files, merge_records_by_id,
+};
 
 pub struct CodexSessionProvider {
     sessions_dir: Option<PathBuf>,
@@ -36,6 +38,15 @@ impl SessionCache {
             entries: HashMap::new(),
         }
     }
+
+    /// Every cached file that belongs to the session `id`.
+    fn files_for(&self, id: &str) -> Vec<PathBuf> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.record.id == id)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
 }
 
 static CODEX_SESSION_CACHE: OnceLock<Mutex<SessionCache>> = OnceLock::new();
@@ -404,10 +415,151 @@ impl CodexParsedEntry {
             timestamp,
             raw,
             tool,
+            display: None,
+        })
+    }
+}
+
+/// The two on-disk layouts Codex has used for session logs.
+enum CodexLog {
+    /// Rollout JSONL: one entry per line, each wrapping a `payload`.
+    Rollout(Vec<Value>),
+    /// Newer releases write a single JSON document with the conversation in a
+    /// root `messages` array.
+    Messages(CodexMessagesLog),
+}
+
+impl CodexLog {
+    fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        // A rollout file has several values, so it only parses as one document
+        // when it is a single line; the `messages` key tells the formats apart
+        if let Ok(document) = serde_json::from_str::<Value>(&content)
+            && document.get("messages").is_some_and(Value::is_array)
+        {
+            let log = serde_json::from_value(document)
+                .with_context(|| format!("Invalid Codex session {}", path.display()))?;
+            return Ok(Self::Messages(log));
+        }
+
+        Ok(Self::Rollout(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect(),
+        ))
+    }
+
+    fn events(&self, include_raw: bool) -> Vec<SessionEvent> {
+        match self {
+            Self::Rollout(entries) => entries
+                .iter()
+                .filter_map(|raw| CodexParsedEntry::parse(raw.clone()))
+                .filter_map(|entry| entry.to_event(include_raw))
+                .collect(),
+            Self::Messages(log) => log.events(include_raw),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexMessagesLog {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default, alias = "working_dir")]
+    cwd: Option<String>,
+    #[serde(default)]
+    originator: Option<String>,
+    #[serde(default)]
+    instructions: Option<String>,
+    #[serde(default, alias = "created_at")]
+    timestamp: Option<String>,
+    messages: Vec<Value>,
+}
+
+impl CodexMessagesLog {
+    fn apply_summary(&self, record: &mut SessionRecord) {
+        if let Some(id) = self.id.as_ref() {
+            record.id = id.clone();
+        }
+        if let Some(cwd) = self.cwd.as_deref() {
+            record.set_working_dir(cwd);
+        }
+        record.originator = self.originator.clone();
+        record.instructions = self
+            .instructions
+            .as_deref()
+            .map(str::trim)
+            .filter(|instructions| !instructions.is_empty())
+            .map(str::to_string);
+        // Messages may carry no timestamps of their own
+        if record.last_timestamp.is_none() {
+            record.last_timestamp = self.timestamp.as_deref().and_then(parse_timestamp);
+        }
+    }
+
+    fn events(&self, include_raw: bool) -> Vec<SessionEvent> {
+        self.messages
+            .iter()
+            .filter_map(|message| self.message_event(message, include_raw))
+            .collect()
+    }
+
+    /// Convert one `{role, content, usage}` entry; `content` is either a
+    /// string or an array of content blocks.
+    fn message_event(&self, message: &Value, include_raw: bool) -> Option<SessionEvent> {
+        let role = message
+            .get("role")
+            .and_then(Value::as_str)
+            .unwrap_or("message");
+        message
+            .get("content")
+            .filter(|content| !content.is_null())?;
+        let text = codex_format_response_item(message)?;
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        let category = match role {
+            "user" => "user_message".to_string(),
+            "assistant" => "assistant_message".to_string(),
+            other => other.to_string(),
+        };
+        let usage = message.get("usage").map(|usage| {
+            let mut map = Map::new();
+            map.insert("usage".to_string(), usage.clone());
+            Value::Object(map)
+        });
+
+        Some(SessionEvent {
+            actor: Some(role.to_string()),
+            category,
+            label: Some(to_title_case(role)),
+            text: Some(trimmed.to_string()),
+            summary_text: None,
+            data: attach_working_dir(usage, self.cwd.clone()),
+            timestamp: message
+                .get("timestamp")
+                .and_then(Value::as_str)
+                .and_then(parse_timestamp),
+            raw: include_raw.then(|| message.clone()),
+            tool: None,
+            display: None,
         })
     }
 }
 
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
 impl CodexSessionProvider {
     pub fn new() -> Self {
         let sessions_dir = std::env::var("HOME")
@@ -417,28 +569,27 @@ impl CodexSessionProvider {
     }
 
     fn parse_session_file(&self, path: &Path) -> Option<SessionRecord> {
-        let file = File::open(path).ok()?;
-        let reader = BufReader::new(file);
-
+        let log = CodexLog::read(path).ok()?;
         let mut record = SessionRecord::new(self.name(), path.to_path_buf());
 
-        for line in reader.lines().map_while(Result::ok) {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
+        match &log {
+            CodexLog::Rollout(entries) => {
+                for raw in entries {
+                    let Some(entry) = CodexParsedEntry::parse(raw.clone()) else {
+                        continue;
+                    };
+                    let (_, _, entry_category) = entry.category_with_actor();
+                    entry.apply_summary(&mut record, &entry_category);
+                    if let Some(event) = entry.to_event(false) {
+                        record.ingest_event(&event);
+                    }
+                }
             }
-
-            let Ok(raw) = serde_json::from_str::<Value>(trimmed) else {
-                continue;
-            };
-
-            let Some(entry) = CodexParsedEntry::parse(raw) else {
-                continue;
-            };
-            let (_, _, entry_category) = entry.category_with_actor();
-            entry.apply_summary(&mut record, &entry_category);
-            if let Some(event) = entry.to_event(false) {
-                record.ingest_event(&event);
+            CodexLog::Messages(messages) => {
+                for event in messages.events(false) {
+                    record.ingest_event(&event);
+                }
+                messages.apply_summary(&mut record);
             }
         }
 
@@ -451,6 +602,10 @@ impl SessionProvider for CodexSessionProvider {
         "codex"
     }
 
+    fn sessions_dir(&self) -> Option<&Path> {
+        self.sessions_dir.as_deref()
+    }
+
     fn list_sessions(&self) -> Result<Vec<SessionRecord>> {
         let Some(dir) = &self.sessions_dir else {
             return Ok(Vec::new());
@@ -476,6 +631,7 @@ impl SessionProvider for CodexSessionProvider {
             .git_global(false)
             .git_exclude(false)
             .follow_links(false)
+            .filter_entry(|entry| entry.depth() != 1 || entry.file_name() != ARCHIVE_DIR)
             .build()
             .filter_map(|e| e.ok())
         {
@@ -484,7 +640,9 @@ impl SessionProvider for CodexSessionProvider {
                 && path
                     .extension()
                     .and_then(|ext| ext.to_str())
-                    .is_some_and(|ext| ext.eq_ignore_ascii_case("jsonl"))
+                    .is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("json")
+                    })
             {
                 let path_buf = path.to_path_buf();
                 seen_paths.insert(path_buf.clone());
@@ -548,45 +706,30 @@ impl SessionProvider for CodexSessionProvider {
             }
         }
 
-        let mut sessions: Vec<SessionRecord> = cache
-            .entries
-            .values()
-            .map(|entry| entry.record.clone())
-            .collect();
-
-        sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
-        Ok(sessions)
+        Ok(merge_records_by_id(
+            cache.entries.values().map(|entry| entry.record.clone()),
+        ))
     }
 
-    fn load_session_events(&self, record: &SessionRecord) -> Result<Vec<SessionEvent>> {
-        let file = File::open(&record.file_path)?;
-        let reader = BufReader::new(file);
-        let mut events = Vec::new();
-
-        for line in reader.lines() {
-            let trimmed = match line {
-                Ok(value) => value.trim().to_string(),
-                Err(_) => continue,
-            };
-            if trimmed.is_empty() {
-                continue;
-            }
-
-            let raw: Value = match serde_json::from_str(&trimmed) {
-                Ok(parsed) => parsed,
-                Err(_) => continue,
-            };
-
-            let Some(entry) = CodexParsedEntry::parse(raw) else {
-                continue;
-            };
-
-            if let Some(event) = entry.to_event(true) {
-                events.push(event);
-            }
+    fn delete_session(&self, record: &SessionRecord) -> Result<Vec<PathBuf>> {
+        let root = self
+            .sessions_dir
+            .as_deref()
+            .context("Codex sessions directory is unknown (HOME is not set)")?;
+        let mut files = vec![record.file_path.clone()];
+        if let Some(cache) = CODEX_SESSION_CACHE.get() {
+            files.extend(
+                cache
+                    .lock()
+                    .expect("codex session cache mutex poisoned")
+                    .files_for(&record.id),
+            );
         }
+        archive_session_files(root, files)
+    }
 
-        Ok(events)
+    fn load_session_events(&self, record: &SessionRecord) -> Result<Vec<SessionEvent>> {
+        Ok(CodexLog::read(&record.file_path)?.events(true))
     }
 }
 
@@ -129,3 +129,73 @@ fn skips_duplicate_user_response_items() {
         Some("I committed the changes.")
     );
 }
+
+#[test]
+fn parses_rollout_format_fixture() {
+    let provider = CodexSessionProvider { sessions_dir: None };
+    let path =
+        fixture_path("rollout-2025-10-17T20-38-29-0199f22d-c547-7eb3-99cd-bfef8fb5430c.jsonl");
+    assert!(matches!(
+        CodexLog::read(&path).expect("read rollout fixture"),
+        CodexLog::Rollout(_)
+    ));
+
+    let record = provider.parse_session_file(&path).expect("parse rollout");
+    assert_eq!(record.id, "0199f22d-c547-7eb3-99cd-bfef8fb5430c");
+    assert_eq!(
+        record.working_dir.as_deref(),
+        Some(Path::new("/Users/moonshot/Projects/AgentDev"))
+    );
+    assert_eq!(record.originator.as_deref(), Some("codex_cli_rs"));
+    assert!(!record.user_messages.is_empty());
+}
+
+#[test]
+fn parses_messages_format_fixture() {
+    let provider = CodexSessionProvider { sessions_dir: None };
+    let path = fixture_path("messages_format.json");
+    assert!(matches!(
+        CodexLog::read(&path).expect("read messages fixture"),
+        CodexLog::Messages(_)
+    ));
+
+    let record = provider.parse_session_file(&path).expect("parse messages");
+    assert_eq!(record.id, "7d2c41e0-5b8a-4f3e-9c16-0a4e2b7f9d31");
+    assert_eq!(
+        record.working_dir.as_deref(),
+        Some(Path::new("/Users/moonshot/Projects/AgentDev"))
+    );
+    assert_eq!(
+        record.user_messages,
+        [
+            "Add a --dry-run flag to worktree gc",
+            "Also cover it with a test"
+        ]
+    );
+    assert_eq!(
+        record.last_timestamp.map(|ts| ts.to_rfc3339()),
+        Some("2025-11-03T09:17:30+00:00".to_string())
+    );
+
+    let events = provider
+        .load_session_events(&record)
+        .expect("load messages events");
+    let categories: Vec<_> = events.iter().map(|e| e.category.as_str()).collect();
+    assert_eq!(
+        categories,
+        ["user_message", "assistant_message", "user_message"]
+    );
+    let assistant = &events[1];
+    assert_eq!(
+        assistant.text.as_deref(),
+        Some("I'll add the flag and print what would be removed.")
+    );
+    assert_eq!(
+        assistant
+            .data
+            .as_ref()
+            .map(|data| &data["usage"]["output_tokens"]),
+        Some(&json!(42))
+    );
+    assert!(assistant.raw.is_some());
+}
@@ -5,13 +5,16 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use super::{SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase};
+use super::{
+    SessionEvent, SessionProvider, SessionRecord, SessionToolEvent, SessionToolPhase,
+    ARCHIVE_DIR, archive_session_files, merge_records_by_id,
+};
 
 pub struct KimiSessionProvider {
     sessions_dir: Option<PathBuf>,
@@ -36,6 +39,15 @@ impl SessionCache {
             entries: HashMap::new(),
         }
     }
+
+    /// Every cached file that belongs to the session `id`.
+    fn files_for(&self, id: &str) -> Vec<PathBuf> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.record.id == id)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
 }
 
 static KIMI_SESSION_CACHE: OnceLock<Mutex<SessionCache>> = OnceLock::new();
@@ -189,6 +201,7 @@ impl KimiParsedEntry {
             timestamp,
             raw,
             tool,
+            display: None,
         })
     }
 }
@@ -206,21 +219,22 @@ struct KimiWorkDir {
 
 impl KimiSessionProvider {
     pub fn new() -> Self {
-        let home = std::env::var("HOME").ok();
-
-        let (sessions_dir, workdir_index) = if let Some(home) = home {
-            let base = PathBuf::from(&home).join(".kimi");
-            let sessions_dir = base.join("sessions");
-            let config_path = base.join("kimi.json");
-            let workdir_index = Self::load_workdirs(&config_path);
-            (Some(sessions_dir), workdir_index)
-        } else {
-            (None, HashMap::new())
-        };
+        match std::env::var("HOME") {
+            Ok(home) => Self::with_root(PathBuf::from(home).join(".kimi")),
+            Err(_) => Self {
+                sessions_dir: None,
+                workdir_index: HashMap::new(),
+            },
+        }
+    }
 
+    /// Read sessions from a kimi data directory (normally `~/.kimi`) holding
+    /// `sessions/` and `kimi.json`.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        let base = root.into();
         Self {
-            sessions_dir,
-            workdir_index,
+            sessions_dir: Some(base.join("sessions")),
+            workdir_index: Self::load_workdirs(&base.join("kimi.json")),
         }
     }
 
@@ -304,6 +318,10 @@ impl SessionProvider for KimiSessionProvider {
         "kimi"
     }
 
+    fn sessions_dir(&self) -> Option<&Path> {
+        self.sessions_dir.as_deref()
+    }
+
     fn list_sessions(&self) -> Result<Vec<SessionRecord>> {
         let Some(root) = &self.sessions_dir else {
             return Ok(Vec::new());
@@ -329,7 +347,7 @@ impl SessionProvider for KimiSessionProvider {
             };
 
             let dir_path = entry.path();
-            if !dir_path.is_dir() {
+            if !dir_path.is_dir() || entry.file_name() == ARCHIVE_DIR {
                 continue;
             }
 
@@ -413,14 +431,19 @@ impl SessionProvider for KimiSessionProvider {
             }
         }
 
-        let mut sessions: Vec<SessionRecord> = cache
-            .entries
-            .values()
-            .map(|entry| entry.record.clone())
-            .collect();
+        Ok(merge_records_by_id(
+            cache.entries.values().map(|entry| entry.record.clone()),
+        ))
+    }
 
-        sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
-        Ok(sessions)
+    fn delete_session(&self, record: &SessionRecord) -> Result<Vec<PathBuf>> {
+        let root = self.sessions_dir.as_deref()
+            .context("Kimi sessions directory is unknown (HOME is not set)")?;
+        let mut files = vec![record.file_path.clone()];
+        if let Some(cache) = KIMI_SESSION_CACHE.get() {
+            files.extend(cache.lock().expect("kimi session cache mutex poisoned").files_for(&record.id));
+        }
+        archive_session_files(root, files)
     }
 
     fn load_session_events(&self, record: &SessionRecord) -> Result<Vec<SessionEvent>> {
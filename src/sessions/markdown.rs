@@ -0,0 +1,213 @@
+//! Markdown rendering of session transcripts, used by `sessions export`.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::{SessionEvent, SessionEventDisplay, SessionRecord, SessionToolEvent};
+
+/// Render a whole session as Markdown.
+///
+/// User and assistant messages become `## User` / `## Assistant` sections and
+/// tool calls and results are folded into `<details>` blocks so the
+/// conversation stays readable. System events are left out.
+pub fn render_markdown(record: &SessionRecord, events: &[SessionEvent]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {} session {}", record.provider, record.id);
+    out.push('\n');
+    if let Some(dir) = &record.working_dir {
+        let _ = writeln!(out, "- Working directory: `{}`", dir.display());
+    }
+    if let Some(originator) = &record.originator {
+        let _ = writeln!(out, "- Originator: {originator}");
+    }
+    if let Some(ts) = record.last_timestamp {
+        let _ = writeln!(out, "- Last activity: {}", format_timestamp(ts));
+    }
+    let _ = writeln!(out, "- Source: `{}`", record.file_path.display());
+
+    for event in events {
+        let display = event
+            .display
+            .unwrap_or_else(|| SessionEventDisplay::classify(event));
+        match display {
+            SessionEventDisplay::User => push_message(&mut out, "User", event),
+            SessionEventDisplay::Assistant => push_message(&mut out, "Assistant", event),
+            SessionEventDisplay::ToolUse | SessionEventDisplay::ToolResult => {
+                push_tool(&mut out, display, event)
+            }
+            SessionEventDisplay::System => {}
+        }
+    }
+    out
+}
+
+fn format_timestamp(ts: DateTime<Utc>) -> String {
+    ts.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+fn push_message(out: &mut String, heading: &str, event: &SessionEvent) {
+    let Some(text) = event.text.as_deref().map(str::trim) else {
+        return;
+    };
+    if text.is_empty() {
+        return;
+    }
+    out.push('\n');
+    match event.timestamp {
+        Some(ts) => {
+            let _ = writeln!(out, "## {heading} ({})", format_timestamp(ts));
+        }
+        None => {
+            let _ = writeln!(out, "## {heading}");
+        }
+    }
+    out.push('\n');
+    let _ = writeln!(out, "{text}");
+}
+
+fn push_tool(out: &mut String, display: SessionEventDisplay, event: &SessionEvent) {
+    let tool = event.tool.as_ref();
+    let name = tool
+        .and_then(|tool| tool.name.as_deref())
+        .or(event.label.as_deref())
+        .unwrap_or("tool");
+    let (kind, payload) = if display == SessionEventDisplay::ToolUse {
+        ("Tool call", tool.and_then(|tool| tool.input.as_ref()))
+    } else {
+        ("Tool result", tool.and_then(|tool| tool.output.as_ref()))
+    };
+
+    let (language, body) = match payload {
+        Some(Value::String(text)) => ("text", text.clone()),
+        Some(value) => (
+            "json",
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string()),
+        ),
+        None => match event.text.as_deref() {
+            Some(text) => ("text", text.to_string()),
+            None => ("text", String::new()),
+        },
+    };
+
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "<details><summary>{kind}: {}</summary>",
+        summary_label(name, tool)
+    );
+    out.push('\n');
+    let body = body.trim_end();
+    if !body.is_empty() {
+        let fence = fence_for(body);
+        let _ = writeln!(out, "{fence}{language}\n{body}\n{fence}");
+        out.push('\n');
+    }
+    out.push_str("</details>\n");
+}
+
+fn summary_label(name: &str, tool: Option<&SessionToolEvent>) -> String {
+    let name = escape_html(name);
+    match tool.and_then(|tool| tool.identifier.as_deref()) {
+        Some(id) => format!("{name} <code>{}</code>", escape_html(id)),
+        None => name,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A backtick fence longer than any run of backticks inside `body`.
+fn fence_for(body: &str) -> String {
+    let longest = body
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::SessionToolPhase;
+    use serde_json::{Map, json};
+    use std::path::PathBuf;
+
+    fn event(actor: &str, text: &str, tool: Option<SessionToolEvent>) -> SessionEvent {
+        SessionEvent {
+            actor: Some(actor.to_string()),
+            category: actor.to_string(),
+            label: None,
+            text: Some(text.to_string()),
+            summary_text: None,
+            data: None,
+            timestamp: None,
+            raw: None,
+            tool,
+            display: None,
+        }
+    }
+
+    fn tool(
+        phase: SessionToolPhase,
+        input: Option<Value>,
+        output: Option<Value>,
+    ) -> SessionToolEvent {
+        SessionToolEvent {
+            phase,
+            name: Some("Bash".to_string()),
+            identifier: Some("call_1".to_string()),
+            input,
+            output,
+            working_dir: None,
+            extras: Map::new(),
+        }
+    }
+
+    #[test]
+    fn renders_messages_and_collapsible_tool_blocks() {
+        let mut record = SessionRecord::new("claude", PathBuf::from("/tmp/abc.jsonl"));
+        record.set_working_dir("/work/repo");
+        let events = vec![
+            event("user", "Run the tests", None),
+            event(
+                "assistant",
+                "",
+                Some(tool(
+                    SessionToolPhase::Use,
+                    Some(json!({"command": "cargo test"})),
+                    None,
+                )),
+            ),
+            event(
+                "user",
+                "",
+                Some(tool(
+                    SessionToolPhase::Result,
+                    None,
+                    Some(json!("```\nok\n```")),
+                )),
+            ),
+            event("system", "usage report", None),
+            event("assistant", "All green.", None),
+        ];
+
+        let markdown = render_markdown(&record, &events);
+
+        assert!(markdown.starts_with("# claude session abc\n"));
+        assert!(markdown.contains("- Working directory: `/work/repo`"));
+        assert!(markdown.contains("## User\n\nRun the tests\n"));
+        assert!(markdown.contains(
+            "<details><summary>Tool call: Bash <code>call_1</code></summary>\n\n```json\n{\n  \"command\": \"cargo test\"\n}\n```\n\n</details>"
+        ));
+        // The result contains a fence itself, so it gets a longer one
+        assert!(markdown.contains("````text\n```\nok\n```\n````"));
+        assert!(markdown.contains("## Assistant\n\nAll green.\n"));
+        assert!(!markdown.contains("usage report"));
+    }
+}
@@ -1,16 +1,20 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::path::{Path, PathBuf};
 
 mod claude_cli;
 mod codex;
 mod kimi;
+mod markdown;
 
 pub use claude_cli::ClaudeCliSessionProvider;
 pub use codex::CodexSessionProvider;
 pub use kimi::KimiSessionProvider;
+pub use markdown::render_markdown;
 
 #[derive(Debug, Clone)]
 pub struct SessionRecord {
@@ -21,9 +25,13 @@ pub struct SessionRecord {
     pub instructions: Option<String>,
     pub first_user_message: Option<String>,
     pub last_user_message: Option<String>,
+    /// Earliest event timestamp seen
+    pub first_timestamp: Option<DateTime<Utc>>,
     pub last_timestamp: Option<DateTime<Utc>>,
     pub file_path: PathBuf,
     pub user_messages: Vec<String>,
+    /// Events ingested into this record
+    pub event_count: usize,
 }
 
 impl SessionRecord {
@@ -42,9 +50,11 @@ impl SessionRecord {
             instructions: None,
             first_user_message: None,
             last_user_message: None,
+            first_timestamp: None,
             last_timestamp: None,
             file_path,
             user_messages: Vec::new(),
+            event_count: 0,
         }
     }
 
@@ -52,6 +62,57 @@ impl SessionRecord {
         self.working_dir = Some(PathBuf::from(path));
     }
 
+    /// Fold `other`, another file of the same logical session, into this record.
+    ///
+    /// Messages of the record with the earlier `last_timestamp` come first and
+    /// repeated messages are kept once. The earlier record supplies
+    /// `first_user_message`; the later one supplies `last_timestamp`,
+    /// `last_user_message` and `file_path`.
+    pub fn merge(&mut self, mut other: SessionRecord) {
+        if other.last_timestamp < self.last_timestamp {
+            std::mem::swap(self, &mut other);
+        }
+        // `self` is now the earlier record and `other` the later one
+        for message in other.user_messages {
+            if !self.user_messages.contains(&message) {
+                self.user_messages.push(message);
+            }
+        }
+        self.first_user_message = self.first_user_message.take().or(other.first_user_message);
+        if other.last_user_message.is_some() {
+            self.last_user_message = other.last_user_message;
+        }
+        self.first_timestamp = match (self.first_timestamp, other.first_timestamp) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.last_timestamp = self.last_timestamp.max(other.last_timestamp);
+        self.event_count += other.event_count;
+        self.file_path = other.file_path;
+        self.working_dir = self.working_dir.take().or(other.working_dir);
+        self.originator = self.originator.take().or(other.originator);
+        self.instructions = self.instructions.take().or(other.instructions);
+    }
+
+    /// Identity used by [`dedup_sessions_by`]: provider, working directory,
+    /// first user message (whitespace-normalized) and earliest timestamp to
+    /// the second.
+    ///
+    /// `None` when the record has no user message or timestamp to go by.
+    pub fn fingerprint(&self) -> Option<SessionFingerprint> {
+        let first_message = self.first_user_message.as_deref()?;
+        let first_timestamp = self.first_timestamp?;
+        Some(SessionFingerprint {
+            provider: self.provider.to_ascii_lowercase(),
+            working_dir: self.working_dir.clone(),
+            first_user_message: first_message
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            first_timestamp: first_timestamp.timestamp(),
+        })
+    }
+
     fn should_skip_user_message(&self, message: &str) -> bool {
         if !self.provider.eq_ignore_ascii_case("codex") {
             return false;
@@ -68,7 +129,14 @@ impl SessionRecord {
     }
 
     pub fn ingest_event(&mut self, event: &SessionEvent) {
+        self.event_count += 1;
         if let Some(timestamp) = event.timestamp {
+            if self
+                .first_timestamp
+                .is_none_or(|current| timestamp < current)
+            {
+                self.first_timestamp = Some(timestamp);
+            }
             if self
                 .last_timestamp
                 .map_or(true, |current| timestamp > current)
@@ -121,7 +189,17 @@ impl SessionRecord {
     }
 }
 
+/// See [`SessionRecord::fingerprint`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionFingerprint {
+    provider: String,
+    working_dir: Option<PathBuf>,
+    first_user_message: String,
+    first_timestamp: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SessionEvent {
     /// Provider-reported actor/role (e.g. "user", "assistant"). Forwarded as-is when present.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -149,9 +227,63 @@ pub struct SessionEvent {
     /// Normalized tool metadata extracted by AgentDev (optional).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tool: Option<SessionToolEvent>,
+    /// Rendering hint computed by AgentDev when serving session details.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<SessionEventDisplay>,
+}
+
+/// Provider-independent classification of a session event for rendering.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEventDisplay {
+    User,
+    Assistant,
+    ToolUse,
+    ToolResult,
+    /// Metadata, checkpoints, usage reports and anything unrecognized.
+    System,
+}
+
+impl SessionEventDisplay {
+    /// Classify from normalized tool metadata first, then the actor, then the
+    /// provider category.
+    pub fn classify(event: &SessionEvent) -> Self {
+        if let Some(tool) = &event.tool {
+            return match tool.phase {
+                SessionToolPhase::Use => Self::ToolUse,
+                SessionToolPhase::Result => Self::ToolResult,
+            };
+        }
+
+        let category = event.category.trim().to_ascii_lowercase();
+        match category.as_str() {
+            "tool_use" => return Self::ToolUse,
+            "tool_result" => return Self::ToolResult,
+            _ => {}
+        }
+
+        let actor = event
+            .actor
+            .as_deref()
+            .map(|actor| actor.trim().to_ascii_lowercase());
+        match actor.as_deref() {
+            Some("user") => return Self::User,
+            Some("assistant") => return Self::Assistant,
+            Some("system") => return Self::System,
+            _ => {}
+        }
+
+        match category.as_str() {
+            "user_message" => Self::User,
+            "assistant_message" | "agent_message" | "response_item" => Self::Assistant,
+            _ => Self::System,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SessionToolPhase {
     Use,
@@ -159,6 +291,7 @@ pub enum SessionToolPhase {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SessionToolEvent {
     /// Phase of the tool interaction (normalized by AgentDev).
     pub phase: SessionToolPhase,
@@ -179,9 +312,57 @@ pub struct SessionToolEvent {
     pub working_dir: Option<String>,
     /// Provider-specific or unrecognized fields preserved for debugging.
     #[serde(default, skip_serializing_if = "Map::is_empty")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub extras: Map<String, Value>,
 }
 
+/// Coarse activity bucket derived from a session's most recent timestamp.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionRecency {
+    /// Activity within the last hour.
+    Active,
+    /// Activity within the last day.
+    Idle,
+    /// No activity for more than a day.
+    Stale,
+    /// The provider did not report any timestamp.
+    Unknown,
+}
+
+impl SessionRecency {
+    /// Bucket a timestamp relative to `now`.
+    pub fn classify(timestamp: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Self {
+        let Some(timestamp) = timestamp else {
+            return Self::Unknown;
+        };
+        let elapsed = now.signed_duration_since(timestamp);
+        if elapsed < chrono::Duration::hours(1) {
+            Self::Active
+        } else if elapsed < chrono::Duration::days(1) {
+            Self::Idle
+        } else {
+            Self::Stale
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Active => "active <1h",
+            Self::Idle => "idle",
+            Self::Stale => "stale",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl SessionRecord {
+    /// Recency bucket for this session based on `last_timestamp`.
+    pub fn recency(&self, now: DateTime<Utc>) -> SessionRecency {
+        SessionRecency::classify(self.last_timestamp, now)
+    }
+}
+
 /// TODO(provider-models): consider upgrading raw entry structs into provider-specific
 /// enums (e.g. `CodexEvent`) before converting to `SessionEvent` so we can enforce
 /// variant coverage at compile time and expose richer metadata downstream.
@@ -190,6 +371,112 @@ pub trait SessionProvider {
     fn name(&self) -> &'static str;
     fn list_sessions(&self) -> Result<Vec<SessionRecord>>;
     fn load_session_events(&self, record: &SessionRecord) -> Result<Vec<SessionEvent>>;
+
+    /// Directory scanned for session logs, if the provider is file-based.
+    fn sessions_dir(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Report what a scan sees, to explain an empty `sessions list`.
+    ///
+    /// Counts `.jsonl` files under `sessions_dir` (outside the archive),
+    /// runs `list_sessions`, and if any file holds a line that is not valid
+    /// JSON, reports the first one.
+    fn diagnostics(&self) -> ProviderDiagnostics {
+        let mut report = ProviderDiagnostics {
+            provider: self.name().to_string(),
+            sessions_dir: self.sessions_dir().map(Path::to_path_buf),
+            dir_exists: false,
+            candidate_files: 0,
+            parsed_sessions: 0,
+            first_error: None,
+        };
+        let Some(dir) = self.sessions_dir() else {
+            return report;
+        };
+        report.dir_exists = dir.is_dir();
+        if !report.dir_exists {
+            return report;
+        }
+
+        let files = session_log_files(dir);
+        report.candidate_files = files.len();
+        match self.list_sessions() {
+            Ok(records) => {
+                report.parsed_sessions = records.len();
+                report.first_error = files.iter().find_map(|file| first_invalid_line(file));
+            }
+            Err(err) => report.first_error = Some(format!("{err:#}")),
+        }
+        report
+    }
+
+    /// Remove a session from future listings, returning where its files went.
+    ///
+    /// File-based providers move the logs into an `archive/` folder under
+    /// their sessions directory instead of deleting them.
+    fn delete_session(&self, record: &SessionRecord) -> Result<Vec<PathBuf>> {
+        let _ = record;
+        anyhow::bail!(
+            "The {} provider does not support deleting sessions",
+            self.name()
+        )
+    }
+}
+
+/// Folder under a provider's sessions directory that archived logs move to.
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// What a provider found on disk, from [`SessionProvider::diagnostics`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDiagnostics {
+    pub provider: String,
+    /// `None` when the provider could not work out where its logs live
+    /// (e.g. `HOME` is unset).
+    pub sessions_dir: Option<PathBuf>,
+    pub dir_exists: bool,
+    pub candidate_files: usize,
+    /// Sessions after merging files that belong to the same session.
+    pub parsed_sessions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_error: Option<String>,
+}
+
+/// Every `.jsonl` file below `root`, skipping the top-level archive folder.
+fn session_log_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() != 1 || entry.file_name() != ARCHIVE_DIR)
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("jsonl"))
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// `path:line: error` for the first non-empty line that is not valid JSON.
+fn first_invalid_line(path: &Path) -> Option<String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => return Some(format!("{}: {err}", path.display())),
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .find_map(|(index, line)| {
+            serde_json::from_str::<Value>(line)
+                .err()
+                .map(|err| format!("{}:{}: {err}", path.display(), index + 1))
+        })
 }
 
 pub fn default_providers() -> Vec<Box<dyn SessionProvider + Send + Sync>> {
@@ -200,6 +487,302 @@ pub fn default_providers() -> Vec<Box<dyn SessionProvider + Send + Sync>> {
     ]
 }
 
+/// Find a session by provider name (case-insensitive) and id.
+pub fn find_session(
+    provider_name: &str,
+    session_id: &str,
+) -> Result<Option<(Box<dyn SessionProvider + Send + Sync>, SessionRecord)>> {
+    let Some(provider) = default_providers()
+        .into_iter()
+        .find(|provider| provider.name().eq_ignore_ascii_case(provider_name))
+    else {
+        return Ok(None);
+    };
+    let record = provider
+        .list_sessions()?
+        .into_iter()
+        .find(|record| record.id == session_id);
+    Ok(record.map(|record| (provider, record)))
+}
+
+/// Move session log files into `root/archive/`, keeping their layout under `root`.
+///
+/// `files` are every file that contributed to the record, since a session
+/// split across files is listed as one merged record.
+pub(crate) fn archive_session_files(root: &Path, mut files: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    files.sort();
+    files.dedup();
+
+    let archive_root = root.join(ARCHIVE_DIR);
+    let mut archived = Vec::with_capacity(files.len());
+    for file in files {
+        let relative = file.strip_prefix(root).with_context(|| {
+            format!(
+                "{} is outside the sessions directory {}",
+                file.display(),
+                root.display()
+            )
+        })?;
+        let target = archive_root.join(relative);
+        if target.exists() {
+            anyhow::bail!("{} is already archived", target.display());
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::rename(&file, &target)
+            .with_context(|| format!("Failed to archive {}", file.display()))?;
+        archived.push(target);
+    }
+    Ok(archived)
+}
+
+/// Merge records sharing an `id` (a session split across files), newest first.
+pub(crate) fn merge_records_by_id(
+    records: impl IntoIterator<Item = SessionRecord>,
+) -> Vec<SessionRecord> {
+    let mut by_id: HashMap<String, SessionRecord> = HashMap::new();
+    for record in records {
+        match by_id.entry(record.id.clone()) {
+            Entry::Occupied(mut existing) => existing.get_mut().merge(record),
+            Entry::Vacant(slot) => {
+                slot.insert(record);
+            }
+        }
+    }
+
+    let mut sessions: Vec<SessionRecord> = by_id.into_values().collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.last_timestamp));
+    sessions
+}
+
+/// Collapse sessions with the same [`SessionRecord::fingerprint`], such as a
+/// `.jsonl` log and a rolled-up copy of it, keeping the one with the most
+/// events.
+///
+/// Survivors keep their relative order. Records without a fingerprint are
+/// always kept. This can hide genuinely distinct sessions that started with
+/// the same message in the same second, so callers only apply it on request.
+pub fn dedup_sessions_by<T>(items: Vec<T>, record: impl Fn(&T) -> &SessionRecord) -> Vec<T> {
+    let mut best: HashMap<SessionFingerprint, usize> = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        let Some(fingerprint) = record(item).fingerprint() else {
+            continue;
+        };
+        match best.entry(fingerprint) {
+            Entry::Occupied(mut kept) => {
+                if record(item).event_count > record(&items[*kept.get()]).event_count {
+                    kept.insert(index);
+                }
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(index);
+            }
+        }
+    }
+
+    let keep: std::collections::HashSet<usize> = best.into_values().collect();
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(index, item)| record(item).fingerprint().is_none() || keep.contains(index))
+        .map(|(_, item)| item)
+        .collect()
+}
+
 pub fn canonicalize(path: &Path) -> Option<PathBuf> {
     std::fs::canonicalize(path).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn event(category: &str, actor: Option<&str>, phase: Option<SessionToolPhase>) -> SessionEvent {
+        SessionEvent {
+            actor: actor.map(str::to_string),
+            category: category.to_string(),
+            label: None,
+            text: None,
+            summary_text: None,
+            data: None,
+            timestamp: None,
+            raw: None,
+            tool: phase.map(|phase| SessionToolEvent {
+                phase,
+                name: Some("Bash".to_string()),
+                identifier: None,
+                input: None,
+                output: None,
+                working_dir: None,
+                extras: Map::new(),
+            }),
+            display: None,
+        }
+    }
+
+    #[test]
+    fn display_classification_covers_each_kind() {
+        let cases = [
+            (event("user", Some("user"), None), SessionEventDisplay::User),
+            (event("user_message", None, None), SessionEventDisplay::User),
+            (
+                event("assistant", Some("Assistant"), None),
+                SessionEventDisplay::Assistant,
+            ),
+            (
+                event("response_item", None, None),
+                SessionEventDisplay::Assistant,
+            ),
+            // Claude reports tool results as user-role messages
+            (
+                event("tool_result", Some("user"), Some(SessionToolPhase::Result)),
+                SessionEventDisplay::ToolResult,
+            ),
+            (
+                event("tool_use", Some("assistant"), Some(SessionToolPhase::Use)),
+                SessionEventDisplay::ToolUse,
+            ),
+            (event("tool_use", None, None), SessionEventDisplay::ToolUse),
+            (
+                event("session_meta", None, None),
+                SessionEventDisplay::System,
+            ),
+            (event("_usage", None, None), SessionEventDisplay::System),
+        ];
+        for (event, expected) in cases {
+            assert_eq!(
+                SessionEventDisplay::classify(&event),
+                expected,
+                "{} / {:?}",
+                event.category,
+                event.actor
+            );
+        }
+    }
+
+    #[test]
+    fn recency_buckets_by_elapsed_time() {
+        let now = Utc::now();
+        assert_eq!(
+            SessionRecency::classify(Some(now - Duration::minutes(5)), now),
+            SessionRecency::Active
+        );
+        assert_eq!(
+            SessionRecency::classify(Some(now - Duration::hours(3)), now),
+            SessionRecency::Idle
+        );
+        assert_eq!(
+            SessionRecency::classify(Some(now - Duration::days(2)), now),
+            SessionRecency::Stale
+        );
+        assert_eq!(SessionRecency::classify(None, now), SessionRecency::Unknown);
+    }
+
+    fn record(file: &str, messages: &[&str], minutes_ago: i64) -> SessionRecord {
+        let mut record = SessionRecord::new("codex", PathBuf::from(file));
+        record.id = "session-1".to_string();
+        record.user_messages = messages.iter().map(|m| m.to_string()).collect();
+        record.first_user_message = record.user_messages.first().cloned();
+        record.last_user_message = record.user_messages.last().cloned();
+        record.last_timestamp = Some(Utc::now() - Duration::minutes(minutes_ago));
+        record
+    }
+
+    #[test]
+    fn merge_appends_later_record() {
+        let earlier = record("part-1.jsonl", &["fix the bug", "add a test"], 30);
+        let later = record("part-2.jsonl", &["add a test", "ship it"], 5);
+        let later_timestamp = later.last_timestamp;
+
+        let mut merged = earlier.clone();
+        merged.merge(later.clone());
+        assert_eq!(
+            merged.user_messages,
+            ["fix the bug", "add a test", "ship it"]
+        );
+        assert_eq!(merged.first_user_message.as_deref(), Some("fix the bug"));
+        assert_eq!(merged.last_user_message.as_deref(), Some("ship it"));
+        assert_eq!(merged.last_timestamp, later_timestamp);
+        assert_eq!(merged.file_path, PathBuf::from("part-2.jsonl"));
+
+        // Merging in the other direction yields the same record
+        let mut reversed = later;
+        reversed.merge(earlier);
+        assert_eq!(reversed.user_messages, merged.user_messages);
+        assert_eq!(reversed.first_user_message, merged.first_user_message);
+        assert_eq!(reversed.last_user_message, merged.last_user_message);
+        assert_eq!(reversed.last_timestamp, merged.last_timestamp);
+        assert_eq!(reversed.file_path, merged.file_path);
+    }
+
+    #[test]
+    fn merge_records_by_id_combines_split_sessions() {
+        let mut other = record("other.jsonl", &["unrelated"], 1);
+        other.id = "session-2".to_string();
+        let sessions = merge_records_by_id([
+            record("part-2.jsonl", &["second"], 10),
+            other,
+            record("part-1.jsonl", &["first"], 60),
+        ]);
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, "session-2");
+        assert_eq!(sessions[1].user_messages, ["first", "second"]);
+    }
+
+    #[test]
+    fn dedup_keeps_the_fullest_copy_of_a_session() {
+        let started = Utc::now() - Duration::hours(1);
+        let copy = |file: &str, first: &str, events: usize| {
+            let mut record = record(file, &[first], 5);
+            record.working_dir = Some(PathBuf::from("/work/repo"));
+            record.first_timestamp = Some(started);
+            record.event_count = events;
+            record
+        };
+        let mut unrelated = copy("other.jsonl", "something else", 1);
+        unrelated.first_timestamp = Some(started - Duration::minutes(1));
+        let mut undated = copy("undated.jsonl", "fix the bug", 1);
+        undated.first_timestamp = None;
+
+        let sessions = dedup_sessions_by(
+            vec![
+                copy("rollup.log.jsonl", "fix the bug", 3),
+                unrelated,
+                copy("session.jsonl", "fix  the bug ", 12),
+                undated,
+            ],
+            |record| record,
+        );
+        let files: Vec<_> = sessions
+            .iter()
+            .map(|record| record.file_path.to_str().unwrap())
+            .collect();
+        assert_eq!(files, ["other.jsonl", "session.jsonl", "undated.jsonl"]);
+    }
+
+    #[test]
+    fn archive_session_files_keeps_layout_under_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("sessions");
+        let file = root.join("2024/05/01/rollout.jsonl");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "{}\n").unwrap();
+
+        let archived = archive_session_files(&root, vec![file.clone(), file.clone()]).unwrap();
+        assert_eq!(
+            archived,
+            [root.join(ARCHIVE_DIR).join("2024/05/01/rollout.jsonl")]
+        );
+        assert!(!file.exists());
+        assert!(archived[0].exists());
+
+        let outside = temp.path().join("elsewhere.jsonl");
+        std::fs::write(&outside, "{}\n").unwrap();
+        assert!(archive_session_files(&root, vec![outside.clone()]).is_err());
+        assert!(outside.exists());
+    }
+}
@@ -2,9 +2,10 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::git::get_repo_name;
 
@@ -23,10 +24,38 @@ pub struct WorktreeInfo {
     pub initial_prompt: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent_alias: Option<String>,
+    /// Mirrors `git worktree lock`; locked worktrees are protected from pruning.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_reason: Option<String>,
+    /// Last time the worktree was used: attaching, sending text, launching a
+    /// command or taking a `snapshot`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_agent_activity: Option<DateTime<Utc>>,
+    /// Ref passed to `worktree create --from`, when the branch started elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_ref: Option<String>,
+    /// Pinned worktrees sort first in listings.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Tags created by `worktree checkpoint`, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checkpoints: Vec<String>,
+    /// Free-text annotations from `worktree note` or the web UI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Symlink created by `worktree link` that points at this worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symlink_path: Option<PathBuf>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct XlaudeState {
+    /// Version of the on-disk layout; see [`MIGRATIONS`]. Missing in files
+    /// written before versioning, which deserialize as version 0.
+    #[serde(default)]
+    pub schema_version: u32,
     // Key format: "{repo_name}/{worktree_name}"
     pub worktrees: HashMap<String, WorktreeInfo>,
     pub editor: Option<String>,
@@ -34,77 +63,160 @@ pub struct XlaudeState {
     pub agent: Option<String>,
 }
 
+impl Default for XlaudeState {
+    fn default() -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            worktrees: HashMap::new(),
+            editor: None,
+            agent: None,
+        }
+    }
+}
+
+/// Upgrades the raw state JSON by one schema version.
+pub type Migration = fn(&mut Value) -> Result<()>;
+
+/// Schema migrations in order; each entry upgrades the state to its version.
+///
+/// Append new entries with the next version number and add a matching
+/// `tests/fixtures/state/v<N>.json`.
+pub const MIGRATIONS: &[(u32, Migration)] = &[(1, migrate_repo_scoped_keys)];
+
+/// Schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].0;
+
+/// Run every migration newer than the state's `schema_version`, bumping the
+/// version after each one. Returns whether anything ran.
+pub fn apply_migrations(state: &mut Value) -> Result<bool> {
+    let mut version = state
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let mut migrated = false;
+
+    for &(target, migrate) in MIGRATIONS {
+        if u64::from(target) <= version {
+            continue;
+        }
+        migrate(state).with_context(|| format!("Failed to migrate state to v{target}"))?;
+        state["schema_version"] = Value::from(target);
+        version = u64::from(target);
+        migrated = true;
+    }
+
+    Ok(migrated)
+}
+
+/// v1: key worktrees as `repo/name` instead of just `name` (xlaude v0.2 -> v0.3).
+fn migrate_repo_scoped_keys(state: &mut Value) -> Result<()> {
+    let Some(worktrees) = state.get_mut("worktrees").and_then(Value::as_object_mut) else {
+        return Ok(());
+    };
+
+    let entries = std::mem::take(worktrees);
+    for (key, info) in entries {
+        let key = if key.contains('/') {
+            key
+        } else {
+            let repo_name = info
+                .get("repo_name")
+                .and_then(Value::as_str)
+                .with_context(|| format!("Worktree '{key}' has no repo_name"))?;
+            let name = info.get("name").and_then(Value::as_str).unwrap_or(&key);
+            XlaudeState::make_key(repo_name, name)
+        };
+        worktrees.insert(key, info);
+    }
+
+    Ok(())
+}
+
 impl XlaudeState {
     pub fn make_key(repo_name: &str, worktree_name: &str) -> String {
         format!("{repo_name}/{worktree_name}")
     }
 
+    /// Load the state file, migrating older layouts.
+    ///
+    /// A file that cannot be parsed (for example one truncated by a crash) is
+    /// moved aside to `state.json.bak` and an empty state is returned, so one
+    /// bad write does not break every command. A file written by a newer
+    /// agentdev is left alone and reported as an error instead.
     pub fn load() -> Result<Self> {
         let config_path = get_config_path()?;
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path).context("Failed to read config file")?;
-            let mut state: Self =
-                serde_json::from_str(&content).context("Failed to parse config file")?;
-
-            // ============================================================================
-            // MIGRATION LOGIC: Upgrade from v0.2 to v0.3 format
-            // TODO: Remove this migration code after v0.3 is stable and most users have upgraded
-            //
-            // In v0.2, keys were just the worktree name: "feature-x"
-            // In v0.3, keys include the repo name: "repo-name/feature-x"
-            // ============================================================================
-            let needs_migration = state.worktrees.keys().any(|k| !k.contains('/'));
-
-            if needs_migration {
-                eprintln!("🔄 Migrating xlaude state from v0.2 to v0.3 format...");
-
-                let mut migrated_worktrees = HashMap::new();
-                for (old_key, info) in state.worktrees {
-                    // Check if this entry needs migration (doesn't contain '/')
-                    let new_key = if old_key.contains('/') {
-                        // Already in new format, keep as-is
-                        old_key
-                    } else {
-                        // Old format, create new key
-                        Self::make_key(&info.repo_name, &info.name)
-                    };
-                    migrated_worktrees.insert(new_key, info);
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path).context("Failed to read config file")?;
+        let (mut state, migrated) = match Self::parse(&content) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                if let Some(version) = newer_schema_version(&content) {
+                    anyhow::bail!(
+                        "{} has schema version {version}, but this agentdev only understands up to {CURRENT_SCHEMA_VERSION}; upgrade agentdev",
+                        config_path.display()
+                    );
                 }
+                let backup = back_up_corrupt_state(&config_path)?;
+                eprintln!(
+                    "⚠️  {} is corrupt ({err:#}).\n⚠️  Moved it to {} and started with an empty state; \
+                     re-adopt worktrees with 'agentdev worktree adopt' or restore the backup by hand.",
+                    config_path.display(),
+                    backup.display()
+                );
+                return Ok(Self::default());
+            }
+        };
 
-                state.worktrees = migrated_worktrees;
+        if migrated {
+            state.backfill_task_ids();
+            state.save().context("Failed to save migrated state")?;
+        } else if state.backfill_task_ids() {
+            let _ = state.save();
+        }
+
+        Ok(state)
+    }
 
-                // Save the migrated state immediately
-                state.save().context("Failed to save migrated state")?;
-                eprintln!("✅ Migration completed successfully");
+    /// Validate, migrate and deserialize the raw state file contents.
+    fn parse(content: &str) -> Result<(Self, bool)> {
+        let mut raw: Value =
+            serde_json::from_str(content).context("Failed to parse config file")?;
+        if !raw.is_object() {
+            anyhow::bail!("Expected a JSON object at the top level");
+        }
+        if let Some(version) = raw.get("schema_version") {
+            match version.as_u64() {
+                Some(version) if version <= u64::from(CURRENT_SCHEMA_VERSION) => {}
+                Some(version) => anyhow::bail!("Unsupported schema_version {version}"),
+                None => anyhow::bail!("schema_version is not a non-negative integer"),
             }
-            // ============================================================================
-            // END OF MIGRATION LOGIC
-            // ============================================================================
-
-            // MIGRATION: Ensure task_id exists; default to worktree name for grouping
-            let mut changed = false;
-            for (k, info) in state.worktrees.iter_mut() {
-                if info.task_id.is_none() {
-                    info.task_id = Some(info.name.clone());
-                    changed = true;
-                }
-                if info.task_name.is_none() {
-                    info.task_name = info.task_id.clone().or_else(|| Some(info.name.clone()));
-                    changed = true;
-                }
-                // Ensure key format is repo/name (already migrated above)
-                if !k.contains('/') {
-                    changed = true; // Already handled in prior migration, keep flag here
-                }
+        }
+
+        let migrated = apply_migrations(&mut raw)?;
+        let state: Self = serde_json::from_value(raw).context("Failed to parse config file")?;
+        Ok((state, migrated))
+    }
+
+    /// Default `task_id` to the worktree name so every worktree groups as a task.
+    ///
+    /// Commands still create worktrees without a task, so this runs on every
+    /// load rather than as a one-off schema migration.
+    fn backfill_task_ids(&mut self) -> bool {
+        let mut changed = false;
+        for info in self.worktrees.values_mut() {
+            if info.task_id.is_none() {
+                info.task_id = Some(info.name.clone());
+                changed = true;
             }
-            if changed {
-                let _ = state.save();
+            if info.task_name.is_none() {
+                info.task_name = info.task_id.clone();
+                changed = true;
             }
-
-            Ok(state)
-        } else {
-            Ok(Self::default())
         }
+        changed
     }
 
     pub fn save(&self) -> Result<()> {
@@ -113,7 +225,22 @@ impl XlaudeState {
             fs::create_dir_all(parent).context("Failed to create config directory")?;
         }
         let content = serde_json::to_string_pretty(self).context("Failed to serialize state")?;
-        fs::write(&config_path, content).context("Failed to write config file")?;
+        // Write a sibling file and rename it over the state so a crash mid-write
+        // never leaves a truncated state.json behind
+        let tmp_path = config_path.with_extension("json.tmp");
+        fs::write(&tmp_path, content).context("Failed to write config file")?;
+        fs::rename(&tmp_path, &config_path).context("Failed to write config file")?;
+        Ok(())
+    }
+
+    /// Stamp `last_agent_activity` on the worktree stored under `key` and save.
+    /// Worktrees that are no longer managed are ignored.
+    pub fn record_activity(key: &str) -> Result<()> {
+        let mut state = Self::load()?;
+        if let Some(info) = state.worktrees.get_mut(key) {
+            info.last_agent_activity = Some(Utc::now());
+            state.save()?;
+        }
         Ok(())
     }
 
@@ -160,8 +287,77 @@ fn get_config_path() -> Result<PathBuf> {
     Ok(get_config_dir()?.join("state.json"))
 }
 
+/// Schema version of a state file written by a newer agentdev, if that is
+/// what `content` is.
+fn newer_schema_version(content: &str) -> Option<u64> {
+    serde_json::from_str::<Value>(content)
+        .ok()?
+        .get("schema_version")?
+        .as_u64()
+        .filter(|&version| version > u64::from(CURRENT_SCHEMA_VERSION))
+}
+
+/// Move an unreadable state file to `state.json.bak`, or `state.json.bak.N`
+/// when earlier backups exist, and return where it went.
+fn back_up_corrupt_state(path: &Path) -> Result<PathBuf> {
+    let mut backup = path.with_extension("json.bak");
+    let mut index = 1;
+    while backup.exists() {
+        backup = path.with_extension(format!("json.bak.{index}"));
+        index += 1;
+    }
+    fs::rename(path, &backup).with_context(|| {
+        format!(
+            "State file {} is corrupt and could not be moved to {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    Ok(backup)
+}
+
 /// Resolve the agent command from state with a sensible default.
 /// Returns the full command line string (not split).
 pub fn get_default_agent() -> String {
     "claude --dangerously-skip-permissions".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(version: u32) -> Value {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/state")
+            .join(format!("v{version}.json"));
+        let content = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("missing fixture {}: {err}", path.display()));
+        serde_json::from_str(&content).unwrap()
+    }
+
+    #[test]
+    fn each_migration_matches_its_fixture() {
+        let mut previous = 0;
+        for &(version, migrate) in MIGRATIONS {
+            assert!(version > previous, "migration versions must increase");
+            let mut state = fixture(previous);
+            migrate(&mut state).unwrap();
+            state["schema_version"] = Value::from(version);
+            assert_eq!(state, fixture(version), "migration to v{version}");
+            previous = version;
+        }
+        assert_eq!(previous, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn apply_migrations_upgrades_legacy_state_once() {
+        let mut state = fixture(0);
+        assert!(apply_migrations(&mut state).unwrap());
+        assert_eq!(state["schema_version"], CURRENT_SCHEMA_VERSION);
+        assert!(!apply_migrations(&mut state).unwrap());
+
+        let state: XlaudeState = serde_json::from_value(state).unwrap();
+        assert_eq!(state.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(state.worktrees.contains_key("another-repo/bugfix"));
+    }
+}
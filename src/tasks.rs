@@ -0,0 +1,216 @@
+//! Task-centric view over managed worktrees.
+//!
+//! A task is the set of worktrees sharing a `task_id`, typically created
+//! together by `POST /api/tasks` with one worktree per agent.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::state::XlaudeState;
+use crate::tmux::TmuxManager;
+
+/// Runtime state of a single agent participating in a task.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TaskMemberStatus {
+    /// The agent's tmux session is alive.
+    Running,
+    /// The worktree exists but no tmux session is attached to it.
+    Stopped,
+    /// The worktree directory no longer exists on disk.
+    Missing,
+}
+
+impl TaskMemberStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskMember {
+    pub worktree_id: String,
+    pub worktree_name: String,
+    pub branch: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_alias: Option<String>,
+    pub status: TaskMemberStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskSummary {
+    pub task_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_prompt: Option<String>,
+    /// Creation time of the earliest member worktree.
+    pub created_at: DateTime<Utc>,
+    pub members: Vec<TaskMember>,
+}
+
+impl TaskSummary {
+    /// A lone worktree whose task id is just its own name.
+    pub fn is_implicit(&self) -> bool {
+        matches!(self.members.as_slice(), [only] if only.worktree_name == self.task_id)
+    }
+
+    /// Members whose agent has a live tmux session.
+    pub fn running_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.status == TaskMemberStatus::Running)
+            .count()
+    }
+}
+
+/// Group managed worktrees by `task_id`, newest task first.
+///
+/// State loading backfills `task_id` with the worktree name, so every plain
+/// worktree forms an implicit single-member task. Those are skipped unless
+/// `include_implicit` is set.
+///
+/// Members are probed for a live tmux session only when tmux is available;
+/// otherwise existing worktrees are reported as stopped.
+pub fn collect_tasks(state: &XlaudeState, include_implicit: bool) -> Vec<TaskSummary> {
+    let tmux = TmuxManager::is_available().then(TmuxManager::new);
+    let mut grouped: BTreeMap<String, TaskSummary> = BTreeMap::new();
+
+    for (key, info) in &state.worktrees {
+        let Some(task_id) = info.task_id.as_ref() else {
+            continue;
+        };
+
+        let status = if !info.path.exists() {
+            TaskMemberStatus::Missing
+        } else if tmux
+            .as_ref()
+            .is_some_and(|tmux| tmux.session_exists(&info.name))
+        {
+            TaskMemberStatus::Running
+        } else {
+            TaskMemberStatus::Stopped
+        };
+
+        let task = grouped
+            .entry(task_id.clone())
+            .or_insert_with(|| TaskSummary {
+                task_id: task_id.clone(),
+                task_name: None,
+                initial_prompt: None,
+                created_at: info.created_at,
+                members: Vec::new(),
+            });
+        if task.task_name.is_none() {
+            task.task_name = info.task_name.clone();
+        }
+        if task.initial_prompt.is_none() {
+            task.initial_prompt = info.initial_prompt.clone();
+        }
+        task.created_at = task.created_at.min(info.created_at);
+        task.members.push(TaskMember {
+            worktree_id: key.clone(),
+            worktree_name: info.name.clone(),
+            branch: info.branch.clone(),
+            path: info.path.display().to_string(),
+            agent_alias: info.agent_alias.clone(),
+            status,
+        });
+    }
+
+    let mut tasks: Vec<TaskSummary> = grouped
+        .into_values()
+        .filter(|task| include_implicit || !task.is_implicit())
+        .collect();
+    for task in &mut tasks {
+        task.members.sort_by(|a, b| {
+            a.agent_alias
+                .cmp(&b.agent_alias)
+                .then_with(|| a.worktree_name.cmp(&b.worktree_name))
+        });
+    }
+    tasks.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.task_id.cmp(&b.task_id))
+    });
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::WorktreeInfo;
+    use chrono::Duration;
+    use std::path::PathBuf;
+
+    fn info(name: &str, task_id: Option<&str>, alias: &str, age_hours: i64) -> WorktreeInfo {
+        WorktreeInfo {
+            name: name.to_string(),
+            branch: name.to_string(),
+            path: PathBuf::from("/nonexistent/agentdev-tasks-test").join(name),
+            repo_name: "repo".to_string(),
+            created_at: Utc::now() - Duration::hours(age_hours),
+            task_id: task_id.map(str::to_string),
+            task_name: task_id.map(|id| format!("task {id}")),
+            initial_prompt: task_id.map(|_| "Fix the login form".to_string()),
+            agent_alias: Some(alias.to_string()),
+            locked: false,
+            lock_reason: None,
+            last_agent_activity: None,
+            base_ref: None,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
+        }
+    }
+
+    #[test]
+    fn groups_worktrees_by_task_newest_first() {
+        let mut state = XlaudeState::default();
+        for info in [
+            info("old-codex", Some("old"), "codex", 48),
+            info("new-codex", Some("new"), "codex", 2),
+            info("new-claude", Some("new"), "claude", 1),
+            info("standalone", None, "claude", 0),
+            info("implicit", Some("implicit"), "claude", 0),
+        ] {
+            let key = XlaudeState::make_key(&info.repo_name, &info.name);
+            state.worktrees.insert(key, info);
+        }
+
+        let tasks = collect_tasks(&state, false);
+        let ids: Vec<_> = tasks.iter().map(|t| t.task_id.as_str()).collect();
+        assert_eq!(ids, ["new", "old"]);
+        assert_eq!(collect_tasks(&state, true).len(), 3);
+
+        let newest = &tasks[0];
+        assert_eq!(newest.task_name.as_deref(), Some("task new"));
+        assert_eq!(
+            newest.created_at,
+            state.worktrees["repo/new-codex"].created_at
+        );
+        let aliases: Vec<_> = newest
+            .members
+            .iter()
+            .map(|m| m.agent_alias.as_deref().unwrap())
+            .collect();
+        assert_eq!(aliases, ["claude", "codex"]);
+        assert!(
+            newest
+                .members
+                .iter()
+                .all(|m| m.status == TaskMemberStatus::Missing)
+        );
+    }
+}
@@ -1,7 +1,76 @@
 use anyhow::{Context, Result};
 use std::fs;
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::clipboard;
+
+/// Env vars overriding the typing pace used for agents without their own settings.
+pub const TYPE_CHUNK_ENV: &str = "AGENTDEV_SLOW_TYPE_CHUNK";
+pub const TYPE_DELAY_MS_ENV: &str = "AGENTDEV_SLOW_TYPE_DELAY_MS";
+
+/// Named tmux buffer used by [`TmuxManager::paste_text`], so the user's own
+/// paste buffers are left alone.
+const PASTE_BUFFER: &str = "agentdev-clipboard";
+
+/// How literal text is typed into a pane. Without a chunk size the whole text
+/// goes out in a single `send-keys`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypingPace {
+    /// Characters per `send-keys` call
+    pub chunk: Option<usize>,
+    /// Pause between chunks
+    pub delay: Duration,
+    /// Pause between the last chunk and Enter
+    pub enter_delay: Duration,
+}
+
+impl TypingPace {
+    /// Global default from `AGENTDEV_SLOW_TYPE_CHUNK` / `AGENTDEV_SLOW_TYPE_DELAY_MS`.
+    pub fn from_env() -> Self {
+        Self::default().with_env_overrides()
+    }
+
+    /// This pace with `AGENTDEV_SLOW_TYPE_CHUNK` / `AGENTDEV_SLOW_TYPE_DELAY_MS`
+    /// applied on top, where set.
+    pub fn with_env_overrides(self) -> Self {
+        let read = |key| {
+            std::env::var(key)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        Self {
+            chunk: read(TYPE_CHUNK_ENV)
+                .filter(|&chunk| chunk > 0)
+                .map(|chunk| chunk as usize)
+                .or(self.chunk),
+            delay: read(TYPE_DELAY_MS_ENV).map_or(self.delay, Duration::from_millis),
+            ..self
+        }
+    }
+
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let Some(chunk) = self.chunk else {
+            return vec![text];
+        };
+        let mut pieces = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let end = rest
+                .char_indices()
+                .nth(chunk)
+                .map_or(rest.len(), |(index, _)| index);
+            let (piece, tail) = rest.split_at(end);
+            pieces.push(piece);
+            rest = tail;
+        }
+        pieces
+    }
+}
 
 pub struct TmuxManager {
     session_prefix: String,
@@ -124,6 +193,30 @@ impl TmuxManager {
             .unwrap_or(false)
     }
 
+    /// Whether a session exists but every pane's program has exited
+    /// (only possible when `remain-on-exit` keeps dead panes around).
+    pub fn session_is_dead(&self, project: &str) -> bool {
+        let session_name = self.make_session_name(project);
+
+        let Ok(output) = Command::new("tmux")
+            .args([
+                "list-panes",
+                "-s",
+                "-t",
+                &session_name,
+                "-F",
+                "#{pane_dead}",
+            ])
+            .output()
+        else {
+            return false;
+        };
+        let panes = String::from_utf8_lossy(&output.stdout);
+        output.status.success()
+            && !panes.trim().is_empty()
+            && panes.lines().all(|line| line.trim() == "1")
+    }
+
     /// Attach to a session
     pub fn attach_session(&self, project: &str) -> Result<()> {
         let session_name = self.make_session_name(project);
@@ -235,8 +328,41 @@ impl TmuxManager {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    /// Send literal text to the session's pane
+    /// Capture recent output prepared for display in a fixed-width preview.
+    ///
+    /// Escape sequences are stripped and each line is clamped to `max_width`
+    /// columns. Set `AGENTDEV_PREVIEW_RAW=1` to keep the capture untouched.
+    pub fn capture_pane_preview(
+        &self,
+        project: &str,
+        lines: usize,
+        max_width: usize,
+    ) -> Result<Vec<String>> {
+        let raw = self.capture_pane(project, lines)?;
+        if std::env::var("AGENTDEV_PREVIEW_RAW").is_ok_and(|v| v == "1" || v == "true") {
+            return Ok(raw.lines().map(str::to_string).collect());
+        }
+        Ok(normalize_capture(&raw, max_width))
+    }
+
+    /// Send literal text to the session's pane at the pace from the environment
     pub fn send_text(&self, project: &str, text: &str) -> Result<()> {
+        self.send_text_paced(project, text, TypingPace::from_env())
+    }
+
+    /// Send literal text in chunks, pausing between them, for agents that drop
+    /// input typed too quickly
+    pub fn send_text_paced(&self, project: &str, text: &str, pace: TypingPace) -> Result<()> {
+        for (index, piece) in pace.split(text).into_iter().enumerate() {
+            if index > 0 && !pace.delay.is_zero() {
+                thread::sleep(pace.delay);
+            }
+            self.send_literal(project, piece)?;
+        }
+        Ok(())
+    }
+
+    fn send_literal(&self, project: &str, text: &str) -> Result<()> {
         let session_name = self.make_session_name(project);
         let output = Command::new("tmux")
             .args(["send-keys", "-t", &session_name, "-l", text])
@@ -263,6 +389,15 @@ Original tmux error: {err}",
         Ok(())
     }
 
+    /// Send Enter once the pace's `enter_delay` has passed, so agents that
+    /// debounce input see the whole prompt first
+    pub fn send_enter_paced(&self, project: &str, pace: TypingPace) -> Result<()> {
+        if !pace.enter_delay.is_zero() {
+            thread::sleep(pace.enter_delay);
+        }
+        self.send_enter(project)
+    }
+
     /// Send Enter key
     pub fn send_enter(&self, project: &str) -> Result<()> {
         let session_name = self.make_session_name(project);
@@ -290,6 +425,67 @@ Original tmux error: {err}",
         Ok(())
     }
 
+    /// Paste the system clipboard into the session's pane.
+    ///
+    /// The text goes through a tmux buffer rather than `send-keys`, so long or
+    /// multi-line content arrives as one bracketed paste and is not submitted
+    /// line by line.
+    pub fn send_clipboard(&self, project: &str) -> Result<()> {
+        let content = clipboard::read_clipboard()?;
+        if content.is_empty() {
+            anyhow::bail!("Clipboard is empty");
+        }
+        self.paste_text(project, &content)
+    }
+
+    /// Load `text` into a tmux buffer and paste it into the session's pane.
+    pub fn paste_text(&self, project: &str, text: &str) -> Result<()> {
+        let session_name = self.make_session_name(project);
+        if !self.session_exists(project) {
+            anyhow::bail!("tmux session '{session_name}' is not running");
+        }
+
+        let mut child = Command::new("tmux")
+            .args(["load-buffer", "-b", PASTE_BUFFER, "-"])
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to load tmux buffer")?;
+        child
+            .stdin
+            .take()
+            .context("Failed to open tmux stdin")?
+            .write_all(text.as_bytes())
+            .context("Failed to write to tmux buffer")?;
+        let output = child
+            .wait_with_output()
+            .context("Failed to load tmux buffer")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to load tmux buffer: {}", stderr.trim());
+        }
+
+        // -d drops the buffer afterwards, -p pastes as bracketed paste when
+        // the application asked for it
+        let output = Command::new("tmux")
+            .args([
+                "paste-buffer",
+                "-d",
+                "-p",
+                "-b",
+                PASTE_BUFFER,
+                "-t",
+                &session_name,
+            ])
+            .output()
+            .context("Failed to paste into tmux session")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to paste into tmux session: {}", stderr.trim());
+        }
+        Ok(())
+    }
+
     /// Configure key bindings for a specific session
     fn configure_session_keys(&self, session_name: &str) -> Result<()> {
         // Set Ctrl+Q to detach (session-specific)
@@ -503,6 +699,93 @@ set -g mouse on"##;
     }
 }
 
+/// Strip ANSI escape sequences and control characters from captured pane
+/// output, then clamp every line to `max_width` display columns.
+///
+/// Carriage returns rewind to the start of the line the way a terminal would,
+/// so progress spinners collapse to their final frame. Tabs expand to the next
+/// multiple of 8 columns.
+pub fn normalize_capture(raw: &str, max_width: usize) -> Vec<String> {
+    raw.lines()
+        .map(|line| clamp_width(&strip_ansi(line), max_width))
+        .collect()
+}
+
+/// Lines of `current` not already shown by `previous`, for tailing a pane.
+///
+/// Two captures of the same pane overlap where the older one's tail is the
+/// newer one's head once output has scrolled; everything after the longest
+/// such overlap is new. Without any overlap (the screen was redrawn or
+/// scrolled past the capture window) the whole capture counts as new.
+pub fn new_capture_lines<'a>(previous: &[String], current: &'a [String]) -> &'a [String] {
+    let max_overlap = previous.len().min(current.len());
+    let overlap = (1..=max_overlap)
+        .rev()
+        .find(|&len| previous[previous.len() - len..] == current[..len])
+        .unwrap_or(0);
+    &current[overlap..]
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters and intermediates, terminated by 0x40..=0x7e
+                Some('[') => {
+                    for next in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+                // OSC / DCS / APC / PM: terminated by BEL or ST (ESC \)
+                Some(']' | 'P' | '_' | '^') => {
+                    while let Some(next) = chars.next() {
+                        if next == '\x07' {
+                            break;
+                        }
+                        if next == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                // Charset designation takes one more byte
+                Some('(' | ')' | '*' | '+') => {
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\r' => out.clear(),
+            '\t' => {
+                let column = UnicodeWidthStr::width(out.as_str());
+                out.extend(std::iter::repeat_n(' ', 8 - column % 8));
+            }
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn clamp_width(line: &str, max_width: usize) -> String {
+    let mut width = 0;
+    let mut out = String::with_capacity(line.len().min(max_width.saturating_mul(4)));
+    for c in line.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + w > max_width {
+            break;
+        }
+        width += w;
+        out.push(c);
+    }
+    out
+}
+
 #[derive(Debug, Clone)]
 pub struct SessionInfo {
     pub project: String,
@@ -572,3 +855,54 @@ impl SessionInfo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typing_pace_splits_on_char_boundaries() {
+        let pace = TypingPace {
+            chunk: Some(2),
+            ..TypingPace::default()
+        };
+        assert_eq!(pace.split("héllo"), ["hé", "ll", "o"]);
+        assert_eq!(TypingPace::default().split("hello"), ["hello"]);
+    }
+
+    #[test]
+    fn new_capture_lines_skips_the_overlap_with_the_previous_capture() {
+        let lines = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let previous = lines(&["a", "b", "c"]);
+        assert_eq!(
+            new_capture_lines(&previous, &lines(&["b", "c", "d", "e"])),
+            lines(&["d", "e"])
+        );
+        assert!(new_capture_lines(&previous, &previous).is_empty());
+        assert_eq!(
+            new_capture_lines(&previous, &lines(&["x", "y"])),
+            lines(&["x", "y"])
+        );
+        assert_eq!(new_capture_lines(&[], &previous), previous);
+    }
+
+    #[test]
+    fn normalize_capture_strips_escape_sequences() {
+        let raw = "\x1b[1;32mok\x1b[0m done\n\x1b]0;title\x07prompt \x1b[2K\x1b[1Gready\n";
+        assert_eq!(normalize_capture(raw, 80), vec!["ok done", "prompt ready"]);
+    }
+
+    #[test]
+    fn normalize_capture_handles_carriage_returns_and_tabs() {
+        let raw = "50%\r100%\na\tb";
+        assert_eq!(normalize_capture(raw, 80), vec!["100%", "a       b"]);
+    }
+
+    #[test]
+    fn normalize_capture_clamps_to_display_width() {
+        assert_eq!(normalize_capture("abcdefgh", 5), vec!["abcde"]);
+        // Wide characters are never split across the boundary
+        assert_eq!(normalize_capture("日本語", 5), vec!["日本"]);
+        assert_eq!(normalize_capture("abc", usize::MAX), vec!["abc"]);
+    }
+}
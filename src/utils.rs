@@ -1,7 +1,15 @@
 use anyhow::{Context, Result};
 use rand::seq::SliceRandom;
 use rand::{RngCore, SeedableRng};
+use std::io::Read;
 use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds a git or agentdev subprocess may run before it is killed; `0` disables the limit.
+pub const COMMAND_TIMEOUT_ENV: &str = "AGENTDEV_GIT_TIMEOUT_SECS";
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 300;
 
 pub fn generate_random_name() -> Result<String> {
     // Allow setting seed for testing
@@ -39,6 +47,11 @@ pub fn sanitize_branch_name(branch: &str) -> String {
     branch.replace('/', "-")
 }
 
+/// Quote `value` as a single POSIX shell word, always in single quotes.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 pub fn execute_in_dir<P, F, R>(path: P, f: F) -> Result<R>
 where
     P: AsRef<Path>,
@@ -85,3 +98,103 @@ pub fn resolve_agent_command_with_override(
 
     Ok((program, args))
 }
+
+/// Subprocess timeout from `AGENTDEV_GIT_TIMEOUT_SECS`, `None` when disabled.
+pub fn command_timeout() -> Option<Duration> {
+    let secs = std::env::var(COMMAND_TIMEOUT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Like [`Command::output`], but kill the child once `timeout` elapses.
+pub fn output_with_timeout(command: &mut Command, timeout: Option<Duration>) -> Result<Output> {
+    let Some(timeout) = timeout else {
+        return command.output().map_err(Into::into);
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain both pipes on threads so a chatty child cannot block on a full pipe
+    let stdout = drain_pipe(child.stdout.take());
+    let stderr = drain_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let mut poll = Duration::from_millis(1);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!(
+                "timed out after {}s (raise {COMMAND_TIMEOUT_ENV} to allow longer)",
+                timeout.as_secs_f32()
+            );
+        }
+        thread::sleep(poll);
+        poll = (poll * 2).min(Duration::from_millis(50));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn drain_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_with_timeout_kills_slow_commands() {
+        let started = Instant::now();
+        let err = output_with_timeout(
+            Command::new("sh").args(["-c", "sleep 5"]),
+            Some(Duration::from_millis(100)),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(4));
+
+        let output = output_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2"]),
+            Some(Duration::from_secs(5)),
+        )
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[test]
+    fn shell_quote_survives_spaces_and_single_quotes() {
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("/work/my repo"), "'/work/my repo'");
+        assert_eq!(shell_quote("/work/it's"), r"'/work/it'\''s'");
+
+        for path in ["/work/my repo", "/work/it's here", "/work/$HOME `x`"] {
+            let output = Command::new("sh")
+                .args(["-c", &format!("printf %s {}", shell_quote(path))])
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), path);
+        }
+    }
+}
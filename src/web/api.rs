@@ -2,41 +2,55 @@ use anyhow::{Error, Result, anyhow};
 use axum::{
     Json,
     extract::{Path as AxumPath, Query},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
 };
 use directories::BaseDirs;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 use crate::{
+    audit::AUDIT_SOURCE_ENV,
+    claude_status::{AgentActivity, ClaudeStatusDetector, PanelStatus, to_panel_status},
+    config::{agent_config_path, load_agent_config, resolve_typing_pace, split_cmdline},
+    conflict_detector::{CrossWorktreeConflict, detect_cross_worktree_conflicts},
     discovery::{
         DiscoveryOptions, add_discovered_to_state,
         discover_worktrees as discover_unmanaged_worktrees,
     },
+    disk_usage::worktree_size,
     git::{
         CommitsAhead, HeadCommitInfo, WorktreeGitStatus, collect_worktree_diff_breakdown,
-        commits_since_merge_base, head_commit_info, summarize_worktree_status,
+        commits_since_merge_base, commits_since_ref, default_branch, get_diff_for_path,
+        get_staged_diff_for_path, git_metadata_present, head_commit_info, list_checkpoint_tags,
+        resolve_main_repo_dir, summarize_worktree_status,
     },
     process_registry::{
-        MAX_PROCESSES_PER_WORKTREE, ProcessRecord, ProcessRegistry,
+        FINISHED_PROCESS_MAX_AGE, MAX_PROCESSES_PER_WORKTREE, ProcessRecord, ProcessRegistry,
         ProcessStatus as RegistryProcessStatus, canonicalize_cwd,
     },
     sessions::{
-        SessionEvent, SessionProvider, SessionRecord, canonicalize as canonicalize_session_path,
-        default_providers,
+        SessionEvent, SessionEventDisplay, SessionProvider, SessionRecord,
+        canonicalize as canonicalize_session_path, dedup_sessions_by, default_providers,
+        find_session,
     },
     state::{WorktreeInfo, XlaudeState},
+    tasks::{TaskSummary, collect_tasks},
+    tmux::{TmuxManager, TypingPace},
+    utils::{command_timeout, output_with_timeout, sanitize_branch_name},
 };
 use rayon::prelude::*;
 
+use super::metrics;
+
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeSessionSummary {
     pub provider: String,
     pub session_id: String,
@@ -47,6 +61,7 @@ pub struct WorktreeSessionSummary {
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SessionSummaryPayload {
     pub provider: String,
     pub session_id: String,
@@ -69,8 +84,15 @@ pub struct SessionSummaryPayload {
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeGitStatusPayload {
-    pub branch: String,
+    /// `None` when HEAD is detached
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub detached: bool,
+    /// Abbreviated HEAD commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
     pub upstream: Option<String>,
     pub ahead: u32,
     pub behind: u32,
@@ -82,20 +104,51 @@ pub struct WorktreeGitStatusPayload {
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeCommitPayload {
     pub commit_id: String,
     pub summary: String,
     pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_email: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeCommitsAheadPayload {
     pub base_branch: String,
     pub merge_base: Option<String>,
     pub commits: Vec<WorktreeCommitPayload>,
 }
 
+/// What the agent in a worktree's tmux session appears to be doing.
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AgentActivityPayload {
+    /// `waiting_for_input`, `processing`, `error`, or `idle`
+    pub status: String,
+    pub waiting_for_input: bool,
+    pub running_tool: bool,
+    pub idle: bool,
+    pub last_line: Option<String>,
+}
+
+impl From<AgentActivity> for AgentActivityPayload {
+    fn from(activity: AgentActivity) -> Self {
+        Self {
+            status: activity.status.as_str().to_string(),
+            waiting_for_input: activity.waiting_for_input,
+            running_tool: activity.running_tool,
+            idle: activity.idle,
+            last_line: activity.last_line,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeSummary {
     pub id: String,
     pub name: String,
@@ -108,13 +161,33 @@ pub struct WorktreeSummary {
     pub task_name: Option<String>,
     pub initial_prompt: Option<String>,
     pub agent_alias: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_reason: Option<String>,
+    /// Pinned worktrees are listed first.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Free-text annotations from `worktree note` or `PATCH /api/worktrees/:id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Symlink created by `worktree link` that points at this worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symlink_path: Option<String>,
     pub git_status: Option<WorktreeGitStatusPayload>,
     pub head_commit: Option<WorktreeCommitPayload>,
     pub commits_ahead: Option<WorktreeCommitsAheadPayload>,
+    /// Disk usage; only filled in by `GET /api/worktrees/:id/size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Present while the worktree has a live agent tmux session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_activity: Option<AgentActivityPayload>,
     pub sessions: Vec<WorktreeSessionSummary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WorktreeProcessStatus {
     Pending,
@@ -125,6 +198,7 @@ pub enum WorktreeProcessStatus {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeProcessSummary {
     pub id: String,
     pub command: Vec<String>,
@@ -138,14 +212,20 @@ pub struct WorktreeProcessSummary {
     pub stdout: Option<String>,
     #[serde(default)]
     pub stderr: Option<String>,
+    /// Process this one retries
+    #[serde(default)]
+    pub parent_id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeProcessListResponse {
     pub processes: Vec<WorktreeProcessSummary>,
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct WorktreeDiscoveryQuery {
     #[serde(default)]
     pub recursive: Option<bool>,
@@ -154,6 +234,7 @@ pub struct WorktreeDiscoveryQuery {
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LaunchWorktreeCommandRequest {
     pub command: String,
     #[serde(default)]
@@ -161,17 +242,27 @@ pub struct LaunchWorktreeCommandRequest {
 }
 
 #[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LaunchWorktreeCommandResponse {
     pub process: WorktreeProcessSummary,
 }
 
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LaunchTaskCommandResponse {
+    /// One process per member worktree, ordered by worktree id.
+    pub processes: Vec<WorktreeProcessSummary>,
+}
+
 #[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LaunchWorktreeShellRequest {
     #[serde(default)]
     pub command: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LaunchShellRequest {
     pub path: String,
     #[serde(default)]
@@ -179,11 +270,56 @@ pub struct LaunchShellRequest {
 }
 
 #[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LaunchWorktreeShellResponse {
     pub status: &'static str,
 }
 
+#[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GcRequest {
+    /// Remove finished processes older than this many hours (default: 168).
+    #[serde(default)]
+    pub max_age_hours: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GcResponse {
+    pub removed_processes: usize,
+    pub remaining_processes: usize,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateWorktreeRequest {
+    /// Replacement notes. Omit to leave them unchanged; an empty string clears them.
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OpenEditorResponse {
+    pub status: &'static str,
+    pub editor: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PasteClipboardResponse {
+    pub status: &'static str,
+    pub session: String,
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum MergeStrategyOption {
     FfOnly,
@@ -208,6 +344,7 @@ impl MergeStrategyOption {
 }
 
 #[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MergeWorktreeRequest {
     #[serde(default)]
     pub strategy: Option<MergeStrategyOption>,
@@ -215,9 +352,16 @@ pub struct MergeWorktreeRequest {
     pub push: bool,
     #[serde(default)]
     pub cleanup: bool,
+    /// Remote to fetch from and push to (defaults to `origin`).
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Make the default branch track the remote after pushing.
+    #[serde(default)]
+    pub set_upstream: bool,
 }
 
 #[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MergeWorktreeResponse {
     pub exit_code: i32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -227,12 +371,14 @@ pub struct MergeWorktreeResponse {
 }
 
 #[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DeleteWorktreeRequest {
     #[serde(default)]
     pub force: bool,
 }
 
 #[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DeleteWorktreeResponse {
     pub exit_code: i32,
     pub removed: bool,
@@ -242,8 +388,87 @@ pub struct DeleteWorktreeResponse {
     pub stderr: Option<String>,
 }
 
+#[derive(Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RebaseWorktreeRequest {
+    /// Branch or commit to rebase onto (defaults to the repository's default branch).
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Interactive rebases need a terminal and are rejected.
+    #[serde(default)]
+    pub interactive: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RebaseWorktreeResponse {
+    pub base: String,
+    pub head: Option<WorktreeCommitPayload>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RebaseConflictPayload {
+    pub message: String,
+    /// Files that could not be rebased cleanly, relative to the worktree root.
+    pub conflicts: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateTaskRequest {
+    /// Path to the repository the task worktrees are created from.
+    pub repo_path: String,
+    /// Human-friendly task name; used as the worktree name prefix.
+    #[serde(default)]
+    pub task_name: Option<String>,
+    /// Prompt sent to every agent once its session starts.
+    pub prompt: String,
+    /// Agent aliases from `config.toml`; one worktree is created per alias.
+    pub agents: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateWorktreeRequest {
+    /// Worktree and branch name; the CLI picks a random word when omitted.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Repository path, or the name of a repository that already has managed
+    /// worktrees.
+    pub repo: String,
+    /// Ref to branch off instead of the repository's current HEAD.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Agent alias from `config.toml` to start in the new worktree.
+    #[serde(default)]
+    pub agent: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskWorktreePayload {
+    pub worktree_id: String,
+    pub worktree_name: String,
+    pub agent_alias: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmux_session: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateTaskResponse {
+    pub task_id: String,
+    pub task_name: String,
+    pub initial_prompt: String,
+    pub worktrees: Vec<TaskWorktreePayload>,
+}
+
 #[derive(Serialize, Clone, Debug)]
-struct CommandFailurePayload {
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub(crate) struct CommandFailurePayload {
     pub message: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stdout: Option<String>,
@@ -265,18 +490,128 @@ impl CommandFailurePayload {
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeListResponse {
     pub worktrees: Vec<WorktreeSummary>,
 }
 
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeStatusFilter {
+    Clean,
+    Dirty,
+}
+
+/// Filters for `GET /api/worktrees/search`; every filter that is set must match.
+#[derive(Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct WorktreeSearchQuery {
+    /// Case-insensitive text matched against name, repo, branch and task id.
+    #[serde(default)]
+    pub q: Option<String>,
+    /// Exact branch name.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Exact repository name.
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// Git working tree state; worktrees whose status is unknown never match.
+    #[serde(default)]
+    pub status: Option<WorktreeStatusFilter>,
+    /// Whether the worktree has a live agent tmux session.
+    #[serde(default)]
+    pub has_session: Option<bool>,
+}
+
+impl WorktreeSearchQuery {
+    fn matches(&self, summary: &WorktreeSummary) -> bool {
+        let text_matches = match self.q.as_deref().map(str::trim) {
+            None | Some("") => true,
+            Some(text) => {
+                let needle = text.to_lowercase();
+                [
+                    Some(summary.name.as_str()),
+                    Some(summary.repo_name.as_str()),
+                    Some(summary.branch.as_str()),
+                    summary.task_id.as_deref(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(&needle))
+            }
+        };
+        let status_matches = self.status.is_none_or(|wanted| {
+            summary
+                .git_status
+                .as_ref()
+                .is_some_and(|status| status.is_clean == (wanted == WorktreeStatusFilter::Clean))
+        });
+
+        text_matches
+            && status_matches
+            && self
+                .branch
+                .as_ref()
+                .is_none_or(|branch| summary.branch == *branch)
+            && self
+                .repo
+                .as_ref()
+                .is_none_or(|repo| summary.repo_name == *repo)
+            && self
+                .has_session
+                .is_none_or(|wanted| summary.agent_activity.is_some() == wanted)
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct SessionListQuery {
+    /// Collapse copies of the same session, keeping the one with the most events.
+    #[serde(default)]
+    pub dedup: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct TaskListQuery {
+    /// Include single worktrees that are not part of a multi-agent task.
+    #[serde(default)]
+    pub all: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskListResponse {
+    pub tasks: Vec<TaskSummary>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SessionListResponse {
     pub sessions: Vec<SessionSummaryPayload>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub providers: Vec<ProviderSessionsPayload>,
+    /// Providers whose sessions could not be listed; their sessions are missing.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_errors: Vec<ProviderErrorPayload>,
+    /// Transcripts of the listed sessions, only when a `mode` was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<SessionDetailPayload>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderErrorPayload {
+    pub provider: String,
+    pub message: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProviderSessionsPayload {
     pub provider: String,
     pub session_count: usize,
@@ -287,6 +622,7 @@ pub struct ProviderSessionsPayload {
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SessionDetailMode {
     UserOnly,
@@ -301,12 +637,24 @@ impl Default for SessionDetailMode {
 }
 
 #[derive(Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct SessionDetailQuery {
     #[serde(default)]
     pub mode: Option<SessionDetailMode>,
 }
 
+#[derive(Serialize, Clone, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteSessionResponse {
+    pub provider: String,
+    pub session_id: String,
+    /// Where the session's log files were moved.
+    pub archived: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SessionDetailPayload {
     pub provider: String,
     pub session_id: String,
@@ -319,12 +667,14 @@ pub struct SessionDetailPayload {
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeCommitDiffPayload {
     pub reference: String,
     pub diff: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeFileDiffPayload {
     pub path: String,
     pub display_path: String,
@@ -332,7 +682,66 @@ pub struct WorktreeFileDiffPayload {
     pub diff: String,
 }
 
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct WorktreeSizeQuery {
+    /// Leave gitignored files (e.g. build output) out of the total.
+    #[serde(default)]
+    pub respect_gitignore: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct WorktreeDiffQuery {
+    /// Return only staged changes instead of the full worktree diff.
+    #[serde(default)]
+    pub staged: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct WorktreeCommitsAheadQuery {
+    /// Ref to compare HEAD against, e.g. `origin/release` or a commit id.
+    pub base: String,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct WorktreeFileQuery {
+    /// File path relative to the worktree root.
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WorktreeSizePayload {
+    pub worktree_id: String,
+    pub size_bytes: u64,
+    pub respect_gitignore: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CheckpointPayload {
+    pub name: String,
+    pub commit_id: String,
+    pub message: String,
+    pub tagged_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WorktreeCheckpointsPayload {
+    pub worktree_id: String,
+    pub checkpoints: Vec<CheckpointPayload>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WorktreeGitDetailsPayload {
     pub commit_diff: Option<WorktreeCommitDiffPayload>,
     pub staged: Vec<WorktreeFileDiffPayload>,
@@ -344,6 +753,8 @@ impl From<WorktreeGitStatus> for WorktreeGitStatusPayload {
     fn from(value: WorktreeGitStatus) -> Self {
         Self {
             branch: value.branch,
+            detached: value.detached,
+            head: value.head,
             upstream: value.upstream,
             ahead: value.ahead,
             behind: value.behind,
@@ -362,6 +773,8 @@ impl From<HeadCommitInfo> for WorktreeCommitPayload {
             commit_id: value.commit_id,
             summary: value.summary,
             timestamp: value.timestamp,
+            author: value.author,
+            author_email: value.author_email,
         }
     }
 }
@@ -466,37 +879,8 @@ fn warn_once(operation: &str, path: &Path, render: impl FnOnce() -> String) {
     if guard.insert(key) {
         let message = render();
         drop(guard);
-        eprintln!("{message}");
-    }
-}
-
-fn git_metadata_present(path: &Path) -> bool {
-    let git_entry = path.join(".git");
-    if git_entry.is_dir() {
-        return true;
+        tracing::warn!(operation, path = %path.display(), "{message}");
     }
-    if git_entry.is_file() {
-        if let Ok(contents) = fs::read_to_string(&git_entry) {
-            for line in contents.lines() {
-                if let Some(rest) = line.strip_prefix("gitdir:") {
-                    let trimmed = rest.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    let candidate = Path::new(trimmed);
-                    let resolved = if candidate.is_absolute() {
-                        candidate.to_path_buf()
-                    } else {
-                        path.join(candidate)
-                    };
-                    if resolved.exists() {
-                        return true;
-                    }
-                }
-            }
-        }
-    }
-    false
 }
 
 struct NormalizedSession {
@@ -504,6 +888,24 @@ struct NormalizedSession {
     canonical_dir: Option<PathBuf>,
 }
 
+fn archive_session(
+    provider_name: String,
+    session_id: String,
+) -> Result<Option<DeleteSessionResponse>> {
+    let Some((provider, record)) = find_session(&provider_name, &session_id)? else {
+        return Ok(None);
+    };
+    let archived = provider.delete_session(&record)?;
+    Ok(Some(DeleteSessionResponse {
+        provider: provider.name().to_string(),
+        session_id: record.id,
+        archived: archived
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect(),
+    }))
+}
+
 fn load_session_detail(
     provider_name: String,
     session_id: String,
@@ -518,25 +920,7 @@ fn load_session_detail(
         let records = provider.list_sessions()?;
         for record in records {
             if record.id == session_id {
-                let events = match mode {
-                    SessionDetailMode::Full => provider.load_session_events(&record)?,
-                    SessionDetailMode::UserOnly => user_messages_to_events(&record),
-                    SessionDetailMode::Conversation => conversation_events(&record, &provider)?,
-                };
-
-                let working_dir = record
-                    .working_dir
-                    .as_ref()
-                    .map(|dir| dir.display().to_string());
-
-                return Ok(Some(SessionDetailPayload {
-                    provider: provider.name().to_string(),
-                    session_id: record.id.clone(),
-                    last_timestamp: record.last_timestamp,
-                    working_dir,
-                    mode,
-                    events,
-                }));
+                return build_session_detail(provider.as_ref(), &record, mode).map(Some);
             }
         }
 
@@ -545,6 +929,109 @@ fn load_session_detail(
     Ok(None)
 }
 
+fn build_session_detail(
+    provider: &(dyn SessionProvider + Send + Sync),
+    record: &SessionRecord,
+    mode: SessionDetailMode,
+) -> Result<SessionDetailPayload> {
+    let mut events = match mode {
+        SessionDetailMode::Full => provider.load_session_events(record)?,
+        SessionDetailMode::UserOnly => user_messages_to_events(record),
+        SessionDetailMode::Conversation => conversation_events(record, provider)?,
+    };
+    for event in &mut events {
+        event.display = Some(SessionEventDisplay::classify(event));
+    }
+
+    let working_dir = record
+        .working_dir
+        .as_ref()
+        .map(|dir| dir.display().to_string());
+
+    Ok(SessionDetailPayload {
+        provider: provider.name().to_string(),
+        session_id: record.id.clone(),
+        last_timestamp: record.last_timestamp,
+        working_dir,
+        mode,
+        events,
+    })
+}
+
+fn collect_worktree_sessions(
+    worktree_id: String,
+    mode: Option<SessionDetailMode>,
+) -> Result<Option<SessionListResponse>> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&worktree_id) else {
+        return Ok(None);
+    };
+
+    let (external_sessions, provider_errors) = collect_external_sessions(&WorktreeProfiler::new());
+    let mut matched = match_sessions_for_worktree(info, &external_sessions);
+    matched.sort_by_key(|session| std::cmp::Reverse(session.last_timestamp));
+
+    let find_record = |provider: &str, session_id: &str| {
+        external_sessions
+            .iter()
+            .find(|session| session.record.provider == provider && session.record.id == session_id)
+    };
+
+    let sessions: Vec<SessionSummaryPayload> = matched
+        .into_iter()
+        .map(|summary| {
+            let working_dir =
+                find_record(&summary.provider, &summary.session_id).and_then(|session| {
+                    session
+                        .canonical_dir
+                        .clone()
+                        .or_else(|| session.record.working_dir.clone())
+                });
+            SessionSummaryPayload {
+                provider: summary.provider,
+                session_id: summary.session_id,
+                last_user_message: summary.last_user_message,
+                last_timestamp: summary.last_timestamp,
+                user_message_count: summary.user_message_count,
+                user_messages_preview: summary.user_messages_preview,
+                worktree_id: Some(worktree_id.clone()),
+                worktree_name: Some(info.name.clone()),
+                repo_name: Some(info.repo_name.clone()),
+                branch: Some(info.branch.clone()),
+                working_dir_exists: working_dir.as_ref().map(|dir| dir.is_dir()),
+                working_dir: working_dir.map(|dir| dir.display().to_string()),
+            }
+        })
+        .collect();
+
+    let mut details = Vec::new();
+    if let Some(mode) = mode {
+        let providers = default_providers();
+        for session in &sessions {
+            let Some(provider) = providers
+                .iter()
+                .find(|provider| provider.name().eq_ignore_ascii_case(&session.provider))
+            else {
+                continue;
+            };
+            if let Some(normalized) = find_record(&session.provider, &session.session_id) {
+                details.push(build_session_detail(
+                    provider.as_ref(),
+                    &normalized.record,
+                    mode,
+                )?);
+            }
+        }
+    }
+
+    Ok(Some(SessionListResponse {
+        providers: summarize_providers(&sessions),
+        sessions,
+        provider_errors,
+        details,
+    }))
+}
+
 fn user_messages_to_events(record: &SessionRecord) -> Vec<SessionEvent> {
     record
         .user_messages
@@ -560,13 +1047,14 @@ fn user_messages_to_events(record: &SessionRecord) -> Vec<SessionEvent> {
             timestamp: None,
             raw: None,
             tool: None,
+            display: None,
         })
         .collect()
 }
 
 fn conversation_events(
     record: &SessionRecord,
-    provider: &Box<dyn SessionProvider + Send + Sync>,
+    provider: &(dyn SessionProvider + Send + Sync),
 ) -> Result<Vec<SessionEvent>> {
     let all_events = provider.load_session_events(record)?;
     Ok(all_events
@@ -618,7 +1106,7 @@ impl WorktreeProfiler {
         }
         let start = Instant::now();
         let value = f();
-        println!("[profile/worktrees] {label} took {:?}", start.elapsed());
+        profile_event(None, label, start.elapsed());
         value
     }
 
@@ -631,7 +1119,7 @@ impl WorktreeProfiler {
         }
         let start = Instant::now();
         let result = f();
-        println!("[profile/worktrees] {label} took {:?}", start.elapsed());
+        profile_event(None, label, start.elapsed());
         result
     }
 
@@ -644,10 +1132,7 @@ impl WorktreeProfiler {
         }
         let start = Instant::now();
         let value = f();
-        println!(
-            "[profile/worktrees] {worktree_id}::{label} took {:?}",
-            start.elapsed()
-        );
+        profile_event(Some(worktree_id), label, start.elapsed());
         value
     }
 
@@ -665,27 +1150,39 @@ impl WorktreeProfiler {
         }
         let start = Instant::now();
         let result = f();
-        println!(
-            "[profile/worktrees] {worktree_id}::{label} took {:?}",
-            start.elapsed()
-        );
+        profile_event(Some(worktree_id), label, start.elapsed());
         result
     }
 }
 
-fn collect_external_sessions(profiler: &WorktreeProfiler) -> Vec<NormalizedSession> {
+/// Timing event emitted by [`WorktreeProfiler`] under the `agentdev::profile` target.
+fn profile_event(worktree_id: Option<&str>, label: &str, elapsed: Duration) {
+    tracing::event!(
+        target: "agentdev::profile",
+        tracing::Level::INFO,
+        worktree_id,
+        label,
+        elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+        "{label} took {elapsed:?}"
+    );
+}
+
+/// Sessions from every provider, plus the providers that failed to list theirs.
+///
+/// A failing provider does not affect the others.
+fn collect_external_sessions(
+    profiler: &WorktreeProfiler,
+) -> (Vec<NormalizedSession>, Vec<ProviderErrorPayload>) {
     profiler.measure("sessions.total", || {
         let mut collected = Vec::new();
+        let mut errors = Vec::new();
 
         for provider in default_providers() {
             let provider_name = provider.name();
             let records_result = if profiler.enabled() {
                 let start = Instant::now();
                 let outcome = provider.list_sessions();
-                println!(
-                    "[profile/worktrees] sessions::{provider_name} took {:?}",
-                    start.elapsed()
-                );
+                profile_event(None, &format!("sessions::{provider_name}"), start.elapsed());
                 outcome
             } else {
                 provider.list_sessions()
@@ -705,12 +1202,16 @@ fn collect_external_sessions(profiler: &WorktreeProfiler) -> Vec<NormalizedSessi
                     }
                 }
                 Err(err) => {
-                    eprintln!("⚠️  Failed to list sessions from {}: {err}", provider_name);
+                    tracing::warn!(provider = provider_name, "Failed to list sessions: {err:#}");
+                    errors.push(ProviderErrorPayload {
+                        provider: provider_name.to_string(),
+                        message: format!("{err:#}"),
+                    });
                 }
             }
         }
 
-        collected
+        (collected, errors)
     })
 }
 
@@ -756,47 +1257,244 @@ fn match_sessions_for_worktree(
     summaries
 }
 
-/// GET /api/sessions - List all known sessions across providers
-pub async fn get_sessions() -> impl IntoResponse {
-    match tokio::task::spawn_blocking(collect_all_sessions).await {
-        Ok(Ok(response)) => Json(response).into_response(),
-        Ok(Err(err)) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to collect sessions: {err}"),
-        )
-            .into_response(),
-        Err(join_err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Session collection task failed: {join_err}"),
+/// GET /api/health - Liveness probe, reachable without the API token
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/health",
+        responses(
+            (status = 200, description = "Server is up", body = HealthResponse)
         )
-            .into_response(),
-    }
+    )
+)]
+pub async fn get_health() -> impl IntoResponse {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+    })
 }
 
-/// GET /api/sessions/:provider/:session_id - Fetch transcript details for a session
-pub async fn get_session_detail(
-    AxumPath((provider, session_id)): AxumPath<(String, String)>,
-    Query(query): Query<SessionDetailQuery>,
-) -> impl IntoResponse {
-    let mode = query.mode.unwrap_or_default();
-    match tokio::task::spawn_blocking(move || load_session_detail(provider, session_id, mode)).await
-    {
-        Ok(Ok(Some(detail))) => Json(detail).into_response(),
-        Ok(Ok(None)) => (StatusCode::NOT_FOUND, "Session not found".to_string()).into_response(),
-        Ok(Err(err)) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to load session detail: {err}"),
+/// GET /api/metrics - Prometheus metrics in the text exposition format
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/metrics",
+        responses(
+            (status = 200, description = "Prometheus metrics", body = String, content_type = "text/plain"),
+            (status = 500, description = "Failed to collect metrics")
         )
-            .into_response(),
-        Err(join_err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Session detail task failed: {join_err}"),
+    )
+)]
+pub async fn get_metrics() -> impl IntoResponse {
+    let result = tokio::task::spawn_blocking(collect_metrics_snapshot).await;
+    match result.map(|snapshot| snapshot.and_then(|snapshot| metrics::render(&snapshot))) {
+        Ok(Ok(body)) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            tracing::error!(
+                endpoint = "/api/metrics",
+                "Failed to collect metrics: {err:#}"
+            );
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(join_err) => {
+            tracing::error!(
+                endpoint = "/api/metrics",
+                "Metrics task panicked: {join_err}"
+            );
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+fn collect_metrics_snapshot() -> Result<metrics::Snapshot> {
+    let state = XlaudeState::load()?;
+    let mut by_status: Vec<(&'static str, usize)> = metrics::WORKTREE_STATUSES
+        .iter()
+        .map(|status| (*status, 0))
+        .collect();
+    let statuses: Vec<&'static str> = state
+        .worktrees
+        .par_iter()
+        .map(|(_, info)| worktree_agent_status(&info.name))
+        .collect();
+    for status in statuses {
+        if let Some(entry) = by_status.iter_mut().find(|(name, _)| *name == status) {
+            entry.1 += 1;
+        }
+    }
+
+    let mut sessions_by_provider = Vec::new();
+    for provider in default_providers() {
+        match provider.list_sessions() {
+            Ok(records) => sessions_by_provider.push((provider.name(), records.len())),
+            Err(err) => tracing::warn!(
+                provider = provider.name(),
+                "Failed to list sessions for metrics: {err:#}"
+            ),
+        }
+    }
+
+    let mut processes_by_status: Vec<(&'static str, usize)> = Vec::new();
+    for record in ProcessRegistry::load()?.processes.values() {
+        let status = match record.status {
+            RegistryProcessStatus::Pending => "pending",
+            RegistryProcessStatus::Running => "running",
+            RegistryProcessStatus::Succeeded => "succeeded",
+            RegistryProcessStatus::Failed => "failed",
+            RegistryProcessStatus::Unknown => "unknown",
+        };
+        match processes_by_status
+            .iter_mut()
+            .find(|(name, _)| *name == status)
+        {
+            Some(entry) => entry.1 += 1,
+            None => processes_by_status.push((status, 1)),
+        }
+    }
+
+    Ok(metrics::Snapshot {
+        worktrees_total: state.worktrees.len(),
+        worktrees_by_status: by_status,
+        sessions_by_provider,
+        processes_by_status,
+    })
+}
+
+/// `running` while the agent is working, `idle` when its tmux session is
+/// waiting for input, `exited` when there is no session.
+fn worktree_agent_status(worktree_name: &str) -> &'static str {
+    let tmux = TmuxManager::new();
+    let has_session = tmux.session_exists(worktree_name);
+    let claude = has_session
+        .then(|| {
+            tmux.capture_pane(worktree_name, AGENT_ACTIVITY_CAPTURE_LINES)
+                .ok()
+        })
+        .flatten()
+        .map(|output| ClaudeStatusDetector::new().analyze_output(&output));
+    match to_panel_status(has_session, claude) {
+        PanelStatus::Running => "running",
+        PanelStatus::Paused => "idle",
+        PanelStatus::Exited => "exited",
+    }
+}
+
+/// GET /api/sessions - List all known sessions across providers
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/sessions",
+        params(SessionListQuery),
+        responses((status = 200, description = "Sessions across all providers", body = SessionListResponse))
+    )
+)]
+pub async fn get_sessions(Query(query): Query<SessionListQuery>) -> impl IntoResponse {
+    let dedup = query.dedup.unwrap_or(false);
+    match tokio::task::spawn_blocking(move || collect_all_sessions(dedup)).await {
+        Ok(Ok(response)) => Json(response).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to collect sessions: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Session collection task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/sessions/:provider/:session_id - Fetch transcript details for a session
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/sessions/{provider}/{session_id}",
+        params(
+            ("provider" = String, Path, description = "Session provider name"),
+            ("session_id" = String, Path, description = "Provider session identifier"),
+            SessionDetailQuery
+        ),
+        responses(
+            (status = 200, description = "Session transcript", body = SessionDetailPayload),
+            (status = 404, description = "Session not found")
+        )
+    )
+)]
+pub async fn get_session_detail(
+    AxumPath((provider, session_id)): AxumPath<(String, String)>,
+    Query(query): Query<SessionDetailQuery>,
+) -> impl IntoResponse {
+    let mode = query.mode.unwrap_or_default();
+    match tokio::task::spawn_blocking(move || load_session_detail(provider, session_id, mode)).await
+    {
+        Ok(Ok(Some(detail))) => Json(detail).into_response(),
+        Ok(Ok(None)) => (StatusCode::NOT_FOUND, "Session not found".to_string()).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to load session detail: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Session detail task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/sessions/:provider/:session_id/delete - Archive a session's log files
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/sessions/{provider}/{session_id}/delete",
+        params(
+            ("provider" = String, Path, description = "Session provider name"),
+            ("session_id" = String, Path, description = "Provider session identifier")
+        ),
+        responses(
+            (status = 200, description = "Session archived", body = DeleteSessionResponse),
+            (status = 404, description = "Session not found")
+        )
+    )
+)]
+pub async fn post_session_delete(
+    AxumPath((provider, session_id)): AxumPath<(String, String)>,
+) -> impl IntoResponse {
+    match tokio::task::spawn_blocking(move || archive_session(provider, session_id)).await {
+        Ok(Ok(Some(response))) => Json(response).into_response(),
+        Ok(Ok(None)) => (StatusCode::NOT_FOUND, "Session not found".to_string()).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to delete session: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Session delete task failed: {join_err}"),
         )
             .into_response(),
     }
 }
 
 /// GET /api/worktrees - Get enriched worktree metadata
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees",
+        responses((status = 200, description = "Managed worktrees", body = WorktreeListResponse))
+    )
+)]
 pub async fn get_worktrees() -> impl IntoResponse {
     match tokio::task::spawn_blocking(collect_worktree_summaries).await {
         Ok(Ok(response)) => Json(response).into_response(),
@@ -813,7 +1511,48 @@ pub async fn get_worktrees() -> impl IntoResponse {
     }
 }
 
+/// GET /api/worktrees/search - Managed worktrees matching every given filter
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/search",
+        params(WorktreeSearchQuery),
+        responses((status = 200, description = "Matching worktrees", body = WorktreeListResponse))
+    )
+)]
+pub async fn get_worktree_search(Query(query): Query<WorktreeSearchQuery>) -> impl IntoResponse {
+    match tokio::task::spawn_blocking(collect_worktree_summaries).await {
+        Ok(Ok(mut response)) => {
+            response.worktrees.retain(|summary| query.matches(summary));
+            Json(response).into_response()
+        }
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to collect worktrees: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Worktree collection task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/worktrees/discovery - List unmanaged git worktrees
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/discovery",
+        params(WorktreeDiscoveryQuery),
+        responses(
+            (status = 200, description = "Unmanaged git worktrees", body = Vec<crate::discovery::DiscoveredWorktree>),
+            (status = 400, description = "Invalid discovery root")
+        )
+    )
+)]
 pub async fn get_worktree_discovery(
     Query(query): Query<WorktreeDiscoveryQuery>,
 ) -> impl IntoResponse {
@@ -887,6 +1626,18 @@ fn expand_discovery_root(input: &str) -> Result<PathBuf, String> {
 }
 
 /// GET /api/worktrees/:id - Get metadata for a specific worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        responses(
+            (status = 200, description = "Worktree summary", body = WorktreeSummary),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
 pub async fn get_worktree(AxumPath(worktree_id): AxumPath<String>) -> impl IntoResponse {
     let id_for_error = worktree_id.clone();
     match tokio::task::spawn_blocking(move || collect_worktree_summary(worktree_id)).await {
@@ -909,7 +1660,114 @@ pub async fn get_worktree(AxumPath(worktree_id): AxumPath<String>) -> impl IntoR
     }
 }
 
+/// PATCH /api/worktrees/:id - Update editable worktree fields
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        patch,
+        path = "/api/worktrees/{worktree_id}",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = UpdateWorktreeRequest,
+        responses(
+            (status = 200, description = "Updated worktree summary", body = WorktreeSummary),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
+pub async fn patch_worktree(
+    AxumPath(worktree_id): AxumPath<String>,
+    Json(payload): Json<UpdateWorktreeRequest>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    match tokio::task::spawn_blocking(move || update_worktree(worktree_id, payload)).await {
+        Ok(Ok(Some(summary))) => Json(summary).into_response(),
+        Ok(Ok(None)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update worktree: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Worktree update task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+fn update_worktree(id: String, payload: UpdateWorktreeRequest) -> Result<Option<WorktreeSummary>> {
+    let mut state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get_mut(&id) else {
+        return Ok(None);
+    };
+    if let Some(notes) = payload.notes {
+        let notes = notes.trim();
+        info.notes = (!notes.is_empty()).then(|| notes.to_string());
+    }
+    state.save()?;
+    collect_worktree_summary(id)
+}
+
+/// GET /api/worktrees/:id/sessions - List agent sessions that ran inside a worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/sessions",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            SessionDetailQuery
+        ),
+        responses(
+            (status = 200, description = "Sessions of the worktree, with transcripts when `mode` is set", body = SessionListResponse),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
+pub async fn get_worktree_sessions(
+    AxumPath(worktree_id): AxumPath<String>,
+    Query(query): Query<SessionDetailQuery>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    match tokio::task::spawn_blocking(move || collect_worktree_sessions(worktree_id, query.mode))
+        .await
+    {
+        Ok(Ok(Some(response))) => Json(response).into_response(),
+        Ok(Ok(None)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to load worktree sessions: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Worktree sessions task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/worktrees/:id/processes - List active and recent processes for a worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/processes",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        responses(
+            (status = 200, description = "Recent processes", body = WorktreeProcessListResponse),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
 pub async fn get_worktree_processes(AxumPath(worktree_id): AxumPath<String>) -> impl IntoResponse {
     let id_for_error = worktree_id.clone();
     match tokio::task::spawn_blocking(move || collect_worktree_processes(&worktree_id)).await {
@@ -932,6 +1790,20 @@ pub async fn get_worktree_processes(AxumPath(worktree_id): AxumPath<String>) ->
     }
 }
 
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/commands",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = LaunchWorktreeCommandRequest,
+        responses(
+            (status = 201, description = "Command launched", body = LaunchWorktreeCommandResponse),
+            (status = 400, description = "Invalid command"),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
 pub async fn post_worktree_command(
     AxumPath(worktree_id): AxumPath<String>,
     Json(payload): Json<LaunchWorktreeCommandRequest>,
@@ -964,6 +1836,77 @@ pub async fn post_worktree_command(
     }
 }
 
+/// POST /api/worktrees/:id/processes/:process_id/retry - Run a finished process again
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/processes/{process_id}/retry",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            ("process_id" = String, Path, description = "Id of the process to retry")
+        ),
+        responses(
+            (status = 201, description = "Retry launched", body = LaunchWorktreeCommandResponse),
+            (status = 404, description = "Worktree or process not found"),
+            (status = 409, description = "Process is still running")
+        )
+    )
+)]
+pub async fn post_worktree_process_retry(
+    AxumPath((worktree_id, process_id)): AxumPath<(String, String)>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    let process_for_error = process_id.clone();
+    match tokio::task::spawn_blocking(move || retry_worktree_process(worktree_id, &process_id))
+        .await
+    {
+        Ok(Ok(RetryProcessResult::Success(process))) => (
+            StatusCode::CREATED,
+            Json(LaunchWorktreeCommandResponse { process: *process }),
+        )
+            .into_response(),
+        Ok(Ok(RetryProcessResult::WorktreeNotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Ok(RetryProcessResult::ProcessNotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Process {process_for_error} not found in {id_for_error}"),
+        )
+            .into_response(),
+        Ok(Ok(RetryProcessResult::StillRunning)) => (
+            StatusCode::CONFLICT,
+            format!("Process {process_for_error} has not finished yet"),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to retry process: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Process retry task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/shell",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = LaunchWorktreeShellRequest,
+        responses(
+            (status = 202, description = "Terminal launched", body = LaunchWorktreeShellResponse),
+            (status = 404, description = "Worktree not found")
+        )
+    )
+)]
 pub async fn post_worktree_shell(
     AxumPath(worktree_id): AxumPath<String>,
     Json(payload): Json<LaunchWorktreeShellRequest>,
@@ -998,6 +1941,18 @@ pub async fn post_worktree_shell(
     }
 }
 
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/shell",
+        request_body = LaunchShellRequest,
+        responses(
+            (status = 202, description = "Terminal launched", body = LaunchWorktreeShellResponse),
+            (status = 400, description = "Invalid path")
+        )
+    )
+)]
 pub async fn post_shell(Json(payload): Json<LaunchShellRequest>) -> impl IntoResponse {
     match tokio::task::spawn_blocking(move || launch_shell_at_path(payload)).await {
         Ok(Ok(LaunchShellResult::Success)) => (
@@ -1030,99 +1985,628 @@ pub async fn post_shell(Json(payload): Json<LaunchShellRequest>) -> impl IntoRes
     }
 }
 
-pub async fn post_worktree_merge(
-    AxumPath(worktree_id): AxumPath<String>,
-    Json(payload): Json<MergeWorktreeRequest>,
-) -> impl IntoResponse {
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/editor",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        responses(
+            (status = 202, description = "Editor launched", body = OpenEditorResponse),
+            (status = 400, description = "Editor binary not found"),
+            (status = 404, description = "Worktree not found"),
+            (status = 412, description = "No editor configured")
+        )
+    )
+)]
+pub async fn post_worktree_editor(AxumPath(worktree_id): AxumPath<String>) -> impl IntoResponse {
     let id_for_error = worktree_id.clone();
-    match tokio::task::spawn_blocking(move || merge_worktree_via_cli(worktree_id, payload)).await {
-        Ok(Ok(response)) => Json(response).into_response(),
-        Ok(Err(WorktreeActionError::NotFound)) => (
+    match tokio::task::spawn_blocking(move || open_worktree_in_editor(worktree_id)).await {
+        Ok(Ok(OpenEditorResult::Launched(editor))) => (
+            StatusCode::ACCEPTED,
+            Json(OpenEditorResponse {
+                status: "launched",
+                editor,
+            }),
+        )
+            .into_response(),
+        Ok(Ok(OpenEditorResult::NotFound)) => (
             StatusCode::NOT_FOUND,
             format!("Worktree {id_for_error} not found"),
         )
             .into_response(),
-        Ok(Err(WorktreeActionError::CommandFailure(payload))) => {
-            (StatusCode::CONFLICT, Json(payload)).into_response()
-        }
-        Ok(Err(WorktreeActionError::Internal(err))) => (
+        Ok(Ok(OpenEditorResult::NotConfigured(hint))) => (
+            StatusCode::PRECONDITION_FAILED,
+            Json(CommandFailurePayload::simple(hint)),
+        )
+            .into_response(),
+        Ok(Ok(OpenEditorResult::MissingBinary(message))) => (
+            StatusCode::BAD_REQUEST,
+            Json(CommandFailurePayload::simple(message)),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to merge worktree: {err}"),
+            format!("Failed to open editor: {err}"),
         )
             .into_response(),
         Err(join_err) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Merge task failed: {join_err}"),
+            format!("Editor launch task failed: {join_err}"),
         )
             .into_response(),
     }
 }
 
-pub async fn post_worktree_delete(
-    AxumPath(worktree_id): AxumPath<String>,
-    Json(payload): Json<DeleteWorktreeRequest>,
-) -> impl IntoResponse {
+/// POST /api/worktrees/:worktree_id/paste - Paste the server's clipboard into the worktree's agent session
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/paste",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        responses(
+            (status = 200, description = "Clipboard pasted", body = PasteClipboardResponse),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "No tmux session for the worktree"),
+            (status = 500, description = "Clipboard could not be read or pasted")
+        )
+    )
+)]
+pub async fn post_worktree_paste(AxumPath(worktree_id): AxumPath<String>) -> impl IntoResponse {
     let id_for_error = worktree_id.clone();
-    match tokio::task::spawn_blocking(move || delete_worktree_via_cli(worktree_id, payload)).await {
-        Ok(Ok(response)) => Json(response).into_response(),
-        Ok(Err(WorktreeActionError::NotFound)) => (
+    match tokio::task::spawn_blocking(move || paste_clipboard_into_worktree(worktree_id)).await {
+        Ok(Ok(PasteClipboardResult::Pasted(session))) => (
+            StatusCode::OK,
+            Json(PasteClipboardResponse {
+                status: "pasted",
+                session,
+            }),
+        )
+            .into_response(),
+        Ok(Ok(PasteClipboardResult::NotFound)) => (
             StatusCode::NOT_FOUND,
             format!("Worktree {id_for_error} not found"),
         )
             .into_response(),
-        Ok(Err(WorktreeActionError::CommandFailure(payload))) => {
-            (StatusCode::CONFLICT, Json(payload)).into_response()
-        }
-        Ok(Err(WorktreeActionError::Internal(err))) => (
+        Ok(Ok(PasteClipboardResult::NoSession(message))) => (
+            StatusCode::CONFLICT,
+            Json(CommandFailurePayload::simple(message)),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to delete worktree: {err}"),
+            Json(CommandFailurePayload::simple(format!(
+                "Failed to paste clipboard: {err:#}"
+            ))),
         )
             .into_response(),
         Err(join_err) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Delete task failed: {join_err}"),
+            format!("Clipboard paste task failed: {join_err}"),
         )
             .into_response(),
     }
 }
 
-enum WorktreeActionError {
-    NotFound,
-    CommandFailure(CommandFailurePayload),
-    Internal(anyhow::Error),
-}
+/// POST /api/admin/gc - Prune old finished processes from the registry
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/admin/gc",
+        request_body = GcRequest,
+        responses(
+            (status = 200, description = "Registry pruned", body = GcResponse)
+        )
+    )
+)]
+pub async fn post_admin_gc(Json(payload): Json<GcRequest>) -> impl IntoResponse {
+    let max_age = payload
+        .max_age_hours
+        .map(|hours| Duration::from_secs(hours.saturating_mul(60 * 60)))
+        .unwrap_or(FINISHED_PROCESS_MAX_AGE);
+    let result = tokio::task::spawn_blocking(move || -> Result<GcResponse> {
+        let mut response = GcResponse {
+            removed_processes: 0,
+            remaining_processes: 0,
+        };
+        ProcessRegistry::mutate(|registry| {
+            response.removed_processes = registry.cleanup_finished(max_age);
+            response.remaining_processes = registry.processes.len();
+            Ok(())
+        })?;
+        Ok(response)
+    })
+    .await;
 
-impl From<anyhow::Error> for WorktreeActionError {
-    fn from(value: anyhow::Error) -> Self {
-        WorktreeActionError::Internal(value)
+    match result {
+        Ok(Ok(response)) => Json(response).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to prune process registry: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Process cleanup task failed: {join_err}"),
+        )
+            .into_response(),
     }
 }
 
-fn merge_worktree_via_cli(
-    worktree_id: String,
-    payload: MergeWorktreeRequest,
-) -> Result<MergeWorktreeResponse, WorktreeActionError> {
-    let state = XlaudeState::load().map_err(WorktreeActionError::from)?;
-    let info = state
-        .worktrees
-        .get(&worktree_id)
-        .cloned()
-        .ok_or(WorktreeActionError::NotFound)?;
-
+/// POST /api/worktrees - Create a worktree through the CLI
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees",
+        request_body = CreateWorktreeRequest,
+        responses(
+            (status = 201, description = "Worktree created", body = WorktreeSummary),
+            (status = 400, description = "Invalid worktree request"),
+            (status = 409, description = "Worktree creation failed", body = CommandFailurePayload)
+        )
+    )
+)]
+pub async fn post_worktree(Json(payload): Json<CreateWorktreeRequest>) -> impl IntoResponse {
+    match tokio::task::spawn_blocking(move || create_worktree_via_cli(payload)).await {
+        Ok(Ok(summary)) => (StatusCode::CREATED, Json(summary)).into_response(),
+        Ok(Err(CreateWorktreeError::InvalidRequest(message))) => {
+            (StatusCode::BAD_REQUEST, message).into_response()
+        }
+        Ok(Err(CreateWorktreeError::CommandFailure(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Err(CreateWorktreeError::Internal(err))) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to create worktree: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Worktree creation task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/merge",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = MergeWorktreeRequest,
+        responses(
+            (status = 200, description = "Merge completed", body = MergeWorktreeResponse),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Merge failed", body = CommandFailurePayload)
+        )
+    )
+)]
+pub async fn post_worktree_merge(
+    AxumPath(worktree_id): AxumPath<String>,
+    Json(payload): Json<MergeWorktreeRequest>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    match tokio::task::spawn_blocking(move || merge_worktree_via_cli(worktree_id, payload)).await {
+        Ok(Ok(response)) => Json(response).into_response(),
+        Ok(Err(WorktreeActionError::NotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(WorktreeActionError::CommandFailure(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Err(WorktreeActionError::Internal(err))) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to merge worktree: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Merge task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/delete",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = DeleteWorktreeRequest,
+        responses(
+            (status = 200, description = "Worktree deleted", body = DeleteWorktreeResponse),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Deletion failed", body = CommandFailurePayload)
+        )
+    )
+)]
+pub async fn post_worktree_delete(
+    AxumPath(worktree_id): AxumPath<String>,
+    Json(payload): Json<DeleteWorktreeRequest>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    match tokio::task::spawn_blocking(move || delete_worktree_via_cli(worktree_id, payload)).await {
+        Ok(Ok(response)) => Json(response).into_response(),
+        Ok(Err(WorktreeActionError::NotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(WorktreeActionError::CommandFailure(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Err(WorktreeActionError::Internal(err))) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to delete worktree: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Delete task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/worktrees/:id/rebase - Rebase the worktree branch onto a base branch
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/worktrees/{worktree_id}/rebase",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        request_body = RebaseWorktreeRequest,
+        responses(
+            (status = 200, description = "Rebase completed", body = RebaseWorktreeResponse),
+            (status = 400, description = "Unknown base revision"),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Rebase hit conflicts and was aborted", body = RebaseConflictPayload),
+            (status = 422, description = "Interactive rebase requested", body = CommandFailurePayload)
+        )
+    )
+)]
+pub async fn post_worktree_rebase(
+    AxumPath(worktree_id): AxumPath<String>,
+    Json(payload): Json<RebaseWorktreeRequest>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    match tokio::task::spawn_blocking(move || rebase_worktree(worktree_id, payload)).await {
+        Ok(Ok(RebaseResult::Rebased(response))) => Json(response).into_response(),
+        Ok(Ok(RebaseResult::NotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Ok(RebaseResult::Interactive)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(CommandFailurePayload::simple(
+                "Interactive rebase needs a terminal; run `git rebase -i` in the worktree shell instead",
+            )),
+        )
+            .into_response(),
+        Ok(Ok(RebaseResult::InvalidBase(message))) => {
+            (StatusCode::BAD_REQUEST, message).into_response()
+        }
+        Ok(Ok(RebaseResult::Conflict(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Ok(RebaseResult::Failed(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to rebase worktree: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Rebase task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/conflicts - Files with uncommitted changes in more than one worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/conflicts",
+        responses(
+            (status = 200, description = "Worktree pairs editing the same files", body = Vec<CrossWorktreeConflict>)
+        )
+    )
+)]
+pub async fn get_conflicts() -> impl IntoResponse {
+    let result = tokio::task::spawn_blocking(|| -> Result<Vec<CrossWorktreeConflict>> {
+        let state = XlaudeState::load()?;
+        Ok(detect_cross_worktree_conflicts(&state))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(conflicts)) => Json(conflicts).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to detect conflicts: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Conflict detection task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/tasks - Managed worktrees grouped by task
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/tasks",
+        params(TaskListQuery),
+        responses((status = 200, description = "Multi-agent tasks", body = TaskListResponse))
+    )
+)]
+pub async fn get_tasks(Query(query): Query<TaskListQuery>) -> impl IntoResponse {
+    let include_implicit = query.all.unwrap_or(false);
+    let result = tokio::task::spawn_blocking(move || -> Result<TaskListResponse> {
+        let state = XlaudeState::load()?;
+        Ok(TaskListResponse {
+            tasks: collect_tasks(&state, include_implicit),
+        })
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => Json(response).into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to collect tasks: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Task collection task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/tasks/:task_id/commands - Run the same command in every worktree of a task
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/tasks/{task_id}/commands",
+        params(("task_id" = String, Path, description = "Task id shared by the member worktrees")),
+        request_body = LaunchWorktreeCommandRequest,
+        responses(
+            (status = 201, description = "Command launched in each member worktree", body = LaunchTaskCommandResponse),
+            (status = 400, description = "Invalid command"),
+            (status = 404, description = "Task not found")
+        )
+    )
+)]
+pub async fn post_task_command(
+    AxumPath(task_id): AxumPath<String>,
+    Json(payload): Json<LaunchWorktreeCommandRequest>,
+) -> impl IntoResponse {
+    let id_for_error = task_id.clone();
+    match tokio::task::spawn_blocking(move || launch_task_command(&task_id, payload)).await {
+        Ok(Ok(LaunchTaskCommandResult::Success(processes))) => (
+            StatusCode::CREATED,
+            Json(LaunchTaskCommandResponse { processes }),
+        )
+            .into_response(),
+        Ok(Ok(LaunchTaskCommandResult::NotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Task {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Ok(LaunchTaskCommandResult::Invalid(message))) => {
+            (StatusCode::BAD_REQUEST, message).into_response()
+        }
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to launch command: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Command launch task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/tasks - Create one worktree per agent for a shared prompt
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/tasks",
+        request_body = CreateTaskRequest,
+        responses(
+            (status = 201, description = "Task created", body = CreateTaskResponse),
+            (status = 400, description = "Invalid task request"),
+            (status = 409, description = "Worktree creation failed", body = CommandFailurePayload)
+        )
+    )
+)]
+pub async fn post_task(Json(payload): Json<CreateTaskRequest>) -> impl IntoResponse {
+    match tokio::task::spawn_blocking(move || create_task(payload)).await {
+        Ok(Ok(response)) => (StatusCode::CREATED, Json(response)).into_response(),
+        Ok(Err(CreateTaskError::InvalidRequest(message))) => {
+            (StatusCode::BAD_REQUEST, message).into_response()
+        }
+        Ok(Err(CreateTaskError::CommandFailure(payload))) => {
+            (StatusCode::CONFLICT, Json(payload)).into_response()
+        }
+        Ok(Err(CreateTaskError::Internal(err))) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to create task: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Task creation failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+enum WorktreeActionError {
+    NotFound,
+    CommandFailure(CommandFailurePayload),
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for WorktreeActionError {
+    fn from(value: anyhow::Error) -> Self {
+        WorktreeActionError::Internal(value)
+    }
+}
+
+enum CreateWorktreeError {
+    InvalidRequest(String),
+    CommandFailure(CommandFailurePayload),
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for CreateWorktreeError {
+    fn from(value: anyhow::Error) -> Self {
+        CreateWorktreeError::Internal(value)
+    }
+}
+
+fn create_worktree_via_cli(
+    payload: CreateWorktreeRequest,
+) -> Result<WorktreeSummary, CreateWorktreeError> {
+    let state = XlaudeState::load()?;
+    let repo = payload.repo.trim();
+    if repo.is_empty() {
+        return Err(CreateWorktreeError::InvalidRequest(
+            "Repository is required".to_string(),
+        ));
+    }
+    let repo_path = resolve_repo_path(&state, repo).ok_or_else(|| {
+        CreateWorktreeError::InvalidRequest(format!(
+            "Repository '{repo}' is neither a directory nor a repository with managed worktrees"
+        ))
+    })?;
+
+    let agent = match payload.agent.as_deref().map(str::trim) {
+        Some(alias) if !alias.is_empty() => {
+            let config = load_agent_config()?;
+            let Some(cmdline) = config.agents.get(alias) else {
+                return Err(CreateWorktreeError::InvalidRequest(format!(
+                    "Unknown agent alias '{alias}'. Define it under [agents] in {}",
+                    agent_config_path().display()
+                )));
+            };
+            Some((alias.to_string(), cmdline.clone()))
+        }
+        _ => None,
+    };
+
+    let mut args = vec!["worktree".to_string(), "create".to_string()];
+    if let Some(name) = payload.name.as_deref().map(str::trim)
+        && !name.is_empty()
+    {
+        args.push(name.to_string());
+    }
+    if let Some(base) = payload.base.as_deref().map(str::trim)
+        && !base.is_empty()
+    {
+        args.push("--from".to_string());
+        args.push(base.to_string());
+    }
+    if let Some((_, cmdline)) = &agent {
+        args.push("--agent".to_string());
+        args.push(cmdline.clone());
+    }
+
+    let existing_keys: HashSet<String> = state.worktrees.keys().cloned().collect();
+    let output = run_agentdev_cli_in_dir(args, Vec::new(), Some(&repo_path))?;
+    if !output.success {
+        let failure = build_command_failure("Worktree creation failed", &output);
+        return Err(CreateWorktreeError::CommandFailure(failure));
+    }
+
+    let mut state = XlaudeState::load()?;
+    let (worktree_id, info) = state
+        .worktrees
+        .iter_mut()
+        .find(|(key, _)| !existing_keys.contains(*key))
+        .ok_or_else(|| anyhow!("Worktree was created but is missing from state"))?;
+    let worktree_id = worktree_id.clone();
+    if let Some((alias, _)) = agent {
+        info.agent_alias = Some(alias);
+        state.save()?;
+    }
+
+    collect_worktree_summary(worktree_id.clone())?
+        .ok_or_else(|| anyhow!("Worktree {worktree_id} disappeared after creation").into())
+}
+
+/// A repository directory, or the main checkout of a repository that already
+/// has managed worktrees under that name.
+fn resolve_repo_path(state: &XlaudeState, repo: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(repo);
+    if path.is_dir() {
+        return Some(path);
+    }
+    state
+        .worktrees
+        .values()
+        .filter(|info| info.repo_name == repo)
+        .find_map(|info| resolve_main_repo_dir(&info.path).ok())
+}
+
+fn merge_worktree_via_cli(
+    worktree_id: String,
+    payload: MergeWorktreeRequest,
+) -> Result<MergeWorktreeResponse, WorktreeActionError> {
+    let state = XlaudeState::load().map_err(WorktreeActionError::from)?;
+    let info = state
+        .worktrees
+        .get(&worktree_id)
+        .cloned()
+        .ok_or(WorktreeActionError::NotFound)?;
+
     let strategy = payload.strategy.unwrap_or_default();
 
     let mut args = vec![
         "worktree".to_string(),
         "merge".to_string(),
         info.name.clone(),
+        "--quiet".to_string(),
     ];
 
     if strategy != MergeStrategyOption::FfOnly {
         args.push("--strategy".to_string());
         args.push(strategy.as_cli_flag().to_string());
     }
+    if let Some(remote) = payload.remote.as_deref().map(str::trim)
+        && !remote.is_empty()
+    {
+        args.push("--remote".to_string());
+        args.push(remote.to_string());
+    }
     if payload.push {
         args.push("--push".to_string());
+        if payload.set_upstream {
+            args.push("--set-upstream".to_string());
+        }
     }
     if payload.cleanup {
         args.push("--cleanup".to_string());
@@ -1194,46 +2678,363 @@ fn delete_worktree_via_cli(
     })
 }
 
-struct CliCommandOutput {
-    stdout: String,
-    stderr: String,
-    exit_code: Option<i32>,
-    success: bool,
+enum CreateTaskError {
+    InvalidRequest(String),
+    CommandFailure(CommandFailurePayload),
+    Internal(anyhow::Error),
 }
 
-impl CliCommandOutput {
-    fn normalized_exit_code(&self) -> i32 {
-        self.exit_code.unwrap_or(if self.success { 0 } else { -1 })
+impl From<anyhow::Error> for CreateTaskError {
+    fn from(value: anyhow::Error) -> Self {
+        CreateTaskError::Internal(value)
     }
+}
 
-    fn stdout_trimmed(&self) -> Option<String> {
-        trimmed_or_none(&self.stdout)
-    }
+/// How long to wait for a freshly started agent to show its input prompt.
+const TASK_PROMPT_READY_TIMEOUT: Duration = Duration::from_secs(30);
+const TASK_PROMPT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Consecutive identical captures required before the prompt counts as ready,
+/// so a banner that is still being drawn is not mistaken for the input box.
+const TASK_PROMPT_SETTLE_POLLS: usize = 2;
+const TASK_PROMPT_CAPTURE_LINES: usize = 50;
 
-    fn stderr_trimmed(&self) -> Option<String> {
-        trimmed_or_none(&self.stderr)
-    }
+/// A task member whose worktree exists, recorded so it can be rolled back.
+struct TaskMember {
+    worktree_id: String,
+    worktree_name: String,
+    worktree_path: PathBuf,
+    alias: String,
+    cmdline: String,
+    tmux_session: Option<String>,
 }
 
-fn run_agentdev_cli(
-    args: Vec<String>,
-    extra_env: Vec<(String, String)>,
-) -> Result<CliCommandOutput> {
+fn create_task(payload: CreateTaskRequest) -> Result<CreateTaskResponse, CreateTaskError> {
+    let prompt = payload.prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err(CreateTaskError::InvalidRequest(
+            "Task prompt cannot be empty".to_string(),
+        ));
+    }
+    if payload.agents.is_empty() {
+        return Err(CreateTaskError::InvalidRequest(
+            "At least one agent alias is required".to_string(),
+        ));
+    }
+
+    let repo_path = PathBuf::from(payload.repo_path.trim());
+    if !repo_path.is_dir() {
+        return Err(CreateTaskError::InvalidRequest(format!(
+            "Repository path {} does not exist",
+            repo_path.display()
+        )));
+    }
+
+    let config = load_agent_config()?;
+    let mut agents: Vec<(String, String)> = Vec::new();
+    for alias in &payload.agents {
+        let alias = alias.trim();
+        let Some(cmdline) = config.agents.get(alias) else {
+            return Err(CreateTaskError::InvalidRequest(format!(
+                "Unknown agent alias '{alias}'. Define it under [agents] in {}",
+                agent_config_path().display()
+            )));
+        };
+        if agents.iter().any(|(existing, _)| existing == alias) {
+            return Err(CreateTaskError::InvalidRequest(format!(
+                "Agent alias '{alias}' listed more than once"
+            )));
+        }
+        agents.push((alias.to_string(), cmdline.clone()));
+    }
+
+    let task_id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let task_name = payload
+        .task_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .unwrap_or_else(|| format!("task-{task_id}"));
+    let name_prefix = sanitize_branch_name(&task_name);
+
+    // Create every worktree first so a failure part-way through can be undone
+    // before any agent has started working.
+    let mut members: Vec<TaskMember> = Vec::new();
+    for (alias, cmdline) in agents {
+        let worktree_name = format!("{name_prefix}-{}", sanitize_branch_name(&alias));
+        match create_task_worktree(
+            &repo_path,
+            &worktree_name,
+            &alias,
+            &task_id,
+            &task_name,
+            &prompt,
+        ) {
+            Ok((worktree_id, worktree_path)) => members.push(TaskMember {
+                worktree_id,
+                worktree_name,
+                worktree_path,
+                alias,
+                cmdline,
+                tmux_session: None,
+            }),
+            Err(err) => {
+                let context = format!("Worktree creation failed for agent '{alias}'");
+                return Err(rollback_task_members(&members, &context, err));
+            }
+        }
+    }
+
+    let tmux = TmuxManager::new();
+    let launch_agents =
+        TmuxManager::is_available() && std::env::var_os("XLAUDE_TEST_MODE").is_none();
+    if launch_agents {
+        for index in 0..members.len() {
+            let member = &members[index];
+            let started = split_cmdline(&member.cmdline).and_then(|(program, program_args)| {
+                tmux.create_session_with_command(
+                    &member.worktree_name,
+                    &member.worktree_path,
+                    &program,
+                    &program_args,
+                )
+            });
+            if let Err(err) = started {
+                let context = format!("Failed to start agent '{}'", member.alias);
+                return Err(rollback_task_members(
+                    &members[..=index],
+                    &context,
+                    CreateTaskError::Internal(err),
+                ));
+            }
+            members[index].tmux_session = Some(tmux.session_name(&members[index].worktree_name));
+        }
+
+        for member in &members {
+            spawn_prompt_sender(
+                member.worktree_name.clone(),
+                prompt.clone(),
+                resolve_typing_pace(Some(&member.alias)),
+            );
+        }
+    }
+
+    let worktrees = members
+        .into_iter()
+        .map(|member| TaskWorktreePayload {
+            worktree_id: member.worktree_id,
+            worktree_name: member.worktree_name,
+            agent_alias: member.alias,
+            tmux_session: member.tmux_session,
+        })
+        .collect();
+
+    Ok(CreateTaskResponse {
+        task_id,
+        task_name,
+        initial_prompt: prompt,
+        worktrees,
+    })
+}
+
+/// Create one task member's worktree through the CLI and tag it with the task
+/// fields. Returns the new state key and worktree path.
+fn create_task_worktree(
+    repo_path: &Path,
+    worktree_name: &str,
+    alias: &str,
+    task_id: &str,
+    task_name: &str,
+    prompt: &str,
+) -> Result<(String, PathBuf), CreateTaskError> {
+    let existing_keys: HashSet<String> = XlaudeState::load()?.worktrees.keys().cloned().collect();
+
+    let args = vec![
+        "worktree".to_string(),
+        "create".to_string(),
+        worktree_name.to_string(),
+    ];
+    let extra_env = vec![("XLAUDE_NO_AUTO_OPEN".to_string(), "1".to_string())];
+    let output = run_agentdev_cli_in_dir(args, extra_env, Some(repo_path))?;
+    if !output.success {
+        let failure = build_command_failure("Worktree creation failed", &output);
+        return Err(CreateTaskError::CommandFailure(failure));
+    }
+
+    let mut state = XlaudeState::load()?;
+    let (worktree_id, info) = state
+        .worktrees
+        .iter_mut()
+        .find(|(key, info)| !existing_keys.contains(*key) && info.name == worktree_name)
+        .ok_or_else(|| anyhow!("Worktree {worktree_name} was created but is missing from state"))?;
+    info.task_id = Some(task_id.to_string());
+    info.task_name = Some(task_name.to_string());
+    info.initial_prompt = Some(prompt.to_string());
+    info.agent_alias = Some(alias.to_string());
+    let created = (worktree_id.clone(), info.path.clone());
+    state.save()?;
+    Ok(created)
+}
+
+/// Undo the members created so far and fold the outcome into `cause`, so the
+/// caller learns which worktrees were removed and which could not be.
+fn rollback_task_members(
+    members: &[TaskMember],
+    context: &str,
+    cause: CreateTaskError,
+) -> CreateTaskError {
+    let tmux = TmuxManager::new();
+    let mut removed = Vec::new();
+    let mut left_behind = Vec::new();
+    for member in members {
+        if let Err(err) = tmux.kill_session(&member.worktree_name) {
+            tracing::warn!(worktree = %member.worktree_name, "Failed to stop task agent: {err:#}");
+        }
+        let args = vec![
+            "worktree".to_string(),
+            "delete".to_string(),
+            member.worktree_name.clone(),
+        ];
+        let extra_env = vec![("XLAUDE_YES".to_string(), "1".to_string())];
+        match run_agentdev_cli(args, extra_env) {
+            Ok(output) if output.success => removed.push(member.worktree_name.clone()),
+            Ok(output) => {
+                let failure = build_command_failure("Worktree deletion failed", &output);
+                left_behind.push(format!("{} ({})", member.worktree_name, failure.message));
+            }
+            Err(err) => left_behind.push(format!("{} ({err:#})", member.worktree_name)),
+        }
+    }
+
+    let mut summary = context.to_string();
+    if !removed.is_empty() {
+        summary.push_str(&format!(
+            "; removed worktrees already created for this task: {}",
+            removed.join(", ")
+        ));
+    }
+    if !left_behind.is_empty() {
+        summary.push_str(&format!("; could not remove: {}", left_behind.join(", ")));
+    }
+
+    match cause {
+        CreateTaskError::CommandFailure(mut failure) => {
+            failure.message = format!("{summary}: {}", failure.message);
+            CreateTaskError::CommandFailure(failure)
+        }
+        CreateTaskError::Internal(err) => CreateTaskError::Internal(err.context(summary)),
+        other => other,
+    }
+}
+
+fn spawn_prompt_sender(worktree_name: String, prompt: String, pace: TypingPace) {
+    thread::spawn(move || {
+        let tmux = TmuxManager::new();
+        if !wait_for_agent_prompt(&tmux, &worktree_name) {
+            tracing::warn!(
+                worktree = %worktree_name,
+                "Agent session ended before it was ready; task prompt not sent"
+            );
+            return;
+        }
+        if let Err(err) = tmux
+            .send_text_paced(&worktree_name, &prompt, pace)
+            .and_then(|_| tmux.send_enter_paced(&worktree_name, pace))
+        {
+            tracing::error!(worktree = %worktree_name, "Failed to send task prompt: {err:#}");
+        }
+    });
+}
+
+/// Poll the agent's pane until it settles on an input prompt. Returns `false`
+/// when the session goes away; on timeout the prompt is sent anyway.
+fn wait_for_agent_prompt(tmux: &TmuxManager, worktree_name: &str) -> bool {
+    let deadline = Instant::now() + TASK_PROMPT_READY_TIMEOUT;
+    let mut detector = ClaudeStatusDetector::new();
+    let mut last_output: Option<String> = None;
+    let mut stable_polls = 0;
+    loop {
+        thread::sleep(TASK_PROMPT_POLL_INTERVAL);
+        if !tmux.session_exists(worktree_name) || tmux.session_is_dead(worktree_name) {
+            return false;
+        }
+        let Ok(output) = tmux.capture_pane(worktree_name, TASK_PROMPT_CAPTURE_LINES) else {
+            continue;
+        };
+        if last_output.as_ref() == Some(&output) {
+            stable_polls += 1;
+        } else {
+            stable_polls = 0;
+        }
+        if !output.trim().is_empty()
+            && stable_polls >= TASK_PROMPT_SETTLE_POLLS
+            && detector.analyze_activity(&output).waiting_for_input
+        {
+            return true;
+        }
+        last_output = Some(output);
+
+        if Instant::now() >= deadline {
+            tracing::warn!(
+                worktree = %worktree_name,
+                "Agent did not show an input prompt within {}s; sending task prompt anyway",
+                TASK_PROMPT_READY_TIMEOUT.as_secs()
+            );
+            return true;
+        }
+    }
+}
+
+struct CliCommandOutput {
+    stdout: String,
+    stderr: String,
+    exit_code: Option<i32>,
+    success: bool,
+}
+
+impl CliCommandOutput {
+    fn normalized_exit_code(&self) -> i32 {
+        self.exit_code.unwrap_or(if self.success { 0 } else { -1 })
+    }
+
+    fn stdout_trimmed(&self) -> Option<String> {
+        trimmed_or_none(&self.stdout)
+    }
+
+    fn stderr_trimmed(&self) -> Option<String> {
+        trimmed_or_none(&self.stderr)
+    }
+}
+
+fn run_agentdev_cli(
+    args: Vec<String>,
+    extra_env: Vec<(String, String)>,
+) -> Result<CliCommandOutput> {
+    run_agentdev_cli_in_dir(args, extra_env, None)
+}
+
+fn run_agentdev_cli_in_dir(
+    args: Vec<String>,
+    extra_env: Vec<(String, String)>,
+    cwd: Option<&Path>,
+) -> Result<CliCommandOutput> {
     let exe = resolve_agentdev_cli_executable()?;
     let mut command = Command::new(&exe);
     command.args(&args);
+    if let Some(dir) = cwd {
+        command.current_dir(dir);
+    }
     command.env("XLAUDE_NON_INTERACTIVE", "1");
     command.env("NO_COLOR", "1");
     command.env("CLICOLOR_FORCE", "0");
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    command.env(AUDIT_SOURCE_ENV, "web");
     for (key, value) in extra_env {
         command.env(key, value);
     }
-    command.stdout(Stdio::piped());
-    command.stderr(Stdio::piped());
 
-    let output = command
-        .output()
-        .map_err(|err| anyhow!("Failed to run agentdev command {:?}: {err}", args))?;
+    let output = output_with_timeout(&mut command, command_timeout())
+        .map_err(|err| anyhow!("Failed to run agentdev command {:?}: {err:#}", args))?;
 
     Ok(CliCommandOutput {
         stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
@@ -1391,7 +3192,7 @@ fn candidate_paths(dir: &Path, name: &str) -> Vec<PathBuf> {
     vec![dir.join(name)]
 }
 
-fn collect_all_sessions() -> Result<SessionListResponse> {
+fn collect_all_sessions(dedup: bool) -> Result<SessionListResponse> {
     let profiler = WorktreeProfiler::new();
     let overall_start = if profiler.enabled() {
         Some(Instant::now())
@@ -1426,7 +3227,10 @@ fn collect_all_sessions() -> Result<SessionListResponse> {
         })
         .collect();
 
-    let external_sessions = collect_external_sessions(&profiler);
+    let (mut external_sessions, provider_errors) = collect_external_sessions(&profiler);
+    if dedup {
+        external_sessions = dedup_sessions_by(external_sessions, |session| &session.record);
+    }
 
     let mut sessions: Vec<SessionSummaryPayload> = external_sessions
         .iter()
@@ -1503,9 +3307,31 @@ fn collect_all_sessions() -> Result<SessionListResponse> {
             .then_with(|| a.session_id.cmp(&b.session_id))
     });
 
+    let providers = summarize_providers(&sessions);
+
+    if let Some(start) = overall_start {
+        tracing::event!(
+            target: "agentdev::profile",
+            tracing::Level::INFO,
+            sessions = sessions.len(),
+            "sessions total took {:?}",
+            start.elapsed()
+        );
+    }
+
+    Ok(SessionListResponse {
+        sessions,
+        providers,
+        provider_errors,
+        details: Vec::new(),
+    })
+}
+
+/// Per-provider session counts, busiest provider first.
+fn summarize_providers(sessions: &[SessionSummaryPayload]) -> Vec<ProviderSessionsPayload> {
     let mut provider_map: std::collections::BTreeMap<String, ProviderSessionsPayload> =
         std::collections::BTreeMap::new();
-    for session in &sessions {
+    for session in sessions {
         let entry = provider_map
             .entry(session.provider.clone())
             .or_insert_with(|| ProviderSessionsPayload {
@@ -1532,18 +3358,39 @@ fn collect_all_sessions() -> Result<SessionListResponse> {
             .cmp(&a.session_count)
             .then_with(|| a.provider.cmp(&b.provider))
     });
+    providers
+}
 
-    if let Some(start) = overall_start {
-        println!(
-            "[profile/sessions] total took {:?} ({} sessions)",
-            start.elapsed(),
-            sessions.len()
-        );
-    }
+/// Env var capping how many worktrees are summarized in parallel.
+const WORKTREE_CONCURRENCY_ENV: &str = "AGENTDEV_WORKTREE_CONCURRENCY";
+
+/// Number of summary workers, from `AGENTDEV_WORKTREE_CONCURRENCY` or the CPU count.
+///
+/// Each worker shells out to git several times per worktree, so the cap bounds
+/// the number of concurrent git processes. A lower value keeps the server and
+/// the machine responsive with many worktrees at the cost of a slower
+/// `/api/worktrees` response; a higher one only helps when git is IO-bound on
+/// fast storage.
+fn worktree_concurrency(value: Option<&str>) -> usize {
+    value
+        .and_then(|raw| raw.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+}
 
-    Ok(SessionListResponse {
-        sessions,
-        providers,
+/// Dedicated pool for worktree summaries, sized once per process.
+///
+/// Kept separate from rayon's global pool so a slow summary pass cannot starve
+/// other parallel work in the server.
+fn worktree_summary_pool() -> &'static rayon::ThreadPool {
+    static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let configured = std::env::var(WORKTREE_CONCURRENCY_ENV).ok();
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(worktree_concurrency(configured.as_deref()))
+            .thread_name(|index| format!("agentdev-worktree-{index}"))
+            .build()
+            .expect("build worktree summary thread pool")
     })
 }
 
@@ -1558,7 +3405,7 @@ fn collect_worktree_summaries() -> Result<WorktreeListResponse> {
     let state = profiler.measure_result("state.load", || XlaudeState::load())?;
 
     let session_profiler = profiler.clone();
-    let session_handle = std::thread::spawn(move || collect_external_sessions(&session_profiler));
+    let session_handle = std::thread::spawn(move || collect_external_sessions(&session_profiler).0);
 
     let worktree_entries: Vec<(String, WorktreeInfo)> = state
         .worktrees
@@ -1569,24 +3416,41 @@ fn collect_worktree_summaries() -> Result<WorktreeListResponse> {
     let external_sessions = session_handle.join().unwrap_or_else(|_| Vec::new());
     let external_sessions = Arc::new(external_sessions);
 
-    let mut summaries: Vec<WorktreeSummary> = worktree_entries
-        .into_par_iter()
-        .map(|(id, info)| {
-            let profiler = profiler.clone();
-            let sessions = external_sessions.clone();
-            profiler.measure_worktree(&id, "summarize", || {
-                summarize_single_worktree(&id, &info, sessions.as_ref().as_slice(), &profiler)
+    let pool = worktree_summary_pool();
+    if profiler.enabled() {
+        tracing::event!(
+            target: "agentdev::profile",
+            tracing::Level::INFO,
+            concurrency = pool.current_num_threads(),
+            "summarizing worktrees"
+        );
+    }
+    let mut summaries: Vec<WorktreeSummary> = pool.install(|| {
+        worktree_entries
+            .into_par_iter()
+            .map(|(id, info)| {
+                let profiler = profiler.clone();
+                let sessions = external_sessions.clone();
+                profiler.measure_worktree(&id, "summarize", || {
+                    summarize_single_worktree(&id, &info, sessions.as_ref().as_slice(), &profiler)
+                })
             })
-        })
-        .collect();
+            .collect()
+    });
 
-    summaries.sort_by(|a, b| b.last_activity_at.cmp(&a.last_activity_at));
+    summaries.sort_by(|a, b| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(|| b.last_activity_at.cmp(&a.last_activity_at))
+    });
 
     if let Some(start) = overall_start {
-        println!(
-            "[profile/worktrees] total took {:?} ({} worktrees)",
-            start.elapsed(),
-            summaries.len()
+        tracing::event!(
+            target: "agentdev::profile",
+            tracing::Level::INFO,
+            worktrees = summaries.len(),
+            "worktrees total took {:?}",
+            start.elapsed()
         );
     }
 
@@ -1604,7 +3468,7 @@ fn collect_worktree_processes(worktree_id: &str) -> Result<Option<WorktreeProces
     if !info.path.exists() {
         warn_once("missing_path", &info.path, || {
             format!(
-                "⚠️  Worktree path missing, skipping process inspection: {}",
+                "Worktree path missing, skipping process inspection: {}",
                 info.path.display()
             )
         });
@@ -1626,6 +3490,19 @@ enum LaunchCommandResult {
     Invalid(String),
 }
 
+enum RetryProcessResult {
+    Success(Box<WorktreeProcessSummary>),
+    WorktreeNotFound,
+    ProcessNotFound,
+    StillRunning,
+}
+
+enum LaunchTaskCommandResult {
+    Success(Vec<WorktreeProcessSummary>),
+    NotFound,
+    Invalid(String),
+}
+
 enum LaunchShellResult {
     Success,
     NotFound,
@@ -1641,7 +3518,180 @@ fn launch_worktree_shell(
         return Ok(LaunchShellResult::NotFound);
     };
 
-    launch_shell_using_path(info.path.as_path(), request.command.as_deref())
+    let result = launch_shell_using_path(info.path.as_path(), request.command.as_deref())?;
+    if matches!(result, LaunchShellResult::Success) {
+        XlaudeState::record_activity(&worktree_id)?;
+    }
+    Ok(result)
+}
+
+enum RebaseResult {
+    Rebased(RebaseWorktreeResponse),
+    NotFound,
+    Interactive,
+    InvalidBase(String),
+    Conflict(RebaseConflictPayload),
+    Failed(CommandFailurePayload),
+}
+
+/// Run `git rebase <base>` in a worktree without a terminal.
+///
+/// A rebase that stops on conflicts is aborted so the worktree is left as it
+/// was; the conflicting files are reported instead.
+fn rebase_worktree(worktree_id: String, request: RebaseWorktreeRequest) -> Result<RebaseResult> {
+    if request.interactive {
+        return Ok(RebaseResult::Interactive);
+    }
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&worktree_id) else {
+        return Ok(RebaseResult::NotFound);
+    };
+    let repo = info
+        .path
+        .to_str()
+        .ok_or_else(|| anyhow!("Worktree path contains invalid UTF-8"))?;
+
+    let base = match request.base.as_deref().map(str::trim) {
+        Some(base) if !base.is_empty() => base.to_string(),
+        _ => default_branch(&info.path),
+    };
+    let base_commit = format!("{base}^{{commit}}");
+    if base.starts_with('-')
+        || !git_output(repo, &["rev-parse", "--verify", "--quiet", &base_commit])?
+            .status
+            .success()
+    {
+        return Ok(RebaseResult::InvalidBase(format!(
+            "Unknown base revision '{base}'"
+        )));
+    }
+
+    let output = git_output(repo, &["rebase", &base])?;
+    if !output.status.success() {
+        let conflicts = String::from_utf8_lossy(
+            &git_output(repo, &["diff", "--name-only", "--diff-filter=U"])?.stdout,
+        )
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+        let in_progress = git_output(repo, &["rebase", "--abort"])?.status.success();
+
+        if !conflicts.is_empty() {
+            return Ok(RebaseResult::Conflict(RebaseConflictPayload {
+                message: format!(
+                    "Rebasing onto {base} hit conflicts in {} file(s); the rebase was aborted",
+                    conflicts.len()
+                ),
+                conflicts,
+            }));
+        }
+        let mut failure = CommandFailurePayload::simple(if in_progress {
+            format!("Rebasing onto {base} failed and was aborted")
+        } else {
+            format!("Rebasing onto {base} failed")
+        });
+        failure.stderr = trimmed_or_none(&String::from_utf8_lossy(&output.stderr));
+        failure.exit_code = output.status.code();
+        return Ok(RebaseResult::Failed(failure));
+    }
+
+    Ok(RebaseResult::Rebased(RebaseWorktreeResponse {
+        base,
+        head: head_commit_info(&info.path)?.map(WorktreeCommitPayload::from),
+        stdout: trimmed_or_none(&String::from_utf8_lossy(&output.stdout)),
+    }))
+}
+
+/// Run git in `repo` with no terminal, capturing its output whatever the exit status.
+fn git_output(repo: &str, args: &[&str]) -> Result<std::process::Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .env("GIT_EDITOR", "true")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| anyhow!("Failed to run git: {err}"))
+}
+
+enum OpenEditorResult {
+    Launched(String),
+    NotFound,
+    NotConfigured(String),
+    MissingBinary(String),
+}
+
+/// Spawn the editor recorded in state on a worktree's directory.
+///
+/// The editor runs detached from the server's stdio; a reaper thread waits on
+/// it so exited editors don't linger as zombies.
+fn open_worktree_in_editor(worktree_id: String) -> Result<OpenEditorResult> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&worktree_id) else {
+        return Ok(OpenEditorResult::NotFound);
+    };
+
+    let Some(editor) = state
+        .editor
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        let state_path = crate::state::get_config_dir()?.join("state.json");
+        return Ok(OpenEditorResult::NotConfigured(format!(
+            "No editor configured. Set \"editor\" (e.g. \"code\" or \"zed\") in {}",
+            state_path.display()
+        )));
+    };
+
+    let (program, args) = split_cmdline(editor)?;
+    let spawned = Command::new(&program)
+        .args(&args)
+        .arg(&info.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(OpenEditorResult::MissingBinary(format!(
+                "Editor '{program}' not found in PATH"
+            )));
+        }
+        Err(err) => return Err(anyhow!("Failed to launch editor '{program}': {err}")),
+    };
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(OpenEditorResult::Launched(editor.to_string()))
+}
+
+enum PasteClipboardResult {
+    Pasted(String),
+    NotFound,
+    NoSession(String),
+}
+
+/// Paste the clipboard of the machine running the server into the tmux
+/// session of a worktree.
+fn paste_clipboard_into_worktree(worktree_id: String) -> Result<PasteClipboardResult> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&worktree_id) else {
+        return Ok(PasteClipboardResult::NotFound);
+    };
+
+    let tmux = TmuxManager::new();
+    if !tmux.session_exists(&info.name) {
+        return Ok(PasteClipboardResult::NoSession(format!(
+            "No tmux session for {}. Open the worktree first with 'agentdev worktree open {}'",
+            info.name, info.name
+        )));
+    }
+    tmux.send_clipboard(&info.name)?;
+    Ok(PasteClipboardResult::Pasted(tmux.session_name(&info.name)))
 }
 
 fn launch_shell_at_path(request: LaunchShellRequest) -> Result<LaunchShellResult> {
@@ -1726,6 +3776,7 @@ fn launch_worktree_command(
         Ok(())
     })?;
 
+    XlaudeState::record_activity(&worktree_id)?;
     let worktree_path = info.path.clone();
     spawn_command_runner(
         worktree_id,
@@ -1739,6 +3790,77 @@ fn launch_worktree_command(
     )))
 }
 
+/// Run a finished process of a worktree again as a new process whose
+/// `parent_id` points at the original.
+fn retry_worktree_process(worktree_id: String, process_id: &str) -> Result<RetryProcessResult> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&worktree_id) else {
+        return Ok(RetryProcessResult::WorktreeNotFound);
+    };
+
+    let registry = ProcessRegistry::load()?;
+    let Some(original) = registry
+        .processes
+        .get(process_id)
+        .filter(|record| record.worktree_key == worktree_id)
+    else {
+        return Ok(RetryProcessResult::ProcessNotFound);
+    };
+    if !original.is_finished() {
+        return Ok(RetryProcessResult::StillRunning);
+    }
+
+    let record = original.retry();
+    let cwd = record.cwd.clone().unwrap_or_else(|| info.path.clone());
+    let record_to_store = record.clone();
+    ProcessRegistry::mutate(move |registry| {
+        registry.insert(record_to_store);
+        registry.retain_recent(MAX_PROCESSES_PER_WORKTREE);
+        Ok(())
+    })?;
+
+    XlaudeState::record_activity(&worktree_id)?;
+    spawn_command_runner(worktree_id, record.id.clone(), record.command.clone(), cwd);
+
+    Ok(RetryProcessResult::Success(Box::new(
+        process_record_to_summary(&record),
+    )))
+}
+
+/// Launch `request` in each worktree whose `task_id` matches, through the
+/// same path as a single-worktree command.
+fn launch_task_command(
+    task_id: &str,
+    request: LaunchWorktreeCommandRequest,
+) -> Result<LaunchTaskCommandResult> {
+    let state = XlaudeState::load()?;
+    let mut members: Vec<String> = state
+        .worktrees
+        .iter()
+        .filter(|(_, info)| info.task_id.as_deref() == Some(task_id))
+        .map(|(key, _)| key.clone())
+        .collect();
+    if members.is_empty() {
+        return Ok(LaunchTaskCommandResult::NotFound);
+    }
+    members.sort();
+
+    let mut processes = Vec::with_capacity(members.len());
+    for worktree_id in members {
+        match launch_worktree_command(worktree_id, request.clone())? {
+            LaunchCommandResult::Success(process) => processes.push(process),
+            // The command is validated before any worktree is touched, so
+            // this can only come from the first member.
+            LaunchCommandResult::Invalid(message) => {
+                return Ok(LaunchTaskCommandResult::Invalid(message));
+            }
+            // Removed from state since we listed the members
+            LaunchCommandResult::NotFound => {}
+        }
+    }
+    Ok(LaunchTaskCommandResult::Success(processes))
+}
+
 fn launch_shell_using_path(path: &Path, command: Option<&str>) -> Result<LaunchShellResult> {
     if let Some(raw) = command {
         if raw.trim().is_empty() {
@@ -1748,8 +3870,20 @@ fn launch_shell_using_path(path: &Path, command: Option<&str>) -> Result<LaunchS
         }
     }
 
-    let command_line = build_terminal_launch_command(path, command)?;
-    execute_terminal_launch(command_line)
+    let launch = build_terminal_launch_command(path, command)?;
+    if launch.detach {
+        spawn_terminal_launch(launch.argv)
+    } else {
+        execute_terminal_launch(launch.argv)
+    }
+}
+
+/// Argument vector that opens a terminal window.
+struct TerminalLaunch {
+    argv: Vec<String>,
+    /// The program keeps running until the window closes, so it is spawned
+    /// in the background instead of waited on.
+    detach: bool,
 }
 
 fn execute_terminal_launch(command_line: Vec<String>) -> Result<LaunchShellResult> {
@@ -1783,10 +3917,32 @@ fn execute_terminal_launch(command_line: Vec<String>) -> Result<LaunchShellResul
     Ok(LaunchShellResult::Success)
 }
 
-fn build_terminal_launch_command(path: &Path, command: Option<&str>) -> Result<Vec<String>> {
+fn spawn_terminal_launch(command_line: Vec<String>) -> Result<LaunchShellResult> {
+    let (program, args) = command_line
+        .split_first()
+        .ok_or_else(|| anyhow!("Terminal command unexpectedly empty"))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| anyhow!("Failed to launch terminal via '{program}': {err}"))?;
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(LaunchShellResult::Success)
+}
+
+fn build_terminal_launch_command(path: &Path, command: Option<&str>) -> Result<TerminalLaunch> {
     if let Ok(template) = std::env::var("AGENTDEV_TERMINAL_COMMAND") {
         if !template.trim().is_empty() {
-            return build_terminal_command_from_template(path, command, &template);
+            return Ok(TerminalLaunch {
+                argv: build_terminal_command_from_template(path, command, &template)?,
+                detach: false,
+            });
         }
     }
     build_default_terminal_command(path, command)
@@ -1816,27 +3972,144 @@ fn build_terminal_command_from_template(
     Ok(tokens)
 }
 
-fn build_default_terminal_command(path: &Path, command: Option<&str>) -> Result<Vec<String>> {
+#[cfg(not(target_os = "macos"))]
+const TERMINAL_SUGGESTION: &str = "Set AGENTDEV_TERMINAL_COMMAND to customize terminal launching, e.g. `wezterm start --cwd '{cwd}' -- bash -lc '{command}'`.";
+
+fn build_default_terminal_command(path: &Path, command: Option<&str>) -> Result<TerminalLaunch> {
     #[cfg(target_os = "macos")]
     {
-        build_macos_terminal_command(path, command)
+        Ok(TerminalLaunch {
+            argv: build_macos_terminal_command(path, command)?,
+            detach: false,
+        })
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
     {
-        let suggestion = "Set AGENTDEV_TERMINAL_COMMAND to customize terminal launching, e.g. `wezterm start --cwd '{cwd}' -- bash -lc '{command}'`.";
+        let terminal = LINUX_TERMINALS
+            .iter()
+            .find(|name| which::which(name).is_ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "No supported terminal found (tried {}). {TERMINAL_SUGGESTION}",
+                    LINUX_TERMINALS.join(", ")
+                )
+            })?;
+        Ok(TerminalLaunch {
+            argv: build_linux_terminal_command(terminal, path, command)?,
+            detach: true,
+        })
+    }
+
+    #[cfg(windows)]
+    {
+        let windows_terminal = which::which("wt.exe").is_ok();
+        Ok(TerminalLaunch {
+            argv: build_windows_terminal_command(windows_terminal, path, command),
+            detach: true,
+        })
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+    {
+        let _ = (path, command);
         Err(anyhow!(
-            "Terminal launch is not implemented for this platform. {suggestion}"
+            "Terminal launch is not implemented for this platform. {TERMINAL_SUGGESTION}"
         ))
     }
 }
 
+/// Terminals tried in order when no template is configured.
+#[cfg(target_os = "linux")]
+const LINUX_TERMINALS: &[&str] = &[
+    "x-terminal-emulator",
+    "gnome-terminal",
+    "konsole",
+    "wezterm",
+    "alacritty",
+];
+
+#[cfg(target_os = "linux")]
+fn build_linux_terminal_command(
+    terminal: &str,
+    path: &Path,
+    command: Option<&str>,
+) -> Result<Vec<String>> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Directory path contains invalid UTF-8"))?;
+    // Keep the window open on a login shell once the command finishes
+    let script = |prefix: &str| match command {
+        Some(cmd) => format!("{prefix}{}; exec \"${{SHELL:-/bin/sh}}\" -l", cmd.trim()),
+        None => format!("{prefix}exec \"${{SHELL:-/bin/sh}}\" -l"),
+    };
+    let run = |flag: &str, script: String| {
+        vec![flag.to_string(), "sh".to_string(), "-c".to_string(), script]
+    };
+
+    let mut argv = vec![terminal.to_string()];
+    match terminal {
+        "gnome-terminal" => {
+            argv.push(format!("--working-directory={path_str}"));
+            argv.extend(run("--", script("")));
+        }
+        "konsole" => {
+            argv.extend(["--workdir".to_string(), path_str.to_string()]);
+            argv.extend(run("-e", script("")));
+        }
+        "wezterm" => {
+            argv.extend([
+                "start".to_string(),
+                "--cwd".to_string(),
+                path_str.to_string(),
+            ]);
+            argv.extend(run("--", script("")));
+        }
+        "alacritty" => {
+            argv.extend(["--working-directory".to_string(), path_str.to_string()]);
+            argv.extend(run("-e", script("")));
+        }
+        // x-terminal-emulator only guarantees `-e`, so change directory in the script
+        _ => {
+            argv.extend(run(
+                "-e",
+                script(&format!("cd {} && ", crate::utils::shell_quote(path_str))),
+            ));
+        }
+    }
+    Ok(argv)
+}
+
+#[cfg(windows)]
+fn build_windows_terminal_command(
+    windows_terminal: bool,
+    path: &Path,
+    command: Option<&str>,
+) -> Vec<String> {
+    let path_str = path.display().to_string();
+    let mut argv: Vec<String> = if windows_terminal {
+        vec!["wt.exe".into(), "-d".into(), path_str]
+    } else {
+        // `start` treats its first quoted argument as the window title
+        ["cmd", "/c", "start", "", "/D"]
+            .into_iter()
+            .map(String::from)
+            .chain([path_str])
+            .collect()
+    };
+    argv.push("cmd".into());
+    if let Some(cmd) = command {
+        argv.extend(["/k".to_string(), cmd.trim().to_string()]);
+    }
+    argv
+}
+
 #[cfg(target_os = "macos")]
 fn build_macos_terminal_command(path: &Path, command: Option<&str>) -> Result<Vec<String>> {
     let path_str = path
         .to_str()
         .ok_or_else(|| anyhow!("Directory path contains invalid UTF-8"))?;
-    let base = format!("cd {} &&", shell_quote(path_str));
+    let base = format!("cd {} &&", crate::utils::shell_quote(path_str));
     let shell_command = match command {
         Some(cmd) => format!(
             "{base} {cmd}; exec \"$SHELL\" -l",
@@ -1865,27 +4138,6 @@ fn escape_applescript_string(value: &str) -> String {
     value.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
-#[cfg(target_os = "macos")]
-fn shell_quote(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    if !value.contains('\'') {
-        return format!("'{value}'");
-    }
-    let mut quoted = String::with_capacity(value.len() + 2);
-    quoted.push('\'');
-    for ch in value.chars() {
-        if ch == '\'' {
-            quoted.push_str("'\\''");
-        } else {
-            quoted.push(ch);
-        }
-    }
-    quoted.push('\'');
-    quoted
-}
-
 fn spawn_command_runner(
     worktree_id: String,
     process_id: String,
@@ -1896,10 +4148,7 @@ fn spawn_command_runner(
         if let Err(err) =
             run_command_runner(&worktree_id, &process_id, &command_tokens, &worktree_path)
         {
-            eprintln!(
-                "Failed to execute command for worktree {}: {err}",
-                worktree_id
-            );
+            tracing::error!(worktree_id = %worktree_id, "Failed to execute command: {err:#}");
         }
     });
 }
@@ -1968,6 +4217,7 @@ fn run_command_runner(
                     );
                 })?;
                 registry.retain_recent(MAX_PROCESSES_PER_WORKTREE);
+                registry.cleanup_finished(FINISHED_PROCESS_MAX_AGE);
                 Ok(())
             })?;
 
@@ -1991,6 +4241,7 @@ fn run_command_runner(
                     );
                 })?;
                 registry.retain_recent(MAX_PROCESSES_PER_WORKTREE);
+                registry.cleanup_finished(FINISHED_PROCESS_MAX_AGE);
                 Ok(())
             })?;
             return Err(anyhow!(error_message));
@@ -2012,180 +4263,650 @@ fn process_record_to_summary(record: &ProcessRecord) -> WorktreeProcessSummary {
         description: record.description.clone().or_else(|| record.error.clone()),
         stdout: record.stdout.clone(),
         stderr: record.stderr.clone(),
+        parent_id: record.parent_id.clone(),
+    }
+}
+
+impl From<crate::git::GitFileDiff> for WorktreeFileDiffPayload {
+    fn from(value: crate::git::GitFileDiff) -> Self {
+        Self {
+            path: value.path,
+            display_path: value.display_path,
+            status: value.status,
+            diff: value.diff,
+        }
+    }
+}
+
+impl From<crate::git::CommitDiffInfo> for WorktreeCommitDiffPayload {
+    fn from(value: crate::git::CommitDiffInfo) -> Self {
+        Self {
+            reference: value.reference,
+            diff: value.diff,
+        }
+    }
+}
+
+fn collect_worktree_summary(id: String) -> Result<Option<WorktreeSummary>> {
+    let profiler = WorktreeProfiler::new();
+    let overall_start = if profiler.enabled() {
+        Some(Instant::now())
+    } else {
+        None
+    };
+
+    let state = profiler.measure_result("state.load", || XlaudeState::load())?;
+    let (external_sessions, _) = collect_external_sessions(&profiler);
+    let summary = state.worktrees.get(&id).map(|info| {
+        profiler.measure_worktree(&id, "summarize", || {
+            summarize_single_worktree(&id, info, &external_sessions, &profiler)
+        })
+    });
+
+    if let Some(start) = overall_start {
+        profile_event(Some(&id), "total_single", start.elapsed());
+    }
+
+    Ok(summary)
+}
+
+fn summarize_single_worktree(
+    id: &str,
+    info: &WorktreeInfo,
+    external_sessions: &[NormalizedSession],
+    profiler: &WorktreeProfiler,
+) -> WorktreeSummary {
+    let path_exists = info.path.exists();
+    let git_ready = path_exists && git_metadata_present(&info.path);
+
+    if path_exists && !git_ready {
+        warn_once("git_metadata", &info.path, || {
+            format!(
+                "Worktree missing git metadata, skipping inspection: {}",
+                info.path.display()
+            )
+        });
+    }
+
+    let git_status = if git_ready {
+        match profiler.measure_worktree_result(id, "git_status", || {
+            summarize_worktree_status(&info.path, &info.branch)
+        }) {
+            Ok(status) => Some(WorktreeGitStatusPayload::from(status)),
+            Err(err) => {
+                warn_once("git_status", &info.path, || {
+                    format!(
+                        "Failed to inspect git status for {}: {err}",
+                        info.path.display()
+                    )
+                });
+                None
+            }
+        }
+    } else if !path_exists {
+        warn_once("missing_path", &info.path, || {
+            format!(
+                "Worktree path missing, skipping git status: {}",
+                info.path.display()
+            )
+        });
+        None
+    } else {
+        None
+    };
+
+    let head_commit = if git_ready {
+        match profiler.measure_worktree_result(id, "head_commit", || head_commit_info(&info.path)) {
+            Ok(result) => result.map(WorktreeCommitPayload::from),
+            Err(err) => {
+                warn_once("head_commit", &info.path, || {
+                    format!(
+                        "Failed to read last commit for {}: {err}",
+                        info.path.display()
+                    )
+                });
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let commits_ahead = if git_ready {
+        match profiler.measure_worktree_result(id, "commits_since_merge_base", || {
+            commits_since_merge_base(&info.path)
+        }) {
+            Ok(result) => result.map(WorktreeCommitsAheadPayload::from),
+            Err(err) => {
+                warn_once("git_commits_ahead", &info.path, || {
+                    format!(
+                        "Failed to read commits relative to base for {}: {err}",
+                        info.path.display()
+                    )
+                });
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut sessions: Vec<WorktreeSessionSummary> = Vec::new();
+    if path_exists {
+        sessions.extend(profiler.measure_worktree(id, "sessions.external", || {
+            match_sessions_for_worktree(info, external_sessions)
+        }));
+    }
+    sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
+
+    let agent_activity =
+        profiler.measure_worktree(id, "agent_activity", || capture_agent_activity(&info.name));
+
+    let mut last_activity = info.created_at;
+    if let Some(ref commit) = head_commit {
+        if let Some(ts) = commit.timestamp {
+            if ts > last_activity {
+                last_activity = ts;
+            }
+        }
+    }
+    for session in &sessions {
+        if let Some(ts) = session.last_timestamp {
+            if ts > last_activity {
+                last_activity = ts;
+            }
+        }
+    }
+    if let Some(ts) = info.last_agent_activity {
+        last_activity = last_activity.max(ts);
+    }
+
+    WorktreeSummary {
+        id: id.to_string(),
+        name: info.name.clone(),
+        branch: info.branch.clone(),
+        repo_name: info.repo_name.clone(),
+        path: info.path.display().to_string(),
+        created_at: info.created_at,
+        last_activity_at: last_activity,
+        task_id: info.task_id.clone(),
+        task_name: info.task_name.clone(),
+        initial_prompt: info.initial_prompt.clone(),
+        agent_alias: info.agent_alias.clone(),
+        locked: info.locked,
+        lock_reason: info.lock_reason.clone(),
+        pinned: info.pinned,
+        notes: info.notes.clone(),
+        symlink_path: info
+            .symlink_path
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        git_status,
+        head_commit,
+        commits_ahead,
+        size_bytes: None,
+        agent_activity,
+        sessions,
+    }
+}
+
+/// Lines of tmux scrollback inspected for the activity summary.
+const AGENT_ACTIVITY_CAPTURE_LINES: usize = 50;
+
+fn capture_agent_activity(worktree_name: &str) -> Option<AgentActivityPayload> {
+    let tmux = TmuxManager::new();
+    if !tmux.session_exists(worktree_name) {
+        return None;
+    }
+    let output = tmux
+        .capture_pane(worktree_name, AGENT_ACTIVITY_CAPTURE_LINES)
+        .ok()?;
+    let activity = ClaudeStatusDetector::new().analyze_activity(&output);
+    Some(activity.into())
+}
+
+/// GET /api/worktrees/:id/size - Disk usage of a worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/size",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            WorktreeSizeQuery
+        ),
+        responses(
+            (status = 200, description = "Worktree disk usage", body = WorktreeSizePayload),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Worktree path missing")
+        )
+    )
+)]
+pub async fn get_worktree_size(
+    AxumPath(worktree_id): AxumPath<String>,
+    Query(query): Query<WorktreeSizeQuery>,
+) -> impl IntoResponse {
+    let respect_gitignore = query.respect_gitignore.unwrap_or(false);
+    let id_for_error = worktree_id.clone();
+    let result = tokio::task::spawn_blocking(move || -> Result<Option<WorktreeSizePayload>> {
+        let state = XlaudeState::load()?;
+        let Some(info) = state.worktrees.get(&worktree_id) else {
+            return Ok(None);
+        };
+        let size_bytes = worktree_size(&info.path, respect_gitignore)?;
+        Ok(Some(WorktreeSizePayload {
+            worktree_id,
+            size_bytes,
+            respect_gitignore,
+        }))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Some(payload))) => Json(payload).into_response(),
+        Ok(Ok(None)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            let message = err.to_string();
+            let status = if message.contains("Worktree path missing") {
+                StatusCode::CONFLICT
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            (
+                status,
+                format!("Failed to measure {id_for_error}: {message}"),
+            )
+                .into_response()
+        }
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Disk usage task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/worktrees/:id/checkpoints - Annotated checkpoint tags reachable from HEAD
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/checkpoints",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)")
+        ),
+        responses(
+            (status = 200, description = "Checkpoint tags, oldest first", body = WorktreeCheckpointsPayload),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Worktree path missing")
+        )
+    )
+)]
+pub async fn get_worktree_checkpoints(
+    AxumPath(worktree_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    let result =
+        tokio::task::spawn_blocking(move || -> Result<Option<WorktreeCheckpointsPayload>> {
+            let state = XlaudeState::load()?;
+            let Some(info) = state.worktrees.get(&worktree_id) else {
+                return Ok(None);
+            };
+            if !info.path.exists() {
+                return Err(anyhow!("Worktree path missing: {}", info.path.display()));
+            }
+            let checkpoints = list_checkpoint_tags(&info.path)?
+                .into_iter()
+                .map(|tag| CheckpointPayload {
+                    name: tag.name,
+                    commit_id: tag.commit_id,
+                    message: tag.message,
+                    tagged_at: tag.tagged_at,
+                })
+                .collect();
+            Ok(Some(WorktreeCheckpointsPayload {
+                worktree_id,
+                checkpoints,
+            }))
+        })
+        .await;
+
+    match result {
+        Ok(Ok(Some(payload))) => Json(payload).into_response(),
+        Ok(Ok(None)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            let message = err.to_string();
+            let status = if message.contains("Worktree path missing") {
+                StatusCode::CONFLICT
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            (
+                status,
+                format!("Failed to list checkpoints for {id_for_error}: {message}"),
+            )
+                .into_response()
+        }
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Checkpoint task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/worktrees/:id/diff - Raw unified diff as a downloadable patch
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/diff",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            WorktreeDiffQuery
+        ),
+        responses(
+            (status = 200, description = "Unified diff", body = String, content_type = "text/plain"),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Worktree path missing")
+        )
+    )
+)]
+pub async fn get_worktree_diff(
+    AxumPath(worktree_id): AxumPath<String>,
+    Query(query): Query<WorktreeDiffQuery>,
+) -> impl IntoResponse {
+    let staged = query.staged.unwrap_or(false);
+    let id_for_error = worktree_id.clone();
+    let result = tokio::task::spawn_blocking(move || -> Result<Option<(String, String)>> {
+        let state = XlaudeState::load()?;
+        let Some(info) = state.worktrees.get(&worktree_id) else {
+            return Ok(None);
+        };
+        if !info.path.exists() {
+            anyhow::bail!("Worktree path missing: {}", info.path.display());
+        }
+        let mut diff = if staged {
+            get_staged_diff_for_path(&info.path)?
+        } else {
+            get_diff_for_path(&info.path)?
+        };
+        if !diff.is_empty() && !diff.ends_with('\n') {
+            diff.push('\n');
+        }
+        Ok(Some((info.name.clone(), diff)))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Some((name, diff)))) => {
+            let filename: String = name
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            (
+                [
+                    (
+                        header::CONTENT_TYPE,
+                        "text/plain; charset=utf-8".to_string(),
+                    ),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{filename}.patch\""),
+                    ),
+                ],
+                diff,
+            )
+                .into_response()
+        }
+        Ok(Ok(None)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            let message = err.to_string();
+            let status = if message.contains("Worktree path missing") {
+                StatusCode::CONFLICT
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            (status, format!("Failed to diff {id_for_error}: {message}")).into_response()
+        }
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Diff task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/worktrees/:id/file - Current contents of a file inside a worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/file",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            WorktreeFileQuery
+        ),
+        responses(
+            (status = 200, description = "File contents with a guessed content type; HTML, SVG and XML come back as plain text", body = String),
+            (status = 400, description = "Path escapes the worktree or is not a file"),
+            (status = 404, description = "Worktree or file not found"),
+            (status = 413, description = "File exceeds AGENTDEV_FILE_MAX_BYTES")
+        )
+    )
+)]
+pub async fn get_worktree_file(
+    AxumPath(worktree_id): AxumPath<String>,
+    Query(query): Query<WorktreeFileQuery>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    let max_bytes = worktree_file_max_bytes(std::env::var(FILE_MAX_BYTES_ENV).ok().as_deref());
+    let result = tokio::task::spawn_blocking(move || {
+        read_worktree_file(&worktree_id, &query.path, max_bytes)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(WorktreeFileResult::Found {
+            content_type,
+            bytes,
+        })) => (
+            [
+                (header::CONTENT_TYPE, content_type.as_str()),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                (header::CONTENT_SECURITY_POLICY, "sandbox"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Ok(Ok(WorktreeFileResult::WorktreeNotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Ok(WorktreeFileResult::FileNotFound(path))) => (
+            StatusCode::NOT_FOUND,
+            format!("File {path} not found in {id_for_error}"),
+        )
+            .into_response(),
+        Ok(Ok(WorktreeFileResult::Invalid(message))) => {
+            (StatusCode::BAD_REQUEST, message).into_response()
+        }
+        Ok(Ok(WorktreeFileResult::TooLarge(size))) => (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("File is {size} bytes; the limit is {max_bytes} (set {FILE_MAX_BYTES_ENV})"),
+        )
+            .into_response(),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read file from {id_for_error}: {err}"),
+        )
+            .into_response(),
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("File read task failed: {join_err}"),
+        )
+            .into_response(),
     }
 }
 
-impl From<crate::git::GitFileDiff> for WorktreeFileDiffPayload {
-    fn from(value: crate::git::GitFileDiff) -> Self {
-        Self {
-            path: value.path,
-            display_path: value.display_path,
-            status: value.status,
-            diff: value.diff,
-        }
-    }
+/// Env var capping the size of files served by `/api/worktrees/:id/file`.
+const FILE_MAX_BYTES_ENV: &str = "AGENTDEV_FILE_MAX_BYTES";
+const DEFAULT_FILE_MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+fn worktree_file_max_bytes(value: Option<&str>) -> u64 {
+    value
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_FILE_MAX_BYTES)
 }
 
-impl From<crate::git::CommitDiffInfo> for WorktreeCommitDiffPayload {
-    fn from(value: crate::git::CommitDiffInfo) -> Self {
-        Self {
-            reference: value.reference,
-            diff: value.diff,
-        }
-    }
+enum WorktreeFileResult {
+    Found {
+        content_type: String,
+        bytes: Vec<u8>,
+    },
+    WorktreeNotFound,
+    FileNotFound(String),
+    Invalid(String),
+    TooLarge(u64),
 }
 
-fn collect_worktree_summary(id: String) -> Result<Option<WorktreeSummary>> {
-    let profiler = WorktreeProfiler::new();
-    let overall_start = if profiler.enabled() {
-        Some(Instant::now())
-    } else {
-        None
+fn read_worktree_file(id: &str, relative: &str, max_bytes: u64) -> Result<WorktreeFileResult> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(id) else {
+        return Ok(WorktreeFileResult::WorktreeNotFound);
+    };
+    let root = match info.path.canonicalize() {
+        Ok(root) => root,
+        Err(_) => return Ok(WorktreeFileResult::WorktreeNotFound),
     };
 
-    let state = profiler.measure_result("state.load", || XlaudeState::load())?;
-    let external_sessions = collect_external_sessions(&profiler);
-    let summary = state.worktrees.get(&id).map(|info| {
-        profiler.measure_worktree(&id, "summarize", || {
-            summarize_single_worktree(&id, info, &external_sessions, &profiler)
-        })
-    });
-
-    if let Some(start) = overall_start {
-        println!(
-            "[profile/worktrees] total_single::{id} took {:?}",
-            start.elapsed()
-        );
+    let path = match resolve_path_within(&root, relative) {
+        Ok(Some(path)) => path,
+        Ok(None) => return Ok(WorktreeFileResult::FileNotFound(relative.to_string())),
+        Err(message) => return Ok(WorktreeFileResult::Invalid(message)),
+    };
+    let metadata = std::fs::metadata(&path)?;
+    if !metadata.is_file() {
+        return Ok(WorktreeFileResult::Invalid(format!(
+            "{relative} is not a regular file"
+        )));
     }
-
-    Ok(summary)
-}
-
-fn summarize_single_worktree(
-    id: &str,
-    info: &WorktreeInfo,
-    external_sessions: &[NormalizedSession],
-    profiler: &WorktreeProfiler,
-) -> WorktreeSummary {
-    let path_exists = info.path.exists();
-    let git_ready = path_exists && git_metadata_present(&info.path);
-
-    if path_exists && !git_ready {
-        warn_once("git_metadata", &info.path, || {
-            format!(
-                "⚠️  Worktree missing git metadata, skipping inspection: {}",
-                info.path.display()
-            )
-        });
+    if metadata.len() > max_bytes {
+        return Ok(WorktreeFileResult::TooLarge(metadata.len()));
     }
 
-    let git_status = if git_ready {
-        match profiler.measure_worktree_result(id, "git_status", || {
-            summarize_worktree_status(&info.path, &info.branch)
-        }) {
-            Ok(status) => Some(WorktreeGitStatusPayload::from(status)),
-            Err(err) => {
-                warn_once("git_status", &info.path, || {
-                    format!(
-                        "⚠️  Failed to inspect git status for {}: {err}",
-                        info.path.display()
-                    )
-                });
-                None
-            }
-        }
-    } else if !path_exists {
-        warn_once("missing_path", &info.path, || {
-            format!(
-                "⚠️  Worktree path missing, skipping git status: {}",
-                info.path.display()
-            )
-        });
-        None
-    } else {
-        None
-    };
-
-    let head_commit = if git_ready {
-        match profiler.measure_worktree_result(id, "head_commit", || head_commit_info(&info.path)) {
-            Ok(result) => result.map(WorktreeCommitPayload::from),
-            Err(err) => {
-                warn_once("head_commit", &info.path, || {
-                    format!(
-                        "⚠️  Failed to read last commit for {}: {err}",
-                        info.path.display()
-                    )
-                });
-                None
-            }
-        }
+    let bytes = std::fs::read(&path)?;
+    let mime = mime_guess::from_path(&path).first_or_text_plain();
+    let content_type = if is_active_content(&mime) {
+        // Worktree files are agent-written; never let the browser render
+        // markup from the API origin, where it could read the API token
+        "text/plain; charset=utf-8".to_string()
+    } else if std::str::from_utf8(&bytes).is_ok() {
+        let essence = if mime.type_() == mime_guess::mime::TEXT
+            || mime.type_() == mime_guess::mime::APPLICATION
+        {
+            mime.essence_str().to_string()
+        } else {
+            "text/plain".to_string()
+        };
+        format!("{essence}; charset=utf-8")
+    } else if mime.type_() == mime_guess::mime::TEXT {
+        // Text we cannot decode is handed over as opaque bytes
+        "application/octet-stream".to_string()
     } else {
-        None
+        mime.essence_str().to_string()
     };
 
-    let commits_ahead = if git_ready {
-        match profiler.measure_worktree_result(id, "commits_since_merge_base", || {
-            commits_since_merge_base(&info.path)
-        }) {
-            Ok(result) => result.map(WorktreeCommitsAheadPayload::from),
-            Err(err) => {
-                warn_once("git_commits_ahead", &info.path, || {
-                    format!(
-                        "⚠️  Failed to read commits relative to base for {}: {err}",
-                        info.path.display()
-                    )
-                });
-                None
-            }
-        }
-    } else {
-        None
-    };
+    Ok(WorktreeFileResult::Found {
+        content_type,
+        bytes,
+    })
+}
 
-    let mut sessions: Vec<WorktreeSessionSummary> = Vec::new();
-    if path_exists {
-        sessions.extend(profiler.measure_worktree(id, "sessions.external", || {
-            match_sessions_for_worktree(info, external_sessions)
-        }));
-    }
-    sessions.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
+/// Types a browser may render as a document that runs script.
+fn is_active_content(mime: &mime_guess::Mime) -> bool {
+    matches!(
+        mime.essence_str(),
+        "text/html"
+            | "text/xml"
+            | "text/xsl"
+            | "application/xml"
+            | "application/xhtml+xml"
+            | "application/xslt+xml"
+            | "image/svg+xml"
+    ) || mime.suffix() == Some(mime_guess::mime::XML)
+}
 
-    let mut last_activity = info.created_at;
-    if let Some(ref commit) = head_commit {
-        if let Some(ts) = commit.timestamp {
-            if ts > last_activity {
-                last_activity = ts;
-            }
-        }
+/// Resolve `relative` under `root`, refusing paths that leave it.
+///
+/// `root` must already be canonical. Absolute paths and `..` components are
+/// rejected up front; symlinks are resolved so a link pointing outside the
+/// worktree is refused as well. Returns `Ok(None)` when nothing exists at
+/// the path.
+fn resolve_path_within(
+    root: &Path,
+    relative: &str,
+) -> std::result::Result<Option<PathBuf>, String> {
+    let requested = Path::new(relative);
+    if relative.trim().is_empty() {
+        return Err("A file path is required".to_string());
     }
-    for session in &sessions {
-        if let Some(ts) = session.last_timestamp {
-            if ts > last_activity {
-                last_activity = ts;
-            }
-        }
+    let escapes = requested.components().any(|component| {
+        !matches!(
+            component,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    });
+    if escapes {
+        return Err(format!(
+            "Path {relative} must be relative to the worktree without '..'"
+        ));
     }
 
-    WorktreeSummary {
-        id: id.to_string(),
-        name: info.name.clone(),
-        branch: info.branch.clone(),
-        repo_name: info.repo_name.clone(),
-        path: info.path.display().to_string(),
-        created_at: info.created_at,
-        last_activity_at: last_activity,
-        task_id: info.task_id.clone(),
-        task_name: info.task_name.clone(),
-        initial_prompt: info.initial_prompt.clone(),
-        agent_alias: info.agent_alias.clone(),
-        git_status,
-        head_commit,
-        commits_ahead,
-        sessions,
+    let resolved = match root.join(requested).canonicalize() {
+        Ok(resolved) => resolved,
+        Err(_) => return Ok(None),
+    };
+    if !resolved.starts_with(root) {
+        return Err(format!("Path {relative} resolves outside the worktree"));
     }
+    Ok(Some(resolved))
 }
 
 /// GET /api/worktrees/:id/git - Detailed git diff breakdown for a worktree
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/git",
+        params(("worktree_id" = String, Path, description = "Worktree state key (repo/name)")),
+        responses(
+            (status = 200, description = "Git diff breakdown", body = WorktreeGitDetailsPayload),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Worktree path or git metadata missing")
+        )
+    )
+)]
 pub async fn get_worktree_git_details(
     AxumPath(worktree_id): AxumPath<String>,
 ) -> impl IntoResponse {
@@ -2220,6 +4941,91 @@ pub async fn get_worktree_git_details(
     }
 }
 
+/// GET /api/worktrees/:id/commits_ahead - Commits on HEAD since it diverged from `base`
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/worktrees/{worktree_id}/commits_ahead",
+        params(
+            ("worktree_id" = String, Path, description = "Worktree state key (repo/name)"),
+            WorktreeCommitsAheadQuery
+        ),
+        responses(
+            (status = 200, description = "Commits ahead of the requested base", body = WorktreeCommitsAheadPayload),
+            (status = 400, description = "Base ref does not exist"),
+            (status = 404, description = "Worktree not found"),
+            (status = 409, description = "Worktree path or git metadata missing")
+        )
+    )
+)]
+pub async fn get_worktree_commits_ahead(
+    AxumPath(worktree_id): AxumPath<String>,
+    Query(query): Query<WorktreeCommitsAheadQuery>,
+) -> impl IntoResponse {
+    let id_for_error = worktree_id.clone();
+    let base = query.base.trim().to_string();
+    let base_for_error = base.clone();
+    match tokio::task::spawn_blocking(move || collect_commits_ahead(worktree_id, &base)).await {
+        Ok(Ok(CommitsAheadResult::Found(payload))) => Json(payload).into_response(),
+        Ok(Ok(CommitsAheadResult::WorktreeNotFound)) => (
+            StatusCode::NOT_FOUND,
+            format!("Worktree {id_for_error} not found"),
+        )
+            .into_response(),
+        Ok(Ok(CommitsAheadResult::UnknownRef)) => (
+            StatusCode::BAD_REQUEST,
+            format!("Ref '{base_for_error}' does not exist in {id_for_error}"),
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            let message = err.to_string();
+            let status = if message.contains("Worktree path missing")
+                || message.contains("Worktree missing git metadata")
+            {
+                StatusCode::CONFLICT
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            (
+                status,
+                format!("Failed to compare {id_for_error} against {base_for_error}: {message}"),
+            )
+                .into_response()
+        }
+        Err(join_err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Commits ahead task failed: {join_err}"),
+        )
+            .into_response(),
+    }
+}
+
+enum CommitsAheadResult {
+    Found(WorktreeCommitsAheadPayload),
+    WorktreeNotFound,
+    UnknownRef,
+}
+
+fn collect_commits_ahead(id: String, base: &str) -> Result<CommitsAheadResult> {
+    let state = XlaudeState::load()?;
+    let Some(info) = state.worktrees.get(&id) else {
+        return Ok(CommitsAheadResult::WorktreeNotFound);
+    };
+
+    if !info.path.exists() {
+        anyhow::bail!("Worktree path missing: {}", info.path.display());
+    }
+    if !git_metadata_present(&info.path) {
+        anyhow::bail!("Worktree missing git metadata: {}", info.path.display());
+    }
+
+    Ok(match commits_since_ref(&info.path, base)? {
+        Some(ahead) => CommitsAheadResult::Found(ahead.into()),
+        None => CommitsAheadResult::UnknownRef,
+    })
+}
+
 fn collect_worktree_git_details(id: String) -> Result<Option<WorktreeGitDetailsPayload>> {
     let state = XlaudeState::load()?;
     let Some(info) = state.worktrees.get(&id) else {
@@ -2256,3 +5062,190 @@ fn collect_worktree_git_details(id: String) -> Result<Option<WorktreeGitDetailsP
 
     Ok(Some(payload))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_terminal_commands_set_working_directory() {
+        let path = Path::new("/work/my repo");
+        let argv = |terminal: &str, command: Option<&str>| {
+            build_linux_terminal_command(terminal, path, command).unwrap()
+        };
+
+        assert_eq!(
+            argv("gnome-terminal", Some("cargo test")),
+            [
+                "gnome-terminal",
+                "--working-directory=/work/my repo",
+                "--",
+                "sh",
+                "-c",
+                "cargo test; exec \"${SHELL:-/bin/sh}\" -l",
+            ]
+        );
+        assert_eq!(
+            argv("konsole", None),
+            [
+                "konsole",
+                "--workdir",
+                "/work/my repo",
+                "-e",
+                "sh",
+                "-c",
+                "exec \"${SHELL:-/bin/sh}\" -l",
+            ]
+        );
+        assert_eq!(
+            argv("wezterm", None)[..4],
+            ["wezterm", "start", "--cwd", "/work/my repo"]
+        );
+        assert_eq!(
+            argv("alacritty", None)[..4],
+            ["alacritty", "--working-directory", "/work/my repo", "-e"]
+        );
+        assert_eq!(
+            argv("x-terminal-emulator", Some("ls")),
+            [
+                "x-terminal-emulator",
+                "-e",
+                "sh",
+                "-c",
+                "cd '/work/my repo' && ls; exec \"${SHELL:-/bin/sh}\" -l",
+            ]
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_terminal_commands_set_working_directory() {
+        let path = Path::new(r"C:\work\repo");
+        assert_eq!(
+            build_windows_terminal_command(true, path, Some("cargo test")),
+            ["wt.exe", "-d", r"C:\work\repo", "cmd", "/k", "cargo test"]
+        );
+        assert_eq!(
+            build_windows_terminal_command(false, path, None),
+            ["cmd", "/c", "start", "", "/D", r"C:\work\repo", "cmd"]
+        );
+    }
+
+    fn summary(name: &str, repo: &str, branch: &str) -> WorktreeSummary {
+        WorktreeSummary {
+            id: format!("{repo}/{name}"),
+            name: name.to_string(),
+            branch: branch.to_string(),
+            repo_name: repo.to_string(),
+            path: format!("/work/{repo}-{name}"),
+            created_at: chrono::Utc::now(),
+            last_activity_at: chrono::Utc::now(),
+            task_id: None,
+            task_name: None,
+            initial_prompt: None,
+            agent_alias: None,
+            locked: false,
+            lock_reason: None,
+            pinned: false,
+            notes: None,
+            symlink_path: None,
+            git_status: None,
+            head_commit: None,
+            commits_ahead: None,
+            size_bytes: None,
+            agent_activity: None,
+            sessions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn search_filters_combine_with_and() {
+        let mut login = summary("fix-login", "app", "fix/login");
+        login.task_id = Some("AUTH-12".to_string());
+        login.git_status = Some(WorktreeGitStatusPayload {
+            branch: Some("fix/login".to_string()),
+            detached: false,
+            head: None,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            staged: 0,
+            unstaged: 2,
+            untracked: 0,
+            conflicts: 0,
+            is_clean: false,
+        });
+        let docs = summary("docs", "web", "docs");
+        let worktrees = [login, docs];
+        let search = |query: WorktreeSearchQuery| -> Vec<String> {
+            worktrees
+                .iter()
+                .filter(|summary| query.matches(summary))
+                .map(|summary| summary.name.clone())
+                .collect()
+        };
+
+        assert_eq!(search(WorktreeSearchQuery::default()).len(), 2);
+        let by_text = |q: &str| WorktreeSearchQuery {
+            q: Some(q.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search(by_text("auth-1")), ["fix-login"]);
+        assert_eq!(search(by_text("WEB")), ["docs"]);
+        assert_eq!(
+            search(WorktreeSearchQuery {
+                status: Some(WorktreeStatusFilter::Dirty),
+                ..Default::default()
+            }),
+            ["fix-login"]
+        );
+        // Unknown git status matches neither clean nor dirty
+        assert!(
+            search(WorktreeSearchQuery {
+                status: Some(WorktreeStatusFilter::Clean),
+                ..Default::default()
+            })
+            .is_empty()
+        );
+        assert!(
+            search(WorktreeSearchQuery {
+                q: Some("login".to_string()),
+                repo: Some("web".to_string()),
+                ..Default::default()
+            })
+            .is_empty()
+        );
+        assert_eq!(
+            search(WorktreeSearchQuery {
+                branch: Some("docs".to_string()),
+                has_session: Some(false),
+                ..Default::default()
+            }),
+            ["docs"]
+        );
+    }
+
+    #[test]
+    fn template_overrides_fill_placeholders() {
+        let argv = build_terminal_command_from_template(
+            Path::new("/work/repo"),
+            None,
+            "wezterm start --cwd '{cwd}' -- bash -lc '{command_or_shell}'",
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            [
+                "wezterm",
+                "start",
+                "--cwd",
+                "/work/repo",
+                "--",
+                "bash",
+                "-lc",
+                "exec \"$SHELL\" -l",
+            ]
+        );
+    }
+}
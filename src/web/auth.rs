@@ -0,0 +1,216 @@
+//! Optional bearer token authentication for the web API.
+//!
+//! Enabled by setting `AGENTDEV_API_TOKEN`. Every `/api` route except
+//! `/api/health` then requires `Authorization: Bearer <token>`; WebSocket
+//! clients that cannot set headers may pass `?token=<token>` instead.
+//! Requests without a matching token get `401 Unauthorized`. The static UI
+//! assets stay public since they carry no data.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    extract::Query,
+    http::{Method, Request, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use constant_time_eq::constant_time_eq;
+use serde::Deserialize;
+use tower::{Layer, Service};
+
+/// Env var holding the token clients must present.
+pub const API_TOKEN_ENV: &str = "AGENTDEV_API_TOKEN";
+
+const PUBLIC_API_PATHS: &[&str] = &["/api/health"];
+
+/// Layer that rejects API requests lacking the configured bearer token.
+#[derive(Clone, Debug, Default)]
+pub struct AuthLayer {
+    token: Option<Arc<str>>,
+}
+
+impl AuthLayer {
+    /// Require `token`; `None` or an empty token disables authentication.
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .map(Arc::from),
+        }
+    }
+
+    /// Read the token from `AGENTDEV_API_TOKEN`.
+    pub fn from_env() -> Self {
+        Self::new(std::env::var(API_TOKEN_ENV).ok())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner,
+            token: self.token.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthService<S> {
+    inner: S,
+    token: Option<Arc<str>>,
+}
+
+impl<S> Service<Request<Body>> for AuthService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        if let Some(token) = &self.token
+            && requires_token(&request)
+            && !presents_token(&request, token)
+        {
+            return Box::pin(async { Ok(unauthorized()) });
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+fn requires_token(request: &Request<Body>) -> bool {
+    let path = request.uri().path();
+    // CORS preflight requests never carry credentials
+    request.method() != Method::OPTIONS
+        && (path == "/api" || path.starts_with("/api/"))
+        && !PUBLIC_API_PATHS.contains(&path.trim_end_matches('/'))
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+fn presents_token(request: &Request<Body>, token: &str) -> bool {
+    let from_header = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    // Clients percent-encode the query value (`encodeURIComponent`), so
+    // decode it before comparing
+    let from_query = Query::<TokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.token);
+
+    [from_header, from_query.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|candidate| constant_time_eq(candidate.as_bytes(), token.as_bytes()))
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        format!("Missing or invalid bearer token (see {API_TOKEN_ENV})"),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, routing::get};
+    use tower::ServiceExt;
+
+    fn app(token: Option<&str>) -> Router {
+        Router::new()
+            .route("/api/health", get(|| async { "ok" }))
+            .route("/api/worktrees", get(|| async { "worktrees" }))
+            .fallback(|| async { "index" })
+            .layer(AuthLayer::new(token.map(str::to_string)))
+    }
+
+    async fn status(app: Router, uri: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn requires_matching_token_on_api_routes() {
+        let app = app(Some("s3cret"));
+        assert_eq!(
+            status(app.clone(), "/api/worktrees", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/api/worktrees", Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/api/worktrees", Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app.clone(), "/api/worktrees?token=s3cret", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app.clone(), "/api/health", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(status(app, "/index.html", None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn query_token_is_percent_decoded() {
+        let app = app(Some("a+b/c=d% e"));
+        assert_eq!(
+            status(
+                app.clone(),
+                "/api/worktrees?since=1&token=a%2Bb%2Fc%3Dd%25%20e",
+                None
+            )
+            .await,
+            StatusCode::OK
+        );
+        // An unencoded `+` decodes to a space
+        assert_eq!(
+            status(app, "/api/worktrees?token=a+b%2Fc%3Dd%25%20e", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_token_disables_auth() {
+        assert!(!AuthLayer::new(Some("  ".to_string())).is_enabled());
+        assert_eq!(
+            status(app(None), "/api/worktrees", None).await,
+            StatusCode::OK
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Prometheus metrics for the web server.
+//!
+//! Request latencies are recorded by [`track_request_duration`] as requests
+//! come in; the worktree, session and process gauges are refreshed from disk
+//! (and tmux) each time `/api/metrics` is scraped.
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+/// Label used for requests that did not match an API route.
+const UNMATCHED_ENDPOINT: &str = "other";
+
+pub const WORKTREE_STATUSES: [&str; 3] = ["running", "idle", "exited"];
+
+struct Metrics {
+    registry: Registry,
+    worktrees_total: IntGauge,
+    worktrees_by_status: IntGaugeVec,
+    sessions_total: IntGaugeVec,
+    processes_total: IntGaugeVec,
+    request_duration: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new();
+        let worktrees_total =
+            IntGauge::new("agentdev_worktrees_total", "Worktrees managed by agentdev")?;
+        let worktrees_by_status = IntGaugeVec::new(
+            Opts::new(
+                "agentdev_worktrees_by_status",
+                "Managed worktrees by agent status",
+            ),
+            &["status"],
+        )?;
+        let sessions_total = IntGaugeVec::new(
+            Opts::new("agentdev_sessions_total", "Agent sessions found on disk"),
+            &["provider"],
+        )?;
+        let processes_total = IntGaugeVec::new(
+            Opts::new(
+                "agentdev_processes_total",
+                "Processes launched from the web UI",
+            ),
+            &["status"],
+        )?;
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "agentdev_api_request_duration_seconds",
+                "API request latency by route",
+            ),
+            &["endpoint"],
+        )?;
+
+        registry.register(Box::new(worktrees_total.clone()))?;
+        registry.register(Box::new(worktrees_by_status.clone()))?;
+        registry.register(Box::new(sessions_total.clone()))?;
+        registry.register(Box::new(processes_total.clone()))?;
+        registry.register(Box::new(request_duration.clone()))?;
+
+        Ok(Self {
+            registry,
+            worktrees_total,
+            worktrees_by_status,
+            sessions_total,
+            processes_total,
+            request_duration,
+        })
+    }
+}
+
+fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics::new().expect("metric definitions are valid"))
+}
+
+/// Point-in-time counts published as gauges on each scrape.
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    pub worktrees_total: usize,
+    /// Keyed by one of [`WORKTREE_STATUSES`].
+    pub worktrees_by_status: Vec<(&'static str, usize)>,
+    pub sessions_by_provider: Vec<(&'static str, usize)>,
+    pub processes_by_status: Vec<(&'static str, usize)>,
+}
+
+/// Replace the gauges with `snapshot` and encode the whole registry in the
+/// Prometheus text exposition format.
+pub fn render(snapshot: &Snapshot) -> Result<String> {
+    let metrics = metrics();
+
+    metrics.worktrees_total.set(snapshot.worktrees_total as i64);
+    // Reset so labels that disappeared since the last scrape don't linger.
+    metrics.worktrees_by_status.reset();
+    for (status, count) in &snapshot.worktrees_by_status {
+        metrics
+            .worktrees_by_status
+            .with_label_values(&[status])
+            .set(*count as i64);
+    }
+    metrics.sessions_total.reset();
+    for (provider, count) in &snapshot.sessions_by_provider {
+        metrics
+            .sessions_total
+            .with_label_values(&[provider])
+            .set(*count as i64);
+    }
+    metrics.processes_total.reset();
+    for (status, count) in &snapshot.processes_by_status {
+        metrics
+            .processes_total
+            .with_label_values(&[status])
+            .set(*count as i64);
+    }
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&metrics.registry.gather(), &mut buffer)
+        .context("Failed to encode metrics")?;
+    String::from_utf8(buffer).context("Metrics output is not valid UTF-8")
+}
+
+/// Middleware recording how long each API request took, labelled by its
+/// route template (e.g. `/api/worktrees/:worktree_id`) to keep cardinality low.
+pub async fn track_request_duration(request: Request, next: Next) -> Response {
+    let endpoint = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ENDPOINT.to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics()
+        .request_duration
+        .with_label_values(&[endpoint.as_str()])
+        .observe(started.elapsed().as_secs_f64());
+    response
+}
@@ -1,22 +1,42 @@
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use axum::{
     Router,
+    body::Body,
+    http::Request,
+    middleware,
     response::IntoResponse,
     routing::{get, post},
 };
 use tokio::net::TcpListener;
 use tower::{ServiceBuilder, make::Shared};
-use tower_http::{cors::CorsLayer, normalize_path::NormalizePathLayer};
+use tower_http::{cors::CorsLayer, normalize_path::NormalizePathLayer, trace::TraceLayer};
 
 pub mod api;
+pub mod auth;
 mod frontend;
+pub mod metrics;
+pub mod notify;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+pub mod telemetry;
+pub mod webhook;
 
 use api::*;
 
+/// Certificate and private key used to serve HTTPS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// PEM-encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// PEM-encoded private key matching the certificate.
+    pub key_path: PathBuf,
+}
+
 /// Configuration options for launching the embedded UI server.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ServerOptions {
     /// Override the port used for the HTTP server. When `None`, fall back to
     /// the `PORT` or `AGENTDEV_BACKEND_PORT` environment variables, then 3000.
@@ -26,21 +46,14 @@ pub struct ServerOptions {
     pub host: Option<IpAddr>,
     /// Whether to attempt opening the default browser after the server starts.
     pub auto_open_browser: bool,
-}
-
-impl Default for ServerOptions {
-    fn default() -> Self {
-        Self {
-            port: None,
-            host: None,
-            auto_open_browser: false,
-        }
-    }
+    /// Serve HTTPS with these credentials instead of plain HTTP. When `None`,
+    /// fall back to `AGENTDEV_TLS_CERT` and `AGENTDEV_TLS_KEY`.
+    pub tls: Option<TlsOptions>,
 }
 
 impl ServerOptions {
     /// Construct options using environment defaults (PORT/AGENTDEV_BACKEND_PORT,
-    /// AGENTDEV_BACKEND_HOST/HOST).
+    /// AGENTDEV_BACKEND_HOST/HOST, AGENTDEV_TLS_CERT/AGENTDEV_TLS_KEY).
     pub fn from_env() -> Self {
         let mut options = Self::default();
         options.port = std::env::var("PORT")
@@ -57,6 +70,7 @@ impl ServerOptions {
             options.auto_open_browser =
                 matches!(normalized.as_str(), "1" | "true" | "yes" | "y" | "on");
         }
+        options.tls = tls_from_env();
         options
     }
 
@@ -77,6 +91,15 @@ impl ServerOptions {
         self.auto_open_browser = enabled;
         self
     }
+
+    /// Return a copy of the options that serves HTTPS using the given PEM files.
+    pub fn with_tls(mut self, cert_path: PathBuf, key_path: PathBuf) -> Self {
+        self.tls = Some(TlsOptions {
+            cert_path,
+            key_path,
+        });
+        self
+    }
 }
 
 /// Run the UI server using a Tokio runtime owned by the caller thread.
@@ -88,9 +111,13 @@ pub fn run_blocking(options: ServerOptions) -> Result<()> {
 }
 
 async fn run_async(options: ServerOptions) -> Result<()> {
+    telemetry::init();
     println!("Starting agentdev UI server...");
 
     let router = build_router();
+    if auth::AuthLayer::from_env().is_enabled() {
+        println!("🔒 API requires the token from {}", auth::API_TOKEN_ENV);
+    }
     let service = ServiceBuilder::new()
         .layer(NormalizePathLayer::trim_trailing_slash())
         .service(router);
@@ -114,46 +141,117 @@ async fn run_async(options: ServerOptions) -> Result<()> {
         .or_else(host_from_env)
         .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
     let addr = SocketAddr::from((host, port));
-    let listener = TcpListener::bind(addr).await?;
 
+    let webhook_config = webhook::WebhookConfig::from_env();
+    let notify_config = match crate::config::load_agent_config() {
+        Ok(config) if config.notify.enabled => Some(config.notify),
+        Ok(_) => None,
+        Err(err) => {
+            tracing::warn!("Idle notifications disabled: {err:#}");
+            None
+        }
+    };
+    if let Some(config) = &webhook_config {
+        println!("🔔 Status webhooks enabled: {}", config.url);
+    }
+    if notify_config.is_some() {
+        println!("🔔 Idle notifications enabled");
+    }
+    if webhook_config.is_some() || notify_config.is_some() {
+        webhook::spawn_notifier(webhook_config, notify_config)?;
+    }
+
+    let tls = options.tls.or_else(tls_from_env);
+    let scheme = if tls.is_some() { "https" } else { "http" };
+
+    if let Some(tls) = tls {
+        install_crypto_provider();
+        let config =
+            axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to load TLS certificate {} and key {}",
+                        tls.cert_path.display(),
+                        tls.key_path.display()
+                    )
+                })?;
+        let server = axum_server::bind_rustls(addr, config);
+        announce_server(scheme, host, port, options.auto_open_browser);
+        server.serve(app).await?;
+    } else {
+        let listener = TcpListener::bind(addr).await?;
+        announce_server(scheme, host, port, options.auto_open_browser);
+        axum::serve(listener, app).await?;
+    }
+
+    Ok(())
+}
+
+fn announce_server(scheme: &str, host: IpAddr, port: u16, auto_open_browser: bool) {
     println!(
-        "🚀 AgentDev UI server running on http://{}:{port}",
+        "🚀 AgentDev UI server running on {scheme}://{}:{port}",
         format_host_for_display(host)
     );
 
-    if options.auto_open_browser {
-        if let Err(e) = open_browser(host, port) {
+    if auto_open_browser {
+        if let Err(e) = open_browser(scheme, host, port) {
             println!(
-                "Failed to open browser: {e}. Please manually visit http://{}:{port}",
+                "Failed to open browser: {e}. Please manually visit {scheme}://{}:{port}",
                 format_host_for_display(host)
             );
         }
     }
-
-    axum::serve(listener, app).await?;
-
-    Ok(())
 }
 
 fn build_router() -> Router {
-    Router::new()
+    let router = Router::new()
         // API routes
+        .route("/api/health", get(get_health))
+        .route("/api/metrics", get(get_metrics))
         .route(
             "/api/sessions/:provider/:session_id",
             get(get_session_detail),
         )
+        .route(
+            "/api/sessions/:provider/:session_id/delete",
+            post(post_session_delete),
+        )
         .route("/api/sessions", get(get_sessions))
-        .route("/api/worktrees", get(get_worktrees))
+        .route("/api/worktrees", get(get_worktrees).post(post_worktree))
+        .route("/api/worktrees/search", get(get_worktree_search))
         .route("/api/worktrees/discovery", get(get_worktree_discovery))
-        .route("/api/worktrees/:worktree_id", get(get_worktree))
+        .route(
+            "/api/worktrees/:worktree_id",
+            get(get_worktree).patch(patch_worktree),
+        )
         .route(
             "/api/worktrees/:worktree_id/git",
             get(get_worktree_git_details),
         )
+        .route(
+            "/api/worktrees/:worktree_id/commits_ahead",
+            get(get_worktree_commits_ahead),
+        )
+        .route("/api/worktrees/:worktree_id/size", get(get_worktree_size))
+        .route("/api/worktrees/:worktree_id/diff", get(get_worktree_diff))
+        .route(
+            "/api/worktrees/:worktree_id/checkpoints",
+            get(get_worktree_checkpoints),
+        )
+        .route("/api/worktrees/:worktree_id/file", get(get_worktree_file))
+        .route(
+            "/api/worktrees/:worktree_id/sessions",
+            get(get_worktree_sessions),
+        )
         .route(
             "/api/worktrees/:worktree_id/processes",
             get(get_worktree_processes),
         )
+        .route(
+            "/api/worktrees/:worktree_id/processes/:process_id/retry",
+            post(post_worktree_process_retry),
+        )
         .route(
             "/api/worktrees/:worktree_id/commands",
             post(post_worktree_command),
@@ -162,18 +260,73 @@ fn build_router() -> Router {
             "/api/worktrees/:worktree_id/shell",
             post(post_worktree_shell),
         )
+        .route(
+            "/api/worktrees/:worktree_id/editor",
+            post(post_worktree_editor),
+        )
+        .route(
+            "/api/worktrees/:worktree_id/paste",
+            post(post_worktree_paste),
+        )
         .route("/api/shell", post(post_shell))
+        .route("/api/conflicts", get(get_conflicts))
+        .route("/api/tasks", get(get_tasks).post(post_task))
+        .route("/api/tasks/:task_id/commands", post(post_task_command))
+        .route("/api/admin/gc", post(post_admin_gc))
         .route(
             "/api/worktrees/:worktree_id/merge",
             post(post_worktree_merge),
         )
+        .route(
+            "/api/worktrees/:worktree_id/rebase",
+            post(post_worktree_rebase),
+        )
         .route(
             "/api/worktrees/:worktree_id/delete",
             post(post_worktree_delete),
         )
+        .route_layer(middleware::from_fn(metrics::track_request_duration));
+
+    #[cfg(feature = "openapi")]
+    let router = router.route("/api/openapi.json", get(openapi::get_openapi));
+
+    router
         // Static file serving (fallback to index.html for SPA)
         .fallback(serve_frontend)
+        .layer(auth::AuthLayer::from_env())
         .layer(CorsLayer::permissive())
+        .layer(TraceLayer::new_for_http().make_span_with(request_span))
+}
+
+/// Span wrapping each request, tagged with the worktree or provider it targets.
+fn request_span(request: &Request<Body>) -> tracing::Span {
+    let path = request.uri().path();
+    let (worktree_id, provider) = route_targets(path);
+    tracing::span!(
+        tracing::Level::INFO,
+        "request",
+        method = %request.method(),
+        path,
+        worktree_id,
+        provider,
+    )
+}
+
+/// Worktree id and session provider named by an API path, if any.
+fn route_targets(path: &str) -> (Option<String>, Option<String>) {
+    let mut segments = path
+        .strip_prefix("/api/")
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let decode = |segment: &str| segment.replace("%2F", "/").replace("%2f", "/");
+    match (segments.next(), segments.next()) {
+        (Some("worktrees"), Some(id)) if !matches!(id, "discovery" | "search") => {
+            (Some(decode(id)), None)
+        }
+        (Some("sessions"), Some(provider)) => (None, Some(provider.to_string())),
+        _ => (None, None),
+    }
 }
 
 async fn serve_frontend(uri: axum::http::Uri) -> impl IntoResponse {
@@ -184,6 +337,21 @@ fn host_from_env() -> Option<IpAddr> {
     parse_env_ip("AGENTDEV_BACKEND_HOST").or_else(|| parse_env_ip("HOST"))
 }
 
+/// Select aws-lc-rs as the process-wide rustls provider for both the HTTPS
+/// listener and outbound webhook requests. Safe to call more than once.
+fn install_crypto_provider() {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+}
+
+fn tls_from_env() -> Option<TlsOptions> {
+    let cert_path = std::env::var_os("AGENTDEV_TLS_CERT").filter(|v| !v.is_empty())?;
+    let key_path = std::env::var_os("AGENTDEV_TLS_KEY").filter(|v| !v.is_empty())?;
+    Some(TlsOptions {
+        cert_path: PathBuf::from(cert_path),
+        key_path: PathBuf::from(key_path),
+    })
+}
+
 fn parse_env_ip(key: &str) -> Option<IpAddr> {
     std::env::var(key)
         .ok()
@@ -206,9 +374,9 @@ fn format_host_for_browser(host: IpAddr) -> String {
     }
 }
 
-fn open_browser(host: IpAddr, port: u16) -> Result<()> {
+fn open_browser(scheme: &str, host: IpAddr, port: u16) -> Result<()> {
     let url_host = format_host_for_browser(host);
-    let url = format!("http://{url_host}:{port}");
+    let url = format!("{scheme}://{url_host}:{port}");
 
     #[cfg(target_os = "macos")]
     std::process::Command::new("open").arg(&url).spawn()?;
@@ -237,6 +405,7 @@ mod tests {
     struct EnvGuard {
         key: &'static str,
         original: Option<std::ffi::OsString>,
+        _lock: Option<std::sync::MutexGuard<'static, ()>>,
     }
 
     impl EnvGuard {
@@ -250,7 +419,11 @@ mod tests {
             unsafe {
                 std::env::set_var(key, value);
             }
-            Self { key, original }
+            Self {
+                key,
+                original,
+                _lock: None,
+            }
         }
     }
 
@@ -268,9 +441,17 @@ mod tests {
         }
     }
 
+    /// Serializes tests that point `HOME` at their own temp dir; the handlers
+    /// under test (and the CLI processes they spawn) read it at call time.
+    static HOME_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
     fn setup_test_env() -> (TempDir, EnvGuard, EnvGuard) {
+        let lock = HOME_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
         let temp = TempDir::new().expect("create temp dir");
-        let home_guard = EnvGuard::set("HOME", temp.path());
+        let mut home_guard = EnvGuard::set("HOME", temp.path());
+        home_guard._lock = Some(lock);
         let config_dir = temp.path().join(".config/xlaude");
         if let Err(err) = std::fs::create_dir_all(&config_dir) {
             panic!("failed to create config dir for test: {err}");
@@ -279,6 +460,44 @@ mod tests {
         (temp, home_guard, config_guard)
     }
 
+    #[test]
+    fn tls_options_from_builder_and_env() {
+        let options =
+            ServerOptions::default().with_tls(PathBuf::from("cert.pem"), PathBuf::from("key.pem"));
+        assert_eq!(
+            options.tls,
+            Some(TlsOptions {
+                cert_path: PathBuf::from("cert.pem"),
+                key_path: PathBuf::from("key.pem"),
+            })
+        );
+
+        let _cert_guard = EnvGuard::set("AGENTDEV_TLS_CERT", "/tmp/env-cert.pem");
+        let _key_guard = EnvGuard::set("AGENTDEV_TLS_KEY", "");
+        assert_eq!(tls_from_env(), None, "both variables are required");
+
+        let _key_guard = EnvGuard::set("AGENTDEV_TLS_KEY", "/tmp/env-key.pem");
+        let tls = ServerOptions::from_env().tls.expect("tls from env");
+        assert_eq!(tls.cert_path, PathBuf::from("/tmp/env-cert.pem"));
+        assert_eq!(tls.key_path, PathBuf::from("/tmp/env-key.pem"));
+    }
+
+    #[test]
+    fn route_targets_extracts_worktree_and_provider() {
+        assert_eq!(
+            route_targets("/api/worktrees/repo%2Ffeature/diff"),
+            (Some("repo/feature".to_string()), None)
+        );
+        assert_eq!(
+            route_targets("/api/sessions/codex/abc"),
+            (None, Some("codex".to_string()))
+        );
+        assert_eq!(route_targets("/api/worktrees/discovery"), (None, None));
+        assert_eq!(route_targets("/api/worktrees/search"), (None, None));
+        assert_eq!(route_targets("/api/worktrees"), (None, None));
+        assert_eq!(route_targets("/index.html"), (None, None));
+    }
+
     #[tokio::test]
     async fn worktrees_endpoint_accepts_trailing_slash() {
         let (_temp, _home_guard, _config_guard) = setup_test_env();
@@ -361,6 +580,1115 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn create_task_rejects_missing_agents() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let body = serde_json::json!({
+            "repo_path": temp.path(),
+            "prompt": "do the thing",
+            "agents": [],
+        });
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/tasks")
+                    .header(axum::http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .expect("create task request");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// Repo plus agent config for the multi-agent task tests. Agents are never
+    /// launched because `XLAUDE_TEST_MODE` is set by the returned guard.
+    fn setup_task_repo(temp: &TempDir) -> (std::path::PathBuf, EnvGuard) {
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "--initial-branch=main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Tester"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+
+        let config_path = crate::config::agent_config_path();
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "[agents]\nalpha = \"sh\"\nbeta = \"sh\"\n").unwrap();
+
+        (repo, EnvGuard::set("XLAUDE_TEST_MODE", "1"))
+    }
+
+    async fn post_task(body: serde_json::Value) -> axum::response::Response {
+        build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/tasks")
+                    .header(axum::http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .expect("create task request")
+    }
+
+    #[tokio::test]
+    async fn create_task_creates_a_worktree_per_agent() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let (repo, _test_mode_guard) = setup_task_repo(&temp);
+
+        let response = post_task(serde_json::json!({
+            "repo_path": repo,
+            "prompt": "do the thing",
+            "agents": ["alpha", "beta"],
+            "task_name": "pair",
+        }))
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let names: Vec<&str> = json["worktrees"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|worktree| worktree["worktree_name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["pair-alpha", "pair-beta"]);
+
+        let state = crate::state::XlaudeState::load().unwrap();
+        for (name, alias) in [("pair-alpha", "alpha"), ("pair-beta", "beta")] {
+            let info = state
+                .worktrees
+                .values()
+                .find(|info| info.name == name)
+                .unwrap_or_else(|| panic!("{name} missing from state"));
+            assert_eq!(info.task_name.as_deref(), Some("pair"));
+            assert_eq!(info.agent_alias.as_deref(), Some(alias));
+            assert_eq!(info.initial_prompt.as_deref(), Some("do the thing"));
+            assert!(info.path.is_dir());
+        }
+    }
+
+    #[tokio::test]
+    async fn create_task_rolls_back_members_when_a_later_one_fails() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let (repo, _test_mode_guard) = setup_task_repo(&temp);
+        // Occupy the second member's worktree directory so its creation fails.
+        let blocked = temp.path().join("repo.worktrees/pair-beta");
+        std::fs::create_dir_all(&blocked).unwrap();
+
+        let response = post_task(serde_json::json!({
+            "repo_path": repo,
+            "prompt": "do the thing",
+            "agents": ["alpha", "beta"],
+            "task_name": "pair",
+        }))
+        .await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let message = json["message"].as_str().unwrap();
+        assert!(
+            message.contains("agent 'beta'"),
+            "failure should name the agent: {message}"
+        );
+        assert!(
+            message.contains("removed worktrees already created for this task: pair-alpha"),
+            "failure should report the rollback: {message}"
+        );
+
+        let state = crate::state::XlaudeState::load().unwrap();
+        assert!(
+            state
+                .worktrees
+                .values()
+                .all(|info| info.name != "pair-alpha"),
+            "rolled-back member should be gone from state"
+        );
+        assert!(!temp.path().join("repo.worktrees/pair-alpha").exists());
+        let branches = std::process::Command::new("git")
+            .args(["branch", "--list", "pair-alpha"])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+    }
+
+    #[tokio::test]
+    async fn worktree_size_returns_not_found_for_unknown_worktree() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/worktrees/nonexistent/size?respect_gitignore=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("worktree size request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_commits_ahead_returns_not_found_for_unknown_worktree() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/worktrees/nonexistent/commits_ahead?base=main")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("commits ahead request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn process_retry_relaunches_finished_processes_only() {
+        use crate::process_registry::{ProcessRecord, ProcessRegistry, ProcessStatus};
+
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/wt".to_string(),
+            crate::state::WorktreeInfo {
+                name: "wt".to_string(),
+                branch: "wt".to_string(),
+                path: temp.path().to_path_buf(),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+
+        let record = |status| {
+            let mut record = ProcessRecord::new(
+                "repo/wt".to_string(),
+                "wt".to_string(),
+                "repo".to_string(),
+                vec!["true".to_string()],
+                Some(temp.path().to_path_buf()),
+                status,
+            );
+            record.description = Some("flaky".to_string());
+            record
+        };
+        let failed = record(ProcessStatus::Failed);
+        let running = record(ProcessStatus::Running);
+        let (failed_id, running_id) = (failed.id.clone(), running.id.clone());
+        ProcessRegistry::mutate(|registry| {
+            registry.insert(failed);
+            registry.insert(running);
+            Ok(())
+        })
+        .unwrap();
+
+        let retry = |process_id: &str| {
+            build_router().oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/api/worktrees/repo%2Fwt/processes/{process_id}/retry"
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = retry(&failed_id).await.expect("retry request");
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read retry body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("retry json");
+        assert_eq!(json["process"]["parent_id"], failed_id.as_str());
+        assert_eq!(json["process"]["command"], serde_json::json!(["true"]));
+        assert_eq!(json["process"]["description"], "flaky");
+        assert_ne!(json["process"]["id"], failed_id.as_str());
+
+        let response = retry(&running_id).await.expect("retry running request");
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let response = retry("missing").await.expect("retry missing request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_editor_returns_not_found_for_unknown_worktree() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/worktrees/nonexistent/editor")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("worktree editor request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_paste_returns_not_found_for_unknown_worktree() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/worktrees/nonexistent/paste")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("worktree paste request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn admin_gc_prunes_process_registry() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/admin/gc")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"max_age_hours":24}"#))
+                    .unwrap(),
+            )
+            .await
+            .expect("admin gc request");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read gc body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("gc json");
+        assert!(json["removed_processes"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint_exports_gauges_and_request_timings() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let app = build_router();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("health request");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("metrics request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(
+            content_type.starts_with("text/plain"),
+            "expected Prometheus text format, got {content_type}"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read metrics body");
+        let text = String::from_utf8(body.to_vec()).expect("metrics utf-8");
+        assert!(text.contains("agentdev_worktrees_total 0"), "{text}");
+        assert!(
+            text.contains("agentdev_worktrees_by_status{status=\"exited\"} 0"),
+            "{text}"
+        );
+        assert!(
+            text.contains("agentdev_api_request_duration_seconds_count{endpoint=\"/api/health\"}"),
+            "{text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn worktree_diff_returns_not_found_for_unknown_worktree() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/worktrees/nonexistent/diff?staged=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("worktree diff request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_file_serves_contents_within_worktree() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let worktree = temp.path().join("repo-feature");
+        std::fs::create_dir_all(worktree.join("src")).unwrap();
+        std::fs::write(
+            worktree.join("src/lib.rs"),
+            "pub fn answer() -> u32 { 42 }\n",
+        )
+        .unwrap();
+        std::fs::write(worktree.join("big.txt"), "x".repeat(64)).unwrap();
+        std::fs::write(temp.path().join("secret.txt"), "outside").unwrap();
+
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/feature".to_string(),
+            crate::state::WorktreeInfo {
+                name: "feature".to_string(),
+                branch: "feature".to_string(),
+                path: worktree.clone(),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+        let _limit_guard = EnvGuard::set("AGENTDEV_FILE_MAX_BYTES", "32");
+
+        let request = |uri: &str| {
+            build_router().oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = request("/api/worktrees/repo%2Ffeature/file?path=src/lib.rs")
+            .await
+            .expect("file request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(content_type.ends_with("charset=utf-8"), "{content_type}");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read file body");
+        assert_eq!(&body[..], b"pub fn answer() -> u32 { 42 }\n");
+
+        for uri in [
+            "/api/worktrees/repo%2Ffeature/file?path=../secret.txt",
+            "/api/worktrees/repo%2Ffeature/file?path=%2Fetc%2Fpasswd",
+        ] {
+            let response = request(uri).await.expect("escaping file request");
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(temp.path().join("secret.txt"), worktree.join("link"))
+                .unwrap();
+            let response = request("/api/worktrees/repo%2Ffeature/file?path=link")
+                .await
+                .expect("symlink file request");
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        for file in ["page.html", "logo.svg", "feed.xml"] {
+            std::fs::write(worktree.join(file), "<script>alert(1)</script>").unwrap();
+            let response = request(&format!("/api/worktrees/repo%2Ffeature/file?path={file}"))
+                .await
+                .expect("active file request");
+            assert_eq!(response.status(), StatusCode::OK);
+            let headers = response.headers();
+            assert_eq!(
+                headers[axum::http::header::CONTENT_TYPE],
+                "text/plain; charset=utf-8",
+                "{file}"
+            );
+            assert_eq!(
+                headers[axum::http::header::X_CONTENT_TYPE_OPTIONS],
+                "nosniff"
+            );
+            assert_eq!(
+                headers[axum::http::header::CONTENT_SECURITY_POLICY],
+                "sandbox"
+            );
+        }
+
+        let response = request("/api/worktrees/repo%2Ffeature/file?path=missing.rs")
+            .await
+            .expect("missing file request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = request("/api/worktrees/repo%2Ffeature/file?path=big.txt")
+            .await
+            .expect("large file request");
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn worktree_checkpoints_lists_annotated_tags_reachable_from_head() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "--initial-branch=main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Tester"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+        git(&[
+            "tag",
+            "-a",
+            "checkpoint-20250101-000000",
+            "-m",
+            "tests pass",
+        ]);
+        git(&["tag", "checkpoint-lightweight"]);
+        git(&["checkout", "-q", "-b", "other"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "elsewhere"]);
+        git(&[
+            "tag",
+            "-a",
+            "checkpoint-20250102-000000",
+            "-m",
+            "other branch",
+        ]);
+        git(&["checkout", "-q", "main"]);
+
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/main".to_string(),
+            crate::state::WorktreeInfo {
+                name: "main".to_string(),
+                branch: "main".to_string(),
+                path: repo.clone(),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/worktrees/repo%2Fmain/checkpoints")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("checkpoints request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read checkpoints body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("checkpoints json");
+        let checkpoints = json["checkpoints"].as_array().expect("checkpoint list");
+        assert_eq!(checkpoints.len(), 1, "{json}");
+        assert_eq!(checkpoints[0]["name"], "checkpoint-20250101-000000");
+        assert_eq!(checkpoints[0]["message"], "tests pass");
+        assert!(checkpoints[0]["tagged_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn task_command_launches_in_every_member_worktree() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        for (name, task) in [
+            ("claude", "compare"),
+            ("codex", "compare"),
+            ("other", "solo"),
+        ] {
+            let path = temp.path().join(name);
+            std::fs::create_dir_all(&path).unwrap();
+            state.worktrees.insert(
+                format!("repo/{name}"),
+                crate::state::WorktreeInfo {
+                    name: name.to_string(),
+                    branch: name.to_string(),
+                    path,
+                    repo_name: "repo".to_string(),
+                    created_at: chrono::Utc::now(),
+                    task_id: Some(task.to_string()),
+                    task_name: None,
+                    initial_prompt: None,
+                    agent_alias: None,
+                    locked: false,
+                    lock_reason: None,
+                    last_agent_activity: None,
+                    base_ref: None,
+                    pinned: false,
+                    checkpoints: Vec::new(),
+                    notes: None,
+                    symlink_path: None,
+                },
+            );
+        }
+        state.save().unwrap();
+
+        let post = |uri: &str, body: &str| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = build_router()
+            .oneshot(post(
+                "/api/tasks/compare/commands",
+                r#"{"command": "true"}"#,
+            ))
+            .await
+            .expect("task command request");
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read task command body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("processes json");
+        let cwds: Vec<_> = json["processes"]
+            .as_array()
+            .expect("process list")
+            .iter()
+            .map(|process| process["cwd"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            cwds,
+            [
+                temp.path().join("claude").display().to_string(),
+                temp.path().join("codex").display().to_string(),
+            ]
+        );
+
+        let response = build_router()
+            .oneshot(post("/api/tasks/compare/commands", r#"{"command": "  "}"#))
+            .await
+            .expect("empty command request");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = build_router()
+            .oneshot(post(
+                "/api/tasks/missing/commands",
+                r#"{"command": "true"}"#,
+            ))
+            .await
+            .expect("missing task request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_listing_counts_recorded_activity() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let now = chrono::Utc::now();
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        for (name, created_days_ago) in [("quiet", 1), ("busy", 5)] {
+            state.worktrees.insert(
+                format!("repo/{name}"),
+                crate::state::WorktreeInfo {
+                    name: name.to_string(),
+                    branch: name.to_string(),
+                    path: temp.path().join(name),
+                    repo_name: "repo".to_string(),
+                    created_at: now - chrono::Duration::days(created_days_ago),
+                    task_id: None,
+                    task_name: None,
+                    initial_prompt: None,
+                    agent_alias: None,
+                    locked: false,
+                    lock_reason: None,
+                    last_agent_activity: None,
+                    base_ref: None,
+                    pinned: false,
+                    checkpoints: Vec::new(),
+                    notes: None,
+                    symlink_path: None,
+                },
+            );
+        }
+        state.save().unwrap();
+        crate::state::XlaudeState::record_activity("repo/busy").unwrap();
+
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/worktrees")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("worktrees request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read worktrees body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("worktrees json");
+        let worktrees = json["worktrees"].as_array().expect("worktree list");
+        let names: Vec<_> = worktrees
+            .iter()
+            .map(|worktree| worktree["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["busy", "quiet"]);
+        let busy_activity: chrono::DateTime<chrono::Utc> =
+            serde_json::from_value(worktrees[0]["last_activity_at"].clone()).unwrap();
+        assert!(busy_activity >= now);
+    }
+
+    #[tokio::test]
+    async fn patch_worktree_updates_and_clears_notes() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/notes".to_string(),
+            crate::state::WorktreeInfo {
+                name: "notes".to_string(),
+                branch: "notes".to_string(),
+                path: temp.path().join("notes"),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+
+        let patch = |uri: &str, body: &str| {
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = build_router()
+            .oneshot(patch(
+                "/api/worktrees/repo%2Fnotes",
+                r#"{"notes": "  waiting on review  "}"#,
+            ))
+            .await
+            .expect("patch request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read patch body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("summary json");
+        assert_eq!(json["notes"], "waiting on review");
+        let stored = crate::state::XlaudeState::load().unwrap();
+        assert_eq!(
+            stored.worktrees["repo/notes"].notes.as_deref(),
+            Some("waiting on review")
+        );
+
+        // Omitting the field leaves the notes alone
+        let response = build_router()
+            .oneshot(patch("/api/worktrees/repo%2Fnotes", "{}"))
+            .await
+            .expect("empty patch request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let stored = crate::state::XlaudeState::load().unwrap();
+        assert!(stored.worktrees["repo/notes"].notes.is_some());
+
+        let response = build_router()
+            .oneshot(patch("/api/worktrees/repo%2Fnotes", r#"{"notes": ""}"#))
+            .await
+            .expect("clearing patch request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let stored = crate::state::XlaudeState::load().unwrap();
+        assert!(stored.worktrees["repo/notes"].notes.is_none());
+
+        let response = build_router()
+            .oneshot(patch("/api/worktrees/repo%2Fmissing", r#"{"notes": "x"}"#))
+            .await
+            .expect("missing worktree request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn worktree_rebase_reports_success_and_conflicts() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        let commit_file = |file: &str, contents: &str, message: &str| {
+            std::fs::write(repo.join(file), contents).unwrap();
+            git(&["add", file]);
+            git(&["commit", "-q", "-m", message]);
+        };
+        git(&["init", "-q", "--initial-branch=main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Tester"]);
+        commit_file("note.txt", "base\n", "initial");
+        git(&["checkout", "-q", "-b", "feature"]);
+        commit_file("feature.txt", "feature\n", "feature work");
+        git(&["checkout", "-q", "main"]);
+        commit_file("main.txt", "main\n", "main work");
+        git(&["checkout", "-q", "feature"]);
+
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/feature".to_string(),
+            crate::state::WorktreeInfo {
+                name: "feature".to_string(),
+                branch: "feature".to_string(),
+                path: repo.clone(),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+
+        let rebase = |body: &'static str| {
+            build_router().oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/worktrees/repo%2Ffeature/rebase")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+        let json_body = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read rebase body");
+            serde_json::from_slice::<serde_json::Value>(&body).expect("rebase json")
+        };
+
+        let response = rebase(r#"{"base":"main","interactive":true}"#)
+            .await
+            .expect("interactive rebase request");
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = rebase(r#"{"base":"no-such-branch"}"#)
+            .await
+            .expect("unknown base request");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = rebase(r#"{"base":"main"}"#).await.expect("rebase request");
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = json_body(response).await;
+        assert_eq!(json["base"], "main");
+        assert_eq!(json["head"]["summary"], "feature work");
+        assert!(repo.join("main.txt").exists());
+
+        git(&["checkout", "-q", "main"]);
+        commit_file("note.txt", "main change\n", "main edits note");
+        git(&["checkout", "-q", "feature"]);
+        commit_file("note.txt", "feature change\n", "feature edits note");
+
+        let response = rebase(r#"{"base":"main"}"#)
+            .await
+            .expect("conflicting rebase request");
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let json = json_body(response).await;
+        assert_eq!(json["conflicts"], serde_json::json!(["note.txt"]));
+        assert_eq!(
+            std::fs::read_to_string(repo.join("note.txt")).unwrap(),
+            "feature change\n",
+            "conflicting rebase should be aborted"
+        );
+    }
+
+    #[tokio::test]
+    async fn tasks_endpoint_returns_task_list() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/tasks")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("tasks request");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read tasks body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("tasks json");
+        assert!(json["tasks"].is_array());
+    }
+
+    #[tokio::test]
+    async fn worktree_sessions_lists_only_matching_sessions() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        let worktree = temp.path().join("repo-feature");
+        let elsewhere = temp.path().join("elsewhere");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::create_dir_all(&elsewhere).unwrap();
+
+        let project = temp.path().join(".claude/projects/demo");
+        std::fs::create_dir_all(&project).unwrap();
+        for (file, cwd, text) in [
+            ("inside.jsonl", &worktree, "fix the feature"),
+            ("outside.jsonl", &elsewhere, "unrelated work"),
+        ] {
+            let line = serde_json::json!({
+                "type": "user",
+                "cwd": cwd,
+                "timestamp": "2025-01-01T00:00:00Z",
+                "message": {"role": "user", "content": text},
+            });
+            std::fs::write(project.join(file), format!("{line}\n")).unwrap();
+        }
+
+        let mut state = crate::state::XlaudeState::load().unwrap();
+        state.worktrees.insert(
+            "repo/feature".to_string(),
+            crate::state::WorktreeInfo {
+                name: "feature".to_string(),
+                branch: "feature".to_string(),
+                path: worktree.clone(),
+                repo_name: "repo".to_string(),
+                created_at: chrono::Utc::now(),
+                task_id: None,
+                task_name: None,
+                initial_prompt: None,
+                agent_alias: None,
+                locked: false,
+                lock_reason: None,
+                last_agent_activity: None,
+                base_ref: None,
+                pinned: false,
+                checkpoints: Vec::new(),
+                notes: None,
+                symlink_path: None,
+            },
+        );
+        state.save().unwrap();
+
+        let fetch = |uri: &'static str| async move {
+            let response = build_router()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .expect("worktree sessions request");
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read sessions body");
+            (status, body)
+        };
+
+        let (status, body) = fetch("/api/worktrees/repo%2Ffeature/sessions").await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("sessions json");
+        let sessions = json["sessions"].as_array().expect("sessions array");
+        assert_eq!(sessions.len(), 1, "{sessions:?}");
+        assert_eq!(sessions[0]["last_user_message"], "fix the feature");
+        assert_eq!(sessions[0]["worktree_id"], "repo/feature");
+        assert!(json.get("details").is_none());
+
+        let (status, body) = fetch("/api/worktrees/repo%2Ffeature/sessions?mode=user_only").await;
+        assert_eq!(status, StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("sessions json");
+        let details = json["details"].as_array().expect("details array");
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0]["mode"], "user_only");
+        assert_eq!(details[0]["events"][0]["text"], "fix the feature");
+
+        let (status, _) = fetch("/api/worktrees/repo%2Fmissing/sessions").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn sessions_endpoint_reports_failing_provider() {
+        let (temp, _home_guard, _config_guard) = setup_test_env();
+        // A file where Kimi expects its sessions directory cannot be listed
+        std::fs::create_dir_all(temp.path().join(".kimi")).unwrap();
+        std::fs::write(temp.path().join(".kimi/sessions"), "not a directory").unwrap();
+
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/sessions")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("sessions request");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read sessions body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("sessions json");
+        assert!(json["sessions"].is_array());
+        let errors = json["provider_errors"].as_array().expect("provider errors");
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0]["provider"], "kimi");
+        assert!(!errors[0]["message"].as_str().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "openapi")]
+    #[tokio::test]
+    async fn openapi_document_lists_worktree_routes() {
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/openapi.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("openapi request");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read openapi body");
+        let doc: serde_json::Value = serde_json::from_slice(&body).expect("openapi json");
+        assert!(
+            doc["openapi"]
+                .as_str()
+                .unwrap_or_default()
+                .starts_with("3.")
+        );
+        assert!(doc["paths"]["/api/worktrees"]["get"].is_object());
+        assert!(doc["paths"]["/api/worktrees"]["post"].is_object());
+        assert!(doc["components"]["schemas"]["WorktreeSummary"].is_object());
+    }
+
+    #[tokio::test]
+    async fn create_worktree_rejects_unknown_repo() {
+        let (_temp, _home_guard, _config_guard) = setup_test_env();
+
+        let response = build_router()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/worktrees")
+                    .header(axum::http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"name":"feature","repo":"no-such-repo"}"#))
+                    .unwrap(),
+            )
+            .await
+            .expect("create worktree request");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read error body");
+        let message = String::from_utf8_lossy(&body);
+        assert!(message.contains("no-such-repo"), "{message}");
+    }
+
     #[tokio::test]
     async fn normalize_layer_handles_trailing_slash_on_simple_route() {
         async fn handler() -> &'static str {
@@ -0,0 +1,327 @@
+//! Notifications sent when an agent stops working and waits for input.
+//!
+//! Enabled by `[notify] enabled = true` in config.toml. The status poller in
+//! [`super::webhook`] hands each watched worktree's status to an
+//! [`IdleTracker`] and, once an agent that was processing has stayed idle for
+//! the debounce window, it is reported on the configured channels: the server
+//! log, a desktop notification (with the `desktop-notify` feature) or a
+//! webhook POST, signed like the status webhook when `AGENTDEV_WEBHOOK_SECRET`
+//! is set.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::webhook::{self, WebhookConfig};
+use crate::claude_status::ClaudeStatus;
+use crate::config::{NotifyChannel, NotifyConfig};
+use crate::state::WorktreeInfo;
+
+/// Idle time required before notifying when `debounce_secs` is unset.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(20);
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IdleEvent {
+    pub worktree_id: String,
+    pub worktree_name: String,
+    pub task_id: Option<String>,
+    pub status: String,
+    pub idle_since: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl IdleEvent {
+    fn message(&self) -> String {
+        match self.status.as_str() {
+            "waiting_for_input" => format!("{} is waiting for input", self.worktree_name),
+            _ => format!("{} has gone idle", self.worktree_name),
+        }
+    }
+}
+
+/// Which worktrees a [`NotifyConfig`] asks to watch.
+pub fn is_watched(config: &NotifyConfig, worktree_id: &str, info: &WorktreeInfo) -> bool {
+    if config.worktrees.is_empty() && config.tasks.is_empty() {
+        return true;
+    }
+    config
+        .worktrees
+        .iter()
+        .any(|entry| entry == worktree_id || *entry == info.name)
+        || info
+            .task_id
+            .as_ref()
+            .is_some_and(|task| config.tasks.contains(task))
+}
+
+#[derive(Default)]
+struct WatchEntry {
+    /// The agent has been processing since the last notification.
+    busy: bool,
+    idle_since: Option<(Instant, DateTime<Utc>)>,
+}
+
+/// Follows each worktree's status and reports busy -> idle transitions that
+/// outlast the debounce window, once per stretch of work.
+pub struct IdleTracker {
+    debounce: Duration,
+    entries: HashMap<String, WatchEntry>,
+}
+
+impl IdleTracker {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Record `status` observed at `now`, returning an event when the agent
+    /// has been idle for the whole debounce window after working.
+    ///
+    /// An agent first seen idle is not reported; it has to start working first.
+    pub fn observe(
+        &mut self,
+        worktree_id: &str,
+        info: &WorktreeInfo,
+        status: ClaudeStatus,
+        now: Instant,
+    ) -> Option<IdleEvent> {
+        let entry = self.entries.entry(worktree_id.to_string()).or_default();
+        if status == ClaudeStatus::Processing {
+            entry.busy = true;
+            entry.idle_since = None;
+            return None;
+        }
+        if !entry.busy {
+            return None;
+        }
+
+        let (since, since_utc) = *entry.idle_since.get_or_insert((now, Utc::now()));
+        if now.duration_since(since) < self.debounce {
+            return None;
+        }
+        entry.busy = false;
+        entry.idle_since = None;
+        Some(IdleEvent {
+            worktree_id: worktree_id.to_string(),
+            worktree_name: info.name.clone(),
+            task_id: info.task_id.clone(),
+            status: status.as_str().to_string(),
+            idle_since: since_utc,
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Forget worktrees for which `live` is false, e.g. ones whose session ended.
+    pub fn retain(&mut self, live: impl Fn(&str) -> bool) {
+        self.entries.retain(|id, _| live(id));
+    }
+}
+
+/// Reject configurations that cannot deliver, warning about unusable channels.
+pub fn validate(config: &NotifyConfig) -> Result<()> {
+    if config.channels.contains(&NotifyChannel::Webhook) && config.webhook_url.is_none() {
+        bail!("[notify] lists the webhook channel but webhook_url is not set");
+    }
+    #[cfg(not(feature = "desktop-notify"))]
+    if config.channels.contains(&NotifyChannel::Desktop) {
+        tracing::warn!(
+            "Desktop notifications need a build with `--features desktop-notify`; skipping"
+        );
+    }
+    Ok(())
+}
+
+/// The configured debounce window.
+pub fn debounce(config: &NotifyConfig) -> Duration {
+    config
+        .debounce_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_DEBOUNCE)
+}
+
+/// Channels to deliver to, defaulting to the log.
+fn channels(config: &NotifyConfig) -> Vec<NotifyChannel> {
+    if config.channels.is_empty() {
+        vec![NotifyChannel::Log]
+    } else {
+        config.channels.clone()
+    }
+}
+
+/// Send `event` on every configured channel, logging failures.
+pub async fn deliver(client: &reqwest::Client, config: &NotifyConfig, event: &IdleEvent) {
+    for channel in channels(config) {
+        let result = match channel {
+            NotifyChannel::Log => {
+                tracing::info!(
+                    worktree = %event.worktree_id,
+                    status = %event.status,
+                    "{}",
+                    event.message()
+                );
+                Ok(())
+            }
+            NotifyChannel::Desktop => show_desktop_notification(event).await,
+            NotifyChannel::Webhook => post_webhook(client, config, event).await,
+        };
+        if let Err(err) = result {
+            tracing::warn!(
+                worktree = %event.worktree_name,
+                "Idle notification via {channel:?} failed: {err:#}"
+            );
+        }
+    }
+}
+
+async fn post_webhook(
+    client: &reqwest::Client,
+    config: &NotifyConfig,
+    event: &IdleEvent,
+) -> Result<()> {
+    let url = config
+        .webhook_url
+        .clone()
+        .context("[notify] webhook_url is not set")?;
+    let webhook = WebhookConfig {
+        url,
+        secret: WebhookConfig::secret_from_env(),
+    };
+    webhook::send_event(client, &webhook, event).await
+}
+
+#[cfg(feature = "desktop-notify")]
+async fn show_desktop_notification(event: &IdleEvent) -> Result<()> {
+    let summary = event.message();
+    let body = event
+        .task_id
+        .as_ref()
+        .map(|task| format!("Task: {task}"))
+        .unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        notify_rust::Notification::new()
+            .appname("agentdev")
+            .summary(&summary)
+            .body(&body)
+            .show()
+            .map(|_| ())
+            .context("Failed to show desktop notification")
+    })
+    .await
+    .context("Desktop notification task panicked")?
+}
+
+#[cfg(not(feature = "desktop-notify"))]
+async fn show_desktop_notification(_event: &IdleEvent) -> Result<()> {
+    bail!("agentdev was built without the `desktop-notify` feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn worktree(name: &str, task: Option<&str>) -> WorktreeInfo {
+        WorktreeInfo {
+            name: name.to_string(),
+            branch: name.to_string(),
+            path: PathBuf::from("/nonexistent/agentdev-notify-test").join(name),
+            repo_name: "repo".to_string(),
+            created_at: Utc::now(),
+            task_id: task.map(str::to_string),
+            task_name: None,
+            initial_prompt: None,
+            agent_alias: None,
+            locked: false,
+            lock_reason: None,
+            last_agent_activity: None,
+            base_ref: None,
+            pinned: false,
+            checkpoints: Vec::new(),
+            notes: None,
+            symlink_path: None,
+        }
+    }
+
+    #[test]
+    fn tracker_notifies_once_after_debounce() {
+        let debounce = Duration::from_secs(10);
+        let mut tracker = IdleTracker::new(debounce);
+        let info = worktree("a", Some("task-1"));
+        let start = Instant::now();
+
+        // Idle from the start: nothing to report until the agent works.
+        assert!(
+            tracker
+                .observe("repo/a", &info, ClaudeStatus::WaitingForInput, start)
+                .is_none()
+        );
+        assert!(
+            tracker
+                .observe("repo/a", &info, ClaudeStatus::Processing, start)
+                .is_none()
+        );
+
+        // A short pause followed by more work is swallowed.
+        let paused = start + Duration::from_secs(1);
+        assert!(
+            tracker
+                .observe("repo/a", &info, ClaudeStatus::WaitingForInput, paused)
+                .is_none()
+        );
+        assert!(
+            tracker
+                .observe("repo/a", &info, ClaudeStatus::Processing, paused + debounce)
+                .is_none()
+        );
+
+        let idle = start + Duration::from_secs(30);
+        assert!(
+            tracker
+                .observe("repo/a", &info, ClaudeStatus::WaitingForInput, idle)
+                .is_none()
+        );
+        let event = tracker
+            .observe(
+                "repo/a",
+                &info,
+                ClaudeStatus::WaitingForInput,
+                idle + debounce,
+            )
+            .expect("idle event after debounce");
+        assert_eq!(event.worktree_id, "repo/a");
+        assert_eq!(event.task_id.as_deref(), Some("task-1"));
+        assert_eq!(event.status, "waiting_for_input");
+
+        assert!(
+            tracker
+                .observe(
+                    "repo/a",
+                    &info,
+                    ClaudeStatus::WaitingForInput,
+                    idle + debounce * 2
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn watch_list_filters_by_worktree_and_task() {
+        let info = worktree("a", Some("task-1"));
+        let mut config = NotifyConfig::default();
+        assert!(is_watched(&config, "repo/a", &info));
+
+        config.worktrees = vec!["b".to_string()];
+        assert!(!is_watched(&config, "repo/a", &info));
+        config.worktrees.push("repo/a".to_string());
+        assert!(is_watched(&config, "repo/a", &info));
+
+        config.worktrees = vec!["b".to_string()];
+        config.tasks = vec!["task-1".to_string()];
+        assert!(is_watched(&config, "repo/a", &info));
+    }
+}
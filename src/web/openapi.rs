@@ -0,0 +1,53 @@
+//! OpenAPI document for the web API, enabled with the `openapi` feature.
+
+use axum::{Json, response::IntoResponse};
+use utoipa::OpenApi;
+
+use super::api;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "agentdev API", description = "HTTP API served by `agentdev ui`"),
+    paths(
+        api::get_health,
+        api::get_metrics,
+        api::get_sessions,
+        api::get_session_detail,
+        api::post_session_delete,
+        api::get_worktrees,
+        api::get_worktree_search,
+        api::get_worktree_discovery,
+        api::get_worktree,
+        api::patch_worktree,
+        api::get_worktree_git_details,
+        api::get_worktree_commits_ahead,
+        api::get_worktree_size,
+        api::get_worktree_diff,
+        api::get_worktree_checkpoints,
+        api::get_worktree_file,
+        api::get_worktree_sessions,
+        api::get_worktree_processes,
+        api::post_worktree_process_retry,
+        api::post_worktree_command,
+        api::post_worktree_shell,
+        api::post_worktree_editor,
+        api::post_worktree_paste,
+        api::post_shell,
+        api::post_worktree,
+        api::post_worktree_merge,
+        api::post_worktree_rebase,
+        api::post_worktree_delete,
+        api::get_conflicts,
+        api::get_tasks,
+        api::post_task,
+        api::post_task_command,
+        api::post_admin_gc,
+    ),
+    tags((name = "agentdev"))
+)]
+pub struct ApiDoc;
+
+/// GET /api/openapi.json - OpenAPI 3 description of the routes above
+pub async fn get_openapi() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
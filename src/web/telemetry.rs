@@ -0,0 +1,49 @@
+//! Structured logging for the web server.
+//!
+//! Events go to stderr, filtered by `RUST_LOG` (default `info`). With the
+//! `otel` feature, spans are also exported over OTLP/gRPC; the exporter reads
+//! the standard `OTEL_EXPORTER_OTLP_*` variables for its endpoint and headers.
+
+use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+const DEFAULT_FILTER: &str = "info";
+
+/// Install the global subscriber; does nothing when one is already set.
+pub fn init() {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr));
+
+    #[cfg(feature = "otel")]
+    let registry = registry.with(otel_layer());
+
+    let _ = registry.try_init();
+}
+
+#[cfg(feature = "otel")]
+fn otel_layer<S>()
+-> Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(err) => {
+            eprintln!("⚠️  OTLP trace export disabled: {err}");
+            return None;
+        }
+    };
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .build();
+    let tracer = provider.tracer("agentdev");
+    opentelemetry::global::set_tracer_provider(provider);
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
@@ -0,0 +1,337 @@
+//! Outbound webhook fired when an agent's detected status changes.
+//!
+//! Enabled by setting `AGENTDEV_WEBHOOK_URL`. The UI server then polls the
+//! tmux pane of every managed worktree, runs it through
+//! [`ClaudeStatusDetector`], and POSTs a JSON [`StatusChangeEvent`] whenever
+//! the status differs from the previous poll. When `AGENTDEV_WEBHOOK_SECRET`
+//! is set, the body is signed with HMAC-SHA256 and the hex digest is sent as
+//! `X-AgentDev-Signature-256: sha256=<digest>`.
+//!
+//! The same poll feeds the idle notifications in [`super::notify`], so each
+//! pane is captured once per tick whichever of the two is enabled.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use super::notify::{self, IdleTracker};
+use crate::claude_status::{ClaudeStatus, ClaudeStatusDetector};
+use crate::config::NotifyConfig;
+use crate::state::{WorktreeInfo, XlaudeState};
+use crate::tmux::TmuxManager;
+
+/// How often worktree statuses are sampled.
+pub const WEBHOOK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Header carrying the `sha256=<hex>` body signature.
+pub const SIGNATURE_HEADER: &str = "X-AgentDev-Signature-256";
+
+const CAPTURE_LINES: usize = 50;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub secret: Option<String>,
+}
+
+impl WebhookConfig {
+    /// Read `AGENTDEV_WEBHOOK_URL` and the optional `AGENTDEV_WEBHOOK_SECRET`.
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("AGENTDEV_WEBHOOK_URL")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())?;
+        Some(Self {
+            url,
+            secret: Self::secret_from_env(),
+        })
+    }
+
+    /// The signing secret from `AGENTDEV_WEBHOOK_SECRET`, if set.
+    pub fn secret_from_env() -> Option<String> {
+        std::env::var("AGENTDEV_WEBHOOK_SECRET")
+            .ok()
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StatusChangeEvent {
+    pub worktree_id: String,
+    pub worktree_name: String,
+    pub old_status: String,
+    pub new_status: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// One worktree's detected status from a poll.
+pub struct StatusSample {
+    pub worktree_id: String,
+    pub info: WorktreeInfo,
+    pub status: ClaudeStatus,
+}
+
+/// Remembers the last status per worktree and reports transitions.
+#[derive(Default)]
+pub struct StatusTracker {
+    last: HashMap<String, ClaudeStatus>,
+    detectors: HashMap<String, ClaudeStatusDetector>,
+}
+
+impl StatusTracker {
+    /// Record `status` for a worktree, returning an event when it changed.
+    ///
+    /// The first observation only establishes a baseline.
+    pub fn observe(
+        &mut self,
+        worktree_id: &str,
+        worktree_name: &str,
+        status: ClaudeStatus,
+    ) -> Option<StatusChangeEvent> {
+        let previous = self.last.insert(worktree_id.to_string(), status.clone())?;
+        if previous == status {
+            return None;
+        }
+        Some(StatusChangeEvent {
+            worktree_id: worktree_id.to_string(),
+            worktree_name: worktree_name.to_string(),
+            old_status: previous.as_str().to_string(),
+            new_status: status.as_str().to_string(),
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Detect the status of every managed worktree that has a live tmux
+    /// session, without recording it.
+    ///
+    /// Worktrees without a session are forgotten, so a restarted agent starts
+    /// from a fresh baseline instead of reporting a stale transition.
+    pub fn sample(&mut self, tmux: &TmuxManager) -> Result<Vec<StatusSample>> {
+        let state = XlaudeState::load()?;
+        let mut samples = Vec::new();
+
+        for (id, info) in state.worktrees {
+            if !tmux.session_exists(&info.name) {
+                continue;
+            }
+            let Ok(output) = tmux.capture_pane(&info.name, CAPTURE_LINES) else {
+                continue;
+            };
+            let status = self
+                .detectors
+                .entry(id.clone())
+                .or_insert_with(ClaudeStatusDetector::new)
+                .analyze_output(&output);
+            samples.push(StatusSample {
+                worktree_id: id,
+                info,
+                status,
+            });
+        }
+
+        let live = |id: &String| samples.iter().any(|sample| &sample.worktree_id == id);
+        self.last.retain(|id, _| live(id));
+        self.detectors.retain(|id, _| live(id));
+        Ok(samples)
+    }
+}
+
+/// Hex-encoded HMAC-SHA256 of `body`, prefixed with `sha256=`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256={hex}")
+}
+
+/// POST a single JSON event to the configured webhook.
+pub async fn send_event(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    event: &impl Serialize,
+) -> Result<()> {
+    let body = serde_json::to_vec(event)?;
+    let mut request = client
+        .post(&config.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json");
+    if let Some(secret) = config.secret.as_deref() {
+        request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+    }
+    request
+        .body(body)
+        .send()
+        .await
+        .context("Failed to deliver webhook")?
+        .error_for_status()
+        .context("Webhook endpoint returned an error")?;
+    Ok(())
+}
+
+/// Start the background poller on the current Tokio runtime.
+///
+/// Status changes go to `webhook` and idle transitions to `notify`; either
+/// may be absent, but not both.
+pub fn spawn_notifier(
+    webhook: Option<WebhookConfig>,
+    notify: Option<NotifyConfig>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    if let Some(config) = &notify {
+        notify::validate(config)?;
+    }
+    super::install_crypto_provider();
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .context("Failed to build webhook HTTP client")?;
+
+    Ok(tokio::spawn(async move {
+        let mut tracker = StatusTracker::default();
+        let mut idle = notify
+            .as_ref()
+            .map(|config| IdleTracker::new(notify::debounce(config)));
+        let mut interval = tokio::time::interval(WEBHOOK_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let polled = tokio::task::spawn_blocking(move || {
+                let samples = tracker.sample(&TmuxManager::new());
+                (tracker, samples)
+            })
+            .await;
+            let samples = match polled {
+                Ok((returned, samples)) => {
+                    tracker = returned;
+                    samples
+                }
+                Err(err) => {
+                    tracing::error!("Webhook status poll panicked: {err}");
+                    return;
+                }
+            };
+            let samples = match samples {
+                Ok(samples) => samples,
+                Err(err) => {
+                    tracing::warn!("Webhook status poll failed: {err:#}");
+                    continue;
+                }
+            };
+
+            if let (Some(idle), Some(config)) = (idle.as_mut(), notify.as_ref()) {
+                idle.retain(|id| samples.iter().any(|sample| sample.worktree_id == id));
+                for sample in &samples {
+                    if !notify::is_watched(config, &sample.worktree_id, &sample.info) {
+                        continue;
+                    }
+                    let now = Instant::now();
+                    let status = sample.status.clone();
+                    if let Some(event) =
+                        idle.observe(&sample.worktree_id, &sample.info, status, now)
+                    {
+                        notify::deliver(&client, config, &event).await;
+                    }
+                }
+            }
+
+            for sample in samples {
+                let Some(event) =
+                    tracker.observe(&sample.worktree_id, &sample.info.name, sample.status)
+                else {
+                    continue;
+                };
+                let Some(config) = &webhook else {
+                    continue;
+                };
+                if let Err(err) = send_event(&client, config, &event).await {
+                    tracing::warn!(
+                        worktree = %event.worktree_name,
+                        "Webhook delivery failed: {err:#}"
+                    );
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Bytes, http::HeaderMap, routing::post};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn tracker_reports_only_transitions() {
+        let mut tracker = StatusTracker::default();
+        assert!(
+            tracker
+                .observe("repo/a", "a", ClaudeStatus::Processing)
+                .is_none()
+        );
+        assert!(
+            tracker
+                .observe("repo/a", "a", ClaudeStatus::Processing)
+                .is_none()
+        );
+
+        let event = tracker
+            .observe("repo/a", "a", ClaudeStatus::WaitingForInput)
+            .expect("transition event");
+        assert_eq!(event.worktree_id, "repo/a");
+        assert_eq!(event.old_status, "processing");
+        assert_eq!(event.new_status, "waiting_for_input");
+    }
+
+    #[test]
+    fn signature_matches_reference_hmac() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_event_posts_signed_json() {
+        type Captured = Arc<Mutex<Option<(HeaderMap, Bytes)>>>;
+        let captured: Captured = Arc::default();
+        let sink = captured.clone();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                *sink.lock().unwrap() = Some((headers, body));
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = WebhookConfig {
+            url: format!("http://{addr}/hook"),
+            secret: Some("s3cret".to_string()),
+        };
+        let event = StatusChangeEvent {
+            worktree_id: "repo/a".to_string(),
+            worktree_name: "a".to_string(),
+            old_status: "processing".to_string(),
+            new_status: "waiting_for_input".to_string(),
+            timestamp: Utc::now(),
+        };
+        super::super::install_crypto_provider();
+        send_event(&reqwest::Client::new(), &config, &event)
+            .await
+            .expect("deliver webhook");
+
+        let (headers, body) = captured.lock().unwrap().take().expect("request received");
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["worktree_name"], "a");
+        assert_eq!(json["new_status"], "waiting_for_input");
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("s3cret", &body)
+        );
+    }
+}
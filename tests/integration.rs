@@ -295,6 +295,10 @@ fn test_exec_runs_command_in_named_worktree() {
         .success();
 
     let expected_path = ctx.canonical_worktree_path("feature-x");
+    let last_activity = |ctx: &TestContext| {
+        ctx.read_state()["worktrees"]["test-repo/feature-x"]["last_agent_activity"].clone()
+    };
+    assert!(last_activity(&ctx).is_null());
     let output = ctx
         .xlaude(&[
             "worktree",
@@ -314,6 +318,10 @@ fn test_exec_runs_command_in_named_worktree() {
         expected_path.to_string_lossy(),
         "command should run inside the selected worktree"
     );
+    assert!(
+        last_activity(&ctx).is_string(),
+        "launching a command counts as activity"
+    );
 }
 
 #[test]
@@ -370,6 +378,37 @@ fn test_exec_parses_single_argument_command() {
     );
 }
 
+#[test]
+fn test_shell_runs_login_shell_in_worktree() {
+    let ctx = TestContext::new("test-repo");
+
+    ctx.xlaude(&["worktree", "create", "feature-x"])
+        .assert()
+        .success();
+
+    let expected_path = ctx.canonical_worktree_path("feature-x");
+    let output = ctx
+        .xlaude(&["worktree", "shell", "feature-x"])
+        .env("SHELL", "/bin/sh")
+        .write_stdin("pwd -P\n")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert_eq!(
+        stdout.lines().last().unwrap_or(""),
+        expected_path.to_string_lossy(),
+        "shell should start inside the worktree"
+    );
+
+    fs::remove_dir_all(&expected_path).unwrap();
+    ctx.xlaude(&["worktree", "shell", "feature-x"])
+        .env("SHELL", "/bin/sh")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no longer exists"));
+}
+
 #[test]
 fn test_discovery_no_unmanaged_worktrees() {
     let ctx = TestContext::new("test-repo");
@@ -786,6 +825,102 @@ fn test_add_duplicate_path_is_rejected() {
     );
 }
 
+#[test]
+fn test_worktree_adopt_registers_external_path() {
+    let ctx = TestContext::new("test-repo");
+
+    std::process::Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            "../test-repo-adopt",
+            "-b",
+            "adopt-branch",
+        ])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    let external = ctx.temp_dir.path().join("test-repo-adopt");
+
+    // Run from outside the worktree
+    ctx.xlaude_in_dir(
+        ctx.temp_dir.path(),
+        &["worktree", "adopt", external.to_str().unwrap()],
+    )
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("added successfully"));
+
+    let state = ctx.read_state();
+    let entry = &state["worktrees"]["test-repo/adopt-branch"];
+    assert_eq!(entry["branch"], "adopt-branch");
+    assert_eq!(
+        PathBuf::from(entry["path"].as_str().unwrap()),
+        fs::canonicalize(&external).unwrap()
+    );
+
+    ctx.xlaude_in_dir(
+        ctx.temp_dir.path(),
+        &["worktree", "adopt", external.to_str().unwrap(), "other"],
+    )
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains("already managed"));
+
+    let plain = ctx.temp_dir.path().join("not-a-worktree");
+    fs::create_dir(&plain).unwrap();
+    ctx.xlaude(&["worktree", "adopt", plain.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a git worktree"));
+}
+
+#[test]
+fn test_worktree_fork_starts_from_source_head() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["worktree", "create", "source"])
+        .assert()
+        .success();
+    let source = ctx.worktree_path("source");
+    let git = |dir: &Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    fs::write(source.join("progress.txt"), "partial work").unwrap();
+    git(&source, &["add", "progress.txt"]);
+    git(&source, &["commit", "-m", "Partial work"]);
+    let source_head = git(&source, &["rev-parse", "HEAD"]);
+
+    let mut state = ctx.read_state();
+    state["worktrees"]["test-repo/source"]["task_id"] = json!("task-123");
+    state["worktrees"]["test-repo/source"]["initial_prompt"] = json!("Build the thing");
+    ctx.write_state(&state);
+
+    ctx.xlaude(&["worktree", "fork", "source", "continued"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Forking 'source'"));
+
+    let fork = ctx.worktree_path("continued");
+    assert_eq!(git(&fork, &["rev-parse", "HEAD"]), source_head);
+    assert_eq!(git(&fork, &["branch", "--show-current"]), "continued");
+
+    let state = ctx.read_state();
+    let entry = &state["worktrees"]["test-repo/continued"];
+    assert_eq!(entry["task_id"], "task-123");
+    assert_eq!(entry["initial_prompt"], "Build the thing");
+
+    ctx.xlaude(&["worktree", "fork", "source", "continued"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists"));
+}
+
 #[test]
 fn test_add_without_name() {
     let ctx = TestContext::new("test-repo");
@@ -866,6 +1001,38 @@ fn test_clean_invalid_worktrees() {
     assert!(worktrees.contains_key("test-repo/valid"));
 }
 
+#[test]
+fn test_clean_repair_relinks_worktrees_after_repo_move() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "linked"]).assert().success();
+    let worktree = ctx.worktree_path("linked");
+
+    let moved = ctx.temp_dir.path().join("moved-repo");
+    std::fs::rename(&ctx.repo_dir, &moved).unwrap();
+
+    let output = ctx
+        .xlaude(&["worktree", "clean", "--repair"])
+        .current_dir(&moved)
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(
+        stdout.contains("Repaired worktree: test-repo/linked"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Repaired 1 worktree"), "{stdout}");
+    assert!(!stdout.contains("Found invalid worktree"), "{stdout}");
+
+    let state = ctx.read_state();
+    assert!(state["worktrees"].get("test-repo/linked").is_some());
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+    assert!(status.status.success());
+}
+
 #[test]
 fn test_clean_with_no_invalid() {
     let ctx = TestContext::new("test-repo");
@@ -1151,164 +1318,2240 @@ fn test_rename_command() {
 }
 
 #[test]
-fn test_create_duplicate_name() {
+fn test_lock_and_unlock_worktree() {
     let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "guarded"]).assert().success();
 
-    // Create a worktree with a specific name
-    ctx.xlaude(&["create", "my-feature"]).assert().success();
-
-    // Try to create another worktree with the same name - should fail
-    ctx.xlaude(&["create", "my-feature"])
+    let output = ctx
+        .xlaude(&["worktree", "lock", "guarded", "--reason", "on usb drive"])
         .assert()
-        .failure()
-        .stderr(predicates::str::contains("already exists"))
-        .stderr(predicates::str::contains("my-feature"));
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Worktree 'guarded' locked"), "{stdout}");
 
-    // Verify only one worktree exists in the state
     let state = ctx.read_state();
-    if let Some(worktrees) = state["worktrees"].as_object() {
-        assert_eq!(worktrees.len(), 1, "Should have exactly one worktree");
-    }
-}
-
-#[test]
-fn test_create_existing_git_worktree() {
-    let ctx = TestContext::new("test-repo");
+    let entry = &state["worktrees"]["test-repo/guarded"];
+    assert_eq!(entry["locked"], json!(true));
+    assert_eq!(entry["lock_reason"], json!("on usb drive"));
 
-    // Create a worktree manually using git (not tracked by xlaude)
-    let manual_root = ctx.temp_dir.path().join("test-repo.worktrees");
-    fs::create_dir_all(&manual_root).unwrap();
-    std::process::Command::new("git")
-        .args([
-            "worktree",
-            "add",
-            "-b",
-            "existing-feature",
-            "../test-repo.worktrees/existing-feature",
-        ])
+    let porcelain = std::process::Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
         .current_dir(&ctx.repo_dir)
         .output()
         .unwrap();
+    let listing = String::from_utf8_lossy(&porcelain.stdout);
+    assert!(listing.contains("locked on usb drive"), "{listing}");
 
-    // Try to create a worktree with the same name through xlaude - should fail
-    ctx.xlaude(&["create", "existing-feature"])
+    let output = ctx.xlaude(&["worktree", "list"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("guarded 🔒"), "{stdout}");
+    assert!(stdout.contains("Locked: on usb drive"), "{stdout}");
+
+    let output = ctx
+        .xlaude(&["worktree", "delete", "guarded"])
         .assert()
-        .failure()
-        .stderr(predicates::str::contains("already exists"));
+        .failure();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(stderr.contains("is locked (on usb drive)"), "{stderr}");
+    assert!(ctx.worktree_exists("guarded"));
+
+    ctx.xlaude(&["worktree", "unlock", "guarded"])
+        .assert()
+        .success();
 
-    // Verify xlaude state is still empty
     let state = ctx.read_state();
-    if let Some(worktrees) = state["worktrees"].as_object() {
-        assert_eq!(
-            worktrees.len(),
-            0,
-            "Should have no worktrees in xlaude state"
-        );
-    }
+    let entry = &state["worktrees"]["test-repo/guarded"];
+    assert!(entry.get("locked").is_none());
+    assert!(entry.get("lock_reason").is_none());
 }
 
 #[test]
-fn test_create_existing_directory() {
+fn test_list_reads_lock_status_from_git() {
     let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "outside"]).assert().success();
+    ctx.xlaude(&["create", "stale"]).assert().success();
+
+    // Locked with plain git, so agentdev state knows nothing about it
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&ctx.repo_dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    };
+    let outside = ctx.worktree_path("outside");
+    git(&[
+        "worktree",
+        "lock",
+        "--reason",
+        "portable disk",
+        outside.to_str().unwrap(),
+    ]);
+
+    // Locked through agentdev, then unlocked behind its back
+    ctx.xlaude(&["worktree", "lock", "stale", "--reason", "old reason"])
+        .assert()
+        .success();
+    let stale = ctx.worktree_path("stale");
+    git(&["worktree", "unlock", stale.to_str().unwrap()]);
 
-    // Create a directory manually (not a git worktree)
-    let existing_dir = ctx.worktree_path("existing-dir");
-    fs::create_dir_all(existing_dir.parent().unwrap()).unwrap();
-    fs::create_dir(&existing_dir).unwrap();
-    fs::write(existing_dir.join("file.txt"), "existing content").unwrap();
+    let output = ctx.xlaude(&["worktree", "list"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("outside 🔒"), "{stdout}");
+    assert!(stdout.contains("Locked: portable disk"), "{stdout}");
+    assert!(!stdout.contains("stale 🔒"), "{stdout}");
+    assert!(!stdout.contains("old reason"), "{stdout}");
 
-    // Try to create a worktree with the same name - should fail
-    ctx.xlaude(&["create", "existing-dir"])
+    let output = ctx
+        .xlaude(&["worktree", "list", "--json"])
         .assert()
-        .failure()
-        .stderr(predicates::str::contains("Directory"))
-        .stderr(predicates::str::contains("already exists"));
-
-    // Verify xlaude state is still empty
-    let state = ctx.read_state();
-    if let Some(worktrees) = state["worktrees"].as_object() {
-        assert_eq!(
-            worktrees.len(),
-            0,
-            "Should have no worktrees in xlaude state"
-        );
-    }
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let worktrees = json["worktrees"].as_array().unwrap();
+    let entry = |name: &str| {
+        worktrees
+            .iter()
+            .find(|w| w["name"] == json!(name))
+            .unwrap_or_else(|| panic!("{name} missing from list"))
+            .clone()
+    };
+    assert_eq!(entry("outside")["locked"], json!(true));
+    assert_eq!(entry("outside")["lock_reason"], json!("portable disk"));
+    assert!(entry("stale").get("locked").is_none());
+    assert!(entry("stale").get("lock_reason").is_none());
 }
 
 #[test]
-fn test_create_with_submodules() {
+fn test_checkpoint_tags_head_and_records_it() {
     let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "milestone"]).assert().success();
+    let worktree = ctx.worktree_path("milestone");
 
-    // Add a fake submodule to the test repo
-    let gitmodules_content = r#"[submodule "lib/helper"]
-    path = lib/helper
-    url = https://github.com/example/helper.git
-"#;
-    fs::write(ctx.repo_dir.join(".gitmodules"), gitmodules_content).unwrap();
+    let output = ctx
+        .xlaude(&[
+            "worktree",
+            "checkpoint",
+            "milestone",
+            "--message",
+            "Parser rewrite done",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Checkpoint 'checkpoint-"), "{stdout}");
 
-    // Stage and commit the .gitmodules file
-    std::process::Command::new("git")
-        .args(["add", ".gitmodules"])
-        .current_dir(&ctx.repo_dir)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "--no-gpg-sign", "-m", "Add submodule"])
-        .current_dir(&ctx.repo_dir)
+    let state = ctx.read_state();
+    let checkpoints = state["worktrees"]["test-repo/milestone"]["checkpoints"]
+        .as_array()
+        .expect("checkpoints recorded in state");
+    assert_eq!(checkpoints.len(), 1);
+    let tag = checkpoints[0].as_str().unwrap();
+    assert!(tag.starts_with("checkpoint-"), "{tag}");
+
+    let tag_info = std::process::Command::new("git")
+        .args(["for-each-ref", "--format=%(objecttype) %(contents:subject)"])
+        .arg(format!("refs/tags/{tag}"))
+        .current_dir(&worktree)
         .output()
         .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&tag_info.stdout).trim(),
+        "tag Parser rewrite done"
+    );
+}
 
-    // Create a worktree
-    let output = ctx.xlaude(&["create", "with-submodule"]).assert().success();
+#[test]
+fn test_note_appends_and_replaces_worktree_notes() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "annotated"]).assert().success();
+    let notes =
+        |ctx: &TestContext| ctx.read_state()["worktrees"]["test-repo/annotated"]["notes"].clone();
 
-    // Snapshot test output with path redaction
-    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
-    let redacted = ctx.redact_paths(&stdout);
-    assert_snapshot!(redacted);
+    ctx.xlaude(&["worktree", "note", "annotated", "Asked to split the parser"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Added note to 'annotated'"));
+    ctx.xlaude(&["worktree", "note", "annotated", "Then add benchmarks"])
+        .assert()
+        .success();
+    assert_eq!(
+        notes(&ctx),
+        "Asked to split the parser\nThen add benchmarks"
+    );
 
-    // Verify worktree was created
-    assert!(ctx.worktree_exists("with-submodule"));
+    ctx.xlaude(&[
+        "worktree",
+        "note",
+        "--set",
+        "annotated",
+        "Blocked on review",
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("Set notes on 'annotated'"));
+    assert_eq!(notes(&ctx), "Blocked on review");
+
+    ctx.xlaude(&["worktree", "note", "--set", "annotated", ""])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Cleared notes on 'annotated'"));
+    assert!(notes(&ctx).is_null());
 }
 
 #[test]
-fn test_create_without_submodules() {
+fn test_link_points_symlink_at_worktree() {
     let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "first"]).assert().success();
+    ctx.xlaude(&["create", "second"]).assert().success();
 
-    // Create a worktree in a repo without submodules
-    let output = ctx.xlaude(&["create", "no-submodule"]).assert().success();
+    let link = ctx.temp_dir.path().join("links/active");
+    let link_str = link.to_str().unwrap();
+    let linked_path =
+        |ctx: &TestContext, key: &str| ctx.read_state()["worktrees"][key]["symlink_path"].clone();
 
-    // Snapshot test output with path redaction
-    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
-    let redacted = ctx.redact_paths(&stdout);
-    assert_snapshot!(redacted);
+    ctx.xlaude(&["worktree", "link", "first", link_str])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_link(&link).unwrap(),
+        ctx.canonical_worktree_path("first")
+    );
+    assert_eq!(linked_path(&ctx, "test-repo/first"), json!(link_str));
 
-    // Verify worktree was created
-    assert!(ctx.worktree_exists("no-submodule"));
+    // Re-pointing the link moves it to the other worktree
+    ctx.xlaude(&["worktree", "link", "second", link_str])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_link(&link).unwrap(),
+        ctx.canonical_worktree_path("second")
+    );
+    assert!(linked_path(&ctx, "test-repo/first").is_null());
+    assert_eq!(linked_path(&ctx, "test-repo/second"), json!(link_str));
 
-    // Ensure no submodule update message appears
-    assert!(!stdout.contains("Updated submodules"));
-    assert!(!stdout.contains("Warning: Failed to update submodules"));
+    // A real file is never replaced
+    let file = ctx.temp_dir.path().join("links/file");
+    fs::write(&file, "keep").unwrap();
+    ctx.xlaude(&["worktree", "link", "first", file.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a symlink"));
+    assert_eq!(fs::read_to_string(&file).unwrap(), "keep");
+
+    ctx.xlaude(&["worktree", "unlink", "second"])
+        .assert()
+        .success();
+    assert!(fs::symlink_metadata(&link).is_err());
+    assert!(linked_path(&ctx, "test-repo/second").is_null());
+    ctx.xlaude(&["worktree", "unlink", "second"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("has no symlink"));
 }
 
 #[test]
-fn test_create_with_slash_in_branch_name() {
+fn test_snapshot_commits_pending_changes() {
     let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "wip"]).assert().success();
+    let worktree = ctx.worktree_path("wip");
 
-    // Create worktree with branch name containing slash
-    let output = ctx.xlaude(&["create", "fix/bug"]).assert().success();
+    // A clean worktree is left untouched
+    let output = ctx
+        .xlaude(&["worktree", "snapshot", "wip"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("nothing to snapshot"), "{stdout}");
 
-    // Snapshot test output with path redaction
+    fs::write(worktree.join("notes.txt"), "half done").unwrap();
+    let output = ctx
+        .xlaude(&["worktree", "snapshot", "wip"])
+        .assert()
+        .success();
     let stdout = String::from_utf8_lossy(&output.get_output().stdout);
-    let redacted = ctx.redact_paths(&stdout);
-    assert_snapshot!(redacted);
+    assert!(stdout.contains("Snapshot of 'wip' committed"), "{stdout}");
 
-    // Verify worktree was created with sanitized directory name
-    assert!(ctx.worktree_exists("fix-bug"));
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--pretty=%s"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+    let subject = String::from_utf8_lossy(&log.stdout);
+    assert!(subject.starts_with("WIP: snapshot "), "{subject}");
 
-    // Verify state file has correct information
-    let state = ctx.read_state();
-    let key = "test-repo/fix-bug".to_string();
-    assert!(state["worktrees"].as_object().unwrap().contains_key(&key));
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+    assert!(status.stdout.is_empty());
+
+    let state = ctx.read_state();
+    assert!(state["worktrees"]["test-repo/wip"]["last_agent_activity"].is_string());
+}
+
+#[test]
+fn test_reset_discards_uncommitted_changes() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "messy"]).assert().success();
+    let worktree = ctx.worktree_path("messy");
+
+    ctx.xlaude(&["worktree", "reset", "messy"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("nothing to reset"));
+
+    fs::write(worktree.join("README.md"), "broken by an agent").unwrap();
+    fs::create_dir_all(worktree.join("scratch")).unwrap();
+    fs::write(worktree.join("scratch/a.txt"), "a").unwrap();
+    fs::write(worktree.join("scratch/b.txt"), "b").unwrap();
+
+    let output = ctx
+        .xlaude(&["worktree", "reset", "messy"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("README.md"), "{stdout}");
+    assert!(stdout.contains("scratch/a.txt"), "{stdout}");
+    assert!(
+        stdout.contains("1 file(s) restored, 2 untracked file(s) removed"),
+        "{stdout}"
+    );
+
+    assert!(!worktree.join("scratch").exists());
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+    assert!(status.stdout.is_empty());
+}
+
+#[test]
+fn test_worktree_log_shows_worktree_commits() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "history"]).assert().success();
+    let worktree = ctx.worktree_path("history");
+
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&worktree)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    fs::write(worktree.join("notes.txt"), "progress").unwrap();
+    git(&["add", "notes.txt"]);
+    git(&["commit", "--no-gpg-sign", "-m", "Record progress notes"]);
+
+    let output = ctx
+        .xlaude(&["worktree", "log", "history"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("* "), "{stdout}");
+    assert!(stdout.contains("Record progress notes"), "{stdout}");
+    assert!(stdout.contains("Initial commit"), "{stdout}");
+
+    let output = ctx
+        .xlaude(&[
+            "worktree",
+            "log",
+            "history",
+            "--author",
+            "nobody-matches-this",
+        ])
+        .assert()
+        .success();
+    assert!(output.get_output().stdout.is_empty());
+}
+
+#[test]
+fn test_worktree_status_summarizes_worktrees() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "dirty"]).assert().success();
+    ctx.xlaude(&["create", "tidy"]).assert().success();
+    ctx.xlaude(&["create", "gone"]).assert().success();
+
+    fs::write(ctx.worktree_path("dirty").join("scratch.txt"), "wip").unwrap();
+    fs::remove_dir_all(ctx.worktree_path("gone")).unwrap();
+
+    let output = ctx
+        .xlaude(&["worktree", "status", "--json"])
+        .assert()
+        .success();
+    let entries: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    let names: Vec<_> = entries
+        .iter()
+        .map(|e| e["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["dirty", "gone", "tidy"]);
+    assert_eq!(entries[0]["untracked"], 1);
+    assert_eq!(entries[0]["clean"], false);
+    assert_eq!(entries[1]["path_missing"], true);
+    assert!(entries[1].get("clean").is_none());
+    assert_eq!(entries[2]["clean"], true);
+    assert_eq!(entries[2]["ahead"], 0);
+
+    let output = ctx.xlaude(&["worktree", "status"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("UNTRACKED"), "{stdout}");
+    assert!(stdout.contains("PATH MISSING"), "{stdout}");
+}
+
+#[test]
+fn test_worktree_create_from_tag() {
+    let ctx = TestContext::new("test-repo");
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&ctx.repo_dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    git(&["tag", "v1.0"]);
+    let tagged = git(&["rev-parse", "HEAD"]);
+    fs::write(ctx.repo_dir.join("later.txt"), "after the release").unwrap();
+    git(&["add", "later.txt"]);
+    git(&["commit", "--no-gpg-sign", "-m", "Work after the release"]);
+
+    ctx.xlaude(&["worktree", "create", "hotfix", "--from", "v1.0"])
+        .assert()
+        .success();
+
+    let worktree = ctx.worktree_path("hotfix");
+    let head = git(&["-C", worktree.to_str().unwrap(), "rev-parse", "HEAD"]);
+    assert_eq!(head, tagged);
+    assert!(!worktree.join("later.txt").exists());
+    let state = ctx.read_state();
+    assert_eq!(state["worktrees"]["test-repo/hotfix"]["base_ref"], "v1.0");
+
+    ctx.xlaude(&["worktree", "create", "broken", "--from", "no-such-ref"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown ref 'no-such-ref'"));
+    assert!(!ctx.worktree_path("broken").exists());
+}
+
+#[test]
+fn test_tasks_list_groups_task_worktrees() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "fix-claude"]).assert().success();
+    ctx.xlaude(&["create", "fix-codex"]).assert().success();
+    ctx.xlaude(&["create", "unrelated"]).assert().success();
+
+    let mut state = ctx.read_state();
+    for (name, alias) in [("fix-claude", "claude"), ("fix-codex", "codex")] {
+        let entry = &mut state["worktrees"][format!("test-repo/{name}")];
+        entry["task_id"] = json!("t1");
+        entry["task_name"] = json!("Fix login");
+        entry["initial_prompt"] = json!("Fix the login form");
+        entry["agent_alias"] = json!(alias);
+    }
+    ctx.write_state(&state);
+
+    let output = ctx.xlaude(&["tasks", "list"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Fix login (t1)"), "{stdout}");
+    assert!(stdout.contains("Worktrees: 2  Running: 0"), "{stdout}");
+    assert!(stdout.contains("Prompt: Fix the login form"), "{stdout}");
+    assert!(stdout.contains("claude → fix-claude"), "{stdout}");
+    assert!(stdout.contains("codex → fix-codex"), "{stdout}");
+    assert!(!stdout.contains("unrelated"), "{stdout}");
+
+    let output = ctx.xlaude(&["tasks", "list", "--json"]).assert().success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let tasks = json["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["members"].as_array().unwrap().len(), 2);
+    assert_eq!(tasks[0]["worktree_count"], 2);
+    assert_eq!(tasks[0]["running_count"], 0);
+}
+
+#[test]
+fn test_tasks_list_without_tasks_suggests_how_to_start_one() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "solo"]).assert().success();
+
+    ctx.xlaude(&["tasks", "list"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("No tasks found"))
+        .stdout(predicates::str::contains("agentdev ui"));
+}
+
+#[test]
+fn test_tasks_delete_merged_only_keeps_unmerged_members() {
+    let ctx = TestContext::new("test-repo");
+    std::process::Command::new("git")
+        .args(["branch", "-M", "main"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    ctx.xlaude(&["create", "fix-claude"]).assert().success();
+    ctx.xlaude(&["create", "fix-codex"]).assert().success();
+
+    let mut state = ctx.read_state();
+    for (name, alias) in [("fix-claude", "claude"), ("fix-codex", "codex")] {
+        let entry = &mut state["worktrees"][format!("test-repo/{name}")];
+        entry["task_id"] = json!("t1");
+        entry["task_name"] = json!("Fix login");
+        entry["agent_alias"] = json!(alias);
+    }
+    ctx.write_state(&state);
+
+    let codex_path = ctx.worktree_path("fix-codex");
+    fs::write(codex_path.join("login.rs"), "fn login() {}\n").unwrap();
+    for args in [
+        vec!["add", "login.rs"],
+        vec!["commit", "--no-gpg-sign", "-m", "Fix login"],
+    ] {
+        std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&codex_path)
+            .output()
+            .unwrap();
+    }
+
+    let output = ctx
+        .xlaude(&["tasks", "delete", "t1", "--merged-only", "--dry-run"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(
+        stdout.contains("fix-claude [merged into main] delete"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("fix-codex [1 unmerged commit(s) vs main] keep"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("would delete 1 worktree(s) and keep 1"),
+        "{stdout}"
+    );
+    assert!(ctx.worktree_exists("fix-claude"));
+    assert!(ctx.worktree_exists("fix-codex"));
+
+    ctx.xlaude(&["tasks", "delete", "Fix login", "--merged-only"])
+        .assert()
+        .success();
+    assert!(!ctx.worktree_exists("fix-claude"));
+    assert!(ctx.worktree_exists("fix-codex"));
+    let state = ctx.read_state();
+    assert!(state["worktrees"].get("test-repo/fix-claude").is_none());
+    assert!(state["worktrees"].get("test-repo/fix-codex").is_some());
+}
+
+#[test]
+fn test_tasks_merge_stops_at_first_conflict() {
+    let ctx = TestContext::new("test-repo");
+    ctx.setup_remote_with_main();
+    for name in ["alpha", "beta", "gamma"] {
+        ctx.xlaude(&["create", name]).assert().success();
+    }
+
+    let mut state = ctx.read_state();
+    for (name, alias) in [("alpha", "a"), ("beta", "b"), ("gamma", "c")] {
+        let entry = &mut state["worktrees"][format!("test-repo/{name}")];
+        entry["task_id"] = json!("t1");
+        entry["agent_alias"] = json!(alias);
+    }
+    ctx.write_state(&state);
+
+    // alpha and beta write the same file differently; gamma never gets a turn
+    for (name, file, content) in [
+        ("alpha", "shared.txt", "alpha\n"),
+        ("beta", "shared.txt", "beta\n"),
+        ("gamma", "gamma.txt", "gamma\n"),
+    ] {
+        let path = ctx.worktree_path(name);
+        fs::write(path.join(file), content).unwrap();
+        for args in [
+            vec!["add", file],
+            vec!["commit", "--no-gpg-sign", "-m", name],
+        ] {
+            std::process::Command::new("git")
+                .args(&args)
+                .current_dir(&path)
+                .output()
+                .unwrap();
+        }
+    }
+
+    let output = ctx
+        .xlaude(&["tasks", "merge", "t1", "--cleanup"])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(stderr.contains("Stopped at 'beta'"), "{stderr}");
+    assert!(stderr.contains("Merged: alpha"), "{stderr}");
+    assert!(stderr.contains("Not attempted: gamma"), "{stderr}");
+
+    assert!(!ctx.worktree_exists("alpha"));
+    assert!(ctx.worktree_exists("beta"));
+    assert!(ctx.worktree_exists("gamma"));
+    assert!(!ctx.repo_dir.join("gamma.txt").exists());
+    let log = std::process::Command::new("git")
+        .args(["log", "--format=%s", "main"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8_lossy(&log.stdout)
+            .lines()
+            .any(|line| line == "alpha")
+    );
+}
+
+#[test]
+fn test_delete_locked_worktree_with_force() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "pinned"]).assert().success();
+    ctx.xlaude(&["worktree", "lock", "pinned"])
+        .assert()
+        .success();
+
+    let output = ctx
+        .xlaude(&["worktree", "delete", "pinned", "--force"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("deleting anyway"), "{stdout}");
+    assert!(!ctx.worktree_exists("pinned"));
+    assert_eq!(ctx.read_state()["worktrees"].as_object().unwrap().len(), 0);
+}
+
+#[test]
+fn test_create_duplicate_name() {
+    let ctx = TestContext::new("test-repo");
+
+    // Create a worktree with a specific name
+    ctx.xlaude(&["create", "my-feature"]).assert().success();
+
+    // Try to create another worktree with the same name - should fail
+    ctx.xlaude(&["create", "my-feature"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists"))
+        .stderr(predicates::str::contains("my-feature"));
+
+    // Verify only one worktree exists in the state
+    let state = ctx.read_state();
+    if let Some(worktrees) = state["worktrees"].as_object() {
+        assert_eq!(worktrees.len(), 1, "Should have exactly one worktree");
+    }
+}
+
+#[test]
+fn test_create_existing_git_worktree() {
+    let ctx = TestContext::new("test-repo");
+
+    // Create a worktree manually using git (not tracked by xlaude)
+    let manual_root = ctx.temp_dir.path().join("test-repo.worktrees");
+    fs::create_dir_all(&manual_root).unwrap();
+    std::process::Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            "-b",
+            "existing-feature",
+            "../test-repo.worktrees/existing-feature",
+        ])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+
+    // Try to create a worktree with the same name through xlaude - should fail
+    ctx.xlaude(&["create", "existing-feature"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists"));
+
+    // Verify xlaude state is still empty
+    let state = ctx.read_state();
+    if let Some(worktrees) = state["worktrees"].as_object() {
+        assert_eq!(
+            worktrees.len(),
+            0,
+            "Should have no worktrees in xlaude state"
+        );
+    }
+}
+
+#[test]
+fn test_create_existing_directory() {
+    let ctx = TestContext::new("test-repo");
+
+    // Create a directory manually (not a git worktree)
+    let existing_dir = ctx.worktree_path("existing-dir");
+    fs::create_dir_all(existing_dir.parent().unwrap()).unwrap();
+    fs::create_dir(&existing_dir).unwrap();
+    fs::write(existing_dir.join("file.txt"), "existing content").unwrap();
+
+    // Try to create a worktree with the same name - should fail
+    ctx.xlaude(&["create", "existing-dir"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Directory"))
+        .stderr(predicates::str::contains("already exists"));
+
+    // Verify xlaude state is still empty
+    let state = ctx.read_state();
+    if let Some(worktrees) = state["worktrees"].as_object() {
+        assert_eq!(
+            worktrees.len(),
+            0,
+            "Should have no worktrees in xlaude state"
+        );
+    }
+}
+
+#[test]
+fn test_clean_branches_deletes_merged_remote_branches() {
+    let ctx = TestContext::new("test-repo");
+    ctx.setup_remote_with_main();
+    ctx.xlaude(&["create", "agent-task"]).assert().success();
+
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&ctx.repo_dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    git(&["push", "origin", "main:refs/heads/done", "agent-task"]);
+    git(&["checkout", "-b", "wip"]);
+    fs::write(ctx.repo_dir.join("wip.txt"), "wip").unwrap();
+    git(&["add", "wip.txt"]);
+    git(&["commit", "--no-gpg-sign", "-m", "WIP"]);
+    git(&["push", "origin", "wip"]);
+    git(&["checkout", "main"]);
+
+    let output = ctx
+        .xlaude(&["worktree", "clean-branches"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    // Candidates are listed under the repository they belong to
+    let repo_root = fs::canonicalize(&ctx.repo_dir).unwrap();
+    let plan = stdout
+        .split_once("Merged remote branches:")
+        .map(|(_, plan)| plan)
+        .unwrap_or_else(|| panic!("missing plan: {stdout}"));
+    let mut plan_lines = plan.lines().filter(|line| !line.trim().is_empty());
+    assert_eq!(
+        plan_lines.next(),
+        Some(format!("  📦 {}", repo_root.display()).as_str()),
+        "{stdout}"
+    );
+    assert_eq!(plan_lines.next(), Some("    • origin/done"), "{stdout}");
+    assert!(!stdout.contains("origin/wip"), "{stdout}");
+    assert!(!stdout.contains("origin/agent-task"), "{stdout}");
+    assert!(!stdout.contains("origin/main"), "{stdout}");
+    assert!(stdout.contains("Re-run with --confirm"), "{stdout}");
+
+    let remote_branches = || {
+        let output = std::process::Command::new("git")
+            .args(["ls-remote", "--heads", "origin"])
+            .current_dir(&ctx.repo_dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert!(remote_branches().contains("refs/heads/done"));
+
+    let output = ctx
+        .xlaude(&["worktree", "clean-branches", "--confirm"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Deleted origin/done"), "{stdout}");
+    assert!(stdout.contains("Deleted 1 of 1 remote branch"), "{stdout}");
+    let remaining = remote_branches();
+    assert!(!remaining.contains("refs/heads/done"), "{remaining}");
+    assert!(remaining.contains("refs/heads/wip"), "{remaining}");
+    assert!(remaining.contains("refs/heads/agent-task"), "{remaining}");
+    assert!(remaining.contains("refs/heads/main"), "{remaining}");
+}
+
+#[test]
+fn test_config_show_marks_values_from_file() {
+    let ctx = TestContext::new("test-repo");
+
+    let output = ctx.xlaude(&["config", "show"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("[agents]"), "{stdout}");
+    assert!(stdout.contains("codex = \"codex\" # default"), "{stdout}");
+
+    let config_path = ctx.temp_dir.path().join(".config/agentdev/config.toml");
+    fs::write(
+        &config_path,
+        "[agents]\ncodex = \"codex --full-auto\"\nmy-agent = \"my-agent --fast\"\n",
+    )
+    .unwrap();
+
+    let output = ctx.xlaude(&["config", "show", "--json"]).assert().success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["agents"]["codex"]["command"], "codex --full-auto");
+    assert_eq!(json["agents"]["codex"]["source"], "file");
+    assert_eq!(json["agents"]["my-agent"]["source"], "file");
+    assert!(json["agents"].get("claude").is_none());
+}
+
+#[test]
+fn test_create_with_template_copies_files() {
+    let ctx = TestContext::new("test-repo");
+    let template = ctx.temp_dir.path().join("templates/web");
+    fs::create_dir_all(template.join(".vscode")).unwrap();
+    fs::write(template.join(".env.local"), "API_URL=http://localhost\n").unwrap();
+    fs::write(template.join(".vscode/settings.json"), "{}\n").unwrap();
+
+    let config_dir = ctx.temp_dir.path().join(".config/agentdev");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            "[agents]\ncodex = \"codex\"\n\n[worktree.templates]\nweb = {:?}\n",
+            template.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+
+    ctx.xlaude(&[
+        "worktree",
+        "create",
+        "unknown-template",
+        "--template",
+        "nope",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains("Unknown template 'nope'"));
+    assert!(!ctx.worktree_exists("unknown-template"));
+
+    ctx.xlaude(&["worktree", "create", "with-template", "--template", "web"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Copied 2 files from template"));
+    let worktree = ctx.worktree_path("with-template");
+    assert_eq!(
+        fs::read_to_string(worktree.join(".env.local")).unwrap(),
+        "API_URL=http://localhost\n"
+    );
+    assert!(worktree.join(".vscode/settings.json").exists());
+
+    // README.md is tracked in the repository
+    fs::write(template.join("README.md"), "# From template").unwrap();
+    ctx.xlaude(&[
+        "worktree",
+        "create",
+        "tracked-conflict",
+        "--template",
+        "web",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains(
+        "Template would overwrite tracked files: README.md",
+    ));
+    let conflicted = ctx.worktree_path("tracked-conflict");
+    assert_eq!(
+        fs::read_to_string(conflicted.join("README.md")).unwrap(),
+        "# Test Repo"
+    );
+
+    ctx.xlaude(&[
+        "worktree",
+        "create",
+        "forced",
+        "--template",
+        "web",
+        "--force-template",
+    ])
+    .assert()
+    .success();
+    assert_eq!(
+        fs::read_to_string(ctx.worktree_path("forced").join("README.md")).unwrap(),
+        "# From template"
+    );
+}
+
+#[test]
+fn test_create_with_file_list_template() {
+    let ctx = TestContext::new("test-repo");
+    fs::write(ctx.repo_dir.join(".env"), "SECRET=1\n").unwrap();
+    let boilerplate = ctx.temp_dir.path().join("boilerplate");
+    fs::create_dir_all(&boilerplate).unwrap();
+    fs::write(boilerplate.join("Makefile"), "all:\n").unwrap();
+    fs::write(boilerplate.join("README.md"), "# Boilerplate").unwrap();
+
+    let config_dir = ctx.temp_dir.path().join(".config/agentdev");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[agents]\ncodex = \"codex\"\n\n[templates.dev]\nfiles = [\".env\", \"~/boilerplate/Makefile\", \"~/boilerplate/README.md\"]\n",
+    )
+    .unwrap();
+
+    // README.md is tracked in the repository, same as for directory templates
+    ctx.xlaude(&["worktree", "create", "plain", "--template", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Template would overwrite tracked files: README.md",
+        ));
+    assert_eq!(
+        fs::read_to_string(ctx.worktree_path("plain").join("README.md")).unwrap(),
+        "# Test Repo"
+    );
+
+    ctx.xlaude(&[
+        "worktree",
+        "create",
+        "replaced",
+        "--template",
+        "dev",
+        "--force-template",
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("Copied 3 files from template"));
+    let worktree = ctx.worktree_path("replaced");
+    assert_eq!(
+        fs::read_to_string(worktree.join(".env")).unwrap(),
+        "SECRET=1\n"
+    );
+    assert!(worktree.join("Makefile").exists());
+    assert_eq!(
+        fs::read_to_string(worktree.join("README.md")).unwrap(),
+        "# Boilerplate"
+    );
+}
+
+#[test]
+fn test_file_list_template_rejects_paths_outside_repo() {
+    let ctx = TestContext::new("test-repo");
+    fs::write(ctx.temp_dir.path().join("outside.txt"), "secret\n").unwrap();
+
+    let config_dir = ctx.temp_dir.path().join(".config/agentdev");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[agents]\ncodex = \"codex\"\n\n[templates.escape]\nfiles = [\"../outside.txt\"]\n",
+    )
+    .unwrap();
+
+    ctx.xlaude(&["worktree", "create", "escape", "--template", "escape"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "must stay inside the repository: ../outside.txt",
+        ));
+    assert!(!ctx.worktree_exists("escape"));
+}
+
+#[test]
+fn test_truncated_state_is_backed_up_and_reset() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["worktree", "create", "kept"])
+        .assert()
+        .success();
+    let state_path = ctx.config_dir.join("state.json");
+    let content = fs::read_to_string(&state_path).unwrap();
+    let truncated = &content[..content.len() / 2];
+    fs::write(&state_path, truncated).unwrap();
+
+    ctx.xlaude(&["worktree", "list", "--json"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("is corrupt"))
+        .stderr(predicates::str::contains("state.json.bak"));
+    assert_eq!(
+        fs::read_to_string(ctx.config_dir.join("state.json.bak")).unwrap(),
+        truncated
+    );
+    assert!(!state_path.exists());
+
+    // Commands keep working on the fresh state
+    ctx.xlaude(&["worktree", "create", "fresh"])
+        .assert()
+        .success();
+    let state = ctx.read_state();
+    let worktrees = state["worktrees"].as_object().unwrap();
+    assert_eq!(worktrees.len(), 1);
+    assert!(worktrees.contains_key("test-repo/fresh"));
+}
+
+#[test]
+fn test_state_from_newer_version_is_left_untouched() {
+    let ctx = TestContext::new("test-repo");
+    let state = json!({ "schema_version": 999, "worktrees": {} });
+    ctx.write_state(&state);
+
+    ctx.xlaude(&["worktree", "list"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("upgrade agentdev"));
+    assert_eq!(ctx.read_state(), state);
+    assert!(!ctx.config_dir.join("state.json.bak").exists());
+}
+
+#[test]
+fn test_worktree_watch_without_session_or_log_fails() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["worktree", "create", "quiet"])
+        .assert()
+        .success();
+
+    ctx.xlaude(&["worktree", "watch", "quiet"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "No tmux session or agent session log found for worktree 'quiet'",
+        ));
+}
+
+#[test]
+fn test_create_enforces_max_worktrees() {
+    let ctx = TestContext::new("test-repo");
+    let config_dir = ctx.temp_dir.path().join(".config/agentdev");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[agents]\ncodex = \"codex\"\n\n[worktree]\nmax_worktrees = 1\n",
+    )
+    .unwrap();
+
+    ctx.xlaude(&["worktree", "create", "first"])
+        .assert()
+        .success();
+    ctx.xlaude(&["worktree", "create", "second"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "1 worktrees exist and the limit is 1",
+        ))
+        .stderr(predicates::str::contains("agentdev worktree delete"));
+    assert!(!ctx.worktree_exists("second"));
+
+    // The environment variable overrides the config file; 0 lifts the limit
+    ctx.xlaude(&["worktree", "create", "second"])
+        .env("AGENTDEV_MAX_WORKTREES", "0")
+        .assert()
+        .success();
+    ctx.xlaude(&["worktree", "create", "third"])
+        .env("AGENTDEV_MAX_WORKTREES", "2")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "2 worktrees exist and the limit is 2",
+        ));
+}
+
+#[test]
+fn test_audit_log_records_create_and_delete() {
+    let ctx = TestContext::new("test-repo");
+
+    ctx.xlaude(&["worktree", "create", "audited"])
+        .assert()
+        .success();
+    ctx.xlaude(&["worktree", "create", "audited"])
+        .assert()
+        .failure();
+    ctx.xlaude(&["worktree", "delete", "audited"])
+        .assert()
+        .success();
+
+    let output = ctx.xlaude(&["audit", "list", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let summary: Vec<(&str, &str)> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            assert_eq!(entry["repo"], "test-repo");
+            assert_eq!(entry["worktree"], "audited");
+            assert_eq!(entry["source"], "cli");
+            (
+                entry["action"].as_str().unwrap(),
+                entry["outcome"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("create", "success"),
+            ("create", "failure"),
+            ("delete", "success")
+        ]
+    );
+
+    let output = ctx
+        .xlaude(&["audit", "list", "--action", "delete"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("delete  ok"), "{stdout}");
+    assert!(stdout.contains("test-repo/audited"), "{stdout}");
+    assert!(!stdout.contains("create"), "{stdout}");
+}
+
+#[test]
+fn test_sessions_prune_deletes_only_old_files_with_yes() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    let session_line = |timestamp: &str, text: &str| {
+        format!(
+            "{{\"type\":\"user\",\"timestamp\":\"{timestamp}\",\"message\":{{\"role\":\"user\",\"content\":\"{text}\"}}}}\n"
+        )
+    };
+    let old = project.join("old-session.jsonl");
+    let recent = project.join("recent-session.jsonl");
+    fs::write(&old, session_line("2020-01-01T00:00:00Z", "ancient work")).unwrap();
+    let now = chrono::Utc::now().to_rfc3339();
+    fs::write(&recent, session_line(&now, "current work")).unwrap();
+
+    ctx.xlaude(&["sessions", "prune", "--provider", "claude"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("old-session"))
+        .stdout(predicates::str::contains(
+            "Dry run: would delete 1 session file(s)",
+        ));
+    assert!(old.exists());
+
+    ctx.xlaude(&["sessions", "prune", "--provider", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown provider 'nope'"));
+
+    ctx.xlaude(&["sessions", "prune", "--older-than", "7", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Deleted 1 session file(s)"));
+    assert!(!old.exists());
+    assert!(recent.exists());
+}
+
+#[test]
+fn test_sessions_delete_archives_session_file() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    let session = project.join("abc123.jsonl");
+    fs::write(
+        &session,
+        "{\"type\":\"user\",\"timestamp\":\"2024-01-01T00:00:00Z\",\"message\":{\"role\":\"user\",\"content\":\"tidy up\"}}\n",
+    )
+    .unwrap();
+
+    ctx.xlaude(&["sessions", "delete", "claude", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Session 'missing' not found for provider 'claude'",
+        ));
+
+    ctx.xlaude(&["sessions", "delete", "claude", "abc123", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Archived session abc123"));
+    assert!(!session.exists());
+    assert!(
+        ctx.temp_dir
+            .path()
+            .join(".claude/projects/archive/demo/abc123.jsonl")
+            .exists()
+    );
+
+    // Archived sessions are no longer listed
+    ctx.xlaude(&["sessions", "delete", "claude", "abc123", "--yes"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_sessions_export_writes_markdown_per_session() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    let session_line = |timestamp: &str, role: &str, text: &str| {
+        format!(
+            "{{\"type\":\"{role}\",\"timestamp\":\"{timestamp}\",\"message\":{{\"role\":\"{role}\",\"content\":\"{text}\"}}}}\n"
+        )
+    };
+    fs::write(
+        project.join("old-session.jsonl"),
+        session_line("2020-01-01T00:00:00Z", "user", "ancient work"),
+    )
+    .unwrap();
+    fs::write(
+        project.join("new-session.jsonl"),
+        session_line("2025-06-01T10:00:00Z", "user", "fix the build")
+            + &session_line("2025-06-01T10:00:05Z", "assistant", "Done."),
+    )
+    .unwrap();
+    let out = ctx.temp_dir.path().join("export");
+    let out_str = out.to_str().unwrap();
+
+    ctx.xlaude(&[
+        "sessions",
+        "export",
+        "--output-dir",
+        out_str,
+        "--provider",
+        "nope",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains("Unknown provider 'nope'"));
+
+    ctx.xlaude(&[
+        "sessions",
+        "export",
+        "--output-dir",
+        out_str,
+        "--provider",
+        "claude",
+        "--since",
+        "2025-01-01",
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("Exported 1 session(s)"));
+
+    let markdown = fs::read_to_string(out.join("claude/new-session.md")).unwrap();
+    assert!(markdown.contains("## User"), "{markdown}");
+    assert!(markdown.contains("fix the build"), "{markdown}");
+    assert!(markdown.contains("## Assistant"), "{markdown}");
+    assert!(!out.join("claude/old-session.md").exists());
+}
+
+#[test]
+fn test_sessions_list_format_table_and_compact() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("0123456789abcdef.jsonl"),
+        "{\"type\":\"user\",\"timestamp\":\"2024-03-01T12:30:00Z\",\"message\":{\"role\":\"user\",\"content\":\"Refactor the session parser so that every provider shares one code path\"}}\n",
+    )
+    .unwrap();
+
+    let output = ctx
+        .xlaude(&["sessions", "list", "--all", "--format", "compact"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "claude/0123456789abcdef 2024-03-01T12:30:00+00:00 Refactor the session parser so that every provider shares one code path\n"
+    );
+
+    let output = ctx
+        .xlaude(&["sessions", "list", "--all", "--format", "table"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(lines[0].contains("PROVIDER"), "{stdout}");
+    assert!(lines[1].starts_with("claude    0123456789ab  "), "{stdout}");
+    assert!(
+        lines[1].contains("Refactor the session parser so that ever...  2024-03-01 12:30"),
+        "{stdout}"
+    );
+
+    // --json still works, but points at --format
+    let output = ctx
+        .xlaude(&["sessions", "list", "--all", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["sessions"][0]["session_id"], "0123456789abcdef");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("use --format json"));
+    ctx.xlaude(&["sessions", "list", "--json", "--format", "table"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_sessions_list_dedup_collapses_copies() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    let user = "{\"type\":\"user\",\"timestamp\":\"2024-03-01T12:30:00Z\",\"message\":{\"role\":\"user\",\"content\":\"Fix the flaky test\"}}\n";
+    let reply = "{\"type\":\"assistant\",\"timestamp\":\"2024-03-01T12:31:00Z\",\"message\":{\"role\":\"assistant\",\"content\":\"Done\"}}\n";
+    fs::write(project.join("rolled-up.jsonl"), user).unwrap();
+    fs::write(project.join("full.jsonl"), format!("{user}{reply}")).unwrap();
+
+    let list = |extra: &[&str]| {
+        let mut args = vec!["sessions", "list", "--all", "--format", "compact"];
+        args.extend_from_slice(extra);
+        let output = ctx.xlaude(&args).output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    assert_eq!(list(&[]).lines().count(), 2);
+    let deduped = list(&["--dedup"]);
+    assert_eq!(deduped.lines().count(), 1, "{deduped}");
+    assert!(deduped.starts_with("claude/full "), "{deduped}");
+}
+
+#[test]
+fn test_sessions_list_diagnose_reports_provider_scan() {
+    let ctx = TestContext::new("test-repo");
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("good.jsonl"),
+        "{\"type\":\"user\",\"timestamp\":\"2024-01-01T00:00:00Z\",\"message\":{\"role\":\"user\",\"content\":\"hello\"}}\n",
+    )
+    .unwrap();
+    fs::write(project.join("broken.jsonl"), "{\"type\":\"user\",\n").unwrap();
+
+    let output = ctx
+        .xlaude(&["sessions", "list", "--diagnose"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(
+        stdout.contains("Candidate files: 2  Parsed sessions: 1"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("broken.jsonl:1:"), "{stdout}");
+    assert!(stdout.contains(".kimi/sessions (missing)"), "{stdout}");
+
+    let output = ctx
+        .xlaude(&["sessions", "list", "--diagnose", "--json"])
+        .assert()
+        .success();
+    let reports: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let claude = &reports.as_array().unwrap()[0];
+    assert_eq!(claude["provider"], "claude");
+    assert_eq!(claude["dir_exists"], true);
+    assert_eq!(claude["candidate_files"], 2);
+    assert_eq!(claude["parsed_sessions"], 1);
+}
+
+#[test]
+fn test_completions_output_writes_renamed_script() {
+    let ctx = TestContext::new("test-repo");
+    let target = ctx.temp_dir.path().join("completions/nested/ad.bash");
+
+    ctx.xlaude(&[
+        "completions",
+        "bash",
+        "--output",
+        target.to_str().unwrap(),
+        "--bin-name",
+        "ad",
+    ])
+    .assert()
+    .success();
+
+    let script = fs::read_to_string(&target).unwrap();
+    assert!(script.contains("complete -F _ad ad"));
+    assert!(script.contains("$(ad complete-worktrees 2>/dev/null)"));
+    assert!(!script.contains("agentdev"));
+
+    ctx.xlaude(&["completions", "bash", "--bin-name", "bad name"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid binary name 'bad name'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_config_edit_rejects_invalid_toml() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let ctx = TestContext::new("test-repo");
+    // Fake editor that overwrites the file it is given with $NEW_CONFIG
+    let editor = ctx.temp_dir.path().join("fake-editor.sh");
+    fs::write(&editor, "#!/bin/sh\nprintf '%s' \"$NEW_CONFIG\" > \"$1\"\n").unwrap();
+    fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).unwrap();
+    let edit = |content: &str| {
+        let mut cmd = ctx.xlaude(&["config", "edit"]);
+        cmd.env_remove("VISUAL")
+            .env("EDITOR", &editor)
+            .env("NEW_CONFIG", content);
+        cmd
+    };
+
+    ctx.xlaude(&["config", "show"]).assert().success();
+    let config_path = ctx.temp_dir.path().join(".config/agentdev/config.toml");
+    let original = fs::read_to_string(&config_path).unwrap();
+
+    edit("[agents\nbroken = ")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Config left unchanged"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+
+    edit("[agents]\nmine = \"mine\"\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Config saved"));
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "[agents]\nmine = \"mine\"\n"
+    );
+    assert!(!config_path.with_extension("toml.edit").exists());
+}
+
+#[test]
+fn test_cp_copies_uncommitted_changes() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "source"]).assert().success();
+    ctx.xlaude(&["create", "dest"]).assert().success();
+
+    let source = ctx.worktree_path("source");
+    fs::write(source.join("README.md"), "# Test Repo\nagent notes\n").unwrap();
+    fs::write(source.join("staged.txt"), "staged\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "staged.txt"])
+        .current_dir(&source)
+        .output()
+        .unwrap();
+
+    let output = ctx
+        .xlaude(&["worktree", "cp", "source", "dest", "--staged-only"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Copied 1 file (+1, -0)"), "{stdout}");
+    let dest = ctx.worktree_path("dest");
+    assert!(dest.join("staged.txt").exists());
+    assert_eq!(
+        fs::read_to_string(dest.join("README.md")).unwrap(),
+        "# Test Repo"
+    );
+
+    // The staged file now exists in dest, so copying everything conflicts
+    let output = ctx
+        .xlaude(&["worktree", "cp", "source", "dest"])
+        .assert()
+        .failure();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("do not apply cleanly"), "{stdout}");
+    assert!(stdout.contains("staged.txt"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(dest.join("README.md")).unwrap(),
+        "# Test Repo"
+    );
+
+    ctx.xlaude(&["worktree", "cp", "source", "dest", "--staged-only"])
+        .assert()
+        .failure();
+    std::process::Command::new("git")
+        .args(["reset", "--hard"])
+        .current_dir(&dest)
+        .output()
+        .unwrap();
+    let output = ctx
+        .xlaude(&["worktree", "cp", "source", "dest"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Copied 2 files"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(dest.join("README.md")).unwrap(),
+        "# Test Repo\nagent notes\n"
+    );
+}
+
+#[test]
+fn test_cp_prints_rejected_hunks() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "source"]).assert().success();
+    ctx.xlaude(&["create", "dest"]).assert().success();
+
+    fs::write(
+        ctx.worktree_path("source").join("README.md"),
+        "# Source heading\n",
+    )
+    .unwrap();
+    let dest = ctx.worktree_path("dest");
+    fs::write(dest.join("README.md"), "# Dest heading\n").unwrap();
+    std::process::Command::new("git")
+        .args(["commit", "--no-gpg-sign", "-am", "Retitle"])
+        .current_dir(&dest)
+        .output()
+        .unwrap();
+
+    let output = ctx
+        .xlaude(&["worktree", "cp", "source", "dest"])
+        .assert()
+        .failure();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("patch failed: README.md:1"), "{stdout}");
+    assert!(stdout.contains("+# Source heading"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(dest.join("README.md")).unwrap(),
+        "# Dest heading\n"
+    );
+}
+
+#[test]
+fn test_list_with_size() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "heavy"]).assert().success();
+    let worktree = ctx.worktree_path("heavy");
+    fs::write(worktree.join(".gitignore"), "target/\n").unwrap();
+    fs::create_dir(worktree.join("target")).unwrap();
+    fs::write(worktree.join("target/app"), vec![0u8; 4096]).unwrap();
+
+    ctx.xlaude(&["list", "--size"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Size: "));
+
+    let size_of = |args: &[&str]| {
+        let output = ctx.xlaude(args).assert().success();
+        let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+        json["worktrees"][0]["size_bytes"].as_u64().unwrap()
+    };
+    let all = size_of(&["list", "--json", "--size"]);
+    let tracked = size_of(&["list", "--json", "--size", "--exclude-ignored"]);
+    assert!(all >= tracked + 4096, "all={all} tracked={tracked}");
+
+    let output = ctx.xlaude(&["list", "--json"]).assert().success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert!(json["worktrees"][0].get("size_bytes").is_none());
+}
+
+#[test]
+fn test_list_output_table_and_csv() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "alpha"]).assert().success();
+    ctx.xlaude(&["create", "a-much-longer-name"])
+        .assert()
+        .success();
+
+    let output = ctx
+        .xlaude(&["worktree", "list", "--output", "table"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[0].starts_with("NAME"), "{stdout}");
+    assert!(
+        lines[1].starts_with("a-much-longer-name  test-repo"),
+        "{stdout}"
+    );
+    assert!(
+        lines[2].starts_with("alpha               test-repo"),
+        "{stdout}"
+    );
+    let path_column = lines[0].find("PATH").unwrap();
+    assert!(
+        lines[1][path_column..].ends_with("a-much-longer-name"),
+        "{stdout}"
+    );
+
+    let output = ctx
+        .xlaude(&["worktree", "list", "--output", "csv"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+    let rows: Vec<&str> = stdout.split("\r\n").collect();
+    assert_eq!(rows[0], "id,name,branch,repo_name,path,created_at");
+    assert!(rows[1].starts_with("test-repo/a-much-longer-name,a-much-longer-name,"));
+    assert!(rows[2].starts_with("test-repo/alpha,alpha,alpha,test-repo,"));
+    assert_eq!(rows[3], "");
+
+    ctx.xlaude(&["list", "--json", "--output", "csv"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_list_porcelain_and_dir_print_only_data() {
+    let ctx = TestContext::new("test-repo");
+
+    let output = ctx
+        .xlaude(&["worktree", "list", "--porcelain"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    ctx.xlaude(&["create", "beta"]).assert().success();
+    ctx.xlaude(&["create", "alpha"]).assert().success();
+
+    let output = ctx
+        .xlaude(&["worktree", "list", "--porcelain"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let expected = format!(
+        "test-repo/alpha\talpha\talpha\ttest-repo\t{}\ntest-repo/beta\tbeta\tbeta\ttest-repo\t{}\n",
+        ctx.worktree_path("alpha").display(),
+        ctx.worktree_path("beta").display()
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+
+    let output = ctx
+        .xlaude(&["list", "--output", "porcelain"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    ctx.xlaude(&["worktree", "list", "--porcelain", "--json"])
+        .assert()
+        .failure();
+
+    let output = ctx.xlaude(&["worktree", "dir", "beta"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n", ctx.worktree_path("beta").display())
+    );
+}
+
+#[test]
+fn test_dir_format_quotes_path_for_eval() {
+    let ctx = TestContext::new("test-repo");
+
+    // A path with a space and a single quote
+    let external = ctx.temp_dir.path().join("it's quoted");
+    std::process::Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            external.to_str().unwrap(),
+            "-b",
+            "quoted",
+        ])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    ctx.xlaude_in_dir(
+        ctx.temp_dir.path(),
+        &["worktree", "adopt", external.to_str().unwrap()],
+    )
+    .assert()
+    .success();
+    let expected = fs::canonicalize(&external).unwrap();
+
+    let dir = |format: &str| {
+        let output = ctx
+            .xlaude(&["worktree", "dir", "quoted", "--format", format])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string()
+    };
+    let eval = |snippet: &str, then: &str| {
+        let output = std::process::Command::new("sh")
+            .args(["-c", &format!("{snippet} && {then}")])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{snippet}");
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string()
+    };
+
+    assert_eq!(dir("plain"), expected.to_string_lossy());
+    let cd = dir("cd");
+    assert!(
+        cd.starts_with("cd '/") && cd.contains(r"it'\''s quoted"),
+        "{cd}"
+    );
+    assert_eq!(eval(&cd, "pwd -P"), expected.to_string_lossy());
+    let export = dir("export");
+    assert!(export.starts_with("AGENTDEV_WORKTREE='"), "{export}");
+    assert_eq!(
+        eval(&export, "printf %s \"$AGENTDEV_WORKTREE\""),
+        expected.to_string_lossy()
+    );
+}
+
+#[test]
+fn test_copy_branch_name_prints_current_branch() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["worktree", "create", "login"])
+        .assert()
+        .success();
+    let worktree = ctx.worktree_path("login");
+    std::process::Command::new("git")
+        .args(["checkout", "-b", "feat/login"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+
+    ctx.xlaude(&["worktree", "copy-branch-name", "login"])
+        .assert()
+        .success()
+        .stdout("feat/login\n");
+    ctx.xlaude_in_dir(&worktree, &["worktree", "copy-branch-name"])
+        .assert()
+        .success()
+        .stdout("feat/login\n");
+}
+
+#[test]
+fn test_list_ndjson_prints_one_object_per_line() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "beta"]).assert().success();
+    ctx.xlaude(&["create", "alpha"]).assert().success();
+
+    let output = ctx
+        .xlaude(&["worktree", "list", "--ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            value["name"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(names, ["alpha", "beta"]);
+    ctx.xlaude(&["worktree", "list", "--ndjson", "--json"])
+        .assert()
+        .failure();
+
+    let project = ctx.temp_dir.path().join(".claude/projects/demo");
+    fs::create_dir_all(&project).unwrap();
+    for (id, text) in [("first", "hello"), ("second", "again")] {
+        fs::write(
+            project.join(format!("{id}.jsonl")),
+            format!(
+                "{{\"type\":\"user\",\"timestamp\":\"2024-01-01T00:00:00Z\",\"message\":{{\"role\":\"user\",\"content\":\"{text}\"}}}}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    let output = ctx
+        .xlaude(&["sessions", "list", "--all", "--ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut ids: Vec<String> = stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["provider"], "claude");
+            value["session_id"].as_str().unwrap().to_string()
+        })
+        .collect();
+    ids.sort();
+    assert_eq!(ids, ["first", "second"], "{stdout}");
+}
+
+#[test]
+fn test_pinned_worktrees_are_listed_first() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "alpha"]).assert().success();
+    ctx.xlaude(&["create", "beta"]).assert().success();
+    let names = |ctx: &TestContext| -> Vec<String> {
+        let output = ctx
+            .xlaude(&["worktree", "list", "--porcelain"])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.split('\t').nth(1).unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(names(&ctx), ["alpha", "beta"]);
+
+    ctx.xlaude(&["worktree", "pin", "beta"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Worktree 'beta' pinned"));
+    assert_eq!(
+        ctx.read_state()["worktrees"]["test-repo/beta"]["pinned"],
+        true
+    );
+    assert_eq!(names(&ctx), ["beta", "alpha"]);
+
+    ctx.xlaude(&["worktree", "unpin", "beta"])
+        .assert()
+        .success();
+    assert!(
+        ctx.read_state()["worktrees"]["test-repo/beta"]
+            .get("pinned")
+            .is_none()
+    );
+    assert_eq!(names(&ctx), ["alpha", "beta"]);
+}
+
+#[test]
+fn test_list_watch_requires_terminal() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "watched"]).assert().success();
+
+    ctx.xlaude(&["worktree", "list", "--watch"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "--watch needs an interactive terminal",
+        ));
+}
+
+#[test]
+fn test_doctor_reports_failures() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "healthy"]).assert().success();
+
+    let mut state = ctx.read_state();
+    state["agent"] = json!("git status");
+    ctx.write_state(&state);
+
+    ctx.xlaude(&["doctor"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("PASS  state file"))
+        .stdout(predicates::str::contains("PASS  agent"))
+        .stdout(predicates::str::contains("0 failed"));
+
+    // Two entries pointing at the same directory
+    let entry = state["worktrees"]["test-repo/healthy"].clone();
+    state["worktrees"]["test-repo/healthy-copy"] = entry;
+    state["agent"] = json!("definitely-not-an-agent --flag");
+    ctx.write_state(&state);
+    let config_path = ctx.temp_dir.path().join(".config/agentdev/config.toml");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(&config_path, "[agents\nclaude = ").unwrap();
+
+    let output = ctx.xlaude(&["doctor"]).assert().failure();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("FAIL  worktree paths"), "{stdout}");
+    assert!(
+        stdout.contains("test-repo/healthy, test-repo/healthy-copy"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("'definitely-not-an-agent' from agent command"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("FAIL  config"), "{stdout}");
+    assert!(stdout.contains("3 failed"), "{stdout}");
+}
+
+#[test]
+fn test_init_sets_up_repo_on_default_branch() {
+    let ctx = TestContext::new("test-repo");
+    fs::write(
+        ctx.repo_dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\n",
+    )
+    .unwrap();
+    let config_path = ctx.temp_dir.path().join(".config/agentdev/config.toml");
+
+    ctx.xlaude(&["init", "--non-interactive"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Getting started"));
+
+    let claude_md = fs::read_to_string(ctx.repo_dir.join("CLAUDE.md")).unwrap();
+    assert!(claude_md.starts_with("# test-repo\n"), "{claude_md}");
+    assert!(claude_md.contains("- Test: `cargo test`"), "{claude_md}");
+    assert!(config_path.exists(), "reference config should be written");
+
+    // Existing files are left untouched on a second run
+    fs::write(ctx.repo_dir.join("CLAUDE.md"), "custom").unwrap();
+    ctx.xlaude(&["init", "--non-interactive"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("CLAUDE.md already exists"));
+    assert_eq!(
+        fs::read_to_string(ctx.repo_dir.join("CLAUDE.md")).unwrap(),
+        "custom"
+    );
+
+    std::process::Command::new("git")
+        .args(["checkout", "-b", "feature-branch"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    ctx.xlaude(&["init", "--non-interactive"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("must run on the default branch"));
+
+    let outside = ctx.temp_dir.path().join("not-a-repo");
+    fs::create_dir_all(&outside).unwrap();
+    ctx.xlaude_in_dir(&outside, &["init", "--non-interactive"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Not in a git repository"));
+}
+
+#[test]
+fn test_send_requires_tmux_session() {
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "no-session"]).assert().success();
+
+    let output = ctx
+        .xlaude(&["worktree", "send", "no-session", "hello"])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(
+        stderr.contains("No tmux session for worktree 'no-session'")
+            || stderr.contains("tmux is not installed"),
+        "{stderr}"
+    );
+
+    ctx.xlaude(&["worktree", "send", "missing", "hello"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not found"));
+}
+
+#[test]
+fn test_send_types_into_tmux_session() {
+    let tmux_available = std::process::Command::new("tmux")
+        .arg("-V")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !tmux_available {
+        return;
+    }
+
+    let ctx = TestContext::new("test-repo");
+    let name = format!("send-{}", std::process::id());
+    ctx.xlaude(&["create", &name]).assert().success();
+
+    // Stand-in agent: everything typed into the pane ends up in received.txt
+    let received = ctx.temp_dir.path().join("received.txt");
+    let session = format!("agentdev_{}", name.replace('-', "_"));
+    let started = std::process::Command::new("tmux")
+        .args(["new-session", "-d", "-s", &session])
+        .arg(format!("cat > '{}'", received.display()))
+        .status()
+        .unwrap();
+    assert!(started.success());
+
+    let output = ctx
+        .xlaude(&["worktree", "send", &name, "CI passed, please push"])
+        .output()
+        .unwrap();
+    let mut contents = String::new();
+    for _ in 0..50 {
+        contents = fs::read_to_string(&received).unwrap_or_default();
+        if !contents.is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    let _ = std::process::Command::new("tmux")
+        .args(["kill-session", "-t", &session])
+        .status();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(contents, "CI passed, please push\n");
+}
+
+#[test]
+fn test_reap_kills_idle_sessions() {
+    let tmux_available = std::process::Command::new("tmux")
+        .arg("-V")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !tmux_available {
+        return;
+    }
+
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "idle-one"]).assert().success();
+
+    // A private tmux server so other sessions on the machine are never reaped
+    let tmux = |args: &[&str]| {
+        std::process::Command::new("tmux")
+            .args(args)
+            .env("TMUX_TMPDIR", ctx.temp_dir.path())
+            .env_remove("TMUX")
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap()
+    };
+    assert!(tmux(&["new-session", "-d", "-s", "agentdev_idle_one", "sleep 600"]).success());
+    let reap = |args: &[&str]| {
+        let mut cmd = ctx.xlaude(&["reap", "--idle-hours", "0"]);
+        cmd.args(args)
+            .env("TMUX_TMPDIR", ctx.temp_dir.path())
+            .env_remove("TMUX");
+        cmd
+    };
+
+    reap(&["--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("idle-one"))
+        .stdout(predicates::str::contains("Dry run"));
+    assert!(tmux(&["has-session", "-t", "agentdev_idle_one"]).success());
+
+    ctx.xlaude(&["reap"])
+        .env("TMUX_TMPDIR", ctx.temp_dir.path())
+        .env_remove("TMUX")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("No tmux sessions idle for 72h"));
+
+    reap(&[])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Killed 1 session(s)"));
+    let still_running = tmux(&["has-session", "-t", "agentdev_idle_one"]).success();
+    let _ = tmux(&["kill-server"]);
+    assert!(!still_running);
+}
+
+#[test]
+fn test_open_send_types_prompt_without_attaching() {
+    let tmux_available = std::process::Command::new("tmux")
+        .arg("-V")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !tmux_available {
+        return;
+    }
+
+    let ctx = TestContext::new("test-repo");
+    ctx.xlaude(&["create", "feature-x"]).assert().success();
+
+    let open = |args: &[&str]| {
+        let mut cmd = ctx.xlaude(&["worktree", "open", "feature-x", "--agent", "cat", "--yes"]);
+        cmd.args(args)
+            .env("SHELL", "/bin/sh")
+            .env("TMUX_TMPDIR", ctx.temp_dir.path())
+            .env_remove("TMUX");
+        cmd
+    };
+    let capture = || {
+        let output = std::process::Command::new("tmux")
+            .args(["capture-pane", "-p", "-t", "agentdev_feature_x"])
+            .env("TMUX_TMPDIR", ctx.temp_dir.path())
+            .env_remove("TMUX")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // New session: started in the background, then typed into
+    open(&["--send", "first prompt"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Sent prompt to 'feature-x'"));
+
+    // Live session: typed into right away
+    let prompt_file = ctx.temp_dir.path().join("prompt.txt");
+    fs::write(&prompt_file, "second prompt\n").unwrap();
+    open(&["--send-file", prompt_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let pane = capture();
+    let _ = std::process::Command::new("tmux")
+        .arg("kill-server")
+        .env("TMUX_TMPDIR", ctx.temp_dir.path())
+        .env_remove("TMUX")
+        .status();
+    assert!(pane.contains("first prompt"), "pane was: {pane}");
+    assert!(pane.contains("second prompt"), "pane was: {pane}");
+}
+
+/// Commit a `.gitmodules` file declaring a fake submodule to the test repo
+fn add_fake_submodule(ctx: &TestContext) {
+    let gitmodules_content = r#"[submodule "lib/helper"]
+    path = lib/helper
+    url = https://github.com/example/helper.git
+"#;
+    fs::write(ctx.repo_dir.join(".gitmodules"), gitmodules_content).unwrap();
+
+    // Stage and commit the .gitmodules file
+    std::process::Command::new("git")
+        .args(["add", ".gitmodules"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    std::process::Command::new("git")
+        .args(["commit", "--no-gpg-sign", "-m", "Add submodule"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_create_with_submodules() {
+    let ctx = TestContext::new("test-repo");
+    add_fake_submodule(&ctx);
+
+    // Create a worktree
+    let output = ctx.xlaude(&["create", "with-submodule"]).assert().success();
+
+    // Snapshot test output with path redaction
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let redacted = ctx.redact_paths(&stdout);
+    assert_snapshot!(redacted);
+
+    // Verify worktree was created
+    assert!(ctx.worktree_exists("with-submodule"));
+}
+
+#[test]
+fn test_create_without_submodules() {
+    let ctx = TestContext::new("test-repo");
+
+    // Create a worktree in a repo without submodules
+    let output = ctx.xlaude(&["create", "no-submodule"]).assert().success();
+
+    // Snapshot test output with path redaction
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let redacted = ctx.redact_paths(&stdout);
+    assert_snapshot!(redacted);
+
+    // Verify worktree was created
+    assert!(ctx.worktree_exists("no-submodule"));
+
+    // Ensure no submodule update message appears
+    assert!(!stdout.contains("Updated submodules"));
+    assert!(!stdout.contains("Warning: Failed to update submodules"));
+}
+
+#[test]
+fn test_create_with_no_submodules_flag() {
+    let ctx = TestContext::new("test-repo");
+    add_fake_submodule(&ctx);
+
+    let output = ctx
+        .xlaude(&["create", "skip-submodule", "--no-submodules"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(ctx.worktree_exists("skip-submodule"));
+    assert!(stdout.contains("Skipped submodule update (--no-submodules)"));
+    assert!(!stdout.contains("Updated submodules"));
+}
+
+#[test]
+fn test_create_with_forced_submodules() {
+    let ctx = TestContext::new("test-repo");
+
+    // Forcing the update in a repo without submodules reports that nothing was done
+    let output = ctx
+        .xlaude(&["create", "force-submodule", "--submodules"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("No submodules to update"));
+
+    // The two flags are mutually exclusive
+    ctx.xlaude(&["create", "both-flags", "--submodules", "--no-submodules"])
+        .assert()
+        .failure();
+    assert!(!ctx.worktree_exists("both-flags"));
+}
+
+#[test]
+fn test_create_with_forced_submodules_failure_cleans_up() {
+    let ctx = TestContext::new("test-repo");
+
+    // Register a submodule whose URL cannot be cloned, with a gitlink in the index
+    let missing = ctx.temp_dir.path().join("missing-helper");
+    let gitmodules_content = format!(
+        "[submodule \"lib/helper\"]\n    path = lib/helper\n    url = {}\n",
+        missing.display()
+    );
+    fs::write(ctx.repo_dir.join(".gitmodules"), gitmodules_content).unwrap();
+    for args in [
+        vec!["add", ".gitmodules"],
+        vec![
+            "update-index",
+            "--add",
+            "--cacheinfo",
+            "160000,1111111111111111111111111111111111111111,lib/helper",
+        ],
+        vec!["commit", "--no-gpg-sign", "-m", "Add broken submodule"],
+    ] {
+        let output = std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&ctx.repo_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    let output = ctx
+        .xlaude(&["create", "broken-sub", "--submodules"])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(stderr.contains("Submodule update failed"));
+
+    // Neither the worktree, the branch nor a state entry is left behind
+    assert!(!ctx.worktree_exists("broken-sub"));
+    let branch = std::process::Command::new("git")
+        .args(["show-ref", "--verify", "--quiet", "refs/heads/broken-sub"])
+        .current_dir(&ctx.repo_dir)
+        .status()
+        .unwrap();
+    assert!(!branch.success());
+    let state = ctx.read_state();
+    assert!(state["worktrees"].get("test-repo/broken-sub").is_none());
+}
+
+#[test]
+fn test_create_with_slash_in_branch_name() {
+    let ctx = TestContext::new("test-repo");
+
+    // Create worktree with branch name containing slash
+    let output = ctx.xlaude(&["create", "fix/bug"]).assert().success();
+
+    // Snapshot test output with path redaction
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let redacted = ctx.redact_paths(&stdout);
+    assert_snapshot!(redacted);
+
+    // Verify worktree was created with sanitized directory name
+    assert!(ctx.worktree_exists("fix-bug"));
+
+    // Verify state file has correct information
+    let state = ctx.read_state();
+    let key = "test-repo/fix-bug".to_string();
+    assert!(state["worktrees"].as_object().unwrap().contains_key(&key));
 
     let worktree_info = &state["worktrees"][&key];
     assert_eq!(worktree_info["name"], "fix-bug");
@@ -1354,6 +3597,30 @@ fn test_delete_with_slash_in_branch_name() {
     assert!(!state["worktrees"].as_object().unwrap().contains_key(&key));
 }
 
+#[test]
+fn test_worktree_merge_quiet_reports_failed_step() {
+    let ctx = TestContext::new("test-repo");
+
+    ctx.setup_remote_with_main();
+
+    ctx.xlaude(&["worktree", "create", "nothing-new"])
+        .assert()
+        .success();
+
+    let output = ctx
+        .xlaude(&["worktree", "merge", "nothing-new", "--squash", "--quiet"])
+        .assert()
+        .failure();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(stdout.contains("Default branch detected: main"), "{stdout}");
+    assert!(
+        stderr.contains("Squash merge produced no staged changes"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("Computing squash"), "{stderr}");
+}
+
 #[test]
 fn test_worktree_merge_with_squash_flag() {
     let ctx = TestContext::new("test-repo");
@@ -1440,6 +3707,242 @@ fn test_worktree_merge_with_squash_flag() {
     );
 }
 
+#[test]
+fn test_worktree_merge_pushes_to_named_remote() {
+    let ctx = TestContext::new("test-repo");
+    ctx.setup_remote_with_main();
+
+    let git = |dir: &Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    git(&ctx.repo_dir, &["remote", "rename", "origin", "upstream"]);
+    git(&ctx.repo_dir, &["branch", "--unset-upstream", "main"]);
+    let remote_dir = ctx.temp_dir.path().join("test-repo.git");
+
+    ctx.xlaude(&["worktree", "create", "feature-push"])
+        .assert()
+        .success();
+    let worktree = ctx.worktree_path("feature-push");
+    fs::write(worktree.join("pushed.txt"), "pushed").unwrap();
+    git(&worktree, &["add", "pushed.txt"]);
+    git(
+        &worktree,
+        &["commit", "--no-gpg-sign", "-m", "Add pushed file"],
+    );
+
+    // Without --remote the merge looks for the missing origin remote
+    ctx.xlaude(&["worktree", "merge", "feature-push", "--push"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Remote 'origin' is not configured",
+        ));
+
+    ctx.xlaude(&[
+        "worktree",
+        "merge",
+        "feature-push",
+        "--remote",
+        "upstream",
+        "--push",
+        "--set-upstream",
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains("main now tracks upstream/main"));
+
+    assert_eq!(
+        git(&remote_dir, &["rev-parse", "main"]),
+        git(&ctx.repo_dir, &["rev-parse", "main"])
+    );
+    assert_eq!(
+        git(&ctx.repo_dir, &["config", "branch.main.remote"]),
+        "upstream"
+    );
+
+    // A rejected push reports the remote's own message
+    let hook = remote_dir.join("hooks/pre-receive");
+    fs::write(
+        &hook,
+        "#!/bin/sh\necho 'main is frozen for release' >&2\nexit 1\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    ctx.xlaude(&["worktree", "create", "feature-frozen"])
+        .assert()
+        .success();
+    let worktree = ctx.worktree_path("feature-frozen");
+    fs::write(worktree.join("frozen.txt"), "frozen").unwrap();
+    git(&worktree, &["add", "frozen.txt"]);
+    git(
+        &worktree,
+        &["commit", "--no-gpg-sign", "-m", "Add frozen file"],
+    );
+
+    ctx.xlaude(&[
+        "worktree",
+        "merge",
+        "feature-frozen",
+        "--remote",
+        "upstream",
+        "--push",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicates::str::contains("Push to 'upstream' was rejected"))
+    .stderr(predicates::str::contains("main is frozen for release"));
+}
+
+#[test]
+fn test_worktree_merge_pre_merge_prompt() {
+    let ctx = TestContext::new("test-repo");
+    ctx.setup_remote_with_main();
+    let name = format!("premerge-{}", std::process::id());
+    ctx.xlaude(&["worktree", "create", &name])
+        .assert()
+        .success();
+
+    // Without an agent session the merge is refused before touching git
+    ctx.xlaude(&["worktree", "merge", &name, "--pre-merge-prompt", "wrap up"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No tmux session for worktree"));
+    ctx.xlaude(&["worktree", "merge", &name, "--timeout", "5"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--pre-merge-prompt"));
+
+    let tmux_available = std::process::Command::new("tmux")
+        .arg("-V")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !tmux_available {
+        return;
+    }
+
+    // Stand-in agent: a shell that runs the prompt, committing in the worktree
+    let worktree_path = ctx.worktree_path(&name);
+    let session = format!("agentdev_{}", name.replace('-', "_"));
+    let started = std::process::Command::new("tmux")
+        .args(["new-session", "-d", "-s", &session, "-c"])
+        .arg(&worktree_path)
+        .arg("bash --norc --noprofile")
+        .status()
+        .unwrap();
+    assert!(started.success());
+
+    let prompt = "echo prepared > prepared.txt && git add prepared.txt && \
+        git -c user.name=Agent -c user.email=agent@example.com commit --no-gpg-sign -qm Prepare";
+    let output = ctx
+        .xlaude(&[
+            "worktree",
+            "merge",
+            &name,
+            "--pre-merge-prompt",
+            prompt,
+            "--timeout",
+            "30",
+        ])
+        .output()
+        .unwrap();
+    let _ = std::process::Command::new("tmux")
+        .args(["kill-session", "-t", &session])
+        .status();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("Agent finished the pre-merge prompt"),
+        "{stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(ctx.repo_dir.join("prepared.txt")).unwrap(),
+        "prepared\n"
+    );
+}
+
+#[test]
+fn test_worktree_gc_removes_squash_merged_worktrees() {
+    let ctx = TestContext::new("test-repo");
+    ctx.setup_remote_with_main();
+
+    let commit_file = |name: &str| {
+        let path = ctx.worktree_path(name);
+        fs::write(path.join(format!("{name}.txt")), name).unwrap();
+        for args in [
+            vec!["add", "."],
+            vec!["commit", "--no-gpg-sign", "-m", name],
+        ] {
+            let output = std::process::Command::new("git")
+                .args(&args)
+                .current_dir(&path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+        }
+    };
+
+    for name in ["gc-landed", "gc-active"] {
+        ctx.xlaude(&["worktree", "create", name]).assert().success();
+        commit_file(name);
+    }
+    ctx.xlaude(&["worktree", "merge", "gc-landed", "--squash", "--push"])
+        .assert()
+        .success();
+
+    // Without --auto the non-interactive default declines
+    let output = ctx.xlaude(&["worktree", "gc"]).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    assert!(stdout.contains("Candidates to remove"), "{stdout}");
+    assert!(
+        stdout.contains("squash-merged into origin/main"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("gc-active"), "{stdout}");
+    assert!(ctx.worktree_exists("gc-landed"));
+
+    ctx.xlaude(&["worktree", "gc", "--auto"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Removed 'gc-landed'"));
+    assert!(!ctx.worktree_exists("gc-landed"));
+    assert!(ctx.worktree_exists("gc-active"));
+
+    let state = ctx.read_state();
+    let names: Vec<_> = state["worktrees"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|info| info["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["gc-active".to_string()]);
+
+    let branches = std::process::Command::new("git")
+        .args(["branch", "--list", "gc-landed"])
+        .current_dir(&ctx.repo_dir)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+}
+
 #[test]
 fn test_worktree_merge_prompts_for_repo_worktree() {
     let ctx = TestContext::new("test-repo");
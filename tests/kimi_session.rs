@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use agentdev::sessions::{KimiSessionProvider, SessionProvider, SessionToolPhase};
+use chrono::{TimeZone, Utc};
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "3f1c9b2e-8d4a-4c6e-9a71-2b5d0e7f4a10";
+
+fn fixture_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/kimi_session.jsonl")
+}
+
+/// Lay the fixture out under `kimi_dir` the way kimi stores sessions: one folder
+/// per working directory, named by the md5 of its path and listed in kimi.json.
+fn install_fixture(kimi_dir: &Path, working_dir: &str) {
+    let session_dir = kimi_dir
+        .join("sessions")
+        .join(format!("{:x}", md5::compute(working_dir)));
+    fs::create_dir_all(&session_dir).unwrap();
+    fs::copy(
+        fixture_path(),
+        session_dir.join(format!("{SESSION_ID}.jsonl")),
+    )
+    .unwrap();
+    fs::write(
+        kimi_dir.join("kimi.json"),
+        serde_json::json!({ "work_dirs": [{ "path": working_dir }] }).to_string(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn kimi_provider_parses_fixture_session() {
+    let kimi_dir = TempDir::new().unwrap();
+    install_fixture(kimi_dir.path(), "/work/demo");
+    let provider = KimiSessionProvider::with_root(kimi_dir.path());
+
+    let records = provider.list_sessions().unwrap();
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.provider, "kimi");
+    assert_eq!(record.id, SESSION_ID);
+    assert_eq!(record.working_dir.as_deref(), Some(Path::new("/work/demo")));
+    assert_eq!(
+        record.first_user_message.as_deref(),
+        Some("List the Rust files under src")
+    );
+    assert_eq!(record.last_user_message.as_deref(), Some("Thanks"));
+    assert_eq!(
+        record.last_timestamp,
+        Some(Utc.with_ymd_and_hms(2025, 11, 2, 9, 16, 30).unwrap())
+    );
+
+    let events = provider.load_session_events(record).unwrap();
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| !event.category.is_empty()));
+
+    let tool_use = events
+        .iter()
+        .find_map(|event| {
+            event
+                .tool
+                .as_ref()
+                .filter(|tool| tool.phase == SessionToolPhase::Use)
+        })
+        .expect("tool use event");
+    assert_eq!(tool_use.name.as_deref(), Some("Shell"));
+    assert_eq!(tool_use.identifier.as_deref(), Some("Shell:0"));
+    let tool_result = events
+        .iter()
+        .find_map(|event| {
+            event
+                .tool
+                .as_ref()
+                .filter(|tool| tool.phase == SessionToolPhase::Result)
+        })
+        .expect("tool result event");
+    assert_eq!(tool_result.identifier, tool_use.identifier);
+}